@@ -1,14 +1,19 @@
 use anyhow::{Context, Result};
 use reqwest::Client;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
+use super::stream::{handshake_url, StreamKind};
+use crate::config::HttpVersion;
 use super::models::{
-    Auth, CollectionDetail, CollectionDetailInfo, CollectionDetailResponse, CollectionInfo,
-    CollectionsResponse, EnvironmentDetail, EnvironmentDetailResponse, EnvironmentInfo,
-    EnvironmentsResponse, ExecutedResponse, Item, Request, Variable, WorkspaceInfo,
-    WorkspacesResponse,
+    content_type_for_language, Auth, CollectionDetail, CollectionDetailInfo, CollectionDetailResponse, CollectionInfo,
+    CollectionsResponse, CreatedCollectionResponse, EnvironmentDetail, EnvironmentDetailResponse, EnvironmentInfo,
+    EnvironmentsResponse, ExecutedResponse, FormField, GraphqlBody, Item, OAuthToken, RateLimit, RedirectHop, Request, RequestUrl, Variable, WorkspaceCounts,
+    WorkspaceDetailResponse, WorkspaceInfo, WorkspacesResponse,
 };
 
-const BASE_URL: &str = "https://api.getpostman.com";
+/// Most redirects an executed request follows before giving up.
+const MAX_REDIRECTS: usize = 10;
 
 /// Apply a request's selected Postman authorization to the outgoing HTTP
 /// request. Values are assumed to already have `{{variables}}` substituted.
@@ -47,24 +52,221 @@ fn apply_auth(builder: reqwest::RequestBuilder, auth: &Auth) -> reqwest::Request
     builder
 }
 
+/// Human-readable summary of what `apply_auth` will add to the request, for
+/// showing before sending. Returns `None` when nothing will be applied.
+pub fn describe_auth(auth: &Auth) -> Option<String> {
+    match auth.auth_type.as_str() {
+        "bearer" => auth
+            .param("token")
+            .map(|token| format!("Authorization: Bearer {}", token)),
+        "basic" => {
+            let username = auth.param("username").unwrap_or_default();
+            let password = auth.param("password").unwrap_or_default();
+            Some(format!("Authorization: Basic base64({}:{})", username, password))
+        }
+        "apikey" => {
+            let key = auth.param("key").unwrap_or_default();
+            let value = auth.param("value").unwrap_or_default();
+            if key.is_empty() {
+                return None;
+            }
+            let in_query = auth
+                .param("in")
+                .map(|loc| loc.eq_ignore_ascii_case("query"))
+                .unwrap_or(false);
+            if in_query {
+                Some(format!("Query: {}={}", key, value))
+            } else {
+                Some(format!("{}: {}", key, value))
+            }
+        }
+        _ => None,
+    }
+}
+
+/// The Postman API turned the API key away: 401 (invalid or expired) or
+/// 403 (valid, but without access to the resource).
+#[derive(Debug)]
+pub struct ApiKeyRejected {
+    pub status: u16,
+    pub message: String,
+}
+
+impl std::fmt::Display for ApiKeyRejected {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "API key invalid or lacks permission ({}): {}", self.status, self.message)
+    }
+}
+
+impl std::error::Error for ApiKeyRejected {}
+
+/// Whether `err` came from the Postman API rejecting the API key.
+pub fn is_api_key_error(err: &anyhow::Error) -> bool {
+    err.downcast_ref::<ApiKeyRejected>().is_some()
+}
+
+/// A bulk collection update was refused because the collection changed in
+/// Postman since the copy it was based on was loaded. Carries the server's
+/// current version so the caller can reload and reapply.
+#[derive(Debug)]
+pub struct CollectionChanged {
+    pub server: CollectionDetail,
+}
+
+impl std::fmt::Display for CollectionChanged {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Collection changed in Postman since it was loaded")
+    }
+}
+
+impl std::error::Error for CollectionChanged {}
+
+/// Postman's `error.message` from an error response body, or the start of
+/// the body when it isn't in that shape.
+fn api_error_message(body: &str) -> String {
+    serde_json::from_str::<serde_json::Value>(body)
+        .ok()
+        .and_then(|v| {
+            v.get("error")?
+                .get("message")?
+                .as_str()
+                .map(|s| s.to_string())
+        })
+        .unwrap_or_else(|| body.chars().take(200).collect())
+}
+
+/// Fail with `ApiKeyRejected` for a 401/403 from the Postman API.
+fn check_api_key(status: reqwest::StatusCode, body: &str) -> Result<()> {
+    if status == reqwest::StatusCode::UNAUTHORIZED || status == reqwest::StatusCode::FORBIDDEN {
+        return Err(ApiKeyRejected {
+            status: status.as_u16(),
+            message: api_error_message(body),
+        }
+        .into());
+    }
+    Ok(())
+}
+
 #[derive(Clone)]
 pub struct PostmanClient {
     client: Client,
+    /// Client for executing collection requests (and OAuth token fetches),
+    /// configured with the active workspace's proxy/TLS settings.
+    exec_client: Client,
+    /// `exec_client`'s settings, limited to HTTP/1.1 for WebSocket upgrades.
+    upgrade_client: Client,
+    /// `exec_client`'s settings without automatic redirects, so executed
+    /// requests can record each hop; one per forced HTTP version.
+    request_client: Client,
+    http1_client: Client,
+    http2_client: Client,
+    /// `[settings] http_version`, for requests without their own.
+    http_version: HttpVersion,
+    /// `[settings] max_response_bytes`: executed responses are cut off here.
+    max_response_bytes: usize,
+    follow_redirects: bool,
+    /// `[settings] default_headers`, added to executed requests that don't
+    /// set a header of the same name.
+    default_headers: Vec<(String, String)>,
     api_key: String,
+    /// `[postman] api_base_url`, without a trailing slash.
+    base_url: String,
+    /// Latest rate-limit budget reported by the Postman API, shared by clones.
+    api_rate_limit: Arc<Mutex<Option<RateLimit>>>,
 }
 
 impl PostmanClient {
-    pub fn new(api_key: String) -> Self {
+    pub fn new(api_key: String, base_url: &str) -> Self {
         PostmanClient {
             client: Client::new(),
+            exec_client: Client::new(),
+            upgrade_client: Client::builder().http1_only().build().unwrap_or_default(),
+            request_client: Client::builder().redirect(reqwest::redirect::Policy::none()).build().unwrap_or_default(),
+            http1_client: Client::builder().redirect(reqwest::redirect::Policy::none()).http1_only().build().unwrap_or_default(),
+            http2_client: Client::builder()
+                .redirect(reqwest::redirect::Policy::none())
+                .http2_prior_knowledge()
+                .build()
+                .unwrap_or_default(),
+            http_version: HttpVersion::Auto,
+            max_response_bytes: usize::MAX,
+            follow_redirects: true,
+            default_headers: Vec::new(),
             api_key,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_rate_limit: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Rebuild the client used to execute requests with a proxy (if any) and
+    /// whether to accept invalid TLS certificates. On error the previous
+    /// client is kept.
+    pub fn configure_execution(&mut self, proxy: Option<&str>, insecure_tls: bool) -> Result<()> {
+        let builder = || -> Result<reqwest::ClientBuilder> {
+            let mut builder = Client::builder().danger_accept_invalid_certs(insecure_tls);
+            if let Some(proxy) = proxy {
+                builder = builder.proxy(
+                    reqwest::Proxy::all(proxy).with_context(|| format!("Invalid proxy URL: {}", proxy))?,
+                );
+            }
+            Ok(builder)
+        };
+        let exec_client = builder()?.build().context("Failed to build HTTP client")?;
+        self.upgrade_client = builder()?.http1_only().build().context("Failed to build HTTP client")?;
+        let no_redirects = || -> Result<reqwest::ClientBuilder> { Ok(builder()?.redirect(reqwest::redirect::Policy::none())) };
+        self.request_client = no_redirects()?.build().context("Failed to build HTTP client")?;
+        self.http1_client = no_redirects()?.http1_only().build().context("Failed to build HTTP client")?;
+        self.http2_client = no_redirects()?.http2_prior_knowledge().build().context("Failed to build HTTP client")?;
+        self.exec_client = exec_client;
+        Ok(())
+    }
+
+    /// Largest executed-response body kept in memory.
+    pub fn set_max_response_bytes(&mut self, max: u64) {
+        self.max_response_bytes = usize::try_from(max).unwrap_or(usize::MAX);
+    }
+
+    /// HTTP version for executed requests that don't set their own.
+    pub fn set_http_version(&mut self, version: HttpVersion) {
+        self.http_version = version;
+    }
+
+    /// Whether executed requests follow redirects.
+    pub fn set_follow_redirects(&mut self, follow: bool) {
+        self.follow_redirects = follow;
+    }
+
+    /// Headers added to every executed request that doesn't set its own.
+    pub fn set_default_headers(&mut self, headers: Vec<(String, String)>) {
+        self.default_headers = headers;
+    }
+
+    /// Use a new Postman API key for subsequent API calls.
+    pub fn set_api_key(&mut self, api_key: String) {
+        self.api_key = api_key;
+    }
+
+    /// The Postman API's rate-limit budget as of the last API call, if known.
+    pub fn api_rate_limit(&self) -> Option<RateLimit> {
+        self.api_rate_limit.lock().ok().and_then(|r| *r)
+    }
+
+    fn record_rate_limit(&self, response: &reqwest::Response) {
+        let headers: Vec<(String, String)> = response
+            .headers()
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_str().unwrap_or("").to_string()))
+            .collect();
+        if let (Some(limit), Ok(mut current)) = (RateLimit::from_headers(&headers), self.api_rate_limit.lock()) {
+            *current = Some(limit);
         }
     }
 
     /// Read a response body, failing with Postman's error message when the
     /// status is non-2xx instead of letting it fall through to a confusing
     /// JSON parse error (e.g. a 401 from an invalid API key).
-    async fn read_success_body(response: reqwest::Response, action: &str) -> Result<String> {
+    async fn read_success_body(&self, response: reqwest::Response, action: &str) -> Result<String> {
+        self.record_rate_limit(&response);
         let status = response.status();
         let body = response
             .text()
@@ -72,23 +274,15 @@ impl PostmanClient {
             .with_context(|| format!("Failed to read {} response", action))?;
 
         if !status.is_success() {
-            let message = serde_json::from_str::<serde_json::Value>(&body)
-                .ok()
-                .and_then(|v| {
-                    v.get("error")?
-                        .get("message")?
-                        .as_str()
-                        .map(|s| s.to_string())
-                })
-                .unwrap_or_else(|| body.chars().take(200).collect());
-            anyhow::bail!("Failed to {} ({}): {}", action, status, message);
+            check_api_key(status, &body)?;
+            anyhow::bail!("Failed to {} ({}): {}", action, status, api_error_message(&body));
         }
 
         Ok(body)
     }
 
     pub async fn list_workspaces(&self) -> Result<Vec<WorkspaceInfo>> {
-        let url = format!("{}/workspaces", BASE_URL);
+        let url = format!("{}/workspaces", self.base_url);
         let response = self
             .client
             .get(&url)
@@ -96,15 +290,35 @@ impl PostmanClient {
             .send()
             .await
             .context("Failed to fetch workspaces")?;
-        let body = Self::read_success_body(response, "fetch workspaces").await?;
+        let body = self.read_success_body(response, "fetch workspaces").await?;
         let response: WorkspacesResponse =
             serde_json::from_str(&body).context("Failed to parse workspaces response")?;
 
         Ok(response.workspaces)
     }
 
+    /// Number of collections and environments in a workspace.
+    pub async fn get_workspace_counts(&self, workspace_id: &str) -> Result<WorkspaceCounts> {
+        let url = format!("{}/workspaces/{}", self.base_url, workspace_id);
+        let response = self
+            .client
+            .get(&url)
+            .header("X-Api-Key", &self.api_key)
+            .send()
+            .await
+            .context("Failed to fetch workspace")?;
+        let body = self.read_success_body(response, "fetch workspace").await?;
+        let response: WorkspaceDetailResponse =
+            serde_json::from_str(&body).context("Failed to parse workspace response")?;
+
+        Ok(WorkspaceCounts {
+            collections: response.workspace.collections.len(),
+            environments: response.workspace.environments.len(),
+        })
+    }
+
     pub async fn list_collections(&self, workspace_id: Option<&str>) -> Result<Vec<CollectionInfo>> {
-        let mut url = format!("{}/collections", BASE_URL);
+        let mut url = format!("{}/collections", self.base_url);
         if let Some(ws_id) = workspace_id {
             url = format!("{}?workspace={}", url, ws_id);
         }
@@ -115,7 +329,7 @@ impl PostmanClient {
             .send()
             .await
             .context("Failed to fetch collections")?;
-        let body = Self::read_success_body(response, "fetch collections").await?;
+        let body = self.read_success_body(response, "fetch collections").await?;
         let response: CollectionsResponse =
             serde_json::from_str(&body).context("Failed to parse collections response")?;
 
@@ -123,7 +337,7 @@ impl PostmanClient {
     }
 
     pub async fn get_collection(&self, collection_uid: &str) -> Result<CollectionDetail> {
-        let url = format!("{}/collections/{}", BASE_URL, collection_uid);
+        let url = format!("{}/collections/{}", self.base_url, collection_uid);
         let response = self
             .client
             .get(&url)
@@ -131,7 +345,7 @@ impl PostmanClient {
             .send()
             .await
             .context("Failed to fetch collection details")?;
-        let response_text = Self::read_success_body(response, "fetch collection details").await?;
+        let response_text = self.read_success_body(response, "fetch collection details").await?;
 
         let response: CollectionDetailResponse = serde_json::from_str(&response_text)
             .with_context(|| {
@@ -144,18 +358,29 @@ impl PostmanClient {
         Ok(response.collection)
     }
 
-    pub async fn execute_request(&self, request: &Request) -> Result<ExecutedResponse> {
-        let url = request.url.to_string();
+    /// The opening request of a WebSocket or event stream, without a
+    /// timeout. An event stream is sent like any request (method, headers,
+    /// auth and body); a WebSocket handshake is a bodiless GET.
+    pub fn stream_request(&self, request: &Request, kind: StreamKind) -> Result<reqwest::RequestBuilder> {
+        match kind {
+            StreamKind::EventStream => self.request_builder(&self.exec_client, request),
+            StreamKind::WebSocket => {
+                let handshake = Request {
+                    method: String::from("GET"),
+                    url: RequestUrl::Simple(handshake_url(&request.url.send_url())),
+                    body: None,
+                    ..request.clone()
+                };
+                self.request_builder(&self.upgrade_client, &handshake)
+            }
+        }
+    }
 
-        let mut req_builder = match request.method.to_uppercase().as_str() {
-            "GET" => self.client.get(&url),
-            "POST" => self.client.post(&url),
-            "PUT" => self.client.put(&url),
-            "DELETE" => self.client.delete(&url),
-            "PATCH" => self.client.patch(&url),
-            "HEAD" => self.client.head(&url),
-            _ => self.client.get(&url),
-        };
+    /// A request's method, URL, enabled headers, default headers, auth and
+    /// body on `client`, ready for a timeout.
+    fn request_builder(&self, client: &Client, request: &Request) -> Result<reqwest::RequestBuilder> {
+        let url = request.url.send_url();
+        let mut req_builder = client.request(http_method(&request.method)?, &url);
 
         for header in &request.header {
             // Skip disabled headers and headers with empty keys
@@ -164,25 +389,117 @@ impl PostmanClient {
             }
             req_builder = req_builder.header(&header.key, &header.value);
         }
+        for (key, value) in missing_default_headers(request, &self.default_headers) {
+            req_builder = req_builder.header(key, value);
+        }
 
         // Apply the request's selected authorization, if any
         if let Some(auth) = &request.auth {
             req_builder = apply_auth(req_builder, auth);
         }
 
-        if let Some(body) = &request.body {
+        let has_content_type = request.header.iter().any(|h| {
+            !h.disabled.unwrap_or(false) && h.key.trim().eq_ignore_ascii_case("content-type")
+        });
+        if let Some(body) = request.body.as_ref().filter(|b| b.is_form_data()) {
+            req_builder = req_builder.multipart(multipart_form(&body.formdata)?);
+        } else if let Some(graphql) = request.body.as_ref().and_then(|b| b.graphql()) {
+            if !has_content_type {
+                req_builder = req_builder.header("Content-Type", "application/json");
+            }
+            req_builder = req_builder.body(graphql_payload(graphql)?.to_string());
+        } else if let Some(body) = &request.body {
             if let Some(raw) = &body.raw {
+                // Label the body from its Postman language unless the request
+                // sets its own Content-Type
+                let implied = body.language().and_then(content_type_for_language).filter(|_| !has_content_type);
+                if let Some(content_type) = implied {
+                    req_builder = req_builder.header("Content-Type", content_type);
+                }
                 req_builder = req_builder.body(raw.clone());
             }
         }
 
-        let response = req_builder
-            .send()
-            .await
-            .with_context(|| format!("Failed to execute request to {}", url))?;
+        Ok(req_builder)
+    }
+
+    /// Send an executed request. `version` is the request's own HTTP
+    /// version, if it sets one; otherwise `[settings] http_version` applies.
+    pub async fn execute_request(&self, request: &Request, timeout: Duration, version: Option<HttpVersion>) -> Result<ExecutedResponse> {
+        let version = version.unwrap_or(self.http_version);
+        let client = match version {
+            HttpVersion::Auto => &self.request_client,
+            HttpVersion::Http1 => &self.http1_client,
+            HttpVersion::Http2 => &self.http2_client,
+        };
+
+        let req_builder = self.request_builder(client, request)?;
+
+        let mut outgoing = req_builder.timeout(timeout).build().context("Failed to build request")?;
+        let mut redirects = Vec::new();
+        let mut response = loop {
+            // Kept for a 307/308, which repeat the request as it was
+            let repeat = outgoing.try_clone();
+            let method = outgoing.method().clone();
+            let sent_to = outgoing.url().clone();
+            let mut headers = outgoing.headers().clone();
+            let response = client.execute(outgoing).await.with_context(|| match version {
+                HttpVersion::Auto => format!("Failed to execute request to {} (timeout {}s)", sent_to, timeout.as_secs()),
+                forced => format!(
+                    "Failed to execute request to {} (timeout {}s) - the server may not accept {}, which this request is set to use (http_version)",
+                    sent_to,
+                    timeout.as_secs(),
+                    forced.label()
+                ),
+            })?;
+
+            let location = response
+                .headers()
+                .get(reqwest::header::LOCATION)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string);
+            let next = location
+                .as_deref()
+                .filter(|_| self.follow_redirects)
+                .and_then(|location| redirect_method(response.status().as_u16(), &method).zip(sent_to.join(location).ok()));
+            let Some(((next_method, keep_body), next_url)) = next else {
+                break response;
+            };
+            if redirects.len() >= MAX_REDIRECTS {
+                anyhow::bail!("Stopped after {} redirects (last to {})", MAX_REDIRECTS, next_url);
+            }
+            redirects.push(RedirectHop {
+                status: response.status().as_u16(),
+                url: sent_to.to_string(),
+                location: location.unwrap_or_default(),
+            });
+
+            let mut next_request = match repeat {
+                Some(repeat) if keep_body => repeat,
+                // A streamed (multipart) body can't be sent twice
+                None if keep_body => break response,
+                _ => {
+                    // The body is dropped along with the headers describing it
+                    headers.remove(reqwest::header::CONTENT_TYPE);
+                    headers.remove(reqwest::header::CONTENT_LENGTH);
+                    let mut bare = reqwest::Request::new(next_method, next_url.clone());
+                    *bare.headers_mut() = headers;
+                    *bare.timeout_mut() = Some(timeout);
+                    bare
+                }
+            };
+            *next_request.url_mut() = next_url.clone();
+            if !same_origin(&sent_to, &next_url) {
+                for name in [reqwest::header::AUTHORIZATION, reqwest::header::COOKIE, reqwest::header::PROXY_AUTHORIZATION] {
+                    next_request.headers_mut().remove(name);
+                }
+            }
+            outgoing = next_request;
+        };
 
         let status = response.status().as_u16();
         let status_text = response.status().to_string();
+        let protocol = format!("{:?}", response.version());
 
         let headers: Vec<(String, String)> = response
             .headers()
@@ -190,26 +507,78 @@ impl PostmanClient {
             .map(|(k, v)| (k.to_string(), v.to_str().unwrap_or("").to_string()))
             .collect();
 
-        let body = response
-            .text()
-            .await
-            .context("Failed to read response body")?;
+        // Read chunk by chunk so a huge body can't exhaust memory
+        let mut bytes = Vec::new();
+        let mut truncated_at = None;
+        while let Some(chunk) = response.chunk().await.context("Failed to read response body")? {
+            if !push_capped(&mut bytes, &chunk, self.max_response_bytes) {
+                truncated_at = Some(self.max_response_bytes);
+                break;
+            }
+        }
+        let body = decode_body(&bytes, &headers);
 
         Ok(ExecutedResponse {
             status,
             status_text,
             headers,
             body,
+            redirects,
+            version: protocol,
+            truncated_at,
         })
     }
 
+    /// Run an OAuth 2.0 client-credentials grant against `token_url`, sending
+    /// the client id/secret in the form-encoded body.
+    pub async fn fetch_client_credentials_token(
+        &self,
+        token_url: &str,
+        client_id: &str,
+        client_secret: &str,
+        scope: &str,
+    ) -> Result<OAuthToken> {
+        let mut form = vec![
+            ("grant_type", "client_credentials"),
+            ("client_id", client_id),
+            ("client_secret", client_secret),
+        ];
+        if !scope.is_empty() {
+            form.push(("scope", scope));
+        }
+
+        let response = self
+            .exec_client
+            .post(token_url)
+            .form(&form)
+            .send()
+            .await
+            .with_context(|| format!("Failed to request token from {}", token_url))?;
+
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .context("Failed to read token response")?;
+        if !status.is_success() {
+            anyhow::bail!(
+                "Token endpoint returned {}: {}",
+                status,
+                body.chars().take(200).collect::<String>()
+            );
+        }
+
+        serde_json::from_str(&body).context("Token response has no access_token")
+    }
+
     pub async fn update_collection(
         &self,
         collection_uid: &str,
         info: &CollectionDetailInfo,
         items: &[Item],
+        variables: &[Variable],
     ) -> Result<()> {
-        let url = format!("{}/collections/{}", BASE_URL, collection_uid);
+        let url = format!("{}/collections/{}", self.base_url, collection_uid);
 
         let body = serde_json::json!({
             "collection": {
@@ -218,7 +587,8 @@ impl PostmanClient {
                     "name": info.name,
                     "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json"
                 },
-                "item": items
+                "item": items,
+                "variable": variables
             }
         });
 
@@ -231,16 +601,89 @@ impl PostmanClient {
             .send()
             .await
             .context("Failed to update collection")?;
+        self.record_rate_limit(&response);
 
         if !response.status().is_success() {
             let status = response.status();
             let error_body = response.text().await.unwrap_or_default();
+            check_api_key(status, &error_body)?;
             anyhow::bail!("API returned error {}: {}", status, error_body);
         }
 
         Ok(())
     }
 
+    /// Create a collection in `workspace_id` (the API key's default workspace
+    /// when None) and return it.
+    pub async fn create_collection(
+        &self,
+        workspace_id: Option<&str>,
+        name: &str,
+        items: &[Item],
+        variables: &[Variable],
+    ) -> Result<CollectionInfo> {
+        let mut url = format!("{}/collections", self.base_url);
+        if let Some(ws_id) = workspace_id {
+            url = format!("{}?workspace={}", url, ws_id);
+        }
+
+        let body = serde_json::json!({
+            "collection": {
+                "info": {
+                    "name": name,
+                    "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json"
+                },
+                "item": items,
+                "variable": variables
+            }
+        });
+
+        let response = self
+            .client
+            .post(&url)
+            .header("X-Api-Key", &self.api_key)
+            .header("Content-Type", "application/json")
+            .json(&body)
+            .send()
+            .await
+            .context("Failed to create collection")?;
+        self.record_rate_limit(&response);
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_body = response.text().await.unwrap_or_default();
+            check_api_key(status, &error_body)?;
+            anyhow::bail!("API returned error {}: {}", status, error_body);
+        }
+
+        let created: CreatedCollectionResponse = response.json().await.context("Failed to parse created collection")?;
+        Ok(created.collection)
+    }
+
+    /// `update_collection`, but only if the collection in Postman still
+    /// matches `base` and `base_variables` (what `items` and `variables`
+    /// were derived from). Otherwise fails with `CollectionChanged` instead
+    /// of overwriting the newer version.
+    pub async fn update_collection_checked(
+        &self,
+        collection_uid: &str,
+        info: &CollectionDetailInfo,
+        base: &[Item],
+        base_variables: &[Variable],
+        items: &[Item],
+        variables: &[Variable],
+    ) -> Result<()> {
+        let server = self.get_collection(collection_uid).await?;
+        let unchanged = serde_json::to_value(base).context("Failed to serialize collection")?
+            == serde_json::to_value(&server.item).context("Failed to serialize collection")?
+            && serde_json::to_value(base_variables).context("Failed to serialize collection")?
+                == serde_json::to_value(&server.variable).context("Failed to serialize collection")?;
+        if !unchanged {
+            return Err(CollectionChanged { server }.into());
+        }
+        self.update_collection(collection_uid, info, items, variables).await
+    }
+
     /// Update a single request using the individual request endpoint
     /// This avoids validation errors from unrelated requests in the collection
     pub async fn update_request(
@@ -250,7 +693,7 @@ impl PostmanClient {
         name: &str,
         request: &Request,
     ) -> Result<()> {
-        let url = format!("{}/collections/{}/requests/{}", BASE_URL, collection_uid, request_id);
+        let url = format!("{}/collections/{}/requests/{}", self.base_url, collection_uid, request_id);
 
         let body = serde_json::json!({
             "name": name,
@@ -266,10 +709,12 @@ impl PostmanClient {
             .send()
             .await
             .context("Failed to update request")?;
+        self.record_rate_limit(&response);
 
         if !response.status().is_success() {
             let status = response.status();
             let error_body = response.text().await.unwrap_or_default();
+            check_api_key(status, &error_body)?;
             anyhow::bail!("API returned error {}: {}", status, error_body);
         }
 
@@ -277,7 +722,7 @@ impl PostmanClient {
     }
 
     pub async fn list_environments(&self, workspace_id: Option<&str>) -> Result<Vec<EnvironmentInfo>> {
-        let mut url = format!("{}/environments", BASE_URL);
+        let mut url = format!("{}/environments", self.base_url);
         if let Some(ws_id) = workspace_id {
             url = format!("{}?workspace={}", url, ws_id);
         }
@@ -288,7 +733,7 @@ impl PostmanClient {
             .send()
             .await
             .context("Failed to fetch environments")?;
-        let body = Self::read_success_body(response, "fetch environments").await?;
+        let body = self.read_success_body(response, "fetch environments").await?;
         let response: EnvironmentsResponse =
             serde_json::from_str(&body).context("Failed to parse environments response")?;
 
@@ -296,7 +741,7 @@ impl PostmanClient {
     }
 
     pub async fn get_environment(&self, environment_uid: &str) -> Result<EnvironmentDetail> {
-        let url = format!("{}/environments/{}", BASE_URL, environment_uid);
+        let url = format!("{}/environments/{}", self.base_url, environment_uid);
         let response = self
             .client
             .get(&url)
@@ -304,7 +749,7 @@ impl PostmanClient {
             .send()
             .await
             .context("Failed to fetch environment details")?;
-        let body = Self::read_success_body(response, "fetch environment details").await?;
+        let body = self.read_success_body(response, "fetch environment details").await?;
         let response: EnvironmentDetailResponse =
             serde_json::from_str(&body).context("Failed to parse environment response")?;
 
@@ -317,7 +762,7 @@ impl PostmanClient {
         name: &str,
         values: &[Variable],
     ) -> Result<()> {
-        let url = format!("{}/environments/{}", BASE_URL, environment_uid);
+        let url = format!("{}/environments/{}", self.base_url, environment_uid);
 
         let body = serde_json::json!({
             "environment": {
@@ -335,13 +780,256 @@ impl PostmanClient {
             .send()
             .await
             .context("Failed to update environment")?;
+        self.record_rate_limit(&response);
 
         if !response.status().is_success() {
             let status = response.status();
             let error_body = response.text().await.unwrap_or_default();
+            check_api_key(status, &error_body)?;
             anyhow::bail!("API returned error {}: {}", status, error_body);
         }
 
         Ok(())
     }
 }
+
+/// Build a multipart form from Postman form-data fields, reading file fields
+/// from disk. Disabled fields are skipped.
+fn multipart_form(fields: &[FormField]) -> Result<reqwest::multipart::Form> {
+    let mut form = reqwest::multipart::Form::new();
+    for field in fields.iter().filter(|f| f.is_enabled()) {
+        if !field.is_file() {
+            form = form.text(field.key.clone(), field.value.clone().unwrap_or_default());
+            continue;
+        }
+        for path in field.files() {
+            let data = std::fs::read(&path)
+                .with_context(|| format!("Failed to read file for form field '{}': {}", field.key, path))?;
+            let file_name = std::path::Path::new(&path)
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| path.clone());
+            let mut part = reqwest::multipart::Part::bytes(data).file_name(file_name);
+            if let Some(content_type) = &field.content_type {
+                part = part
+                    .mime_str(content_type)
+                    .with_context(|| format!("Invalid content type for form field '{}': {}", field.key, content_type))?;
+            }
+            form = form.part(field.key.clone(), part);
+        }
+    }
+    Ok(form)
+}
+
+/// The JSON document a GraphQL body is sent as. Blank variables are left
+/// out; anything else must be valid JSON.
+pub fn graphql_payload(body: &GraphqlBody) -> Result<serde_json::Value> {
+    let mut payload = serde_json::json!({ "query": body.query });
+    if let Some(variables) = body.variables.as_deref().filter(|v| !v.trim().is_empty()) {
+        let variables: serde_json::Value =
+            serde_json::from_str(variables).context("GraphQL variables are not valid JSON")?;
+        payload["variables"] = variables;
+    }
+    Ok(payload)
+}
+
+/// The `defaults` that `request` doesn't set itself: a header of the same
+/// name (case-insensitively) that is enabled on the request wins.
+pub fn missing_default_headers<'a>(request: &Request, defaults: &'a [(String, String)]) -> Vec<(&'a str, &'a str)> {
+    defaults
+        .iter()
+        .filter(|(key, _)| !key.trim().is_empty())
+        .filter(|(key, _)| {
+            !request
+                .header
+                .iter()
+                .any(|h| !h.disabled.unwrap_or(false) && h.key.trim().eq_ignore_ascii_case(key.trim()))
+        })
+        .map(|(key, value)| (key.as_str(), value.as_str()))
+        .collect()
+}
+
+/// Append `chunk` to `body` without letting it grow past `cap` bytes.
+/// Returns false when part of the chunk had to be dropped.
+fn push_capped(body: &mut Vec<u8>, chunk: &[u8], cap: usize) -> bool {
+    let room = cap.saturating_sub(body.len());
+    body.extend_from_slice(&chunk[..chunk.len().min(room)]);
+    chunk.len() <= room
+}
+
+/// How to follow a redirect with `status` to a request sent with `method`:
+/// the method to use and whether to keep the body, or None if `status`
+/// isn't a redirect. 307/308 (and 301/302 other than after a POST) repeat
+/// the request; 303 and a redirected POST switch to a bodiless GET.
+fn redirect_method(status: u16, method: &reqwest::Method) -> Option<(reqwest::Method, bool)> {
+    match status {
+        307 | 308 => Some((method.clone(), true)),
+        301 | 302 if *method != reqwest::Method::POST => Some((method.clone(), true)),
+        303 if *method == reqwest::Method::HEAD => Some((reqwest::Method::HEAD, false)),
+        301..=303 => Some((reqwest::Method::GET, false)),
+        _ => None,
+    }
+}
+
+/// Whether two URLs share scheme, host and port; credentials aren't
+/// forwarded across origins.
+fn same_origin(a: &reqwest::Url, b: &reqwest::Url) -> bool {
+    a.scheme() == b.scheme() && a.host_str() == b.host_str() && a.port_or_known_default() == b.port_or_known_default()
+}
+
+/// The HTTP method to send for a request's method string. Blank means GET
+/// (Postman's default); anything else that isn't a valid token is an error
+/// rather than silently becoming a GET.
+fn http_method(method: &str) -> Result<reqwest::Method> {
+    let method = method.trim().to_uppercase();
+    match method.as_str() {
+        "" | "GET" => Ok(reqwest::Method::GET),
+        "POST" => Ok(reqwest::Method::POST),
+        "PUT" => Ok(reqwest::Method::PUT),
+        "DELETE" => Ok(reqwest::Method::DELETE),
+        "PATCH" => Ok(reqwest::Method::PATCH),
+        "HEAD" => Ok(reqwest::Method::HEAD),
+        "OPTIONS" => Ok(reqwest::Method::OPTIONS),
+        "TRACE" => Ok(reqwest::Method::TRACE),
+        other => reqwest::Method::from_bytes(other.as_bytes())
+            .map_err(|_| anyhow::anyhow!("Invalid HTTP method: {}", other)),
+    }
+}
+
+/// A response body as text, in the charset its Content-Type names (as
+/// reqwest's `text()` does), or lossy UTF-8 without a known one.
+fn decode_body(bytes: &[u8], headers: &[(String, String)]) -> String {
+    let charset = headers
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case("content-type"))
+        .and_then(|(_, value)| {
+            value.split(';').skip(1).find_map(|param| {
+                let (name, value) = param.split_once('=')?;
+                name.trim().eq_ignore_ascii_case("charset").then(|| value.trim().trim_matches('"'))
+            })
+        });
+    match charset.and_then(|label| encoding_rs::Encoding::for_label(label.as_bytes())) {
+        Some(encoding) => encoding.decode(bytes).0.into_owned(),
+        None => String::from_utf8_lossy(bytes).into_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_api_key, decode_body, graphql_payload, http_method, is_api_key_error, missing_default_headers, multipart_form, push_capped, redirect_method, same_origin};
+    use reqwest::{Method, StatusCode};
+
+    #[test]
+    fn options_request_is_sent_as_options() {
+        let request = reqwest::Client::new()
+            .request(http_method("options").unwrap(), "http://localhost/")
+            .build()
+            .unwrap();
+        assert_eq!(request.method(), Method::OPTIONS);
+    }
+
+    #[test]
+    fn unknown_methods_are_kept_or_rejected() {
+        assert_eq!(http_method("TRACE").unwrap(), Method::TRACE);
+        assert_eq!(http_method("PROPFIND").unwrap().as_str(), "PROPFIND");
+        assert_eq!(http_method("").unwrap(), Method::GET);
+        assert!(http_method("BAD METHOD").is_err());
+    }
+
+    #[test]
+    fn unauthorized_and_forbidden_are_api_key_errors() {
+        let body = r#"{"error":{"name":"AuthenticationError","message":"Invalid API Key."}}"#;
+        let err = check_api_key(StatusCode::UNAUTHORIZED, body).unwrap_err();
+        assert!(is_api_key_error(&err));
+        assert!(err.to_string().ends_with("(401): Invalid API Key."));
+        assert!(check_api_key(StatusCode::FORBIDDEN, "").is_err());
+        assert!(check_api_key(StatusCode::NOT_FOUND, body).is_ok());
+    }
+
+    #[test]
+    fn unreadable_form_file_names_the_field() {
+        let field: crate::api::FormField = serde_json::from_value(serde_json::json!({
+            "key": "avatar",
+            "type": "file",
+            "src": "/nonexistent/lazypost-avatar.png"
+        }))
+        .unwrap();
+        let err = multipart_form(&[field]).unwrap_err();
+        assert!(err.to_string().contains("form field 'avatar'"));
+    }
+
+    #[test]
+    fn graphql_body_is_sent_as_query_and_variables_json() {
+        let body: crate::api::RequestBody = serde_json::from_value(serde_json::json!({
+            "mode": "graphql",
+            "graphql": { "query": "query($id: ID!) { user(id: $id) { name } }", "variables": "{\"id\": \"42\"}" }
+        }))
+        .unwrap();
+        let payload = graphql_payload(body.graphql().unwrap()).unwrap();
+        assert_eq!(
+            payload,
+            serde_json::json!({ "query": "query($id: ID!) { user(id: $id) { name } }", "variables": { "id": "42" } })
+        );
+
+        let no_variables = crate::api::GraphqlBody { query: String::from("{ me }"), variables: Some(String::from("  ")), ..Default::default() };
+        assert_eq!(graphql_payload(&no_variables).unwrap(), serde_json::json!({ "query": "{ me }" }));
+
+        let invalid = crate::api::GraphqlBody { variables: Some(String::from("{id:")), ..Default::default() };
+        assert!(graphql_payload(&invalid).is_err());
+    }
+
+    #[test]
+    fn default_headers_do_not_override_request_headers() {
+        let request: crate::api::Request = serde_json::from_value(serde_json::json!({
+            "method": "GET",
+            "url": "https://example.com",
+            "header": [
+                { "key": "x-org-id", "value": "7" },
+                { "key": "Accept", "value": "text/plain", "disabled": true }
+            ]
+        }))
+        .unwrap();
+        let defaults = vec![
+            (String::from("X-Org-Id"), String::from("42")),
+            (String::from("Accept"), String::from("application/json")),
+            (String::from(" "), String::from("ignored")),
+        ];
+        assert_eq!(missing_default_headers(&request, &defaults), vec![("Accept", "application/json")]);
+    }
+
+    #[test]
+    fn response_bodies_stop_at_the_cap() {
+        let mut body = Vec::new();
+        assert!(push_capped(&mut body, b"hello ", 10));
+        assert!(!push_capped(&mut body, b"world", 10));
+        assert_eq!(body, b"hello worl");
+        assert!(!push_capped(&mut body, b"!", 10));
+        assert_eq!(body.len(), 10);
+        assert!(push_capped(&mut body, b"", 10));
+    }
+
+    #[test]
+    fn redirects_keep_or_drop_the_method_and_body() {
+        assert_eq!(redirect_method(307, &Method::POST), Some((Method::POST, true)));
+        assert_eq!(redirect_method(302, &Method::POST), Some((Method::GET, false)));
+        assert_eq!(redirect_method(301, &Method::PUT), Some((Method::PUT, true)));
+        assert_eq!(redirect_method(303, &Method::PUT), Some((Method::GET, false)));
+        assert_eq!(redirect_method(304, &Method::GET), None);
+        assert_eq!(redirect_method(200, &Method::GET), None);
+
+        let url = |u: &str| reqwest::Url::parse(u).unwrap();
+        assert!(same_origin(&url("https://api.example.com/a"), &url("https://api.example.com:443/b")));
+        assert!(!same_origin(&url("https://api.example.com/a"), &url("https://cdn.example.com/a")));
+        assert!(!same_origin(&url("http://api.example.com/a"), &url("https://api.example.com/a")));
+    }
+
+    #[test]
+    fn bodies_decode_in_their_declared_charset() {
+        let ct = |v: &str| vec![(String::from("Content-Type"), v.to_string())];
+        assert_eq!(decode_body(b"caf\xe9", &ct("text/plain; charset=ISO-8859-1")), "caf\u{e9}");
+        assert_eq!(decode_body(b"caf\xe9", &ct("text/plain; charset=\"windows-1252\"")), "caf\u{e9}");
+        assert_eq!(decode_body("caf\u{e9}".as_bytes(), &ct("application/json")), "caf\u{e9}");
+        assert_eq!(decode_body(b"caf\xe9", &ct("text/plain; charset=no-such-charset")), "caf\u{fffd}");
+        assert_eq!(decode_body(b"ok", &[]), "ok");
+    }
+}
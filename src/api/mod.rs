@@ -1,5 +1,6 @@
 pub mod client;
 pub mod models;
+pub mod stream;
 
 pub use client::PostmanClient;
 pub use models::*;
@@ -11,6 +11,11 @@ pub struct CollectionInfo {
     pub uid: String,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreatedCollectionResponse {
+    pub collection: CollectionInfo,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct CollectionDetailResponse {
     pub collection: CollectionDetail,
@@ -46,6 +51,9 @@ pub struct Folder {
     pub item: Vec<Item>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<serde_json::Value>,
+    /// Folder-scoped variables, visible to every request beneath the folder.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub variable: Vec<Variable>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -56,6 +64,94 @@ pub struct RequestItem {
     pub request: Request,
     #[serde(default)]
     pub response: Vec<serde_json::Value>,
+    /// Pre-request and test scripts. Shown read-only; never executed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub event: Vec<Event>,
+    #[serde(rename = "protocolProfileBehavior", default, skip_serializing_if = "Option::is_none")]
+    pub protocol_profile_behavior: Option<ProtocolProfileBehavior>,
+}
+
+/// Postman's per-request protocol settings. Only `disableBodyPruning` is
+/// used; the rest are kept so saving preserves them.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct ProtocolProfileBehavior {
+    /// Send the body even on GET/HEAD (Postman drops it otherwise).
+    #[serde(rename = "disableBodyPruning", default, skip_serializing_if = "Option::is_none")]
+    pub disable_body_pruning: Option<bool>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl RequestItem {
+    /// Whether a body on a GET/HEAD request is sent rather than dropped.
+    pub fn keeps_body_on_get(&self) -> bool {
+        self.protocol_profile_behavior
+            .as_ref()
+            .and_then(|p| p.disable_body_pruning)
+            .unwrap_or(false)
+    }
+}
+
+impl Request {
+    /// Whether the method is one Postman strips bodies from by default.
+    pub fn prunes_body(&self) -> bool {
+        let method = self.method.trim();
+        method.is_empty() || method.eq_ignore_ascii_case("GET") || method.eq_ignore_ascii_case("HEAD")
+    }
+}
+
+/// A script hook on a request, e.g. `{ "listen": "test", "script": { "exec": [...] } }`.
+/// Fields lazypost doesn't use are kept so saving a collection preserves them.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Event {
+    /// `prerequest` or `test`.
+    pub listen: String,
+    #[serde(default)]
+    pub script: Script,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct Script {
+    #[serde(default)]
+    pub exec: ScriptSource,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Script source: Postman stores it as an array of lines, but a single
+/// string is accepted too.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum ScriptSource {
+    Lines(Vec<String>),
+    Text(String),
+}
+
+impl Default for ScriptSource {
+    fn default() -> Self {
+        ScriptSource::Lines(Vec::new())
+    }
+}
+
+impl Event {
+    /// Display name for the hook, e.g. "Pre-request script".
+    pub fn label(&self) -> String {
+        match self.listen.as_str() {
+            "prerequest" => String::from("Pre-request script"),
+            "test" => String::from("Tests"),
+            other => format!("{} script", other),
+        }
+    }
+
+    /// The script's source lines.
+    pub fn lines(&self) -> Vec<String> {
+        match &self.script.exec {
+            ScriptSource::Lines(lines) => lines.clone(),
+            ScriptSource::Text(text) => text.lines().map(|l| l.to_string()).collect(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -150,6 +246,28 @@ impl RequestUrl {
             RequestUrl::Empty => String::new(),
         }
     }
+
+    /// The URL as it is sent: the raw URL without the query parameters
+    /// Postman has disabled (some exports keep them in `raw`).
+    pub fn send_url(&self) -> String {
+        let RequestUrl::Complex(detail) = self else {
+            return self.to_string();
+        };
+        let disabled: Vec<(&str, Option<&str>)> = self
+            .disabled_query()
+            .map(|p| (p.key.as_str(), p.value.as_deref()))
+            .collect();
+        crate::util::remove_query_params(&detail.raw.clone().unwrap_or_default(), &disabled)
+    }
+
+    /// Query parameters Postman has disabled, which are not sent.
+    pub fn disabled_query(&self) -> impl Iterator<Item = &QueryParam> {
+        let query = match self {
+            RequestUrl::Complex(detail) => detail.query.as_slice(),
+            _ => &[],
+        };
+        query.iter().filter(|p| p.is_disabled())
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -164,14 +282,22 @@ pub struct UrlDetail {
     pub query: Vec<QueryParam>,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct QueryParam {
     pub key: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub value: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none", deserialize_with = "deserialize_bool_option")]
+    pub disabled: Option<bool>,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+impl QueryParam {
+    pub fn is_disabled(&self) -> bool {
+        self.disabled.unwrap_or(false)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct Header {
     pub key: String,
     pub value: String,
@@ -236,6 +362,170 @@ pub struct RequestBody {
     pub mode: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub raw: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub options: Option<BodyOptions>,
+    /// Fields of a `formdata` (multipart) body.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub formdata: Vec<FormField>,
+    /// Query and variables of a `graphql` body.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub graphql: Option<GraphqlBody>,
+}
+
+/// A GraphQL body, sent as JSON `{"query": ..., "variables": ...}`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct GraphqlBody {
+    #[serde(default)]
+    pub query: String,
+    /// The variables as JSON text, as Postman stores them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variables: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// One multipart field: text (`value`) or a file read from `src` at send time.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FormField {
+    pub key: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    /// `text` (the default) or `file`.
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub field_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub src: Option<FormFileSource>,
+    #[serde(rename = "contentType", default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none", deserialize_with = "deserialize_bool_option")]
+    pub disabled: Option<bool>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Path(s) of a file field; Postman writes one path as a string and several
+/// as an array.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum FormFileSource {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl FormField {
+    pub fn is_file(&self) -> bool {
+        self.field_type.as_deref() == Some("file")
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.disabled.unwrap_or(false) && !self.key.trim().is_empty()
+    }
+
+    /// Local paths of a file field's files.
+    pub fn files(&self) -> Vec<String> {
+        match &self.src {
+            Some(FormFileSource::One(path)) if !path.is_empty() => vec![path.clone()],
+            Some(FormFileSource::Many(paths)) => paths.clone(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct BodyOptions {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw: Option<RawBodyOptions>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct RawBodyOptions {
+    /// Postman's raw body language: `json`, `xml`, `html`, `javascript` or `text`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+}
+
+/// Raw body languages Postman offers, in the order `C` cycles through them.
+pub const BODY_LANGUAGES: &[&str] = &["text", "json", "xml", "html", "javascript"];
+
+impl RequestBody {
+    /// A raw body, tagged with its language when one is known.
+    pub fn raw(text: String, language: Option<String>) -> Self {
+        RequestBody {
+            mode: Some("raw".to_string()),
+            raw: Some(text),
+            options: language.map(|language| BodyOptions {
+                raw: Some(RawBodyOptions { language: Some(language) }),
+            }),
+            formdata: Vec::new(),
+            graphql: None,
+        }
+    }
+
+    /// Whether the body is sent as multipart/form-data.
+    pub fn is_form_data(&self) -> bool {
+        self.mode.as_deref() == Some("formdata")
+    }
+
+    /// The GraphQL query and variables, when the body is in `graphql` mode.
+    pub fn graphql(&self) -> Option<&GraphqlBody> {
+        self.graphql.as_ref().filter(|_| self.mode.as_deref() == Some("graphql"))
+    }
+
+    /// Form-data and GraphQL bodies aren't edited as raw text, so a text
+    /// edit without a body leaves them as they are.
+    pub fn is_structured(&self) -> bool {
+        self.is_form_data() || self.graphql().is_some()
+    }
+
+    pub fn language(&self) -> Option<&str> {
+        self.options.as_ref()?.raw.as_ref()?.language.as_deref()
+    }
+}
+
+/// `Content-Type` sent for a raw body of the given Postman language.
+pub fn content_type_for_language(language: &str) -> Option<&'static str> {
+    match language {
+        "json" => Some("application/json"),
+        "xml" => Some("application/xml"),
+        "html" => Some("text/html"),
+        "javascript" => Some("application/javascript"),
+        "text" => Some("text/plain"),
+        _ => None,
+    }
+}
+
+/// Rate-limit budget reported by `X-RateLimit-Remaining`/`X-RateLimit-Limit`
+/// (or the unprefixed `RateLimit-*`) response headers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimit {
+    pub remaining: u64,
+    pub limit: Option<u64>,
+}
+
+impl RateLimit {
+    const REMAINING: [&'static str; 3] = ["x-ratelimit-remaining", "ratelimit-remaining", "x-rate-limit-remaining"];
+    const LIMIT: [&'static str; 3] = ["x-ratelimit-limit", "ratelimit-limit", "x-rate-limit-limit"];
+
+    pub fn from_headers(headers: &[(String, String)]) -> Option<Self> {
+        let find = |names: &[&str]| {
+            headers
+                .iter()
+                .find(|(k, _)| names.iter().any(|n| k.eq_ignore_ascii_case(n)))
+                .and_then(|(_, v)| v.trim().parse::<u64>().ok())
+        };
+        Some(RateLimit {
+            remaining: find(&Self::REMAINING)?,
+            limit: find(&Self::LIMIT),
+        })
+    }
+
+    /// Under 10% of the limit left (or 5 requests when the limit is unknown).
+    pub fn is_low(&self) -> bool {
+        match self.limit {
+            Some(limit) if limit > 0 => self.remaining * 10 < limit,
+            _ => self.remaining < 5,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -244,6 +534,31 @@ pub struct ExecutedResponse {
     pub status_text: String,
     pub headers: Vec<(String, String)>,
     pub body: String,
+    /// Redirects followed on the way to this response, in order.
+    pub redirects: Vec<RedirectHop>,
+    /// Protocol the response came over, e.g. `HTTP/1.1` or `HTTP/2.0`.
+    pub version: String,
+    /// Set to the cap (`max_response_bytes`) when the body was longer and
+    /// only its start was kept.
+    pub truncated_at: Option<usize>,
+}
+
+/// One redirect response: its status and the URL that returned it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RedirectHop {
+    pub status: u16,
+    pub url: String,
+    /// Where the `Location` header pointed.
+    pub location: String,
+}
+
+/// Successful response from an OAuth 2.0 token endpoint.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OAuthToken {
+    pub access_token: String,
+    /// Lifetime in seconds, when the server reports one.
+    #[serde(default)]
+    pub expires_in: Option<u64>,
 }
 
 // Environment models
@@ -276,6 +591,9 @@ pub struct Variable {
     pub value: String,
     #[serde(default)]
     pub enabled: Option<bool>,
+    /// Postman variable type (`default` or `secret`).
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub var_type: Option<String>,
 }
 
 // Workspace models
@@ -289,3 +607,25 @@ pub struct WorkspaceInfo {
     pub id: String,
     pub name: String,
 }
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct WorkspaceDetailResponse {
+    pub workspace: WorkspaceDetail,
+}
+
+/// A single workspace as returned by `GET /workspaces/{id}`; only the number
+/// of collections and environments is kept.
+#[derive(Debug, Clone, Deserialize)]
+pub struct WorkspaceDetail {
+    #[serde(default)]
+    pub collections: Vec<serde::de::IgnoredAny>,
+    #[serde(default)]
+    pub environments: Vec<serde::de::IgnoredAny>,
+}
+
+/// How many collections and environments a workspace contains.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WorkspaceCounts {
+    pub collections: usize,
+    pub environments: usize,
+}
@@ -0,0 +1,386 @@
+//! Connections whose response arrives over time: WebSocket (`ws://`,
+//! `wss://`) and server-sent events (`text/event-stream`).
+
+use anyhow::{Context, Result};
+use base64::Engine;
+use std::sync::mpsc::Sender;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::sync::mpsc::UnboundedReceiver;
+
+use super::models::Request;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamKind {
+    WebSocket,
+    EventStream,
+}
+
+impl StreamKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            StreamKind::WebSocket => "WebSocket",
+            StreamKind::EventStream => "SSE",
+        }
+    }
+}
+
+/// Something that happened on an open stream, in the order it happened.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StreamEvent {
+    Connected(String),
+    Received(String),
+    Sent(String),
+    Closed(String),
+    Failed(String),
+}
+
+/// Whether `request` opens a stream instead of a one-off exchange: a
+/// `ws://`/`wss://` URL, or an `Accept: text/event-stream` header.
+pub fn stream_kind(request: &Request) -> Option<StreamKind> {
+    let url = request.url.to_string().trim().to_ascii_lowercase();
+    if url.starts_with("ws://") || url.starts_with("wss://") {
+        return Some(StreamKind::WebSocket);
+    }
+    request
+        .header
+        .iter()
+        .filter(|h| !h.disabled.unwrap_or(false))
+        .any(|h| h.key.trim().eq_ignore_ascii_case("accept") && h.value.contains("text/event-stream"))
+        .then_some(StreamKind::EventStream)
+}
+
+/// The `http(s)://` URL a WebSocket handshake is sent to.
+pub fn handshake_url(url: &str) -> String {
+    let trimmed = url.trim();
+    let lower = trimmed.to_ascii_lowercase();
+    if lower.starts_with("wss://") {
+        format!("https://{}", &trimmed[6..])
+    } else if lower.starts_with("ws://") {
+        format!("http://{}", &trimmed[5..])
+    } else {
+        trimmed.to_string()
+    }
+}
+
+const OP_CONTINUATION: u8 = 0x0;
+const OP_TEXT: u8 = 0x1;
+const OP_BINARY: u8 = 0x2;
+const OP_CLOSE: u8 = 0x8;
+const OP_PING: u8 = 0x9;
+const OP_PONG: u8 = 0xA;
+
+/// The largest frame, or message across continuation frames, a peer may
+/// send before the stream fails rather than buffering it.
+const MAX_MESSAGE_SIZE: usize = 16 * 1024 * 1024;
+
+/// One WebSocket frame, unmasked.
+#[derive(Debug, PartialEq)]
+pub struct Frame {
+    pub fin: bool,
+    pub opcode: u8,
+    pub payload: Vec<u8>,
+}
+
+/// Parse the frame at the start of `buf`, returning it and the number of
+/// bytes it used, or None until the whole frame has arrived. Fails on a
+/// frame larger than `MAX_MESSAGE_SIZE`.
+pub fn parse_frame(buf: &[u8]) -> Result<Option<(Frame, usize)>> {
+    let Some((first, len, mask, pos)) = frame_header(buf) else {
+        return Ok(None);
+    };
+    if len > MAX_MESSAGE_SIZE {
+        anyhow::bail!("Server sent a {} frame (limit {})", crate::util::format_bytes(len), crate::util::format_bytes(MAX_MESSAGE_SIZE));
+    }
+    let Some(payload) = buf.get(pos..pos + len) else {
+        return Ok(None);
+    };
+    let mut payload = payload.to_vec();
+    if let Some(mask) = mask {
+        payload.iter_mut().enumerate().for_each(|(i, b)| *b ^= mask[i % 4]);
+    }
+    let frame = Frame { fin: first & 0x80 != 0, opcode: first & 0x0F, payload };
+    Ok(Some((frame, pos + len)))
+}
+
+/// A frame's first byte, payload length, mask and where its payload
+/// starts, or None until the header has arrived.
+fn frame_header(buf: &[u8]) -> Option<(u8, usize, Option<[u8; 4]>, usize)> {
+    let (&first, &second) = (buf.first()?, buf.get(1)?);
+    let masked = second & 0x80 != 0;
+    let (len, mut pos) = match second & 0x7F {
+        126 => (u16::from_be_bytes(buf.get(2..4)?.try_into().ok()?) as usize, 4),
+        127 => (usize::try_from(u64::from_be_bytes(buf.get(2..10)?.try_into().ok()?)).unwrap_or(usize::MAX), 10),
+        n => (n as usize, 2),
+    };
+    let mask = if masked {
+        let mask: [u8; 4] = buf.get(pos..pos + 4)?.try_into().ok()?;
+        pos += 4;
+        Some(mask)
+    } else {
+        None
+    };
+    Some((first, len, mask, pos))
+}
+
+/// A complete (FIN) frame as a client sends it, masked with `mask`.
+pub fn encode_frame(opcode: u8, payload: &[u8], mask: [u8; 4]) -> Vec<u8> {
+    let mut frame = vec![0x80 | opcode];
+    match payload.len() {
+        len if len < 126 => frame.push(0x80 | len as u8),
+        len if len <= u16::MAX as usize => {
+            frame.push(0x80 | 126);
+            frame.extend_from_slice(&(len as u16).to_be_bytes());
+        }
+        len => {
+            frame.push(0x80 | 127);
+            frame.extend_from_slice(&(len as u64).to_be_bytes());
+        }
+    }
+    frame.extend_from_slice(&mask);
+    frame.extend(payload.iter().enumerate().map(|(i, b)| b ^ mask[i % 4]));
+    frame
+}
+
+fn random_mask() -> [u8; 4] {
+    let bytes = uuid::Uuid::new_v4().into_bytes();
+    [bytes[0], bytes[1], bytes[2], bytes[3]]
+}
+
+/// "code: reason" from a close frame's payload.
+fn close_reason(payload: &[u8]) -> String {
+    match payload {
+        [hi, lo, reason @ ..] => {
+            let code = u16::from_be_bytes([*hi, *lo]);
+            let reason = String::from_utf8_lossy(reason);
+            if reason.is_empty() { format!("code {}", code) } else { format!("code {}: {}", code, reason) }
+        }
+        _ => String::from("no reason given"),
+    }
+}
+
+/// Perform the WebSocket handshake with `builder` (a GET to the
+/// `handshake_url`, over HTTP/1.1) and relay messages until either side
+/// closes. Dropping the sender of `outgoing` closes the connection.
+pub async fn run_websocket(
+    builder: reqwest::RequestBuilder,
+    events: &Sender<StreamEvent>,
+    mut outgoing: UnboundedReceiver<String>,
+) -> Result<()> {
+    let key = base64::engine::general_purpose::STANDARD.encode(uuid::Uuid::new_v4().as_bytes());
+    let response = builder
+        .header("Connection", "Upgrade")
+        .header("Upgrade", "websocket")
+        .header("Sec-WebSocket-Version", "13")
+        .header("Sec-WebSocket-Key", key)
+        .send()
+        .await
+        .context("Failed to connect")?;
+    let status = response.status();
+    if status != reqwest::StatusCode::SWITCHING_PROTOCOLS {
+        anyhow::bail!("Server refused the WebSocket upgrade: {}", status);
+    }
+    let _ = events.send(StreamEvent::Connected(status.to_string()));
+
+    let upgraded = response.upgrade().await.context("Failed to upgrade the connection")?;
+    let (mut reader, mut writer) = tokio::io::split(upgraded);
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 8192];
+    // A message split across continuation frames: its opcode and data so far
+    let mut partial: Option<(u8, Vec<u8>)> = None;
+
+    loop {
+        tokio::select! {
+            read = reader.read(&mut chunk) => {
+                let n = read.context("Connection lost")?;
+                if n == 0 {
+                    let _ = events.send(StreamEvent::Closed(String::from("Connection closed by the server")));
+                    return Ok(());
+                }
+                buf.extend_from_slice(&chunk[..n]);
+                while let Some((frame, used)) = parse_frame(&buf)? {
+                    buf.drain(..used);
+                    match frame.opcode {
+                        OP_PING => writer.write_all(&encode_frame(OP_PONG, &frame.payload, random_mask())).await?,
+                        OP_PONG => {}
+                        OP_CLOSE => {
+                            let _ = writer.write_all(&encode_frame(OP_CLOSE, &frame.payload, random_mask())).await;
+                            let reason = close_reason(&frame.payload);
+                            let _ = events.send(StreamEvent::Closed(format!("Closed by the server ({})", reason)));
+                            return Ok(());
+                        }
+                        opcode => {
+                            let (opcode, mut data) = match (opcode, partial.take()) {
+                                (OP_CONTINUATION, Some(started)) => started,
+                                _ => (opcode, Vec::new()),
+                            };
+                            data.extend_from_slice(&frame.payload);
+                            if data.len() > MAX_MESSAGE_SIZE {
+                                anyhow::bail!("Server sent a message over {}", crate::util::format_bytes(MAX_MESSAGE_SIZE));
+                            }
+                            if !frame.fin {
+                                partial = Some((opcode, data));
+                                continue;
+                            }
+                            let message = match opcode {
+                                OP_TEXT => String::from_utf8_lossy(&data).into_owned(),
+                                OP_BINARY => format!("(binary, {})", crate::util::format_bytes(data.len())),
+                                other => format!("(frame type {}, {})", other, crate::util::format_bytes(data.len())),
+                            };
+                            let _ = events.send(StreamEvent::Received(message));
+                        }
+                    }
+                }
+            }
+            message = outgoing.recv() => match message {
+                Some(text) => {
+                    writer.write_all(&encode_frame(OP_TEXT, text.as_bytes(), random_mask())).await.context("Failed to send")?;
+                    let _ = events.send(StreamEvent::Sent(text));
+                }
+                None => {
+                    let _ = writer.write_all(&encode_frame(OP_CLOSE, &1000u16.to_be_bytes(), random_mask())).await;
+                    let _ = events.send(StreamEvent::Closed(String::from("Closed")));
+                    return Ok(());
+                }
+            }
+        }
+    }
+}
+
+/// Splits a `text/event-stream` body into events as its bytes arrive.
+#[derive(Default)]
+pub struct EventStreamParser {
+    pending: Vec<u8>,
+    event: Option<String>,
+    data: Vec<String>,
+}
+
+impl EventStreamParser {
+    /// Feed the next chunk of the body; returns the events it completed, as
+    /// their data (`[name] data` for named events).
+    pub fn push(&mut self, chunk: &[u8]) -> Vec<String> {
+        self.pending.extend_from_slice(chunk);
+        let mut events = Vec::new();
+        while let Some(end) = self.pending.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.pending.drain(..=end).collect();
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim_end_matches(['\n', '\r']);
+            if line.is_empty() {
+                if let Some(event) = self.dispatch() {
+                    events.push(event);
+                }
+                continue;
+            }
+            if line.starts_with(':') {
+                continue; // comment / keep-alive
+            }
+            let (field, value) = line.split_once(':').unwrap_or((line, ""));
+            let value = value.strip_prefix(' ').unwrap_or(value);
+            match field {
+                "data" => self.data.push(value.to_string()),
+                "event" => self.event = Some(value.to_string()),
+                _ => {}
+            }
+        }
+        events
+    }
+
+    fn dispatch(&mut self) -> Option<String> {
+        let event = self.event.take();
+        if self.data.is_empty() {
+            return None;
+        }
+        let data = std::mem::take(&mut self.data).join("\n");
+        Some(match event {
+            Some(name) if !name.is_empty() && name != "message" => format!("[{}] {}", name, data),
+            _ => data,
+        })
+    }
+}
+
+/// Send `builder` and relay each server-sent event until the stream ends.
+pub async fn run_event_stream(builder: reqwest::RequestBuilder, events: &Sender<StreamEvent>) -> Result<()> {
+    let mut response = builder.send().await.context("Failed to connect")?;
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        anyhow::bail!("Server returned {}: {}", status, body);
+    }
+    let _ = events.send(StreamEvent::Connected(status.to_string()));
+
+    let mut parser = EventStreamParser::default();
+    while let Some(chunk) = response.chunk().await.context("Connection lost")? {
+        for event in parser.push(&chunk) {
+            if events.send(StreamEvent::Received(event)).is_err() {
+                return Ok(()); // Nobody is listening any more
+            }
+        }
+    }
+    let _ = events.send(StreamEvent::Closed(String::from("Stream ended")));
+    Ok(())
+}
+
+/// Run the stream for `builder`, reporting a failure as a final event.
+pub async fn run_stream(
+    kind: StreamKind,
+    builder: reqwest::RequestBuilder,
+    events: Sender<StreamEvent>,
+    outgoing: UnboundedReceiver<String>,
+) {
+    let result = match kind {
+        StreamKind::WebSocket => run_websocket(builder, &events, outgoing).await,
+        StreamKind::EventStream => run_event_stream(builder, &events).await,
+    };
+    if let Err(e) = result {
+        let _ = events.send(StreamEvent::Failed(format!("{:#}", e)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{encode_frame, handshake_url, parse_frame, EventStreamParser, MAX_MESSAGE_SIZE, OP_TEXT};
+
+    #[test]
+    fn frames_round_trip_through_the_mask() {
+        let payload = "hello".repeat(30); // 150 bytes: the 16-bit length form
+        let encoded = encode_frame(OP_TEXT, payload.as_bytes(), [1, 2, 3, 4]);
+        assert_eq!(encoded[1], 0x80 | 126);
+        let (frame, used) = parse_frame(&encoded).unwrap().unwrap();
+        assert_eq!(used, encoded.len());
+        assert!(frame.fin);
+        assert_eq!(frame.opcode, OP_TEXT);
+        assert_eq!(frame.payload, payload.as_bytes());
+
+        // Incomplete frames wait for more bytes
+        assert!(parse_frame(&encoded[..encoded.len() - 1]).unwrap().is_none());
+        assert!(parse_frame(&encoded[..1]).unwrap().is_none());
+    }
+
+    #[test]
+    fn oversized_frames_fail_before_buffering() {
+        let mut header = vec![0x82, 127];
+        header.extend_from_slice(&(MAX_MESSAGE_SIZE as u64 + 1).to_be_bytes());
+        assert!(parse_frame(&header).is_err());
+        header[2..].copy_from_slice(&u64::MAX.to_be_bytes());
+        assert!(parse_frame(&header).is_err());
+    }
+
+    #[test]
+    fn unmasked_server_frames_parse() {
+        let (frame, used) = parse_frame(&[0x81, 0x02, b'h', b'i', 0x81]).unwrap().unwrap();
+        assert_eq!((frame.payload.as_slice(), used), (&b"hi"[..], 4));
+    }
+
+    #[test]
+    fn handshake_uses_http_scheme() {
+        assert_eq!(handshake_url("wss://example.com/ws"), "https://example.com/ws");
+        assert_eq!(handshake_url("WS://localhost:8080"), "http://localhost:8080");
+    }
+
+    #[test]
+    fn event_stream_events_split_across_chunks() {
+        let mut parser = EventStreamParser::default();
+        assert!(parser.push(b": keep-alive\n\ndata: one\nda").is_empty());
+        assert_eq!(parser.push(b"ta: two\n\nevent: ping\r\ndata:{}\r\n\r\n"), vec!["one\ntwo", "[ping] {}"]);
+        assert!(parser.push(b"event: empty\n\n").is_empty());
+    }
+}
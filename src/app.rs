@@ -1,11 +1,22 @@
-use crate::api::{CollectionDetail, CollectionInfo, EnvironmentDetail, EnvironmentInfo, ExecutedResponse, Item, PostmanClient, Request, RequestItem, RequestUrl, WorkspaceInfo};
-use crate::config::{CacheStore, Config, LocalEditsStore, ParamValuesStore};
+use crate::api::client::{is_api_key_error, CollectionChanged};
+use crate::api::stream::{StreamEvent, StreamKind};
+use crate::api::{BODY_LANGUAGES, CollectionDetail, CollectionInfo, EnvironmentDetail, EnvironmentInfo, ExecutedResponse, Folder, Header, Item, OAuthToken, PostmanClient, QueryParam, RateLimit, Request, RequestItem, RequestUrl, UrlDetail, Variable, WorkspaceCounts, WorkspaceInfo};
+use crate::config::{BaselineEntry, BaselineStore, CacheStore, Config, FavoriteRequest, HttpVersion, LocalEdit, LocalEditsStore, ParamValuesStore, RecentRequest, RequestSort, VariableHistoryStore};
 use crate::logging::log_error;
-use crate::ui::JsonViewerState;
-use anyhow::Result;
+use crate::assertions::AssertionResult;
+use crate::palette;
+use crate::ui::json_diff::{self, JsonChange};
+use crate::ui::{xml_viewer, JsonViewerState};
+use crate::util::ResponseView;
+use anyhow::{Context, Result};
 use ratatui::widgets::ListState;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+/// Labels for quick-jump rows, in on-screen order (home row first).
+const QUICK_JUMP_KEYS: &str = "asdfghjklqwertyuiopzxcvbnm";
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EditableRequest {
@@ -14,6 +25,76 @@ pub struct EditableRequest {
     pub url: String,
     #[serde(default)]
     pub body: String,
+    /// Custom timeout for this request; omitted to use the global default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    /// Path of a file whose contents are sent as the body instead of `body`
+    /// (variables are substituted at send time).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body_file: Option<String>,
+    /// Raw body language (`json`, `xml`, `html`, `javascript`, `text`),
+    /// which also sets the Content-Type sent with the body.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body_language: Option<String>,
+    /// HTTP version to send this request with (`auto`, `http1`, `http2`);
+    /// omitted to use `[settings] http_version`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http_version: Option<HttpVersion>,
+    /// Query params that are kept but not sent (Postman's disabled params);
+    /// the enabled ones are part of `url`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub disabled_query: Vec<QueryParam>,
+    /// Headers replacing the request's own (set in the headers editor);
+    /// omitted to keep the ones from Postman.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub headers: Option<Vec<Header>>,
+}
+
+/// A one-off request composed in the editor (key `o`) and sent without
+/// touching any collection. Headers are written as `"Key: Value"` lines.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScratchRequest {
+    pub method: String,
+    pub url: String,
+    #[serde(default)]
+    pub headers: Vec<String>,
+    #[serde(default)]
+    pub body: String,
+}
+
+impl Default for ScratchRequest {
+    fn default() -> Self {
+        Self {
+            method: String::from("GET"),
+            url: String::new(),
+            headers: Vec::new(),
+            body: String::new(),
+        }
+    }
+}
+
+impl ScratchRequest {
+    /// Convert into an API request. Header lines without a `:` are ignored.
+    pub fn to_request(&self) -> Request {
+        let header = self
+            .headers
+            .iter()
+            .filter_map(|line| parse_header_line(line))
+            .collect();
+
+        Request {
+            method: self.method.trim().to_uppercase(),
+            url: RequestUrl::Simple(self.url.trim().to_string()),
+            header,
+            auth: None,
+            body: if self.body.is_empty() {
+                None
+            } else {
+                Some(crate::api::RequestBody::raw(self.body.clone(), None))
+            },
+            description: None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -25,6 +106,30 @@ pub enum FocusedPane {
     Response,
 }
 
+impl FocusedPane {
+    /// Name stored in `last_state.focused_pane`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FocusedPane::Collections => "collections",
+            FocusedPane::Requests => "requests",
+            FocusedPane::Favorites => "favorites",
+            FocusedPane::Preview => "preview",
+            FocusedPane::Response => "response",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "collections" => Some(FocusedPane::Collections),
+            "requests" => Some(FocusedPane::Requests),
+            "favorites" => Some(FocusedPane::Favorites),
+            "preview" => Some(FocusedPane::Preview),
+            "response" => Some(FocusedPane::Response),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum InputMode {
     Normal,
@@ -35,9 +140,31 @@ pub enum InputMode {
     VariablesView,
     WorkspaceSelect,
     JsonSearch,
+    ResponseSearch,
     ExecuteConfirm,
     ParamsInput,
+    RowsEditor,
+    UrlEdit,
+    OpenApiImport,
+    NoteEdit,
+    TransferSelect,
     DeleteEditConfirm,
+    ClearEditsConfirm,
+    InvalidBodyConfirm,
+    RunConfirm,
+    RunResults,
+    DryRun,
+    CommandPalette,
+    BaselineDiff,
+    ExtraHeaders,
+    RecentRequests,
+    ApiKeyInput,
+    ScriptView,
+    QuickJump,
+    CollectionConflict,
+    EnvironmentConflict,
+    StreamMessage,
+    GlobalFinder,
 }
 
 /// A dialog for filling in `{{placeholder}}` values before firing a request.
@@ -54,17 +181,262 @@ pub struct ParamsDialog {
     pub editing: bool,
 }
 
+/// The key/value rows editor: the selected request's query params (`P` in
+/// Preview, written back to its URL) or headers (`h` in Preview), stored as
+/// a local edit.
+#[derive(Debug, Clone)]
+pub struct RowsEditor {
+    pub target: RowsTarget,
+    pub rows: Vec<KeyValueRow>,
+    pub selected: usize,
+    /// The field being typed into; None while navigating rows.
+    pub editing: Option<RowField>,
+    /// Cursor byte-offset within the field being typed into.
+    pub cursor_position: usize,
+}
+
+/// The inline URL editor (`u` in Preview): the selected request's URL as a
+/// single-line field, stored as a local edit on Enter.
+#[derive(Debug, Clone)]
+pub struct UrlEdit {
+    pub url: String,
+    /// Cursor byte-offset within `url`.
+    pub cursor_position: usize,
+}
+
+/// The note editor (Ctrl+n): the selected request's local note as a
+/// single-line field.
+#[derive(Debug, Clone)]
+pub struct NoteEdit {
+    pub note: String,
+    /// Cursor byte-offset within `note`.
+    pub cursor_position: usize,
+}
+
+/// The collection picker for copying or moving a request into another
+/// collection (`M` in Requests).
+#[derive(Debug, Clone)]
+pub struct TransferDialog {
+    /// The request's path in the loaded collection.
+    pub path: Vec<usize>,
+    pub name: String,
+    /// Every other collection, in list order.
+    pub targets: Vec<CollectionInfo>,
+    pub selected: usize,
+}
+
+/// The OpenAPI import prompt (`I` in Collections): the path of the spec
+/// file to create a collection from.
+#[derive(Debug, Clone)]
+pub struct ImportPrompt {
+    pub path: String,
+    /// Cursor byte-offset within `path`.
+    pub cursor_position: usize,
+}
+
+/// One row of a `RowsEditor`. `value` is None for a query param written
+/// without `=`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyValueRow {
+    pub key: String,
+    pub value: Option<String>,
+    pub disabled: bool,
+}
+
+/// What the rows of a `RowsEditor` are.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RowsTarget {
+    QueryParams,
+    Headers,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RowField {
+    Key,
+    Value,
+}
+
+impl RowsEditor {
+    fn field_mut(&mut self) -> Option<&mut String> {
+        let field = self.editing?;
+        let row = self.rows.get_mut(self.selected)?;
+        Some(match field {
+            RowField::Key => &mut row.key,
+            RowField::Value => row.value.get_or_insert_with(String::new),
+        })
+    }
+}
+
+/// A bulk change to the loaded collection, kept so it can be reapplied on
+/// top of a newer version from Postman.
+#[derive(Debug, Clone)]
+pub enum BulkChange {
+    NewRequest {
+        name: String,
+        url: String,
+        folder_path: Vec<usize>,
+    },
+    Move {
+        path: Vec<usize>,
+        name: String,
+        request_id: Option<String>,
+        delta: isize,
+    },
+}
+
+/// A bulk update held back because the collection changed in Postman since
+/// it was loaded.
+#[derive(Debug, Clone)]
+pub struct CollectionConflict {
+    pub change: BulkChange,
+    pub server: CollectionDetail,
+    /// What differs on the server, one line per request.
+    pub summary: Vec<String>,
+}
+
+/// Whose variables the variables view shows and edits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VariablesScope {
+    Environment,
+    Collection,
+}
+
+/// A variables save held back because the environment changed in Postman
+/// since it was loaded.
+#[derive(Debug, Clone)]
+pub struct EnvironmentConflict {
+    pub server: EnvironmentDetail,
+    /// What differs on the server, one line per variable (no values).
+    pub summary: Vec<String>,
+}
+
+/// A WebSocket or event stream opened by executing a request. Its log is
+/// shown in the Response pane while open, and afterwards until dismissed.
+pub struct StreamSession {
+    pub kind: StreamKind,
+    pub url: String,
+    /// Each event with the local time it arrived (`HH:MM:SS`).
+    pub log: Vec<(String, StreamEvent)>,
+    pub open: bool,
+    /// Lines scrolled back from the newest; 0 follows new messages.
+    pub scroll_back: usize,
+    events: std::sync::mpsc::Receiver<StreamEvent>,
+    /// Messages to send (WebSocket only). Dropping it closes the connection.
+    outgoing: Option<tokio::sync::mpsc::UnboundedSender<String>>,
+    handle: tokio::task::JoinHandle<()>,
+}
+
 #[derive(Debug, Clone)]
 pub struct PendingSave {
     pub edited: EditableRequest,
     pub item_index: usize,
 }
 
+/// A J/K move waiting to be pushed to Postman: the collection as loaded,
+/// its items with the two siblings swapped, and where the moved item
+/// (at `path`) ends up among its siblings.
+#[derive(Debug, Clone)]
+pub struct PendingMove {
+    pub collection_uid: String,
+    pub collection: CollectionDetail,
+    pub items: Vec<Item>,
+    pub path: Vec<usize>,
+    pub name: String,
+    pub request_id: Option<String>,
+    pub target: usize,
+    pub delta: isize,
+}
+
+/// A copy or move (`remove_source`) waiting to be pushed to Postman: the
+/// source collection as loaded, the request at `path`, and the copy that
+/// goes to the end of `target`.
+#[derive(Debug, Clone)]
+pub struct PendingTransfer {
+    pub collection_uid: String,
+    pub collection: CollectionDetail,
+    pub path: Vec<usize>,
+    pub name: String,
+    pub copy: RequestItem,
+    pub target: CollectionInfo,
+    pub remove_source: bool,
+}
+
+/// What became of a transfer: the copy's index in the target collection,
+/// and for a move, the source items with the original taken out.
+#[derive(Debug)]
+pub struct TransferOutcome {
+    pub copied: Result<usize>,
+    pub removed: Option<Result<Vec<Item>>>,
+}
+
+/// An OpenAPI import waiting to be created in Postman: the collection
+/// built from the spec at `path`, going into `workspace_id`.
+#[derive(Debug)]
+pub struct PendingImport {
+    pub workspace_id: Option<String>,
+    pub path: std::path::PathBuf,
+    pub imported: crate::openapi::ImportedCollection,
+}
+
+/// The command palette: a filtered list of `palette::COMMANDS`.
+#[derive(Debug, Clone, Default)]
+pub struct CommandPalette {
+    pub query: String,
+    /// Indices into `palette::COMMANDS` matching `query`.
+    pub matches: Vec<usize>,
+    pub selected: usize,
+}
+
+/// Earlier values of one variable being stepped through with `h` in the
+/// variables view; `values[0]` is the value it had before the first step.
+#[derive(Debug, Clone)]
+struct VariableHistoryCycle {
+    index: usize,
+    values: Vec<String>,
+    position: usize,
+}
+
+/// The global finder (`Ctrl+f`): request and folder names matched across
+/// every collection whose detail has been fetched.
+#[derive(Debug, Clone, Default)]
+pub struct GlobalFinder {
+    pub query: String,
+    pub matches: Vec<FinderMatch>,
+    pub selected: usize,
+    /// Collections whose detail is still being fetched for the finder.
+    pub loading: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct FinderMatch {
+    pub collection_uid: String,
+    pub collection_name: String,
+    pub path: Vec<usize>,
+    /// Enclosing folder names, outermost first.
+    pub folders: Vec<String>,
+    pub name: String,
+    pub is_folder: bool,
+    pub method: Option<String>,
+}
+
+/// Most matches the finder lists; a longer query narrows them down.
+const MAX_FINDER_MATCHES: usize = 200;
+
+/// An editor result whose JSON body failed to parse, held until the user
+/// decides whether to store it anyway.
+#[derive(Debug, Clone)]
+pub struct PendingInvalidBody {
+    pub edit: PendingSave,
+    pub error: String,
+}
+
 #[derive(Debug, Clone)]
 pub struct PendingExecute {
     pub method: String,
     pub url: String,
     pub name: String,
+    /// Whether this is the scratch request (`o`) rather than the selected one.
+    pub scratch: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -74,10 +446,103 @@ pub struct PendingDeleteEdit {
     pub item_index: usize,
 }
 
+/// Every local edit in a collection, held for confirmation before they are
+/// all discarded.
+#[derive(Debug, Clone)]
+pub struct PendingClearEdits {
+    pub collection_uid: String,
+    pub collection_name: String,
+    pub count: usize,
+}
+
+/// Outcome of pushing every local edit in a collection to Postman.
+#[derive(Debug, Clone)]
+pub struct SaveAllReport {
+    /// Paths of edits that were saved (and can be dropped from the store).
+    pub saved: Vec<Vec<usize>>,
+    /// (request name, error) for edits that failed and stay stored for retry.
+    pub failed: Vec<(String, String)>,
+    /// The collection re-fetched after saving, when the reload succeeded.
+    pub updated: Option<CollectionDetail>,
+}
+
+/// An OAuth access token fetched via the `[oauth]` client-credentials config.
+#[derive(Debug, Clone)]
+pub struct FetchedToken {
+    pub access_token: String,
+    pub expires_at: Option<std::time::Instant>,
+}
+
+/// A queued OAuth token request with its config values already resolved.
+#[derive(Debug, Clone)]
+pub struct TokenFetch {
+    pub token_url: String,
+    pub client_id: String,
+    pub client_secret: String,
+    pub scope: String,
+    /// Execute the current request once the token arrives.
+    pub then_execute: bool,
+}
+
+/// One request queued as part of a run, already variable-substituted.
+#[derive(Debug, Clone)]
+pub struct RunStep {
+    pub name: String,
+    pub collection_uid: String,
+    pub path: Vec<usize>,
+    pub request: Request,
+    pub timeout: Duration,
+    /// The request's own HTTP version, if it sets one.
+    pub http_version: Option<HttpVersion>,
+    /// Assertion rules configured for the request, checked on its response.
+    pub assertions: Vec<String>,
+}
+
+/// A batch of requests to execute one after another (e.g. every request in a
+/// folder). Held for confirmation, then queued for the main loop to run.
+#[derive(Debug, Clone)]
+pub struct PendingRun {
+    pub title: String,
+    pub steps: Vec<RunStep>,
+}
+
+/// Outcome of a single request within a run.
+#[derive(Debug, Clone)]
+pub struct RunResult {
+    pub name: String,
+    pub method: String,
+    pub collection_uid: String,
+    pub path: Vec<usize>,
+    pub status: Option<u16>,
+    pub error: Option<String>,
+    pub duration_ms: u128,
+    pub assertions: Vec<AssertionResult>,
+}
+
+impl RunResult {
+    /// A request passes when it got a response with a non-error status and
+    /// all of its assertions held.
+    pub fn passed(&self) -> bool {
+        self.error.is_none() && self.status.map(|s| s < 400).unwrap_or(false) && self.assertions.iter().all(|a| a.passed)
+    }
+}
+
+/// Results of the last completed run, kept so they can be reopened later.
+#[derive(Debug, Clone)]
+pub struct RunReport {
+    pub title: String,
+    pub results: Vec<RunResult>,
+    /// Requests that never ran because the run stopped at a failure.
+    pub skipped: usize,
+}
+
 /// An in-flight background refresh of a collection's requests. The fetch runs on
 /// a spawned task and delivers its result over `rx`, polled by the main loop.
 pub struct CollectionRefresh {
     pub uid: String,
+    /// Asked for explicitly (reload key) rather than a background refresh,
+    /// so the outcome is reported either way.
+    pub reload: bool,
     pub rx: std::sync::mpsc::Receiver<Result<CollectionDetail>>,
 }
 
@@ -116,6 +581,8 @@ pub struct FlatItem {
     pub request: Option<Request>,
     pub request_id: Option<String>,
     pub path: Vec<usize>,
+    /// Requests anywhere beneath a folder row; zero for requests.
+    pub request_count: usize,
 }
 
 /// A favorited request resolved for display in the Favorites pane. Name and
@@ -160,23 +627,72 @@ pub struct App {
     pub expanded_folders: HashSet<Vec<usize>>,
     pub collections_favorites_expanded: bool,
     pub current_request: Option<Request>,
+    // Tree path of `current_request`, used to layer its folders' variables
+    current_request_path: Option<Vec<usize>>,
     pub response: Option<ExecutedResponse>,
+    // Documents in the response when it is NDJSON (shown as an array)
+    pub response_ndjson_count: Option<usize>,
+    // The resolved request `response` answered, for the shareable report
+    response_request: Option<Request>,
+    // `M` was pressed: the next key picks where the report goes
+    pub report_pending: bool,
+    // `X` was pressed on a request: the next key picks how it is exported
+    pub request_export_pending: bool,
+    // Outcome of the executed request's `[[assertions]]` rules, if it has any
+    pub assertion_results: Vec<AssertionResult>,
+    /// Keep the response (and its viewer state) while browsing other
+    /// requests. A new execution still replaces it.
+    pub response_pinned: bool,
+    /// The queued execution came from quick execute (`x` in Requests), so
+    /// focus stays in the Requests pane when the response arrives.
+    quick_execute: bool,
     pub json_viewer_state: Option<JsonViewerState>,
+    /// Reindented body of an XML response, shown highlighted in place of the
+    /// raw text. None for JSON and other non-XML responses.
+    pub xml_pretty: Option<String>,
+    /// How the shown response body is displayed.
+    pub response_view: ResponseView,
+    /// View picked with `r`, overriding the one detected from the response.
+    response_view_override: Option<ResponseView>,
+    // Plain-text response view: top line of the viewport and `/` search state
+    pub response_scroll: usize,
+    pub response_search_query: String,
+    pub response_search_matches: Vec<(usize, usize)>, // (body line, byte offset)
+    pub response_match_index: usize,
     // Persistent scroll state for the collection/request lists so the view only
     // scrolls when the selection reaches the top or bottom edge of the pane.
-    pub collections_list_state: ListState,
-    pub requests_list_state: ListState,
+    /// First visible row of the Collections and Requests lists.
+    pub collections_scroll: usize,
+    pub requests_scroll: usize,
+    // Rows visible in the Collections/Requests lists at the last render,
+    // so quick-jump labels cover exactly what's on screen
+    pub collections_view_rows: usize,
+    pub requests_view_rows: usize,
+    // Lines the request preview is scrolled down by, and its visible rows
+    // and furthest useful scroll at the last render
+    pub preview_scroll: u16,
+    pub preview_view_rows: u16,
+    pub preview_max_scroll: u16,
+    /// Show the preview with `{{variables}}` substituted instead of as written.
+    pub preview_resolved: bool,
     pub favorites_list_state: ListState,
     pub selected_favorite_index: usize,
     /// Request path to select once a collection load kicked off from the
     /// Favorites pane completes.
     pub pending_favorite_path: Option<Vec<usize>>,
+    /// Set when loading a collection switched to its remembered environment;
+    /// the main loop then fetches that environment's variables.
+    pub pending_environment_load: bool,
     pub loading: bool,
     pub error: Option<String>,
     pub status_message: String,
+    // When `status_message` last changed, so informational notices can fade
+    status_seen: String,
+    status_since: Instant,
     pub input_mode: InputMode,
     pub new_request_dialog: Option<NewRequestDialog>,
     pub pending_save: Option<PendingSave>,
+    pub pending_invalid_body: Option<PendingInvalidBody>,
     // Search state
     pub search_query: String,
     pub search_matches: Vec<usize>,
@@ -191,34 +707,144 @@ pub struct App {
     pub environment_popup_index: usize,
     // Variables view state
     pub variables_popup_index: usize,
+    pub variables_scope: VariablesScope,
     pub editing_variable: Option<(usize, String)>, // (index, new value being edited)
     pub variable_cursor_position: usize,
     pub variables_modified: bool,
     pub variables_search_query: String,
     pub variables_search_active: bool,
+    /// Waiting for confirmation to export the environment's values unmasked.
+    pub variables_export_confirm: bool,
+    /// Earlier values of edited environment variables, for reverting.
+    pub variable_history: VariableHistoryStore,
+    /// `h` is stepping through the selected variable's earlier values.
+    variable_history_cycle: Option<VariableHistoryCycle>,
+    /// Which of the selected request's variables `]` jumps to next.
+    pub definition_cursor: usize,
+    /// `z` was pressed in the JSON viewer; the next digit picks a depth.
+    pub json_depth_pending: bool,
     pub variables_filtered_indices: Vec<usize>, // Indices of variables matching search
     // Workspace state
     pub workspaces: Vec<WorkspaceInfo>,
     pub selected_workspace_index: Option<usize>, // None = "All Workspaces"
     pub workspace_popup_index: usize,
     pub workspace_loading: Option<String>, // Name of workspace being loaded
+    /// Collection/environment counts per workspace id, shown in the popup.
+    pub workspace_counts: HashMap<String, WorkspaceCounts>,
+    /// Counts being fetched in the background, delivered one workspace at a time.
+    pub pending_workspace_counts: Option<std::sync::mpsc::Receiver<(String, Result<WorkspaceCounts>)>>,
     pub collection_loading: Option<String>, // Name of collection being loaded
     // Unsaved edit state
     pub unsaved_edit: Option<(EditableRequest, usize)>, // (edited request, item_index)
     // Execute confirmation state
     pub pending_execute: Option<PendingExecute>,
+    /// One-shot headers (key `+`) merged over the selected request's headers
+    /// for its next execution only.
+    pub extra_headers: Vec<Header>,
+    /// The `Key: Value` line being typed in the extra headers overlay.
+    pub extra_header_input: String,
+    // Bulk update waiting on reload-and-reapply after a concurrent change
+    pub collection_conflict: Option<CollectionConflict>,
+    pub environment_conflict: Option<EnvironmentConflict>,
+    /// Variables of the current environment as last loaded from (or saved
+    /// to) Postman, to spot changes made there before saving over them.
+    loaded_environment_values: Vec<Variable>,
+    /// The loaded collection's variables when the variables view opened, put
+    /// back if edits to them are closed unsaved (they're edited in place).
+    loaded_collection_variables: Vec<Variable>,
+    // Re-entry prompt shown when Postman rejects the API key
+    pub api_key_input: String,
+    pub api_key_error: Option<String>,
+    /// Collections whose destructive requests run unconfirmed for the rest
+    /// of this session ("don't ask again" in the confirmation).
+    pub confirm_skipped_collections: HashSet<String>,
     /// A resolved request queued for execution, run (cancellably) in the main
-    /// loop rather than inline in a key handler.
-    pub pending_execution: Option<Request>,
+    /// loop rather than inline in a key handler, with its timeout and own
+    /// HTTP version.
+    pub pending_execution: Option<(Request, Duration, Option<HttpVersion>)>,
+    /// Local edits queued to be saved together (see `start_save_all`).
+    pub pending_save_all: Option<Vec<LocalEdit>>,
+    /// A J/K move queued to be pushed to Postman (see `move_selected_item`).
+    pub pending_move: Option<PendingMove>,
+    /// A copy or move to another collection queued to be pushed to Postman
+    /// (see `confirm_transfer`).
+    pub pending_transfer: Option<PendingTransfer>,
+    /// An OpenAPI import queued to be created in Postman (see
+    /// `confirm_openapi_import`).
+    pub pending_import: Option<PendingImport>,
     pub pending_delete_edit: Option<PendingDeleteEdit>,
+    pub pending_clear_edits: Option<PendingClearEdits>,
     // Request execution state
     pub request_executing: bool,
+    /// When the in-flight request was sent, for the elapsed time shown
+    /// while waiting on it.
+    pub execution_started: Option<Instant>,
+    /// Open (or just closed) WebSocket / event stream
+    pub stream: Option<StreamSession>,
+    /// Message being typed for the WebSocket
+    pub stream_input: String,
+    /// The last execution (of the request in `response_key`) failed without
+    /// a response.
+    pub response_failed: bool,
     // Parameter (placeholder) input state
     pub params_dialog: Option<ParamsDialog>,
+    pub rows_editor: Option<RowsEditor>,
+    pub url_edit: Option<UrlEdit>,
+    pub openapi_import: Option<ImportPrompt>,
+    pub note_edit: Option<NoteEdit>,
+    pub transfer_dialog: Option<TransferDialog>,
     // Per-request placeholder overrides applied during substitution (highest priority)
     pub param_overrides: HashMap<String, String>,
     // Persisted placeholder values entered in the params dialog, keyed by request
     pub param_values: ParamValuesStore,
+    pub baselines: BaselineStore,
+    // (collection_uid, path) of the request the shown response came from
+    response_key: Option<(String, Vec<usize>)>,
+    // (collection_uid, path) of the request being executed; becomes
+    // `response_key` once its response arrives
+    executing_key: Option<(String, Vec<usize>)>,
+    // Differences between the shown response and its saved baseline
+    pub baseline_diff: Option<Vec<JsonChange>>,
+    pub baseline_diff_scroll: u16,
+    // Folder run state: awaiting confirmation, queued, in flight, and the
+    // last report (reopenable from the Requests pane)
+    pub pending_run_confirm: Option<PendingRun>,
+    pub pending_run: Option<PendingRun>,
+    pub run_in_progress: Option<String>,
+    pub run_report: Option<RunReport>,
+    pub run_results_index: usize,
+    // Collections to fetch before running favorites across all collections
+    pub pending_favorites_fetch: Option<Vec<String>>,
+    // Dry run overlay: the resolved request that would be sent, and its scroll
+    pub dry_run: Option<Request>,
+    pub dry_run_scroll: u16,
+    // Read-only script overlay (`x`)
+    pub script_view_scroll: u16,
+    pub palette: Option<CommandPalette>,
+    pub finder: Option<GlobalFinder>,
+    // Collection details fetched in the background for the global finder
+    pub pending_finder_fetch: Option<std::sync::mpsc::Receiver<(String, Result<CollectionDetail>)>>,
+    // Highlighted entry in the recently-executed popup (`;`)
+    pub recent_index: usize,
+    // Wrap responses as {"__headers", "__body"} in the JSON viewer (toggle `i`)
+    pub headers_in_json_viewer: bool,
+    // Highlighted entry in the `{{variable}}` completion popup
+    pub completion_index: usize,
+    // Command chosen in the palette, replayed as a key press by the main loop
+    pub pending_palette_command: Option<usize>,
+    // OAuth client-credentials token state
+    pub oauth_token: Option<FetchedToken>,
+    pub pending_token_fetch: Option<TokenFetch>,
+    pub token_fetching: bool,
+    // Rate-limit budget from the last executed response's headers
+    pub response_rate_limit: Option<RateLimit>,
+    // When a collection or environment was last fetched from Postman (cached
+    // copies shown at startup don't count)
+    pub last_refresh: Option<chrono::DateTime<chrono::Local>>,
+    // Advances once per drawn frame; animates loading spinners
+    pub spinner_frame: usize,
+    // Last scratch request, kept in memory only so `o` can reopen it
+    pub scratch_request: Option<ScratchRequest>,
     // Clipboard (kept alive to persist content on Linux)
     clipboard: Option<arboard::Clipboard>,
 }
@@ -228,7 +854,7 @@ impl App {
         let api_key = config.postman.api_key.clone();
         let local_edits = LocalEditsStore::load().unwrap_or_default();
         App {
-            client: PostmanClient::new(api_key),
+            client: PostmanClient::new(api_key, &config.postman.api_base_url),
             config,
             local_edits,
             focused_pane: FocusedPane::Collections,
@@ -244,19 +870,42 @@ impl App {
             expanded_folders: HashSet::new(),
             collections_favorites_expanded: true,
             current_request: None,
+            current_request_path: None,
             response: None,
+            response_ndjson_count: None,
+            response_request: None,
+            report_pending: false,
+            request_export_pending: false,
+            assertion_results: Vec::new(),
+            response_pinned: false,
+            quick_execute: false,
             json_viewer_state: None,
-            collections_list_state: ListState::default(),
-            requests_list_state: ListState::default(),
+            xml_pretty: None,
+            response_view: ResponseView::Text,
+            response_view_override: None,
+            response_scroll: 0,
+            response_search_query: String::new(),
+            response_search_matches: Vec::new(),
+            response_match_index: 0,
+            collections_scroll: 0,
+            requests_scroll: 0,
+            collections_view_rows: 0,
+            requests_view_rows: 0,
+            preview_scroll: 0,
+            preview_resolved: false,
+            preview_view_rows: 0,
+            preview_max_scroll: 0,
             favorites_list_state: ListState::default(),
             selected_favorite_index: 0,
             pending_favorite_path: None,
+            pending_environment_load: false,
             loading: false,
             error: None,
             status_message: String::from("1-5: Switch pane | j/k: Navigate | Enter: Select | f: Favorite | Ctrl+q: Quit"),
             input_mode: InputMode::Normal,
             new_request_dialog: None,
             pending_save: None,
+            pending_invalid_body: None,
             search_query: String::new(),
             search_matches: Vec::new(),
             search_match_paths: Vec::new(),
@@ -273,20 +922,81 @@ impl App {
             variables_modified: false,
             variables_search_query: String::new(),
             variables_search_active: false,
+            variables_export_confirm: false,
+            variables_scope: VariablesScope::Environment,
+            variable_history: VariableHistoryStore::load().unwrap_or_default(),
+            variable_history_cycle: None,
+            definition_cursor: 0,
+            json_depth_pending: false,
             variables_filtered_indices: Vec::new(),
             workspaces: Vec::new(),
             selected_workspace_index: None,
             workspace_popup_index: 0,
+            workspace_counts: HashMap::new(),
+            pending_workspace_counts: None,
             workspace_loading: None,
             collection_loading: None,
             unsaved_edit: None,
             pending_execute: None,
+            extra_headers: Vec::new(),
+            extra_header_input: String::new(),
+            collection_conflict: None,
+            environment_conflict: None,
+            loaded_environment_values: Vec::new(),
+            loaded_collection_variables: Vec::new(),
+            api_key_input: String::new(),
+            api_key_error: None,
+            confirm_skipped_collections: HashSet::new(),
             pending_execution: None,
+            pending_save_all: None,
+            pending_move: None,
+            pending_transfer: None,
+            pending_import: None,
             pending_delete_edit: None,
+            pending_clear_edits: None,
             request_executing: false,
+            execution_started: None,
+            stream: None,
+            stream_input: String::new(),
+            response_failed: false,
             params_dialog: None,
+            rows_editor: None,
+            url_edit: None,
+            openapi_import: None,
+            note_edit: None,
+            transfer_dialog: None,
             param_overrides: HashMap::new(),
             param_values: ParamValuesStore::load().unwrap_or_default(),
+            baselines: BaselineStore::load().unwrap_or_default(),
+            response_key: None,
+            executing_key: None,
+            baseline_diff: None,
+            baseline_diff_scroll: 0,
+            pending_run_confirm: None,
+            pending_run: None,
+            run_in_progress: None,
+            run_report: None,
+            run_results_index: 0,
+            pending_favorites_fetch: None,
+            dry_run: None,
+            dry_run_scroll: 0,
+            script_view_scroll: 0,
+            palette: None,
+            finder: None,
+            pending_finder_fetch: None,
+            recent_index: 0,
+            headers_in_json_viewer: false,
+            completion_index: 0,
+            pending_palette_command: None,
+            oauth_token: None,
+            pending_token_fetch: None,
+            token_fetching: false,
+            response_rate_limit: None,
+            last_refresh: None,
+            spinner_frame: 0,
+            status_seen: String::new(),
+            status_since: Instant::now(),
+            scratch_request: None,
             clipboard: None,
         }
     }
@@ -334,12 +1044,13 @@ impl App {
                 };
                 let path = item.path.clone();
                 let name = item.name.clone();
+                let request_id = item.request_id.clone();
 
                 if self.config.is_request_favorite(&collection_uid, &path) {
                     self.config.remove_favorite_request(&collection_uid, &path);
                     self.status_message = String::from("Removed from favorites");
                 } else {
-                    self.config.add_favorite_request(collection_uid, path.clone(), name);
+                    self.config.add_favorite_request(collection_uid, path.clone(), name, request_id);
                     self.status_message = String::from("Added to favorites");
                 }
 
@@ -382,6 +1093,83 @@ impl App {
         }
     }
 
+    /// Favorite every request under the selected folder, or unfavorite them all
+    /// when every one is already a favorite.
+    pub fn toggle_folder_favorites(&mut self) {
+        if self.focused_pane != FocusedPane::Requests {
+            return;
+        }
+        let item = match self.flat_items.get(self.selected_item_index) {
+            Some(item) => item,
+            None => return,
+        };
+        if item.path == [usize::MAX] {
+            self.status_message = String::from("Select a folder in the collection tree");
+            return;
+        }
+        if !item.is_folder {
+            self.status_message = String::from("Select a folder to favorite all its requests");
+            return;
+        }
+        let (folder_name, folder_path) = (item.name.clone(), item.path.clone());
+        let collection_uid = match self.get_current_collection_uid() {
+            Some(uid) => uid,
+            None => return,
+        };
+        let items = match &self.current_collection {
+            Some(collection) => &collection.item,
+            None => return,
+        };
+        let children = match get_folder_items_at_path(items, &folder_path) {
+            Some(children) => children,
+            None => return,
+        };
+
+        let mut requests = Vec::new();
+        collect_requests_recursive(children, folder_path.clone(), &mut requests);
+        let request_ids: Vec<Option<String>> = requests
+            .iter()
+            .map(|(_, path, _)| get_item_at_path(items, path).and_then(|(_, _, id)| id))
+            .collect();
+        if requests.is_empty() {
+            self.status_message = format!("No requests in '{}'", folder_name);
+            return;
+        }
+
+        let all_favorite = requests
+            .iter()
+            .all(|(_, path, _)| self.config.is_request_favorite(&collection_uid, path));
+        let count = requests.len();
+        for ((name, path, _), request_id) in requests.into_iter().zip(request_ids) {
+            if all_favorite {
+                self.config.remove_favorite_request(&collection_uid, &path);
+            } else {
+                // add_favorite_request skips ones already favorited
+                self.config.add_favorite_request(collection_uid.clone(), path, name, request_id);
+            }
+        }
+        self.status_message = if all_favorite {
+            format!("Removed {} requests in '{}' from favorites", count, folder_name)
+        } else {
+            format!("Added {} requests in '{}' to favorites", count, folder_name)
+        };
+
+        // Re-flatten to update the Favorites section, keeping the folder selected
+        self.flatten_items();
+        if let Some(new_index) = self.flat_items.iter().rposition(|item| item.path == folder_path) {
+            self.selected_item_index = new_index;
+        }
+        self.selected_favorite_index = self
+            .selected_favorite_index
+            .min(self.config.favorite_requests.len().saturating_sub(1));
+
+        if let Err(e) = self.config.save() {
+            let error_msg = format!("Failed to save favorites: {}", e);
+            log_error("toggle_folder_favorites", &error_msg);
+            self.error = Some(error_msg);
+        }
+    }
+
     /// All favorited requests across collections, resolved for display. Names
     /// and methods come from the session cache when available (so renames show
     /// through), falling back to the name stored when the request was favorited.
@@ -449,64 +1237,205 @@ impl App {
         self.start_collection_load()
     }
 
-    pub fn is_request_favorite(&self, path: &[usize]) -> bool {
-        match &self.current_collection_uid {
-            Some(uid) => self.config.is_request_favorite(uid, path),
-            None => false,
+    pub fn open_recent_requests(&mut self) {
+        if self.config.recent_requests.is_empty() {
+            self.status_message = String::from("No requests executed yet");
+            return;
         }
+        self.recent_index = 0;
+        self.input_mode = InputMode::RecentRequests;
     }
 
-    fn sort_collections(&mut self) {
-        // Sort alphabetically (favorites are handled separately in flatten)
-        self.collections.sort_by(|a, b| {
-            a.name.to_lowercase().cmp(&b.name.to_lowercase())
-        });
+    pub fn close_recent_requests(&mut self) {
+        self.input_mode = InputMode::Normal;
     }
 
-    pub fn flatten_collections(&mut self) {
-        self.flat_collections.clear();
+    pub fn recent_requests_up(&mut self) {
+        self.recent_index = self.recent_index.saturating_sub(1);
+    }
 
-        let favorite_uids: Vec<&String> = self.collections
-            .iter()
-            .filter(|c| self.config.is_favorite(&c.uid))
-            .map(|c| &c.uid)
-            .collect();
+    pub fn recent_requests_down(&mut self) {
+        if self.recent_index + 1 < self.config.recent_requests.len() {
+            self.recent_index += 1;
+        }
+    }
 
-        // Add Favorites section if there are any
-        if !favorite_uids.is_empty() {
-            self.flat_collections.push(FlatCollection {
-                name: String::from("Favorites"),
-                uid: String::new(),
-                is_favorites_folder: true,
-                depth: 0,
-            });
+    /// Open the highlighted recent request: select it straight away when its
+    /// collection is already loaded, otherwise load the collection (like a
+    /// favorite) and select it once it's in place.
+    pub fn start_recent_load(&mut self) -> CollectionLoad {
+        self.input_mode = InputMode::Normal;
+        let entry = match self.config.recent_requests.get(self.recent_index) {
+            Some(e) => e.clone(),
+            None => return CollectionLoad::None,
+        };
 
-            if self.collections_favorites_expanded {
-                for collection in &self.collections {
-                    if self.config.is_favorite(&collection.uid) {
-                        self.flat_collections.push(FlatCollection {
-                            name: collection.name.clone(),
-                            uid: collection.uid.clone(),
-                            is_favorites_folder: false,
-                            depth: 1,
-                        });
-                    }
-                }
-            }
-        }
+        // Prefer the request's id, since positional paths shift with edits.
+        let path = entry
+            .request_id
+            .as_deref()
+            .and_then(|id| {
+                self.collection_cache
+                    .get(&entry.collection_uid)
+                    .and_then(|detail| find_request_path_by_id(&detail.item, id))
+            })
+            .unwrap_or(entry.path);
 
-        // Add all collections
-        for collection in &self.collections {
-            self.flat_collections.push(FlatCollection {
-                name: collection.name.clone(),
-                uid: collection.uid.clone(),
-                is_favorites_folder: false,
-                depth: 0,
-            });
+        if self.current_collection_uid.as_deref() == Some(entry.collection_uid.as_str()) {
+            self.restore_request_path(&path);
+            self.select_request();
+            return CollectionLoad::None;
         }
-    }
 
-    pub fn toggle_collections_favorites_folder(&mut self) {
+        let collection_index = self.flat_collections.iter().position(|c| {
+            !c.is_favorites_folder && c.uid == entry.collection_uid
+        });
+        match collection_index {
+            Some(idx) => self.selected_collection_index = idx,
+            None => {
+                self.status_message =
+                    String::from("Collection not available in this workspace");
+                return CollectionLoad::None;
+            }
+        }
+
+        self.pending_favorite_path = Some(path);
+        self.start_collection_load()
+    }
+
+    /// Add the request being executed to the recently-executed list.
+    fn record_recent_execution(&mut self) {
+        let (collection_uid, path) = match &self.executing_key {
+            Some(key) => key.clone(),
+            None => return,
+        };
+        let (name, _, request_id) = match self
+            .current_collection
+            .as_ref()
+            .and_then(|c| get_item_at_path(&c.item, &path))
+        {
+            Some(item) => item,
+            None => return,
+        };
+        self.config.record_recent_request(RecentRequest {
+            collection_uid,
+            path,
+            name,
+            request_id,
+            executed_at: chrono::Local::now().format("%Y-%m-%d %H:%M").to_string(),
+        });
+        if let Err(e) = self.config.save() {
+            log_error("record_recent_execution", &e.to_string());
+        }
+    }
+
+    pub fn is_request_favorite(&self, path: &[usize]) -> bool {
+        match &self.current_collection_uid {
+            Some(uid) => self.config.is_request_favorite(uid, path),
+            None => false,
+        }
+    }
+
+    /// Cycle the Requests pane between original, name and method order for
+    /// the loaded collection, keeping the selection, and remember the choice.
+    pub fn cycle_request_sort(&mut self) {
+        let Some(uid) = self.current_collection_uid.clone() else {
+            return;
+        };
+        let sort = self.config.request_sort.get(&uid).copied().unwrap_or_default().next();
+        if sort == RequestSort::Original {
+            self.config.request_sort.remove(&uid);
+        } else {
+            self.config.request_sort.insert(uid, sort);
+        }
+        let selected = self.flat_items.get(self.selected_item_index).map(|item| (item.path.clone(), item.depth));
+        self.flatten_items();
+        let index = selected.and_then(|(path, depth)| {
+            self.flat_items.iter().position(|item| item.path == path && item.depth == depth)
+        });
+        if let Some(index) = index {
+            self.selected_item_index = index;
+        }
+        self.status_message = format!("Requests sorted by {}", sort.label());
+        if let Err(e) = self.config.save() {
+            log_error("cycle_request_sort", &e.to_string());
+        }
+    }
+
+    /// Switch the Requests pane between names only and the detailed rows,
+    /// and remember the choice.
+    pub fn toggle_compact_requests(&mut self) {
+        self.config.compact_requests = !self.config.compact_requests;
+        self.status_message = if self.config.compact_requests {
+            String::from("Requests pane: names only")
+        } else {
+            String::from("Requests pane: detailed")
+        };
+        if let Err(e) = self.config.save() {
+            log_error("toggle_compact_requests", &e.to_string());
+        }
+    }
+
+    /// The loaded collection's Requests pane order.
+    pub fn request_sort(&self) -> RequestSort {
+        self.current_collection_uid
+            .as_ref()
+            .and_then(|uid| self.config.request_sort.get(uid))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    fn sort_collections(&mut self) {
+        // Sort alphabetically (favorites are handled separately in flatten)
+        self.collections.sort_by(|a, b| {
+            a.name.to_lowercase().cmp(&b.name.to_lowercase())
+        });
+    }
+
+    pub fn flatten_collections(&mut self) {
+        self.flat_collections.clear();
+
+        let favorite_uids: Vec<&String> = self.collections
+            .iter()
+            .filter(|c| self.config.is_favorite(&c.uid))
+            .map(|c| &c.uid)
+            .collect();
+
+        // Add Favorites section if there are any
+        if !favorite_uids.is_empty() {
+            self.flat_collections.push(FlatCollection {
+                name: String::from("Favorites"),
+                uid: String::new(),
+                is_favorites_folder: true,
+                depth: 0,
+            });
+
+            if self.collections_favorites_expanded {
+                for collection in &self.collections {
+                    if self.config.is_favorite(&collection.uid) {
+                        self.flat_collections.push(FlatCollection {
+                            name: collection.name.clone(),
+                            uid: collection.uid.clone(),
+                            is_favorites_folder: false,
+                            depth: 1,
+                        });
+                    }
+                }
+            }
+        }
+
+        // Add all collections
+        for collection in &self.collections {
+            self.flat_collections.push(FlatCollection {
+                name: collection.name.clone(),
+                uid: collection.uid.clone(),
+                is_favorites_folder: false,
+                depth: 0,
+            });
+        }
+    }
+
+    pub fn toggle_collections_favorites_folder(&mut self) {
         self.collections_favorites_expanded = !self.collections_favorites_expanded;
         let selected_uid = self.flat_collections.get(self.selected_collection_index)
             .map(|c| c.uid.clone());
@@ -519,6 +1448,45 @@ impl App {
         }
     }
 
+    /// Advance the loading spinner by one frame. Called once per draw tick.
+    pub fn tick(&mut self) {
+        self.spinner_frame = self.spinner_frame.wrapping_add(1);
+        self.expire_status_message();
+    }
+
+    /// Revert an informational notice to the pane's key hints once it has been
+    /// shown for a few seconds. Prompts (anything outside Normal mode) and
+    /// in-progress messages stay put; errors live in `self.error` and persist
+    /// until dismissed with Esc.
+    fn expire_status_message(&mut self) {
+        const STATUS_TTL: Duration = Duration::from_secs(5);
+
+        if self.status_message != self.status_seen {
+            self.status_seen = self.status_message.clone();
+            self.status_since = Instant::now();
+            return;
+        }
+        if self.input_mode != InputMode::Normal || self.loading || self.status_since.elapsed() < STATUS_TTL {
+            return;
+        }
+        self.update_status_for_pane();
+        self.status_seen = self.status_message.clone();
+        self.status_since = Instant::now();
+    }
+
+    /// Dismiss the error shown in the status bar.
+    pub fn clear_error(&mut self) {
+        if self.error.take().is_some() {
+            self.update_status_for_pane();
+        }
+    }
+
+    /// The current spinner glyph, for loading popups and the status bar.
+    pub fn spinner(&self) -> &'static str {
+        const FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+        FRAMES[self.spinner_frame % FRAMES.len()]
+    }
+
     pub fn set_focus(&mut self, pane: FocusedPane) {
         self.focused_pane = pane;
         self.update_status_for_pane();
@@ -535,6 +1503,29 @@ impl App {
     }
 
 
+    /// Timeout for requests without their own, from the active workspace's
+    /// settings.
+    pub fn default_timeout_secs(&self) -> u64 {
+        self.config
+            .execution_settings(self.get_selected_workspace_id().as_deref())
+            .timeout_secs
+    }
+
+    /// Point the execution client at the active workspace's proxy and TLS
+    /// settings (falling back to `[settings]`).
+    pub fn apply_execution_settings(&mut self) {
+        let settings = self.config.execution_settings(self.get_selected_workspace_id().as_deref());
+        self.client.set_default_headers(self.config.settings.default_headers.clone());
+        self.client.set_follow_redirects(self.config.settings.follow_redirects);
+        self.client.set_http_version(self.config.settings.http_version);
+        self.client.set_max_response_bytes(self.config.settings.max_response_bytes);
+        if let Err(e) = self.client.configure_execution(settings.proxy.as_deref(), settings.insecure_tls) {
+            let error_msg = format!("{:#}", e);
+            log_error("configure_execution", &error_msg);
+            self.error = Some(error_msg);
+        }
+    }
+
     fn get_selected_workspace_id(&self) -> Option<String> {
         self.selected_workspace_index
             .and_then(|idx| self.workspaces.get(idx))
@@ -546,10 +1537,7 @@ impl App {
             if let Some(env_info) = self.environments.get(idx) {
                 let env_uid = env_info.uid.clone();
                 match self.client.get_environment(&env_uid).await {
-                    Ok(env_detail) => {
-                        self.current_environment = Some(env_detail);
-                        self.rebuild_variables();
-                    }
+                    Ok(env_detail) => self.set_loaded_environment(env_detail),
                     Err(e) => {
                         log_error("load_environment", &e.to_string());
                     }
@@ -561,42 +1549,111 @@ impl App {
         }
     }
 
+    /// Fetch the environment switched to by `restore_collection_environment`.
+    pub async fn load_pending_environment(&mut self) {
+        self.pending_environment_load = false;
+        self.load_selected_environment().await;
+        self.status_message = format!(
+            "{} | Environment: {}",
+            self.status_message,
+            self.get_current_environment_name()
+        );
+    }
+
+    /// Make `detail`, just fetched from Postman, the current environment.
+    fn set_loaded_environment(&mut self, detail: EnvironmentDetail) {
+        self.loaded_environment_values = detail.values.clone();
+        self.current_environment = Some(detail);
+        self.last_refresh = Some(chrono::Local::now());
+        self.rebuild_variables();
+    }
+
     pub fn rebuild_variables(&mut self) {
-        self.variables.clear();
+        self.variables = self.variables_for_path(self.current_request_path.as_deref());
+    }
 
-        // First add collection variables (lower priority)
-        if let Some(collection) = &self.current_collection {
-            for var in &collection.variable {
+    /// Variables visible to the request at `path`, layered from lowest to
+    /// highest priority: collection, enclosing folders (outermost first),
+    /// environment, then a fetched OAuth token.
+    fn variables_for_path(&self, path: Option<&[usize]>) -> HashMap<String, String> {
+        self.variables_in(self.current_collection.as_ref(), path)
+    }
+
+    /// `variables_for_path` for a request in `collection`, which need not be
+    /// the loaded one.
+    fn variables_in(&self, collection: Option<&CollectionDetail>, path: Option<&[usize]>) -> HashMap<String, String> {
+        let mut variables = HashMap::new();
+        let mut insert_enabled = |vars: &[Variable]| {
+            for var in vars {
                 if var.enabled.unwrap_or(true) {
-                    self.variables.insert(var.key.clone(), var.value.clone());
+                    variables.insert(var.key.clone(), var.value.clone());
                 }
             }
-        }
+        };
 
-        // Then add environment variables (higher priority, overwrites collection vars)
-        if let Some(env) = &self.current_environment {
-            for var in &env.values {
-                if var.enabled.unwrap_or(true) {
-                    self.variables.insert(var.key.clone(), var.value.clone());
+        if let Some(collection) = collection {
+            insert_enabled(&collection.variable);
+            if let Some(path) = path {
+                for folder in folders_along_path(&collection.item, path) {
+                    insert_enabled(&folder.variable);
                 }
             }
         }
+
+        if let Some(env) = &self.current_environment {
+            insert_enabled(&env.values);
+        }
+
+        // A fetched OAuth token is the freshest value for its variable
+        if let (Some(oauth), Some(token)) = (&self.config.oauth, &self.oauth_token) {
+            variables.insert(oauth.variable.clone(), token.access_token.clone());
+        }
+        variables
     }
 
     pub fn substitute_variables(&self, text: &str) -> String {
+        self.substitute_with(text, &self.variables)
+    }
+
+    fn substitute_with(&self, text: &str, variables: &HashMap<String, String>) -> String {
+        // Postman's dynamic variables, unless a scope defined one of the same name
+        crate::util::substitute_dynamic_variables(&self.substitute_defined(text, variables))
+    }
+
+    /// `text` with the variables defined in scope substituted; dynamic ones
+    /// like `{{$guid}}` are left as written.
+    fn substitute_defined(&self, text: &str, variables: &HashMap<String, String>) -> String {
         let mut result = text.to_string();
         // Per-request placeholder overrides win over environment/collection vars.
         for (key, value) in &self.param_overrides {
             let pattern = format!("{{{{{}}}}}", key);
             result = result.replace(&pattern, value);
         }
-        for (key, value) in &self.variables {
+        for (key, value) in variables {
             let pattern = format!("{{{{{}}}}}", key);
             result = result.replace(&pattern, value);
         }
         result
     }
 
+    /// `text` as the resolved preview shows it: defined variables
+    /// substituted, dynamic ones kept (they get a fresh value on every send,
+    /// so substituting them here would make the preview flicker).
+    pub fn substitute_for_preview(&self, text: &str) -> String {
+        self.substitute_defined(text, &self.variables)
+    }
+
+    /// Flip the request preview between `{{variables}}` as written and
+    /// substituted with their current values.
+    pub fn toggle_preview_resolved(&mut self) {
+        self.preview_resolved = !self.preview_resolved;
+        self.status_message = if self.preview_resolved {
+            String::from("Preview: variables resolved (unresolved ones in red)")
+        } else {
+            String::from("Preview: variables as written")
+        };
+    }
+
     pub fn open_environment_popup(&mut self) {
         // Set the popup index to current selection, or 0 for "No Environment"
         self.environment_popup_index = self.selected_environment_index
@@ -629,6 +1686,24 @@ impl App {
         self.status_message = format!("Environment: {}", env_name);
     }
 
+    /// Switch to the next (`delta > 0`) or previous environment without
+    /// the popup, wrapping through "No Environment", so the same request
+    /// can be re-run against dev, staging and prod in turn.
+    pub async fn cycle_environment(&mut self, delta: isize) {
+        if self.environments.is_empty() {
+            self.status_message = String::from("No environments in this workspace");
+            return;
+        }
+        // Same indices as the popup: 0 is "No Environment"
+        let count = self.environments.len() as isize + 1;
+        let current = self.selected_environment_index.map_or(0, |i| i + 1) as isize;
+        self.environment_popup_index = (current + delta).rem_euclid(count) as usize;
+        self.confirm_environment_selection().await;
+        if let Some(base_url) = self.variables.get("baseUrl") {
+            self.status_message = format!("{} | baseUrl: {}", self.status_message, base_url);
+        }
+    }
+
     pub fn environment_popup_up(&mut self) {
         if self.environment_popup_index > 0 {
             self.environment_popup_index -= 1;
@@ -661,6 +1736,26 @@ impl App {
         self.input_mode = InputMode::WorkspaceSelect;
     }
 
+    /// Ids of workspaces whose counts haven't been fetched yet.
+    pub fn workspaces_missing_counts(&self) -> Vec<String> {
+        self.workspaces
+            .iter()
+            .filter(|w| !self.workspace_counts.contains_key(&w.id))
+            .map(|w| w.id.clone())
+            .collect()
+    }
+
+    /// Record a workspace's fetched counts; on failure the popup keeps
+    /// showing just the name.
+    pub fn apply_workspace_counts(&mut self, workspace_id: String, result: Result<WorkspaceCounts>) {
+        match result {
+            Ok(counts) => {
+                self.workspace_counts.insert(workspace_id, counts);
+            }
+            Err(e) => log_error("fetch_workspace_counts", &format!("{:#}", e)),
+        }
+    }
+
     pub fn close_workspace_popup(&mut self) {
         self.input_mode = InputMode::Normal;
     }
@@ -678,6 +1773,7 @@ impl App {
 
         let new_workspace_id = self.get_selected_workspace_id();
         self.input_mode = InputMode::Normal;
+        self.apply_execution_settings();
 
         // Save workspace selection
         self.save_workspace_state();
@@ -692,7 +1788,9 @@ impl App {
             self.current_collection_uid = None;
             self.flat_items.clear();
             self.current_request = None;
+            self.current_request_path = None;
             self.response = None;
+            self.response_pinned = false;
             self.selected_collection_index = 0;
             self.selected_item_index = 0;
 
@@ -776,25 +1874,103 @@ impl App {
     }
 
     // Variables view methods
+    /// Opens on the environment's variables, or the collection's when no
+    /// environment is selected.
     pub fn open_variables_popup(&mut self) {
-        if self.current_environment.is_none() {
-            self.status_message = String::from("No environment selected");
+        self.variables_scope = if self.current_environment.is_some() {
+            VariablesScope::Environment
+        } else if self.current_collection.is_some() {
+            VariablesScope::Collection
+        } else {
+            self.status_message = String::from("No environment or collection selected");
             return;
-        }
+        };
         self.variables_popup_index = 0;
         self.editing_variable = None;
         self.variable_cursor_position = 0;
         self.variables_modified = false;
+        self.loaded_collection_variables = self.current_collection.as_ref().map(|c| c.variable.clone()).unwrap_or_default();
         self.variables_search_query.clear();
         self.variables_search_active = false;
         self.variables_filtered_indices.clear();
+        self.variables_export_confirm = false;
+        self.variable_history_cycle = None;
         self.input_mode = InputMode::VariablesView;
     }
 
+    /// Jump to where the next `{{variable}}` of the selected request is
+    /// defined (the first one in the URL on the first press). Environment
+    /// variables open in the variables view, filtered to that name and ready
+    /// to edit; other scopes are reported in the status bar.
+    pub fn go_to_variable_definition(&mut self) {
+        let names = self.current_request_params();
+        if names.is_empty() {
+            self.status_message = String::from("Request uses no {{variables}}");
+            return;
+        }
+        let name = names[self.definition_cursor % names.len()].clone();
+        self.definition_cursor = (self.definition_cursor + 1) % names.len();
+
+        match self.variable_definition(&name) {
+            Some((scope, value, true)) => {
+                self.open_variables_popup();
+                self.variables_scope = VariablesScope::Environment;
+                self.variables_search_query = name.clone();
+                self.update_variables_search_matches();
+                let exact = self.current_environment.as_ref().and_then(|env| {
+                    self.variables_filtered_indices
+                        .iter()
+                        .position(|&i| env.values.get(i).is_some_and(|v| v.key == name))
+                });
+                self.variables_popup_index = exact.unwrap_or(0);
+                self.status_message = format!("{{{{{}}}}} = {} ({}) - e to edit", name, value, scope);
+            }
+            Some((scope, value, false)) => {
+                self.status_message = format!("{{{{{}}}}} = {} ({})", name, value, scope);
+            }
+            None => {
+                self.status_message = format!("{{{{{}}}}} is not defined in any scope", name);
+            }
+        }
+    }
+
+    /// Where `name` gets its value for the selected request, checked from the
+    /// highest-priority scope down: (scope label, value, is environment).
+    fn variable_definition(&self, name: &str) -> Option<(String, String, bool)> {
+        let find = |vars: &[Variable]| {
+            vars.iter()
+                .find(|v| v.key == name && v.enabled.unwrap_or(true))
+                .map(|v| v.value.clone())
+        };
+
+        let oauth_variable = self.config.oauth.as_ref().filter(|o| o.variable == name);
+        if let Some(token) = oauth_variable.and(self.oauth_token.as_ref()) {
+            return Some((String::from("OAuth token"), token.access_token.clone(), false));
+        }
+        if let Some(value) = self.current_environment.as_ref().and_then(|env| find(&env.values)) {
+            let scope = format!("environment '{}'", self.get_current_environment_name());
+            return Some((scope, value, true));
+        }
+        let collection = self.current_collection.as_ref()?;
+        if let Some(path) = self.current_request_path.as_deref() {
+            for folder in folders_along_path(&collection.item, path).iter().rev() {
+                if let Some(value) = find(&folder.variable) {
+                    return Some((format!("folder '{}'", folder.name), value, false));
+                }
+            }
+        }
+        find(&collection.variable).map(|value| (format!("collection '{}'", collection.info.name), value, false))
+    }
+
     pub fn close_variables_popup(&mut self) {
         self.editing_variable = None;
         self.input_mode = InputMode::Normal;
         if self.variables_modified {
+            if self.variables_scope == VariablesScope::Collection
+                && let Some(collection) = self.current_collection.as_mut()
+            {
+                collection.variable = self.loaded_collection_variables.clone();
+            }
             self.status_message = String::from("Unsaved changes discarded");
         }
         self.variables_modified = false;
@@ -815,10 +1991,8 @@ impl App {
         }
         let max_index = if !self.variables_search_query.is_empty() {
             self.variables_filtered_indices.len().saturating_sub(1)
-        } else if let Some(env) = &self.current_environment {
-            env.values.len().saturating_sub(1)
         } else {
-            0
+            self.scoped_variables().len().saturating_sub(1)
         };
         if self.variables_popup_index < max_index {
             self.variables_popup_index += 1;
@@ -829,13 +2003,68 @@ impl App {
         if self.variables_search_active {
             return; // Can't edit while searching
         }
+        self.variable_history_cycle = None;
         let actual_index = self.get_actual_variable_index();
-        if let Some(env) = &self.current_environment {
-            if let Some(var) = env.values.get(actual_index) {
-                let current_value = var.value.clone();
-                self.variable_cursor_position = current_value.len();
-                self.editing_variable = Some((actual_index, current_value));
+        if let Some(var) = self.scoped_variables().get(actual_index) {
+            let current_value = var.value.clone();
+            self.variable_cursor_position = current_value.len();
+            self.editing_variable = Some((actual_index, current_value));
+        }
+    }
+
+    /// The variables the variables view is showing, per `variables_scope`.
+    fn scoped_variables(&self) -> &[Variable] {
+        match self.variables_scope {
+            VariablesScope::Environment => self.current_variables(),
+            VariablesScope::Collection => self.current_collection.as_ref().map_or(&[], |c| c.variable.as_slice()),
+        }
+    }
+
+    fn scoped_variables_mut(&mut self) -> Option<&mut Vec<Variable>> {
+        match self.variables_scope {
+            VariablesScope::Environment => self.current_environment.as_mut().map(|env| &mut env.values),
+            VariablesScope::Collection => self.current_collection.as_mut().map(|c| &mut c.variable),
+        }
+    }
+
+    /// Switch the variables view between the environment's variables and
+    /// the collection's. Unsaved edits have to be saved first, as `s` only
+    /// saves the scope on screen.
+    pub fn toggle_variables_scope(&mut self) {
+        if self.editing_variable.is_some() || self.variables_search_active {
+            return;
+        }
+        if self.variables_modified {
+            self.status_message = String::from("Save (s) these changes before switching");
+            return;
+        }
+        let scope = match self.variables_scope {
+            VariablesScope::Environment if self.current_collection.is_some() => VariablesScope::Collection,
+            VariablesScope::Collection if self.current_environment.is_some() => VariablesScope::Environment,
+            VariablesScope::Environment => {
+                self.status_message = String::from("No collection loaded");
+                return;
+            }
+            VariablesScope::Collection => {
+                self.status_message = String::from("No environment selected");
+                return;
             }
+        };
+        self.variables_scope = scope;
+        self.variables_popup_index = 0;
+        self.variables_search_query.clear();
+        self.variables_filtered_indices.clear();
+        self.variable_history_cycle = None;
+    }
+
+    /// Title for the variables view: the environment or collection name.
+    pub fn variables_scope_label(&self) -> String {
+        match self.variables_scope {
+            VariablesScope::Environment => format!("Environment: {}", self.get_current_environment_name()),
+            VariablesScope::Collection => format!(
+                "Collection: {}",
+                self.current_collection.as_ref().map_or("", |c| c.info.name.as_str())
+            ),
         }
     }
 
@@ -878,15 +2107,15 @@ impl App {
         }
 
         let query = self.variables_search_query.to_lowercase();
-        if let Some(env) = &self.current_environment {
-            for (i, var) in env.values.iter().enumerate() {
-                if var.key.to_lowercase().contains(&query)
+        let matches: Vec<usize> = self.scoped_variables().iter()
+            .enumerate()
+            .filter(|(_, var)| {
+                var.key.to_lowercase().contains(&query)
                     || var.value.to_lowercase().contains(&query)
-                {
-                    self.variables_filtered_indices.push(i);
-                }
-            }
-        }
+            })
+            .map(|(i, _)| i)
+            .collect();
+        self.variables_filtered_indices = matches;
     }
 
     pub fn confirm_variables_search(&mut self) {
@@ -904,70 +2133,151 @@ impl App {
     pub fn variable_input_char(&mut self, c: char) {
         if let Some((_, ref mut value)) = self.editing_variable {
             value.insert(self.variable_cursor_position, c);
-            self.variable_cursor_position += 1;
+            self.variable_cursor_position += c.len_utf8();
         }
     }
 
     pub fn variable_backspace(&mut self) {
-        if let Some((_, ref mut value)) = self.editing_variable {
-            if self.variable_cursor_position > 0 {
-                self.variable_cursor_position -= 1;
-                value.remove(self.variable_cursor_position);
-            }
+        if let Some((_, ref mut value)) = self.editing_variable
+            && let Some((index, _)) = value[..self.variable_cursor_position].char_indices().next_back()
+        {
+            value.remove(index);
+            self.variable_cursor_position = index;
         }
     }
 
     pub fn variable_cursor_left(&mut self) {
-        if self.variable_cursor_position > 0 {
-            self.variable_cursor_position -= 1;
+        if let Some((_, ref value)) = self.editing_variable
+            && let Some((index, _)) = value[..self.variable_cursor_position].char_indices().next_back()
+        {
+            self.variable_cursor_position = index;
         }
     }
 
     pub fn variable_cursor_right(&mut self) {
-        if let Some((_, ref value)) = self.editing_variable {
-            if self.variable_cursor_position < value.len() {
-                self.variable_cursor_position += 1;
-            }
+        if let Some((_, ref value)) = self.editing_variable
+            && let Some(c) = value[self.variable_cursor_position..].chars().next()
+        {
+            self.variable_cursor_position += c.len_utf8();
         }
     }
 
     pub fn confirm_variable_edit(&mut self) {
+        // Only environment variables keep a history
+        let environment_uid = self.selected_environment_uid()
+            .filter(|_| self.variables_scope == VariablesScope::Environment);
         if let Some((index, new_value)) = self.editing_variable.take() {
-            if let Some(env) = &mut self.current_environment {
-                if let Some(var) = env.values.get_mut(index) {
-                    log_error("confirm_variable_edit", &format!(
-                        "index={}, key={}, old_value={}, new_value={}, changed={}",
-                        index, var.key, var.value, new_value, var.value != new_value
-                    ));
-                    if var.value != new_value {
+            if let Some(var) = self.scoped_variables().get(index).cloned() {
+                log_error("confirm_variable_edit", &format!(
+                    "index={}, key={}, old_value={}, new_value={}, changed={}",
+                    index, var.key, var.value, new_value, var.value != new_value
+                ));
+                if var.value != new_value {
+                    // Secret values are never written to disk
+                    if let Some(uid) = environment_uid.as_deref().filter(|_| !is_secret_variable(&var)) {
+                        self.variable_history.record(uid, &var.key, var.value.clone());
+                        if let Err(e) = self.variable_history.save() {
+                            log_error("save_variable_history", &e.to_string());
+                        }
+                    }
+                    if let Some(var) = self.scoped_variables_mut().and_then(|vars| vars.get_mut(index)) {
                         var.value = new_value;
-                        self.variables_modified = true;
-                        self.rebuild_variables();
                     }
+                    self.variables_modified = true;
+                    self.rebuild_variables();
                 }
             }
         }
         self.variable_cursor_position = 0;
     }
 
-    pub fn cancel_variable_edit(&mut self) {
-        self.editing_variable = None;
-        self.variable_cursor_position = 0;
-    }
-
-    pub async fn save_variables_to_postman(&mut self) {
-        log_error("save_variables_to_postman", &format!(
-            "called, variables_modified={}",
-            self.variables_modified
-        ));
-        if !self.variables_modified {
-            self.status_message = String::from("No changes to save");
+    /// Step the selected variable through its earlier values (newest first),
+    /// wrapping back round to the value it had when cycling began.
+    pub fn cycle_variable_history(&mut self) {
+        if self.editing_variable.is_some() || self.variables_search_active {
             return;
         }
-
-        let env_idx = match self.selected_environment_index {
-            Some(idx) => idx,
-            None => {
+        if self.variables_scope == VariablesScope::Collection {
+            self.status_message = String::from("Earlier values are kept for environment variables only");
+            return;
+        }
+        let index = self.get_actual_variable_index();
+        let Some((key, value, secret)) = self.current_environment
+            .as_ref()
+            .and_then(|env| env.values.get(index))
+            .map(|var| (var.key.clone(), var.value.clone(), is_secret_variable(var)))
+        else {
+            return;
+        };
+        let mut cycle = match self.variable_history_cycle.take() {
+            Some(cycle) if cycle.index == index => cycle,
+            _ => {
+                // Keep the value being cycled away from (it may have just
+                // been typed), so it isn't lost if an earlier one is kept
+                if let Some(uid) = self.selected_environment_uid().filter(|_| !secret && !value.is_empty()) {
+                    let known = self.variable_history.get(&uid, &key).contains(&value);
+                    if !known {
+                        self.variable_history.record(&uid, &key, value.clone());
+                        if let Err(e) = self.variable_history.save() {
+                            log_error("save_variable_history", &e.to_string());
+                        }
+                    }
+                }
+                let earlier = self.selected_environment_uid()
+                    .map(|uid| self.variable_history.get(&uid, &key).to_vec())
+                    .unwrap_or_default();
+                let mut values = vec![value.clone()];
+                values.extend(earlier.into_iter().filter(|v| *v != value));
+                if values.len() < 2 {
+                    self.status_message = format!("No earlier values of {}", key);
+                    return;
+                }
+                VariableHistoryCycle { index, values, position: 0 }
+            }
+        };
+        cycle.position = (cycle.position + 1) % cycle.values.len();
+        let restored = cycle.values[cycle.position].clone();
+        self.status_message = if cycle.position == 0 {
+            format!("{}: back to the current value", key)
+        } else {
+            format!("{}: earlier value {} of {} (s to save)", key, cycle.position, cycle.values.len() - 1)
+        };
+        self.variable_history_cycle = Some(cycle);
+        if let Some(var) = self.current_environment.as_mut().and_then(|env| env.values.get_mut(index)) {
+            var.value = restored;
+            self.variables_modified = true;
+            self.rebuild_variables();
+        }
+    }
+
+    fn selected_environment_uid(&self) -> Option<String> {
+        self.selected_environment_index
+            .and_then(|idx| self.environments.get(idx))
+            .map(|env| env.uid.clone())
+    }
+
+    pub fn cancel_variable_edit(&mut self) {
+        self.editing_variable = None;
+        self.variable_cursor_position = 0;
+    }
+
+    pub async fn save_variables_to_postman(&mut self) {
+        log_error("save_variables_to_postman", &format!(
+            "called, variables_modified={}",
+            self.variables_modified
+        ));
+        if !self.variables_modified {
+            self.status_message = String::from("No changes to save");
+            return;
+        }
+        if self.variables_scope == VariablesScope::Collection {
+            self.save_collection_variables().await;
+            return;
+        }
+
+        let env_idx = match self.selected_environment_index {
+            Some(idx) => idx,
+            None => {
                 self.status_message = String::from("No environment selected");
                 return;
             }
@@ -981,24 +2291,85 @@ impl App {
             }
         };
 
-        let env_detail = match &self.current_environment {
-            Some(detail) => detail.clone(),
-            None => {
-                self.status_message = String::from("No environment loaded");
+        if self.current_environment.is_none() {
+            self.status_message = String::from("No environment loaded");
+            return;
+        }
+
+        if self.config.settings.check_environment_conflicts {
+            match self.client.get_environment(&env_info.uid).await {
+                Ok(server) => {
+                    let summary = environment_changes(&self.loaded_environment_values, &server.values, self.current_variables());
+                    if !summary.is_empty() {
+                        self.environment_conflict = Some(EnvironmentConflict { server, summary });
+                        self.input_mode = InputMode::EnvironmentConflict;
+                        self.status_message = String::from("Environment changed in Postman - nothing was overwritten");
+                        return;
+                    }
+                }
+                Err(e) => {
+                    let error_msg = format!("Couldn't check Postman for changes before saving: {:#}", e);
+                    log_error("save_variables", &error_msg);
+                    self.error = Some(error_msg);
+                    self.status_message = String::from("Variables not saved");
+                    return;
+                }
+            }
+        }
+        self.put_environment_values(&env_info).await;
+    }
+
+    /// PUT the loaded collection with its edited variables, unless it
+    /// changed in Postman since it was loaded.
+    async fn save_collection_variables(&mut self) {
+        let (collection, collection_uid) = match (&self.current_collection, self.get_current_collection_uid()) {
+            (Some(c), Some(uid)) => (c.clone(), uid),
+            _ => {
+                self.status_message = String::from("No collection loaded");
                 return;
             }
         };
+        self.status_message = String::from("Saving collection variables...");
+        let result = self.client
+            .update_collection_checked(&collection_uid, &collection.info, &collection.item, &self.loaded_collection_variables, &collection.item, &collection.variable)
+            .await;
+        match result {
+            Ok(()) => {
+                self.variables_modified = false;
+                self.loaded_collection_variables = collection.variable.clone();
+                self.collection_cache.insert(collection_uid, collection);
+                self.status_message = String::from("Collection variables saved");
+            }
+            Err(e) if e.is::<CollectionChanged>() => {
+                self.status_message = String::from("Collection changed in Postman - refresh it and edit again; nothing was overwritten");
+            }
+            Err(e) => {
+                let error_msg = format!("{:#}", e);
+                log_error("save_collection_variables", &error_msg);
+                self.error = Some(error_msg);
+                self.status_message = String::from("Failed to save collection variables");
+            }
+        }
+    }
 
+    fn current_variables(&self) -> &[Variable] {
+        self.current_environment.as_ref().map_or(&[], |env| env.values.as_slice())
+    }
+
+    /// PUT the current environment's variables to Postman, replacing what's
+    /// there.
+    async fn put_environment_values(&mut self, env_info: &EnvironmentInfo) {
         self.status_message = String::from("Saving variables...");
 
         // Replace empty keys with "NOT_SET" to avoid API rejection
-        let values: Vec<_> = env_detail.values.iter()
+        let values: Vec<_> = self.current_variables().iter()
             .map(|v| {
                 if v.key.trim().is_empty() {
                     crate::api::Variable {
                         key: "NOT_SET".to_string(),
                         value: v.value.clone(),
                         enabled: v.enabled,
+                        var_type: v.var_type.clone(),
                     }
                 } else {
                     v.clone()
@@ -1009,6 +2380,7 @@ impl App {
         match self.client.update_environment(&env_info.uid, &env_info.name, &values).await {
             Ok(()) => {
                 self.variables_modified = false;
+                self.loaded_environment_values = self.current_variables().to_vec();
                 self.status_message = String::from("Variables saved successfully");
             }
             Err(e) => {
@@ -1020,23 +2392,130 @@ impl App {
         }
     }
 
-    pub fn get_variables_for_display(&self) -> Vec<(usize, String, String, bool)> {
-        match &self.current_environment {
-            Some(env) => {
-                if !self.variables_search_query.is_empty() {
-                    // Return only filtered variables with their original indices
-                    self.variables_filtered_indices.iter()
-                        .filter_map(|&i| env.values.get(i).map(|v| (i, v.key.clone(), v.value.clone(), v.enabled.unwrap_or(true))))
-                        .collect()
-                } else {
-                    // Return all variables with indices
-                    env.values.iter()
-                        .enumerate()
-                        .map(|(i, v)| (i, v.key.clone(), v.value.clone(), v.enabled.unwrap_or(true)))
-                        .collect()
-                }
+    /// Settle a held-back variables save: `merge` keeps Postman's changes to
+    /// variables edited only there and applies the local edits on top (local
+    /// wins where both changed); otherwise the local variables overwrite
+    /// Postman's.
+    pub async fn resolve_environment_conflict(&mut self, merge: bool) {
+        self.input_mode = InputMode::VariablesView;
+        let Some(conflict) = self.environment_conflict.take() else {
+            return;
+        };
+        let Some(env_info) = self.selected_environment_index.and_then(|idx| self.environments.get(idx)).cloned() else {
+            return;
+        };
+        if merge {
+            let merged = merge_environment(&self.loaded_environment_values, self.current_variables(), &conflict.server.values);
+            if let Some(env) = &mut self.current_environment {
+                env.values = merged;
             }
-            None => Vec::new(),
+            self.variable_history_cycle = None;
+            self.variables_popup_index = 0;
+            self.variables_search_query.clear();
+            self.variables_filtered_indices.clear();
+            self.rebuild_variables();
+        }
+        self.put_environment_values(&env_info).await;
+    }
+
+    pub fn cancel_environment_conflict(&mut self) {
+        self.environment_conflict = None;
+        self.input_mode = InputMode::VariablesView;
+        self.status_message = String::from("Save cancelled - your edits are kept");
+    }
+
+    /// The environment's enabled variables, as (key, value), for export.
+    fn exportable_variables(&self) -> Vec<(String, String)> {
+        self.current_environment
+            .iter()
+            .flat_map(|env| &env.values)
+            .filter(|v| v.enabled.unwrap_or(true) && !v.key.trim().is_empty())
+            .map(|v| (v.key.clone(), v.value.clone()))
+            .collect()
+    }
+
+    /// Where `f` in the export prompt writes the `.env` file: the working
+    /// directory, named after the environment.
+    pub fn variables_export_path(&self) -> std::path::PathBuf {
+        let name = crate::util::env_var_name(&self.get_current_environment_name()).to_lowercase();
+        std::env::current_dir().unwrap_or_default().join(format!("{}.env", name))
+    }
+
+    /// Ask for confirmation before exporting; the export contains every
+    /// value in plain text, secrets included.
+    pub fn start_variables_export(&mut self) {
+        if self.editing_variable.is_some() || self.variables_search_active {
+            return;
+        }
+        if self.variables_scope == VariablesScope::Collection {
+            self.status_message = String::from("Export is for environment variables - Tab to switch");
+            return;
+        }
+        let count = self.exportable_variables().len();
+        if count == 0 {
+            self.status_message = String::from("No enabled variables to export");
+            return;
+        }
+        self.variables_export_confirm = true;
+        self.status_message = format!("Export {} variables unmasked, secrets included?", count);
+    }
+
+    pub fn cancel_variables_export(&mut self) {
+        self.variables_export_confirm = false;
+        self.status_message = String::from("Export cancelled");
+    }
+
+    /// Write the enabled variables to `variables_export_path` as a `.env`
+    /// file, replacing one that exists (the prompt says so).
+    pub fn export_variables_to_file(&mut self) {
+        self.variables_export_confirm = false;
+        let variables = self.exportable_variables();
+        let content: String = variables
+            .iter()
+            .map(|(key, value)| crate::util::dotenv_line(key, value) + "\n")
+            .collect();
+        let path = self.variables_export_path();
+        let existed = path.exists();
+        match std::fs::write(&path, content) {
+            Ok(()) => {
+                let verb = if existed { "Overwrote" } else { "Exported to" };
+                self.status_message = format!("{} {} with {} variables", verb, path.display(), variables.len());
+            }
+            Err(e) => {
+                let error_msg = format!("Failed to write {}: {}", path.display(), e);
+                log_error("export_variables", &error_msg);
+                self.error = Some(error_msg);
+                self.status_message = String::from("Export failed");
+            }
+        }
+    }
+
+    /// Copy the enabled variables to the clipboard as `export KEY='value'` lines.
+    pub fn copy_variables_as_exports(&mut self) {
+        self.variables_export_confirm = false;
+        let variables = self.exportable_variables();
+        let lines: Vec<String> = variables
+            .iter()
+            .map(|(key, value)| crate::util::shell_export_line(key, value))
+            .collect();
+        if self.copy_to_clipboard(&lines.join("\n")) {
+            self.status_message = format!("Copied {} export lines to clipboard", lines.len());
+        }
+    }
+
+    pub fn get_variables_for_display(&self) -> Vec<(usize, String, String, bool)> {
+        let values = self.scoped_variables();
+        if !self.variables_search_query.is_empty() {
+            // Return only filtered variables with their original indices
+            self.variables_filtered_indices.iter()
+                .filter_map(|&i| values.get(i).map(|v| (i, v.key.clone(), v.value.clone(), v.enabled.unwrap_or(true))))
+                .collect()
+        } else {
+            // Return all variables with indices
+            values.iter()
+                .enumerate()
+                .map(|(i, v)| (i, v.key.clone(), v.value.clone(), v.enabled.unwrap_or(true)))
+                .collect()
         }
     }
 
@@ -1068,21 +2547,84 @@ impl App {
         CollectionLoad::Fetch
     }
 
+    /// Re-point favorites and the saved last request at where their Postman ids
+    /// now live in the loaded collection, since positional paths shift when
+    /// requests are added, removed or reordered. Entries saved before ids were
+    /// recorded pick up the id at their current path (a one-time migration).
+    fn reconcile_request_paths(&mut self) {
+        let (uid, items) = match (&self.current_collection_uid, &self.current_collection) {
+            (Some(uid), Some(collection)) => (uid, &collection.item),
+            _ => return,
+        };
+
+        let mut changed = false;
+        let pending_favorite_path = &mut self.pending_favorite_path;
+        self.config.favorite_requests.retain_mut(|fav| {
+            if &fav.collection_uid != uid {
+                return true;
+            }
+            let old_path = fav.path.clone();
+            match reconcile_request_path(items, &mut fav.path, &mut fav.request_id) {
+                StoredPath::Unchanged => true,
+                StoredPath::Updated => {
+                    if fav.path != old_path && pending_favorite_path.as_ref() == Some(&old_path) {
+                        *pending_favorite_path = Some(fav.path.clone());
+                    }
+                    changed = true;
+                    true
+                }
+                // Its path would now point at another request
+                StoredPath::Gone => {
+                    if pending_favorite_path.as_ref() == Some(&old_path) {
+                        *pending_favorite_path = None;
+                    }
+                    changed = true;
+                    false
+                }
+            }
+        });
+
+        if let Some(state) = self.config.last_state.as_mut().filter(|s| &s.collection_uid == uid) {
+            match reconcile_request_path(items, &mut state.request_path, &mut state.request_id) {
+                StoredPath::Unchanged => {}
+                StoredPath::Updated => changed = true,
+                StoredPath::Gone => {
+                    state.request_path.clear();
+                    state.request_id = None;
+                    changed = true;
+                }
+            }
+        }
+
+        if !changed {
+            return;
+        }
+        if let Err(e) = self.config.save() {
+            log_error("reconcile_request_paths", &e.to_string());
+        }
+    }
+
     /// Make a freshly-loaded collection detail the active one: cache it, rebuild
     /// the request tree, and focus the Requests pane. Shared by the cached,
     /// blocking-fetch, and startup paths.
     fn apply_collection_detail(&mut self, detail: CollectionDetail, uid: String) {
         self.collection_cache.insert(uid.clone(), detail.clone());
         self.current_collection = Some(detail);
+        self.restore_collection_environment(&uid);
         self.current_collection_uid = Some(uid);
+        self.current_request_path = None;
+        self.reconcile_request_paths();
         self.rebuild_variables();
         self.expanded_folders.clear();
         self.flatten_items();
         self.selected_item_index = 0;
         self.current_request = None;
-        self.response = None;
+        if !self.response_pinned {
+            self.response = None;
+        }
         self.set_focus(FocusedPane::Requests);
-        // Jump straight to the request when the load came from the Favorites pane.
+        // Jump straight to the request when the load came from the Favorites
+        // pane or the recently-executed list.
         if let Some(path) = self.pending_favorite_path.take() {
             self.restore_request_path(&path);
             self.select_request();
@@ -1107,37 +2649,87 @@ impl App {
             Ok(detail) => {
                 let uid = self.collection_load_target().0;
                 self.apply_collection_detail(detail, uid);
+                self.last_refresh = Some(chrono::Local::now());
                 self.status_message = format!("Loaded {}", collection_name);
             }
             Err(e) => {
                 let error_msg = e.to_string();
                 log_error("load_collection_data", &error_msg);
-                self.error = Some(error_msg);
-                self.status_message = String::from("Failed to load collection");
+                if is_api_key_error(&e) {
+                    self.prompt_for_api_key(error_msg);
+                } else {
+                    self.error = Some(error_msg);
+                    self.status_message = String::from("Failed to load collection");
+                }
             }
         }
         self.collection_loading = None;
     }
 
+    /// Announce a re-fetch of the loaded collection (e.g. after editing it
+    /// in Postman); the main loop spawns the fetch itself.
+    pub fn start_collection_reload(&mut self) {
+        self.status_message = if self.current_collection_uid.is_some() {
+            String::from("Reloading collection...")
+        } else {
+            String::from("No collection loaded")
+        };
+    }
+
     /// Apply a background refresh result. Always updates the cache; only touches
     /// the visible request tree when the user is still on that collection, and
     /// preserves their expanded folders and selection so browsing isn't disrupted.
-    pub fn apply_collection_refresh(&mut self, uid: String, result: Result<CollectionDetail>) {
+    pub fn apply_collection_refresh(&mut self, uid: String, result: Result<CollectionDetail>, reload: bool) {
         match result {
             Ok(detail) => {
                 self.collection_cache.insert(uid.clone(), detail.clone());
                 if self.current_collection_uid.as_deref() == Some(uid.as_str()) {
+                    // Keep the selection on the same item (expanded folders are
+                    // tracked by path, so they survive the re-flatten)
+                    let selected = self
+                        .flat_items
+                        .get(self.selected_item_index)
+                        .map(|item| (item.path.clone(), self.in_favorites_section(self.selected_item_index)));
+
                     self.current_collection = Some(detail);
+                    self.last_refresh = Some(chrono::Local::now());
+                    self.reconcile_request_paths();
                     self.rebuild_variables();
                     self.flatten_items();
-                    if !self.flat_items.is_empty()
-                        && self.selected_item_index >= self.flat_items.len()
-                    {
-                        self.selected_item_index = self.flat_items.len() - 1;
+
+                    let restored = selected.and_then(|(path, in_favorites)| {
+                        (0..self.flat_items.len()).find(|&i| {
+                            self.flat_items[i].path == path && self.in_favorites_section(i) == in_favorites
+                        })
+                    });
+                    match restored {
+                        Some(index) => self.selected_item_index = index,
+                        None if !self.flat_items.is_empty()
+                            && self.selected_item_index >= self.flat_items.len() =>
+                        {
+                            self.selected_item_index = self.flat_items.len() - 1;
+                        }
+                        None => {}
                     }
-                    self.status_message = String::from("Requests updated");
+                    self.refresh_current_request();
+                    self.status_message = if reload {
+                        String::from("Reloaded")
+                    } else {
+                        String::from("Requests updated")
+                    };
                 }
             }
+            Err(e) if is_api_key_error(&e) => {
+                let error_msg = e.to_string();
+                log_error("collection_refresh", &error_msg);
+                self.prompt_for_api_key(error_msg);
+            }
+            Err(e) if reload => {
+                let error_msg = format!("{:#}", e);
+                log_error("collection_reload", &error_msg);
+                self.error = Some(error_msg);
+                self.status_message = String::from("Reload failed");
+            }
             Err(e) => {
                 // Keep the cached copy on screen; a stale-but-usable view beats
                 // disrupting the user with an error mid-browse.
@@ -1146,6 +2738,22 @@ impl App {
         }
     }
 
+    /// Re-read the previewed request from the (refreshed) collection tree,
+    /// with any local edit still applied on top.
+    fn refresh_current_request(&mut self) {
+        let Some(path) = self.current_request_path.clone() else {
+            return;
+        };
+        let request = self
+            .current_collection
+            .as_ref()
+            .and_then(|c| get_item_at_path(&c.item, &path))
+            .and_then(|(_, request, _)| request);
+        if let Some(request) = request {
+            self.set_current_request(request, &path);
+        }
+    }
+
     /// Reset loading state after the user cancels an in-flight collection load.
     pub fn cancel_collection_load(&mut self) {
         self.collection_loading = None;
@@ -1154,6 +2762,21 @@ impl App {
     }
 
 
+    /// Request count for a collection, when its tree is known — either the
+    /// loaded collection or one already in the session cache.
+    pub fn collection_request_count(&self, uid: &str) -> Option<usize> {
+        self.collection_detail(uid).map(|detail| count_requests(&detail.item))
+    }
+
+    /// A collection's tree, when known: the loaded collection or one in the
+    /// session cache.
+    fn collection_detail(&self, uid: &str) -> Option<&CollectionDetail> {
+        self.current_collection
+            .as_ref()
+            .filter(|_| self.current_collection_uid.as_deref() == Some(uid))
+            .or_else(|| self.collection_cache.get(uid))
+    }
+
     pub fn flatten_items(&mut self) {
         self.flat_items.clear();
         if let Some(collection) = &self.current_collection {
@@ -1178,6 +2801,7 @@ impl App {
                     request: None,
                     request_id: None,
                     path: vec![usize::MAX], // Special path for favorites folder
+                    request_count: favorite_paths.len(),
                 });
 
                 if favorites_expanded {
@@ -1192,6 +2816,7 @@ impl App {
                                 request,
                                 request_id,
                                 path: fav_path.clone(),
+                                request_count: 0,
                             });
                         }
                     }
@@ -1199,7 +2824,8 @@ impl App {
             }
 
             // Then add the normal tree
-            flatten_recursive(&items, 0, vec![], &expanded, &mut self.flat_items);
+            let sort = self.config.request_sort.get(&collection_uid).copied().unwrap_or_default();
+            flatten_recursive(&items, 0, vec![], &expanded, sort, &mut self.flat_items);
         }
     }
 
@@ -1223,48 +2849,59 @@ impl App {
     }
 
     pub fn select_request(&mut self) {
-        if self.flat_items.is_empty() {
-            return;
+        if self.load_highlighted_request() {
+            self.quick_execute = false;
+            self.set_focus(FocusedPane::Preview);
         }
+    }
 
-        let item = &self.flat_items[self.selected_item_index];
+    /// Make the highlighted request the current one. Returns false on a
+    /// folder or an empty list.
+    fn load_highlighted_request(&mut self) -> bool {
+        let Some(item) = self.flat_items.get(self.selected_item_index) else {
+            return false;
+        };
         let item_path = item.path.clone();
-
-        if let Some(request) = &item.request {
-            let request = request.clone();
-            self.set_current_request(request, &item_path);
+        let Some(request) = item.request.clone() else {
+            return false;
+        };
+        self.set_current_request(request, &item_path);
+        if !self.response_pinned {
             self.response = None;
             self.json_viewer_state = None;
-            self.set_focus(FocusedPane::Preview);
+        }
 
-            // Save state for persistence
-            self.save_last_state();
+        // Save state for persistence
+        self.save_last_state();
+        true
+    }
+
+    /// Execute the highlighted request without leaving the Requests pane
+    /// (`x`), so `j`, `x`, `j`, `x` runs several in a row. Params and the
+    /// destructive-request confirmation still apply.
+    pub fn quick_execute(&mut self) {
+        if !self.load_highlighted_request() {
+            self.status_message = String::from("Select a request to execute");
+            return;
+        }
+        self.quick_execute = true;
+        if !self.start_params_input() && !self.start_execute_confirmation() {
+            self.queue_execution();
         }
     }
 
     /// Set `current_request` for the given item, applying any local edit
     /// stored for it (and marking the edit as unsaved so the UI shows it).
     fn set_current_request(&mut self, request: Request, item_path: &[usize]) {
+        if self.current_request_path.as_deref() != Some(item_path) {
+            self.extra_headers.clear();
+            self.definition_cursor = 0;
+            self.preview_scroll = 0;
+        }
+        self.current_request_path = Some(item_path.to_vec());
+        self.rebuild_variables();
         if let Some(local_edit) = self.get_local_edit(item_path) {
-            self.current_request = Some(Request {
-                method: local_edit.method.clone(),
-                url: if local_edit.url.is_empty() {
-                    RequestUrl::Empty
-                } else {
-                    RequestUrl::Simple(local_edit.url.clone())
-                },
-                header: request.header.clone(),
-                auth: request.auth.clone(),
-                body: if local_edit.body.is_empty() {
-                    None
-                } else {
-                    Some(crate::api::RequestBody {
-                        mode: Some("raw".to_string()),
-                        raw: Some(local_edit.body.clone()),
-                    })
-                },
-                description: request.description.clone(),
-            });
+            self.current_request = Some(apply_local_edit(&request, &local_edit));
             self.unsaved_edit = Some((local_edit, self.selected_item_index));
         } else {
             self.current_request = Some(request);
@@ -1283,16 +2920,10 @@ impl App {
             return;
         }
         let collection_uid = flat_col.uid.clone();
-        let request_path = if self.flat_items.is_empty() {
-            vec![]
-        } else {
-            let path = self.flat_items[self.selected_item_index].path.clone();
+        let (request_path, request_id) = match self.flat_items.get(self.selected_item_index) {
             // Don't save if path contains usize::MAX (Favorites marker) - TOML can't serialize it
-            if path.contains(&usize::MAX) {
-                vec![]
-            } else {
-                path
-            }
+            Some(item) if !item.path.contains(&usize::MAX) => (item.path.clone(), item.request_id.clone()),
+            _ => (vec![], None),
         };
 
         let environment_uid = self.selected_environment_index
@@ -1301,7 +2932,7 @@ impl App {
 
         let workspace_id = self.get_selected_workspace_id();
 
-        self.config.set_last_state(collection_uid, request_path, environment_uid, workspace_id);
+        self.config.set_last_state(collection_uid, request_path, request_id, environment_uid, workspace_id);
         let _ = self.config.save(); // Ignore errors for state saving
     }
 
@@ -1310,10 +2941,31 @@ impl App {
             .and_then(|idx| self.environments.get(idx))
             .map(|env| env.uid.clone());
 
+        if let Some(collection_uid) = self.current_collection_uid.clone() {
+            self.config.set_collection_environment(&collection_uid, environment_uid.clone());
+        }
         self.config.set_last_environment(environment_uid);
         let _ = self.config.save();
     }
 
+    /// Switch to the environment remembered for `collection_uid`, if it differs
+    /// from the current one. The variables themselves are fetched by the main
+    /// loop (see `pending_environment_load`).
+    fn restore_collection_environment(&mut self, collection_uid: &str) {
+        let env_uid = match self.config.collection_environments.get(collection_uid) {
+            Some(uid) => uid,
+            None => return,
+        };
+        let index = match self.environments.iter().position(|e| &e.uid == env_uid) {
+            Some(i) => i,
+            None => return,
+        };
+        if self.selected_environment_index != Some(index) {
+            self.selected_environment_index = Some(index);
+            self.pending_environment_load = true;
+        }
+    }
+
     /// Select the saved request by path within the (already populated)
     /// flat_items, expanding folders if needed. Shared by restore paths.
     fn restore_request_path(&mut self, request_path: &[usize]) {
@@ -1356,6 +3008,7 @@ impl App {
                     self.workspaces.iter().position(|w| &w.id == ws_id);
             }
         }
+        self.apply_execution_settings();
 
         self.sort_collections();
         self.flatten_collections();
@@ -1363,9 +3016,9 @@ impl App {
         // Restore collection/environment selection indices from the cached
         // lists (their detail contents are fetched live by the refresh).
         if let Some(state) = self.config.last_state.clone() {
-            if let Some(env_uid) = &state.environment_uid {
+            if let Some(env_uid) = self.config.environment_for_collection(&state.collection_uid) {
                 self.selected_environment_index =
-                    self.environments.iter().position(|e| &e.uid == env_uid);
+                    self.environments.iter().position(|e| e.uid == env_uid);
             }
             if !state.collection_uid.is_empty() {
                 if let Some(idx) = self.flat_collections.iter().position(|c| {
@@ -1379,6 +3032,54 @@ impl App {
         self.status_message = format!("Loaded {} collections (cached)", self.collections.len());
     }
 
+    /// Ask for a new API key after Postman rejected the current one;
+    /// `reason` is the API's complaint, shown in the prompt.
+    pub fn prompt_for_api_key(&mut self, reason: String) {
+        self.loading = false;
+        self.api_key_input.clear();
+        self.api_key_error = Some(reason);
+        self.input_mode = InputMode::ApiKeyInput;
+        self.status_message = String::from("API key invalid or lacks permission");
+    }
+
+    pub fn api_key_input_char(&mut self, c: char) {
+        self.api_key_input.push(c);
+    }
+
+    pub fn api_key_backspace(&mut self) {
+        self.api_key_input.pop();
+    }
+
+    pub fn cancel_api_key_prompt(&mut self) {
+        self.input_mode = InputMode::Normal;
+        self.api_key_input.clear();
+        self.api_key_error = None;
+        self.status_message = String::from("API key unchanged");
+    }
+
+    /// Switch to the typed API key and save it to config.toml. Returns true
+    /// when the key was accepted, so the caller can retry loading.
+    pub fn submit_api_key(&mut self) -> bool {
+        let key = self.api_key_input.trim().to_string();
+        if let Err(msg) = crate::config::validate_api_key(&key) {
+            self.api_key_error = Some(msg);
+            return false;
+        }
+        self.client.set_api_key(key.clone());
+        self.config.postman.api_key = key;
+        if let Err(e) = self.config.save() {
+            let error_msg = e.to_string();
+            log_error("save_api_key", &error_msg);
+            self.error = Some(error_msg);
+        }
+        self.api_key_input.clear();
+        self.api_key_error = None;
+        self.input_mode = InputMode::Normal;
+        self.loading = true;
+        self.status_message = String::from("Retrying with the new API key...");
+        true
+    }
+
     /// Apply the result of the background startup fetch to the app, preserving
     /// the user's current selection (matched by UID) across the data swap.
     pub fn apply_refresh(&mut self, data: RefreshData) {
@@ -1391,13 +3092,6 @@ impl App {
             .selected_environment_index
             .and_then(|i| self.environments.get(i))
             .map(|e| e.uid.clone());
-        let request_path = self
-            .config
-            .last_state
-            .as_ref()
-            .map(|s| s.request_path.clone())
-            .unwrap_or_default();
-
         // Workspaces (best-effort) — re-resolve the saved selection.
         if let Some(workspaces) = data.workspaces {
             self.workspaces = workspaces;
@@ -1407,6 +3101,7 @@ impl App {
                         self.workspaces.iter().position(|w| &w.id == ws_id);
                 }
             }
+            self.apply_execution_settings();
         }
 
         // Collections — errors surface to the user; cached list stays on error.
@@ -1429,8 +3124,12 @@ impl App {
             Err(e) => {
                 self.loading = false;
                 log_error("refresh_collections", &e);
-                self.error = Some(e);
-                self.status_message = String::from("Failed to load collections");
+                if data.api_key_rejected {
+                    self.prompt_for_api_key(e);
+                } else {
+                    self.error = Some(e);
+                    self.status_message = String::from("Failed to load collections");
+                }
             }
         }
 
@@ -1446,10 +3145,7 @@ impl App {
         // Environment values (secrets — fetched live, never cached).
         if let Some(res) = data.environment_detail {
             match res {
-                Ok(detail) => {
-                    self.current_environment = Some(detail);
-                    self.rebuild_variables();
-                }
+                Ok(detail) => self.set_loaded_environment(detail),
                 Err(e) => log_error("refresh_environment", &e),
             }
         }
@@ -1467,9 +3163,16 @@ impl App {
                         }
                     }
                     self.current_collection = Some(detail);
+                    self.reconcile_request_paths();
                     self.rebuild_variables();
                     self.expanded_folders.clear();
                     self.flatten_items();
+                    let request_path = self
+                        .config
+                        .last_state
+                        .as_ref()
+                        .map(|s| s.request_path.clone())
+                        .unwrap_or_default();
                     self.restore_request_path(&request_path);
                 }
                 Err(e) => {
@@ -1479,6 +3182,8 @@ impl App {
             }
         }
 
+        self.restore_focused_pane();
+
         // Persist the fresh lists (metadata only) for next launch.
         let cache = CacheStore {
             workspaces: self.workspaces.clone(),
@@ -1490,15 +3195,46 @@ impl App {
         }
     }
 
-    fn expand_to_path(&mut self, target_path: &[usize]) {
-        // Expand all parent folders leading to the target
-        for i in 1..target_path.len() {
-            let parent_path: Vec<usize> = target_path[..i].to_vec();
-            self.expanded_folders.insert(parent_path);
+    /// Return to the pane focused when the last session ended. A restored
+    /// request lands in Preview; panes with nothing to show (no collection
+    /// loaded, no response yet) fall back to the nearest useful one. Skipped
+    /// if the user has already moved off the startup pane.
+    fn restore_focused_pane(&mut self) {
+        if self.focused_pane != FocusedPane::Collections || self.input_mode != InputMode::Normal {
+            return;
         }
-        self.flatten_items();
-    }
-
+        let saved = self
+            .config
+            .last_state
+            .as_ref()
+            .and_then(|s| s.focused_pane.as_deref())
+            .and_then(FocusedPane::from_name);
+        self.focused_pane = match saved {
+            None => return,
+            Some(_) if self.current_request.is_some() => FocusedPane::Preview,
+            Some(FocusedPane::Favorites) => FocusedPane::Favorites,
+            Some(FocusedPane::Collections) => FocusedPane::Collections,
+            // Requests, or Preview/Response with nothing restored to show
+            Some(_) if self.current_collection.is_some() => FocusedPane::Requests,
+            Some(_) => FocusedPane::Collections,
+        };
+    }
+
+    /// Remember the focused pane for the next session. Called on quit.
+    pub fn save_focused_pane(&mut self) {
+        self.config.set_last_focused_pane(self.focused_pane.as_str());
+        let _ = self.config.save(); // Ignore errors for state saving
+    }
+
+    fn expand_to_path(&mut self, target_path: &[usize]) {
+        // Expand all parent folders leading to the target
+        for i in 1..target_path.len() {
+            let parent_path: Vec<usize> = target_path[..i].to_vec();
+            self.expanded_folders.insert(parent_path);
+        }
+        self.flatten_items();
+    }
+
     /// Check if the HTTP method modifies data and requires confirmation
     pub fn is_destructive_method(method: &str) -> bool {
         matches!(
@@ -1507,11 +3243,18 @@ impl App {
         )
     }
 
-    /// Start the execute confirmation dialog for destructive requests
+    /// Whether a `method` request asks before running: destructive ones, or
+    /// every one with `confirm_all_executions` set.
+    fn needs_execute_confirmation(&self, method: &str) -> bool {
+        self.config.settings.confirm_all_executions || Self::is_destructive_method(method)
+    }
+
+    /// Start the execute confirmation dialog for destructive requests, or
+    /// for every request with `confirm_all_executions` set
     pub fn start_execute_confirmation(&mut self) -> bool {
         if let Some(request) = &self.current_request {
-            let method = request.method.to_uppercase();
-            if Self::is_destructive_method(&method) {
+            let method = if request.method.trim().is_empty() { String::from("GET") } else { request.method.to_uppercase() };
+            if self.needs_execute_confirmation(&method) && !self.is_confirm_exempt() {
                 // Get the request name from flat_items
                 let name = self.flat_items.get(self.selected_item_index)
                     .map(|item| item.name.clone())
@@ -1521,6 +3264,7 @@ impl App {
                     method: method.clone(),
                     url: self.substitute_variables(&request.url.to_string()),
                     name,
+                    scratch: false,
                 });
                 self.input_mode = InputMode::ExecuteConfirm;
                 self.status_message = format!("Confirm {} request? (y/n)", method);
@@ -1530,9 +3274,101 @@ impl App {
         false // No confirmation needed (GET, HEAD, OPTIONS, etc.)
     }
 
+    /// Whether the selected request skips the execute confirmation, via
+    /// `[confirm_exempt]` or "don't ask again".
+    fn is_confirm_exempt(&self) -> bool {
+        let Some(collection) = &self.current_collection else {
+            return false;
+        };
+        let uid = self.current_collection_uid.as_deref().unwrap_or_default();
+        if self.confirm_skipped_collections.contains(uid) {
+            return true;
+        }
+        let folders: Vec<&str> = self
+            .current_request_path
+            .as_deref()
+            .map(|path| folders_along_path(&collection.item, path).iter().map(|f| f.name.as_str()).collect())
+            .unwrap_or_default();
+        let url = self
+            .current_request
+            .as_ref()
+            .map(|r| self.substitute_variables(&r.url.to_string()))
+            .unwrap_or_default();
+        self.config.confirm_exempt.is_exempt((uid, &collection.info.name), &folders, &url)
+    }
+
+    /// Run the request waiting in the execute confirmation.
+    pub fn confirm_execute(&mut self) {
+        let pending = self.pending_execute.take();
+        self.input_mode = InputMode::Normal;
+        match pending {
+            Some(pending) if pending.scratch => self.execute_scratch(),
+            _ => self.queue_execution(),
+        }
+    }
+
+    /// Confirm the pending request and stop asking for the current
+    /// collection until lazypost is restarted. The scratch request belongs
+    /// to no collection, so it is just confirmed.
+    pub fn confirm_execute_always(&mut self) {
+        let scratch = self.pending_execute.as_ref().is_some_and(|p| p.scratch);
+        if !scratch && let Some(uid) = self.current_collection_uid.clone() {
+            self.confirm_skipped_collections.insert(uid);
+        }
+        self.confirm_execute();
+    }
+
+    /// Open the overlay for adding one-shot headers to the next execution.
+    pub fn open_extra_headers(&mut self) {
+        if self.current_request.is_none() {
+            self.status_message = String::from("No request selected");
+            return;
+        }
+        self.extra_header_input.clear();
+        self.input_mode = InputMode::ExtraHeaders;
+    }
+
+    pub fn close_extra_headers(&mut self) {
+        self.extra_header_input.clear();
+        self.input_mode = InputMode::Normal;
+        self.status_message = match self.extra_headers.len() {
+            0 => String::from("No extra headers"),
+            n => format!("{} extra header(s) for the next run", n),
+        };
+    }
+
+    pub fn extra_header_input_char(&mut self, c: char) {
+        self.extra_header_input.push(c);
+    }
+
+    /// Delete a character, or with an empty input, the last added header.
+    pub fn extra_header_backspace(&mut self) {
+        if self.extra_header_input.pop().is_none() {
+            self.extra_headers.pop();
+        }
+    }
+
+    /// Add the typed `Key: Value` header (replacing one with the same key);
+    /// an empty line closes the overlay.
+    pub fn submit_extra_header(&mut self) {
+        if self.extra_header_input.trim().is_empty() {
+            self.close_extra_headers();
+            return;
+        }
+        match parse_header_line(&self.extra_header_input).filter(|h| !h.key.is_empty()) {
+            Some(header) => {
+                self.extra_headers.retain(|h| !h.key.eq_ignore_ascii_case(&header.key));
+                self.extra_headers.push(header);
+                self.extra_header_input.clear();
+            }
+            None => self.status_message = String::from("Headers are written as Key: Value"),
+        }
+    }
+
     /// Cancel the execute confirmation
     pub fn cancel_execute_confirmation(&mut self) {
         self.pending_execute = None;
+        self.quick_execute = false;
         self.input_mode = InputMode::Normal;
         self.status_message = String::from("Request cancelled");
     }
@@ -1592,6 +3428,63 @@ impl App {
         self.input_mode = InputMode::Normal;
     }
 
+    /// Start confirmation to discard every local edit in the highlighted
+    /// collection, which needn't be the loaded one.
+    pub fn start_clear_edits_confirmation(&mut self) {
+        let Some(collection) = self.flat_collections.get(self.selected_collection_index).filter(|c| !c.is_favorites_folder) else {
+            self.status_message = String::from("No collection selected");
+            return;
+        };
+        let collection_uid = collection.uid.clone();
+        let count = self.local_edits.edits.iter().filter(|e| e.collection_uid == collection_uid).count();
+        if count == 0 {
+            self.status_message = String::from("No local edits in this collection");
+            return;
+        }
+        self.pending_clear_edits = Some(PendingClearEdits {
+            collection_uid,
+            collection_name: collection.name.clone(),
+            count,
+        });
+        self.input_mode = InputMode::ClearEditsConfirm;
+        self.status_message = format!("Discard all {} local edits? (y/n)", count);
+    }
+
+    pub fn cancel_clear_edits(&mut self) {
+        self.pending_clear_edits = None;
+        self.input_mode = InputMode::Normal;
+        self.status_message = String::from("Discard cancelled");
+    }
+
+    /// Discard every local edit in the collection, reverting the preview to
+    /// the version from Postman.
+    pub fn confirm_clear_edits(&mut self) {
+        self.input_mode = InputMode::Normal;
+        let Some(pending) = self.pending_clear_edits.take() else {
+            return;
+        };
+        let discarded = self.local_edits.clear_collection(&pending.collection_uid);
+        if let Err(e) = self.local_edits.save() {
+            log_error("clear_local_edits", &e.to_string());
+        }
+        if self.get_current_collection_uid().as_deref() == Some(pending.collection_uid.as_str()) {
+            self.unsaved_edit = None;
+            self.flatten_items();
+            if let Some(path) = self.current_request_path.clone() {
+                let request = self.current_collection.as_ref().and_then(|c| request_item_at_path(&c.item, &path));
+                if let Some(item) = request {
+                    self.current_request = Some(item.request.clone());
+                }
+            }
+        }
+        self.status_message = format!(
+            "Discarded {} local edit{} in {}",
+            discarded,
+            if discarded == 1 { "" } else { "s" },
+            pending.collection_name
+        );
+    }
+
     /// Unique `{{placeholder}}` keys referenced by the current request (URL,
     /// headers, and body), in first-seen order. Empty when no request is
     /// selected or it contains no placeholders.
@@ -1610,11 +3503,29 @@ impl App {
                     extract_placeholders(raw, &mut keys, &mut seen);
                 }
             }
+            if let Some(graphql) = request.body.as_ref().and_then(|b| b.graphql()) {
+                extract_placeholders(&graphql.query, &mut keys, &mut seen);
+                extract_placeholders(graphql.variables.as_deref().unwrap_or_default(), &mut keys, &mut seen);
+            }
         }
 
+        // Generated at send time, so there's nothing to fill in
+        keys.retain(|key| !crate::util::DYNAMIC_VARIABLES.contains(&key.as_str()) || self.variables.contains_key(key));
         keys
     }
 
+    /// The variables the current request references, each paired with whether
+    /// the active collection/folder/environment scope defines it.
+    pub fn current_request_variables(&self) -> Vec<(String, bool)> {
+        self.current_request_params()
+            .into_iter()
+            .map(|key| {
+                let resolved = self.variables.contains_key(&key);
+                (key, resolved)
+            })
+            .collect()
+    }
+
     /// The (collection_uid, request_path) identifying the selected request,
     /// used to key persisted param values. None when there's no valid, saveable
     /// selection (e.g. the Favorites folder marker, which isn't serializable).
@@ -1627,6 +3538,293 @@ impl App {
         Some((uid, path))
     }
 
+    /// Open the query-parameter editor for the selected request (`P`).
+    pub fn start_query_editor(&mut self) {
+        let Some((editable, _)) = self.get_current_request_for_edit().filter(|_| self.current_request_key().is_some()) else {
+            self.status_message = String::from("Select a request to edit its query params");
+            return;
+        };
+        let rows: Vec<KeyValueRow> = crate::util::query_pairs(&editable.url)
+            .into_iter()
+            .map(|(key, value)| KeyValueRow { key, value, disabled: false })
+            .chain(editable.disabled_query.into_iter().map(|p| KeyValueRow { key: p.key, value: p.value, disabled: true }))
+            .collect();
+        self.rows_editor = Some(RowsEditor {
+            target: RowsTarget::QueryParams,
+            rows,
+            selected: 0,
+            editing: None,
+            cursor_position: 0,
+        });
+        self.input_mode = InputMode::RowsEditor;
+        self.status_message = String::from("Query params | a: Add | d: Delete | Space: Toggle | Enter: Apply | Esc: Cancel");
+    }
+
+    pub fn rows_editor_up(&mut self) {
+        if let Some(editor) = &mut self.rows_editor {
+            editor.selected = editor.selected.saturating_sub(1);
+        }
+    }
+
+    pub fn rows_editor_down(&mut self) {
+        if let Some(editor) = self.rows_editor.as_mut().filter(|e| e.selected + 1 < e.rows.len()) {
+            editor.selected += 1;
+        }
+    }
+
+    /// Add an empty row below the others and start typing its key.
+    pub fn rows_editor_add(&mut self) {
+        if let Some(editor) = &mut self.rows_editor {
+            editor.rows.push(KeyValueRow { key: String::new(), value: None, disabled: false });
+            editor.selected = editor.rows.len() - 1;
+            editor.editing = Some(RowField::Key);
+            editor.cursor_position = 0;
+        }
+    }
+
+    pub fn rows_editor_delete(&mut self) {
+        if let Some(editor) = self.rows_editor.as_mut().filter(|e| e.selected < e.rows.len()) {
+            editor.rows.remove(editor.selected);
+            editor.selected = editor.selected.min(editor.rows.len().saturating_sub(1));
+        }
+    }
+
+    /// Enable or disable the selected row (disabled ones aren't sent).
+    pub fn rows_editor_toggle(&mut self) {
+        if let Some(row) = self.rows_editor.as_mut().and_then(|e| e.rows.get_mut(e.selected)) {
+            row.disabled = !row.disabled;
+        }
+    }
+
+    /// Start typing into the selected row's key or value, cursor at the end.
+    pub fn rows_editor_edit(&mut self, field: RowField) {
+        if let Some(editor) = &mut self.rows_editor {
+            let Some(row) = editor.rows.get(editor.selected) else {
+                return;
+            };
+            editor.cursor_position = match field {
+                RowField::Key => row.key.len(),
+                RowField::Value => row.value.as_deref().map_or(0, str::len),
+            };
+            editor.editing = Some(field);
+        }
+    }
+
+    /// Move typing between the key and value of the selected row.
+    pub fn rows_editor_switch_field(&mut self) {
+        let field = match self.rows_editor.as_ref().and_then(|e| e.editing) {
+            Some(RowField::Key) => RowField::Value,
+            _ => RowField::Key,
+        };
+        self.rows_editor_edit(field);
+    }
+
+    pub fn rows_editor_stop_edit(&mut self) {
+        if let Some(editor) = &mut self.rows_editor {
+            editor.editing = None;
+        }
+    }
+
+    pub fn rows_editor_input_char(&mut self, c: char) {
+        if let Some(editor) = &mut self.rows_editor {
+            let cursor = editor.cursor_position;
+            if let Some(field) = editor.field_mut() {
+                field.insert(cursor, c);
+                editor.cursor_position += c.len_utf8();
+            }
+        }
+    }
+
+    pub fn rows_editor_backspace(&mut self) {
+        if let Some(editor) = &mut self.rows_editor {
+            let cursor = editor.cursor_position;
+            let Some(field) = editor.field_mut() else {
+                return;
+            };
+            if let Some((index, _)) = field[..cursor].char_indices().next_back() {
+                field.remove(index);
+                editor.cursor_position = index;
+            }
+        }
+    }
+
+    pub fn rows_editor_cursor_left(&mut self) {
+        if let Some(editor) = &mut self.rows_editor {
+            let cursor = editor.cursor_position;
+            if let Some(index) = editor.field_mut().and_then(|f| f[..cursor].char_indices().next_back().map(|(i, _)| i)) {
+                editor.cursor_position = index;
+            }
+        }
+    }
+
+    pub fn rows_editor_cursor_right(&mut self) {
+        if let Some(editor) = &mut self.rows_editor {
+            let cursor = editor.cursor_position;
+            if let Some(width) = editor.field_mut().and_then(|f| f[cursor..].chars().next().map(char::len_utf8)) {
+                editor.cursor_position += width;
+            }
+        }
+    }
+
+    /// Edit the selected request's URL in place, without the external editor.
+    pub fn start_url_edit(&mut self) {
+        let Some((editable, _)) = self.get_current_request_for_edit().filter(|_| self.current_request_key().is_some()) else {
+            self.status_message = String::from("Select a request to edit its URL");
+            return;
+        };
+        self.url_edit = Some(UrlEdit {
+            cursor_position: editable.url.len(),
+            url: editable.url,
+        });
+        self.input_mode = InputMode::UrlEdit;
+        self.status_message = String::from("Edit URL | Enter: Store | Esc: Cancel");
+    }
+
+    pub fn cancel_url_edit(&mut self) {
+        self.url_edit = None;
+        self.input_mode = InputMode::Normal;
+        self.status_message = String::from("URL unchanged");
+    }
+
+    /// Store the edited URL as a local edit of the selected request.
+    pub fn confirm_url_edit(&mut self) {
+        self.input_mode = InputMode::Normal;
+        let Some(edit) = self.url_edit.take() else {
+            return;
+        };
+        let Some((mut editable, item_index)) = self.get_current_request_for_edit() else {
+            return;
+        };
+        let url = edit.url.trim();
+        if url == editable.url {
+            self.status_message = String::from("URL unchanged");
+            return;
+        }
+        editable.url = url.to_string();
+        self.submit_local_edit(editable, item_index);
+    }
+
+    /// The selected request's local note, if it has one.
+    pub fn current_note(&self) -> Option<&str> {
+        let uid = self.current_collection_uid.as_deref()?;
+        self.local_edits.get_note(uid, self.current_request_path.as_deref()?)
+    }
+
+    /// Edit the selected request's note, kept locally and never synced.
+    pub fn start_note_edit(&mut self) {
+        if self.current_collection_uid.is_none() || self.current_request_path.is_none() {
+            self.status_message = String::from("Select a request to add a note");
+            return;
+        }
+        let note = self.current_note().unwrap_or_default().to_string();
+        self.note_edit = Some(NoteEdit { cursor_position: note.len(), note });
+        self.input_mode = InputMode::NoteEdit;
+        self.status_message = String::from("Edit note | Enter: Store | Esc: Cancel");
+    }
+
+    pub fn cancel_note_edit(&mut self) {
+        self.note_edit = None;
+        self.input_mode = InputMode::Normal;
+        self.status_message = String::from("Note unchanged");
+    }
+
+    /// Store the edited note; an empty one removes it.
+    pub fn confirm_note_edit(&mut self) {
+        self.input_mode = InputMode::Normal;
+        let Some(edit) = self.note_edit.take() else {
+            return;
+        };
+        let (Some(uid), Some(path)) = (self.current_collection_uid.clone(), self.current_request_path.clone()) else {
+            return;
+        };
+        self.local_edits.set_note(&uid, &path, &edit.note);
+        if let Err(e) = self.local_edits.save() {
+            log_error("save_note", &e.to_string());
+            self.error = Some(e.to_string());
+            self.status_message = String::from("Failed to save note");
+            return;
+        }
+        self.status_message = String::from(if edit.note.trim().is_empty() { "Note removed" } else { "Note saved (local only)" });
+    }
+
+    pub fn cancel_rows_editor(&mut self) {
+        let target = self.rows_editor.take().map(|e| e.target);
+        self.input_mode = InputMode::Normal;
+        self.status_message = String::from(match target {
+            Some(RowsTarget::Headers) => "Headers unchanged",
+            _ => "Query params unchanged",
+        });
+    }
+
+    /// Open the headers editor on the selected request's headers: toggle,
+    /// add, edit and delete them, stored as a local edit on Enter.
+    pub fn start_header_editor(&mut self) {
+        if self.get_current_request_for_edit().filter(|_| self.current_request_key().is_some()).is_none() {
+            self.status_message = String::from("Select a request to edit its headers");
+            return;
+        }
+        let rows: Vec<KeyValueRow> = self
+            .current_request
+            .iter()
+            .flat_map(|r| &r.header)
+            .map(|h| KeyValueRow { key: h.key.clone(), value: Some(h.value.clone()), disabled: h.disabled.unwrap_or(false) })
+            .collect();
+        self.rows_editor = Some(RowsEditor {
+            target: RowsTarget::Headers,
+            rows,
+            selected: 0,
+            editing: None,
+            cursor_position: 0,
+        });
+        self.input_mode = InputMode::RowsEditor;
+        self.status_message = String::from("Headers | a: Add | d: Delete | Space: Toggle | Enter: Apply | Esc: Cancel");
+    }
+
+    /// Write the rows back to the request as a local edit: headers replace
+    /// the request's own; enabled params go into the URL's query string,
+    /// disabled ones kept aside.
+    pub fn confirm_rows_editor(&mut self) {
+        self.input_mode = InputMode::Normal;
+        let Some(editor) = self.rows_editor.take() else {
+            return;
+        };
+        let Some((mut editable, item_index)) = self.get_current_request_for_edit() else {
+            return;
+        };
+        if editor.target == RowsTarget::Headers {
+            let headers: Vec<Header> = editor
+                .rows
+                .into_iter()
+                .filter(|row| !row.key.trim().is_empty())
+                .map(|row| Header {
+                    disabled: row.disabled.then_some(true),
+                    key: row.key,
+                    value: row.value.unwrap_or_default(),
+                })
+                .collect();
+            let current = self.current_request.as_ref().map(|r| &r.header);
+            if current == Some(&headers) {
+                self.status_message = String::from("Headers unchanged");
+                return;
+            }
+            editable.headers = Some(headers);
+            self.submit_local_edit(editable, item_index);
+            return;
+        }
+        let (disabled, enabled): (Vec<KeyValueRow>, Vec<KeyValueRow>) = editor
+            .rows
+            .into_iter()
+            .filter(|row| !row.key.trim().is_empty())
+            .partition(|row| row.disabled);
+        let pairs: Vec<(String, Option<String>)> = enabled.into_iter().map(|row| (row.key, row.value)).collect();
+        editable.url = crate::util::with_query(&editable.url, &pairs);
+        editable.disabled_query = disabled
+            .into_iter()
+            .map(|row| QueryParam { key: row.key, value: row.value, disabled: Some(true) })
+            .collect();
+        self.submit_local_edit(editable, item_index);
+    }
+
     /// Scan the current request for `{{placeholder}}` values and, if any are
     /// found, open the parameter-input dialog. Each field is pre-filled from
     /// the last value entered for this request (persisted), then falling back
@@ -1694,19 +3892,18 @@ impl App {
         if let Some(dialog) = &mut self.params_dialog {
             if let Some((_, value)) = dialog.params.get_mut(dialog.selected) {
                 value.insert(dialog.cursor_position, c);
-                dialog.cursor_position += 1;
+                dialog.cursor_position += c.len_utf8();
             }
         }
     }
 
     pub fn params_backspace(&mut self) {
-        if let Some(dialog) = &mut self.params_dialog {
-            if dialog.cursor_position > 0 {
-                if let Some((_, value)) = dialog.params.get_mut(dialog.selected) {
-                    dialog.cursor_position -= 1;
-                    value.remove(dialog.cursor_position);
-                }
-            }
+        if let Some(dialog) = &mut self.params_dialog
+            && let Some((_, value)) = dialog.params.get_mut(dialog.selected)
+            && let Some((index, _)) = value[..dialog.cursor_position].char_indices().next_back()
+        {
+            value.remove(index);
+            dialog.cursor_position = index;
         }
     }
 
@@ -1722,19 +3919,20 @@ impl App {
     }
 
     pub fn params_cursor_left(&mut self) {
-        if let Some(dialog) = &mut self.params_dialog {
-            if dialog.cursor_position > 0 {
-                dialog.cursor_position -= 1;
-            }
+        if let Some(dialog) = &mut self.params_dialog
+            && let Some((_, value)) = dialog.params.get(dialog.selected)
+            && let Some((index, _)) = value[..dialog.cursor_position].char_indices().next_back()
+        {
+            dialog.cursor_position = index;
         }
     }
 
     pub fn params_cursor_right(&mut self) {
-        if let Some(dialog) = &mut self.params_dialog {
-            let len = dialog.params.get(dialog.selected).map(|(_, v)| v.len()).unwrap_or(0);
-            if dialog.cursor_position < len {
-                dialog.cursor_position += 1;
-            }
+        if let Some(dialog) = &mut self.params_dialog
+            && let Some((_, value)) = dialog.params.get(dialog.selected)
+            && let Some(c) = value[dialog.cursor_position..].chars().next()
+        {
+            dialog.cursor_position += c.len_utf8();
         }
     }
 
@@ -1758,6 +3956,7 @@ impl App {
 
     pub fn cancel_params_input(&mut self) {
         self.params_dialog = None;
+        self.quick_execute = false;
         self.input_mode = InputMode::Normal;
         self.status_message = String::from("Request cancelled");
     }
@@ -1794,37 +3993,26 @@ impl App {
     /// call is run separately so it can be cancelled (see `main::run_cancellable`).
     pub fn prepare_execution_request(&mut self) -> Option<Request> {
         let request = self.current_request.as_ref()?;
-
-        // Create a copy of the request with variables substituted
-        let mut resolved_request = request.clone();
-
-        // Substitute variables in URL
-        let url_str = resolved_request.url.to_string();
-        let resolved_url = self.substitute_variables(&url_str);
-        resolved_request.url = RequestUrl::Simple(resolved_url);
-
-        // Substitute variables in headers
-        for header in &mut resolved_request.header {
-            header.key = self.substitute_variables(&header.key);
-            header.value = self.substitute_variables(&header.value);
-        }
-
-        // Substitute variables in body
-        if let Some(body) = &mut resolved_request.body {
-            if let Some(raw) = &body.raw {
-                body.raw = Some(self.substitute_variables(raw));
-            }
-        }
-
-        // Substitute variables in auth parameter values (e.g. a `{{token}}`)
-        if let Some(auth) = &mut resolved_request.auth {
-            for params in auth.params.values_mut() {
-                for param in params.iter_mut() {
-                    if let serde_json::Value::String(s) = &param.value {
-                        param.value = serde_json::Value::String(self.substitute_variables(s));
-                    }
+        let request = match self.current_request_path.as_deref() {
+            Some(path) => match self.with_body_file(request, path) {
+                Ok(request) => request,
+                Err(e) => {
+                    let error_msg = format!("{:#}", e);
+                    log_error("read_body_file", &error_msg);
+                    self.error = Some(error_msg);
+                    self.status_message = String::from("Failed to read body file");
+                    return None;
                 }
-            }
+            },
+            None => request.clone(),
+        };
+        let request = self.prune_get_body(request, self.current_request_path.as_deref());
+        let request = with_extra_headers(&request, &self.extra_headers);
+        let resolved_request = self.resolve_request(&request);
+        if let Some(error_msg) = missing_form_file(&resolved_request) {
+            self.error = Some(error_msg);
+            self.status_message = String::from("Form file not found - request not sent");
+            return None;
         }
 
         self.loading = true;
@@ -1833,63 +4021,1889 @@ impl App {
         Some(resolved_request)
     }
 
-    /// Queue the current request for execution. The main loop picks this up and
-    /// runs it in a cancellable background task (same path as collection loads).
-    pub fn queue_execution(&mut self) {
-        if let Some(resolved) = self.prepare_execution_request() {
-            self.pending_execution = Some(resolved);
+    /// The file the body of the request at `path` is read from, if any.
+    pub fn body_file(&self, path: &[usize]) -> Option<String> {
+        let collection_uid = self.get_current_collection_uid()?;
+        self.local_edits.get_body_file(&collection_uid, path).map(String::from)
+    }
+
+    /// Body file of the selected request, for the preview.
+    pub fn current_body_file(&self) -> Option<String> {
+        self.body_file(self.current_request_path.as_deref()?)
+    }
+
+    /// Language of the body of `request` (at `path`); a body-file request with
+    /// no inline body keeps it only in its local edit.
+    fn body_language(&self, request: &Request, path: &[usize]) -> Option<String> {
+        request
+            .body
+            .as_ref()
+            .and_then(|b| b.language())
+            .map(String::from)
+            .or_else(|| self.get_local_edit(path)?.body_language)
+    }
+
+    /// Body language of the selected request, for the preview.
+    pub fn current_body_language(&self) -> Option<String> {
+        let request = self.current_request.as_ref()?;
+        match self.current_request_path.as_deref() {
+            Some(path) => self.body_language(request, path),
+            None => request.body.as_ref()?.language().map(String::from),
         }
     }
 
-    /// Apply the outcome of a (possibly failed) request execution.
-    pub fn apply_execution_result(&mut self, result: Result<ExecutedResponse>) {
-        self.loading = false;
-        self.request_executing = false;
-        match result {
-            Ok(response) => {
-                // Try to parse response body as JSON for the viewer
-                self.json_viewer_state = JsonViewerState::new(&response.body);
-                self.response = Some(response);
+    /// Switch the selected request's raw body to the next language
+    /// (text, json, xml, ...), stored as a local edit.
+    pub fn cycle_body_language(&mut self) {
+        let Some((mut editable, item_index)) = self.get_current_request_for_edit() else {
+            self.status_message = String::from("No request selected");
+            return;
+        };
+        if editable.body.is_empty() && editable.body_file.is_none() {
+            self.status_message = String::from("Request has no body");
+            return;
+        }
+        if let Some(path) = self.flat_items.get(item_index).map(|item| item.path.clone()) {
+            editable.body_language = editable.body_language.or_else(|| self.get_local_edit(&path)?.body_language);
+        }
+        let next = editable
+            .body_language
+            .as_deref()
+            .and_then(|current| BODY_LANGUAGES.iter().position(|l| *l == current))
+            .map_or(0, |i| (i + 1) % BODY_LANGUAGES.len());
+        let language = BODY_LANGUAGES[next];
+        editable.body_language = Some(language.to_string());
+        self.store_local_edit(editable, item_index);
+        self.status_message = format!("Body language: {} (stored locally, S to save to Postman)", language);
+    }
+
+    /// `request` with its body replaced by the contents of its body file,
+    /// when the request at `path` has one.
+    fn with_body_file(&self, request: &Request, path: &[usize]) -> Result<Request> {
+        let mut request = request.clone();
+        if let Some(file) = self.body_file(path) {
+            request.body = Some(crate::api::RequestBody::raw(read_body_file(&file)?, self.body_language(&request, path)));
+        }
+        Ok(request)
+    }
+
+    /// Drop the body of a GET/HEAD request, as Postman does, unless the
+    /// request at `path` sets `protocolProfileBehavior.disableBodyPruning`.
+    fn prune_get_body(&self, mut request: Request, path: Option<&[usize]>) -> Request {
+        if request.prunes_body() && !self.keeps_body_on_get(path) {
+            request.body = None;
+        }
+        request
+    }
+
+    fn keeps_body_on_get(&self, path: Option<&[usize]>) -> bool {
+        let (Some(collection), Some(path)) = (&self.current_collection, path) else {
+            return false;
+        };
+        request_item_at_path(&collection.item, path).is_some_and(|item| item.keeps_body_on_get())
+    }
+
+    /// Whether the selected request has a body that won't be sent because
+    /// of its method, for the preview.
+    pub fn current_body_pruned(&self) -> bool {
+        let Some(request) = &self.current_request else {
+            return false;
+        };
+        let has_body = request.body.is_some() || self.current_body_file().is_some();
+        has_body && request.prunes_body() && !self.keeps_body_on_get(self.current_request_path.as_deref())
+    }
+
+    /// A copy of `request` with `{{variables}}` substituted in the URL,
+    /// headers, body, and auth parameters, ready to send.
+    pub fn resolve_request(&self, request: &Request) -> Request {
+        self.resolve_request_with(request, &self.variables)
+    }
+
+    /// `resolve_request` against an explicit variable map, for requests other
+    /// than the selected one (e.g. each step of a folder run).
+    fn resolve_request_with(&self, request: &Request, variables: &HashMap<String, String>) -> Request {
+        let mut resolved_request = request.clone();
+        let substitute = |text: &str| self.substitute_with(text, variables);
+
+        // Substitute variables in URL (leaving out disabled query params)
+        let url_str = resolved_request.url.send_url();
+        let resolved_url = substitute(&url_str);
+        resolved_request.url = RequestUrl::Simple(resolved_url);
+
+        // Substitute variables in headers
+        for header in &mut resolved_request.header {
+            header.key = substitute(&header.key);
+            header.value = substitute(&header.value);
+        }
+
+        // Substitute variables in body
+        if let Some(body) = &mut resolved_request.body {
+            if let Some(raw) = &body.raw {
+                body.raw = Some(substitute(raw));
+            }
+            if let Some(graphql) = &mut body.graphql {
+                graphql.query = substitute(&graphql.query);
+                graphql.variables = graphql.variables.as_deref().map(substitute);
+            }
+            // Form fields, including file paths (with `~` expanded)
+            for field in &mut body.formdata {
+                field.key = substitute(&field.key);
+                field.value = field.value.as_deref().map(substitute);
+                if field.is_file() {
+                    let files = field
+                        .files()
+                        .iter()
+                        .map(|path| expand_home(&substitute(path)).to_string_lossy().into_owned())
+                        .collect();
+                    field.src = Some(crate::api::FormFileSource::Many(files));
+                }
+            }
+        }
+
+        // Substitute variables in auth parameter values (e.g. a `{{token}}`)
+        if let Some(auth) = &mut resolved_request.auth {
+            for params in auth.params.values_mut() {
+                for param in params.iter_mut() {
+                    if let serde_json::Value::String(s) = &param.value {
+                        param.value = serde_json::Value::String(substitute(s));
+                    }
+                }
+            }
+        }
+
+        resolved_request
+    }
+
+    /// The text field being typed into and its cursor, if any: the new
+    /// request dialog, a params dialog value, a variable being edited, or
+    /// the inline URL editor.
+    fn active_text_input(&self) -> Option<(&str, usize)> {
+        match self.input_mode {
+            InputMode::TextInput => {
+                let dialog = self.new_request_dialog.as_ref()?;
+                let text = match dialog.step {
+                    DialogStep::Name => &dialog.name,
+                    DialogStep::Url => &dialog.url,
+                };
+                Some((text, dialog.cursor_position))
+            }
+            InputMode::ParamsInput => {
+                let dialog = self.params_dialog.as_ref().filter(|d| d.editing)?;
+                let (_, value) = dialog.params.get(dialog.selected)?;
+                Some((value, dialog.cursor_position))
+            }
+            InputMode::VariablesView => {
+                let (_, value) = self.editing_variable.as_ref()?;
+                Some((value, self.variable_cursor_position))
+            }
+            InputMode::UrlEdit => {
+                let edit = self.url_edit.as_ref()?;
+                Some((&edit.url, edit.cursor_position))
+            }
+            InputMode::OpenApiImport => {
+                let prompt = self.openapi_import.as_ref()?;
+                Some((&prompt.path, prompt.cursor_position))
+            }
+            InputMode::NoteEdit => {
+                let edit = self.note_edit.as_ref()?;
+                Some((&edit.note, edit.cursor_position))
+            }
+            _ => None,
+        }
+    }
+
+    fn active_text_input_mut(&mut self) -> Option<(&mut String, &mut usize)> {
+        match self.input_mode {
+            InputMode::TextInput => {
+                let dialog = self.new_request_dialog.as_mut()?;
+                let text = match dialog.step {
+                    DialogStep::Name => &mut dialog.name,
+                    DialogStep::Url => &mut dialog.url,
+                };
+                Some((text, &mut dialog.cursor_position))
+            }
+            InputMode::ParamsInput => {
+                let dialog = self.params_dialog.as_mut().filter(|d| d.editing)?;
+                let (_, value) = dialog.params.get_mut(dialog.selected)?;
+                Some((value, &mut dialog.cursor_position))
+            }
+            InputMode::VariablesView => {
+                let (_, value) = self.editing_variable.as_mut()?;
+                Some((value, &mut self.variable_cursor_position))
+            }
+            InputMode::UrlEdit => {
+                let edit = self.url_edit.as_mut()?;
+                Some((&mut edit.url, &mut edit.cursor_position))
+            }
+            InputMode::OpenApiImport => {
+                let prompt = self.openapi_import.as_mut()?;
+                Some((&mut prompt.path, &mut prompt.cursor_position))
+            }
+            InputMode::NoteEdit => {
+                let edit = self.note_edit.as_mut()?;
+                Some((&mut edit.note, &mut edit.cursor_position))
+            }
+            _ => None,
+        }
+    }
+
+    /// Variable names completing the `{{partial` just before the cursor in the
+    /// active text input, sorted. Empty when the cursor isn't inside an
+    /// unclosed `{{`.
+    pub fn completion_candidates(&self) -> Vec<String> {
+        let partial = match self
+            .active_text_input()
+            .and_then(|(text, cursor)| open_placeholder(text, cursor).map(|start| text[start..cursor].to_lowercase()))
+        {
+            Some(partial) => partial,
+            None => return Vec::new(),
+        };
+        let mut names: Vec<String> = self
+            .variables
+            .keys()
+            .filter(|k| k.to_lowercase().starts_with(&partial))
+            .cloned()
+            .collect();
+        names.sort();
+        names
+    }
+
+    pub fn has_completion(&self) -> bool {
+        !self.completion_candidates().is_empty()
+    }
+
+    pub fn completion_move(&mut self, delta: isize) {
+        let len = self.completion_candidates().len() as isize;
+        if len > 0 {
+            self.completion_index = (self.completion_index as isize + delta).rem_euclid(len) as usize;
+        }
+    }
+
+    /// Replace the `{{partial` before the cursor with the highlighted variable
+    /// name and its closing `}}`.
+    pub fn accept_completion(&mut self) {
+        let candidates = self.completion_candidates();
+        if candidates.is_empty() {
+            return;
+        }
+        let name = candidates[self.completion_index % candidates.len()].clone();
+        self.completion_index = 0;
+        if let Some((text, cursor)) = self.active_text_input_mut() {
+            let start = match open_placeholder(text, *cursor) {
+                Some(start) => start,
+                None => return,
+            };
+            let closing = if text[*cursor..].starts_with("}}") { "" } else { "}}" };
+            let replacement = format!("{}{}", name, closing);
+            text.replace_range(start..*cursor, &replacement);
+            *cursor = start + name.len() + 2;
+        }
+    }
+
+    pub fn open_palette(&mut self) {
+        self.palette = Some(CommandPalette {
+            matches: palette::filter(""),
+            ..Default::default()
+        });
+        self.input_mode = InputMode::CommandPalette;
+    }
+
+    pub fn close_palette(&mut self) {
+        self.palette = None;
+        self.input_mode = InputMode::Normal;
+    }
+
+    pub fn palette_input(&mut self, c: char) {
+        if let Some(p) = &mut self.palette {
+            p.query.push(c);
+            p.matches = palette::filter(&p.query);
+            p.selected = 0;
+        }
+    }
+
+    pub fn palette_backspace(&mut self) {
+        if let Some(p) = &mut self.palette {
+            p.query.pop();
+            p.matches = palette::filter(&p.query);
+            p.selected = 0;
+        }
+    }
+
+    pub fn palette_move(&mut self, delta: isize) {
+        if let Some(p) = self.palette.as_mut().filter(|p| !p.matches.is_empty()) {
+            let len = p.matches.len() as isize;
+            p.selected = (p.selected as isize + delta).rem_euclid(len) as usize;
+        }
+    }
+
+    /// Close the palette and queue the selected command, focusing the pane it
+    /// acts on first.
+    pub fn confirm_palette(&mut self) {
+        let selected = self.palette.take().and_then(|p| p.matches.get(p.selected).copied());
+        self.input_mode = InputMode::Normal;
+        let index = match selected {
+            Some(index) => index,
+            None => return,
+        };
+        match palette::COMMANDS[index].pane {
+            Some(FocusedPane::Response) if self.response.is_none() => {}
+            Some(pane) => self.set_focus(pane),
+            None => {}
+        }
+        self.pending_palette_command = Some(index);
+    }
+
+    pub fn open_global_finder(&mut self) {
+        self.finder = Some(GlobalFinder::default());
+        self.input_mode = InputMode::GlobalFinder;
+        self.refresh_finder_matches();
+    }
+
+    pub fn close_global_finder(&mut self) {
+        self.finder = None;
+        self.input_mode = InputMode::Normal;
+    }
+
+    /// Collections in the workspace whose detail isn't cached yet, which the
+    /// finder fetches so it can search them too.
+    pub fn collections_missing_detail(&self) -> Vec<String> {
+        self.collections
+            .iter()
+            .map(|c| c.uid.clone())
+            .filter(|uid| !self.collection_cache.contains_key(uid) && self.current_collection_uid.as_ref() != Some(uid))
+            .collect()
+    }
+
+    /// Cache a collection fetched for the finder and search it.
+    pub fn apply_finder_collection(&mut self, uid: String, result: Result<CollectionDetail>) {
+        match result {
+            Ok(detail) => {
+                self.collection_cache.entry(uid).or_insert(detail);
+            }
+            Err(e) => log_error("global_finder", &format!("{}: {:#}", uid, e)),
+        }
+        if let Some(finder) = &mut self.finder {
+            finder.loading = finder.loading.saturating_sub(1);
+        }
+        self.refresh_finder_matches();
+    }
+
+    /// Re-run the finder query over the loaded collection and every cached
+    /// one, keeping the highlighted match where possible.
+    fn refresh_finder_matches(&mut self) {
+        let Some(finder) = &self.finder else {
+            return;
+        };
+        let query = finder.query.clone();
+        let selected = finder.matches.get(finder.selected).map(|m| (m.collection_uid.clone(), m.path.clone()));
+        let mut matches = Vec::new();
+        if !query.trim().is_empty() {
+            for collection in &self.collections {
+                let items = match &self.current_collection {
+                    Some(current) if self.current_collection_uid.as_ref() == Some(&collection.uid) => &current.item,
+                    _ => match self.collection_cache.get(&collection.uid) {
+                        Some(detail) => &detail.item,
+                        None => continue,
+                    },
+                };
+                let owner = (collection.uid.as_str(), collection.name.as_str());
+                find_items_by_name(items, &query, owner, &mut Vec::new(), &mut Vec::new(), &mut matches);
+                if matches.len() >= MAX_FINDER_MATCHES {
+                    matches.truncate(MAX_FINDER_MATCHES);
+                    break;
+                }
+            }
+        }
+        if let Some(finder) = &mut self.finder {
+            finder.selected = selected
+                .and_then(|(uid, path)| matches.iter().position(|m| m.collection_uid == uid && m.path == path))
+                .unwrap_or(0);
+            finder.matches = matches;
+        }
+    }
+
+    pub fn finder_input(&mut self, c: char) {
+        if let Some(finder) = &mut self.finder {
+            finder.query.push(c);
+            finder.selected = 0;
+        }
+        self.refresh_finder_matches();
+    }
+
+    pub fn finder_backspace(&mut self) {
+        if let Some(finder) = &mut self.finder {
+            finder.query.pop();
+            finder.selected = 0;
+        }
+        self.refresh_finder_matches();
+    }
+
+    pub fn finder_move(&mut self, delta: isize) {
+        if let Some(finder) = self.finder.as_mut().filter(|f| !f.matches.is_empty()) {
+            let len = finder.matches.len() as isize;
+            finder.selected = (finder.selected as isize + delta).rem_euclid(len) as usize;
+        }
+    }
+
+    /// Open the highlighted finder match: select it in place when its
+    /// collection is loaded, otherwise load the collection first (like a
+    /// recent request).
+    pub fn confirm_global_finder(&mut self) -> CollectionLoad {
+        let target = self.finder.take().and_then(|f| f.matches.into_iter().nth(f.selected));
+        self.input_mode = InputMode::Normal;
+        match target {
+            Some(target) => self.go_to_collection_item(&target.collection_uid, target.path, target.is_folder),
+            None => CollectionLoad::None,
+        }
+    }
+
+    /// Select the request (or folder) at `path` in any collection, loading
+    /// the collection first when it isn't the open one.
+    fn go_to_collection_item(&mut self, collection_uid: &str, path: Vec<usize>, is_folder: bool) -> CollectionLoad {
+        if self.current_collection_uid.as_deref() == Some(collection_uid) {
+            self.restore_request_path(&path);
+            if is_folder {
+                self.set_focus(FocusedPane::Requests);
+            } else {
+                self.select_request();
+            }
+            return CollectionLoad::None;
+        }
+
+        let collection_index = self
+            .flat_collections
+            .iter()
+            .position(|c| !c.is_favorites_folder && c.uid == collection_uid);
+        match collection_index {
+            Some(idx) => self.selected_collection_index = idx,
+            None => {
+                self.status_message = String::from("Collection not available in this workspace");
+                return CollectionLoad::None;
+            }
+        }
+
+        self.pending_favorite_path = Some(path);
+        self.start_collection_load()
+    }
+
+    /// Show the current request exactly as it would be sent (variables
+    /// substituted, auth applied) without executing it.
+    pub fn open_dry_run(&mut self) {
+        let request = match self.current_request.as_ref() {
+            Some(r) => r,
+            None => {
+                self.status_message = String::from("No request selected");
+                return;
+            }
+        };
+        let request = match self.current_request_path.as_deref() {
+            Some(path) => match self.with_body_file(request, path) {
+                Ok(request) => request,
+                Err(e) => {
+                    self.error = Some(format!("{:#}", e));
+                    self.status_message = String::from("Failed to read body file");
+                    return;
+                }
+            },
+            None => request.clone(),
+        };
+        let request = self.prune_get_body(request, self.current_request_path.as_deref());
+        let request = with_extra_headers(&request, &self.extra_headers);
+        self.dry_run = Some(self.resolve_request(&request));
+        self.dry_run_scroll = 0;
+        self.input_mode = InputMode::DryRun;
+        self.status_message = String::from("Dry run - nothing was sent");
+    }
+
+    pub fn close_dry_run(&mut self) {
+        self.dry_run = None;
+        self.input_mode = InputMode::Normal;
+        self.update_status_for_pane();
+    }
+
+    pub fn dry_run_scroll_down(&mut self) {
+        self.dry_run_scroll = self.dry_run_scroll.saturating_add(1);
+    }
+
+    pub fn dry_run_scroll_up(&mut self) {
+        self.dry_run_scroll = self.dry_run_scroll.saturating_sub(1);
+    }
+
+    /// Non-empty pre-request/test scripts of the selected request, as
+    /// (label, source lines).
+    pub fn current_request_scripts(&self) -> Vec<(String, Vec<String>)> {
+        let (collection, path) = match (&self.current_collection, &self.current_request_path) {
+            (Some(collection), Some(path)) => (collection, path),
+            _ => return Vec::new(),
+        };
+        request_item_at_path(&collection.item, path)
+            .map(|item| {
+                item.event
+                    .iter()
+                    .map(|event| (event.label(), event.lines()))
+                    .filter(|(_, lines)| lines.iter().any(|l| !l.trim().is_empty()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn open_script_view(&mut self) {
+        if self.current_request_scripts().is_empty() {
+            self.status_message = String::from("This request has no scripts");
+            return;
+        }
+        self.script_view_scroll = 0;
+        self.input_mode = InputMode::ScriptView;
+    }
+
+    pub fn close_script_view(&mut self) {
+        self.input_mode = InputMode::Normal;
+        self.update_status_for_pane();
+    }
+
+    pub fn script_view_scroll_down(&mut self) {
+        self.script_view_scroll = self.script_view_scroll.saturating_add(1);
+    }
+
+    pub fn script_view_scroll_up(&mut self) {
+        self.script_view_scroll = self.script_view_scroll.saturating_sub(1);
+    }
+
+    /// Queue the current request for execution. The main loop picks this up and
+    /// runs it in a cancellable background task (same path as collection loads).
+    pub fn queue_execution(&mut self) {
+        // Fetch (or refresh) the OAuth token first if the request needs it
+        if self.token_needs_refresh() {
+            self.start_token_fetch(true);
+            return;
+        }
+        self.queue_resolved_execution();
+    }
+
+    fn queue_resolved_execution(&mut self) {
+        self.executing_key = self.current_collection_uid.clone().zip(self.current_request_path.clone());
+        if let Some(resolved) = self.prepare_execution_request() {
+            let timeout = self.current_request_timeout().0;
+            let version = self
+                .current_request_key()
+                .and_then(|(uid, path)| self.local_edits.get_http_version(&uid, &path));
+            self.pending_execution = Some((resolved, Duration::from_secs(timeout), version));
+            self.extra_headers.clear();
+            self.record_recent_execution();
+        }
+    }
+
+    /// Whether the current request uses the OAuth token variable and the token
+    /// is missing or about to expire.
+    fn token_needs_refresh(&self) -> bool {
+        let oauth = match &self.config.oauth {
+            Some(o) => o,
+            None => return false,
+        };
+        let placeholder = format!("{{{{{}}}}}", oauth.variable);
+        let uses_token = self.current_request_params().contains(&oauth.variable)
+            || self
+                .current_request
+                .as_ref()
+                .and_then(|r| r.auth.as_ref())
+                .is_some_and(|auth| {
+                    auth.params.values().flatten().any(|p| {
+                        crate::api::auth_value_to_string(&p.value).contains(&placeholder)
+                    })
+                });
+        if !uses_token {
+            return false;
+        }
+        match &self.oauth_token {
+            None => true,
+            Some(token) => token.expires_at.is_some_and(|at| {
+                // Refresh a little early so the token can't lapse mid-request
+                at.saturating_duration_since(std::time::Instant::now()) < Duration::from_secs(30)
+            }),
+        }
+    }
+
+    /// Queue an OAuth client-credentials token fetch using the `[oauth]`
+    /// config. With `then_execute`, the current request runs once it lands.
+    pub fn start_token_fetch(&mut self, then_execute: bool) {
+        let oauth = match &self.config.oauth {
+            Some(o) => o.clone(),
+            None => {
+                self.status_message = String::from("No [oauth] section in config.toml");
+                return;
+            }
+        };
+        self.pending_token_fetch = Some(TokenFetch {
+            token_url: self.substitute_variables(&oauth.token_url),
+            client_id: self.substitute_variables(&oauth.client_id),
+            client_secret: self.substitute_variables(&oauth.client_secret),
+            scope: self.substitute_variables(&oauth.scope),
+            then_execute,
+        });
+        self.token_fetching = true;
+        self.loading = true;
+        self.status_message = String::from("Fetching OAuth token...");
+    }
+
+    /// Store a fetched token as a variable, then continue with the request
+    /// that was waiting on it (if any).
+    pub fn apply_token_result(&mut self, result: Result<OAuthToken>, then_execute: bool) {
+        self.token_fetching = false;
+        self.loading = false;
+        match result {
+            Ok(token) => {
+                let variable = self
+                    .config
+                    .oauth
+                    .as_ref()
+                    .map(|o| o.variable.clone())
+                    .unwrap_or_default();
+                self.oauth_token = Some(FetchedToken {
+                    access_token: token.access_token,
+                    expires_at: token
+                        .expires_in
+                        .map(|secs| std::time::Instant::now() + Duration::from_secs(secs)),
+                });
+                self.rebuild_variables();
+                self.status_message = match token.expires_in {
+                    Some(secs) => format!("Fetched OAuth token into {{{{{}}}}} (expires in {}s)", variable, secs),
+                    None => format!("Fetched OAuth token into {{{{{}}}}}", variable),
+                };
+                if then_execute {
+                    self.queue_resolved_execution();
+                }
+            }
+            Err(e) => {
+                let error_msg = format!("{:#}", e);
+                log_error("fetch_oauth_token", &error_msg);
+                self.error = Some(error_msg);
+                self.status_message = String::from("Failed to fetch OAuth token");
+            }
+        }
+    }
+
+    pub fn cancel_token_fetch(&mut self) {
+        self.token_fetching = false;
+        self.loading = false;
+        self.status_message = String::from("Token fetch cancelled");
+    }
+
+    /// Effective timeout in seconds for the request at `path`, and whether it
+    /// is a per-request override (as opposed to the global default).
+    pub fn request_timeout(&self, path: &[usize]) -> (u64, bool) {
+        let custom = self
+            .get_current_collection_uid()
+            .and_then(|uid| self.local_edits.get_timeout(&uid, path));
+        match custom {
+            Some(secs) => (secs, true),
+            None => (self.default_timeout_secs(), false),
+        }
+    }
+
+    /// Effective timeout for the selected request (see `request_timeout`).
+    pub fn current_request_timeout(&self) -> (u64, bool) {
+        match self.current_request_key() {
+            Some((_, path)) => self.request_timeout(&path),
+            None => (self.default_timeout_secs(), false),
+        }
+    }
+
+    /// Apply the outcome of a (possibly failed) request execution.
+    pub fn apply_execution_result(&mut self, request: Request, result: Result<ExecutedResponse>) {
+        self.loading = false;
+        self.request_executing = false;
+        self.execution_started = None;
+        self.response_failed = result.is_err();
+        self.dismiss_stream();
+        let executed_key = self.executing_key.take();
+        match result {
+            Ok(response) => {
+                self.response_key = executed_key;
+                self.response_view_override = None;
+                self.build_response_view(&response);
+                self.response_scroll = 0;
+                self.response_search_query.clear();
+                self.response_search_matches.clear();
+                self.response_match_index = 0;
+                self.response_rate_limit = RateLimit::from_headers(&response.headers);
+                self.baseline_diff = None;
+                let retry_after = response
+                    .headers
+                    .iter()
+                    .find(|(k, _)| k.eq_ignore_ascii_case("retry-after"))
+                    .map(|(_, v)| v.clone());
+                let rate_limited = response.status == 429;
+                let rules = self.response_assertion_rules();
+                self.assertion_results = crate::assertions::evaluate(&rules, &response);
+                self.response = Some(response);
+                self.response_request = Some(request);
                 // Jump focus to the response so it can be browsed right away
-                // (before setting the status, since set_focus rewrites it).
-                self.set_focus(FocusedPane::Response);
-                self.status_message = String::from("Request completed");
+                // (before setting the status, since set_focus rewrites it),
+                // unless quick execute keeps it in the Requests pane.
+                if !std::mem::take(&mut self.quick_execute) {
+                    self.set_focus(FocusedPane::Response);
+                }
+                self.status_message = if rate_limited {
+                    match retry_after {
+                        Some(wait) => format!("Rate limited (429) - retry after {}", wait),
+                        None => String::from("Rate limited (429) - slow down"),
+                    }
+                } else {
+                    let completed = match self.compare_with_baseline() {
+                        Some(0) => String::from("Request completed - matches baseline"),
+                        Some(n) => format!("Request completed - {} changes vs baseline (B to view)", n),
+                        None => String::from("Request completed"),
+                    };
+                    let passed = self.assertion_results.iter().filter(|r| r.passed).count();
+                    match self.assertion_results.len() {
+                        0 => completed,
+                        total => format!("{} - {}/{} assertions passed", completed, passed, total),
+                    }
+                };
+            }
+            Err(e) => {
+                // Include root cause in error message
+                let error_msg = format!("{:#}", e);
+                log_error("execute_request", &error_msg);
+                self.error = Some(error_msg);
+                self.status_message = String::from("Request failed");
+                // Don't leave an earlier response looking like this run's
+                if !self.response_pinned {
+                    // Kept so the pane can say which request failed
+                    self.response_key = executed_key;
+                    self.response = None;
+                    self.json_viewer_state = None;
+                    self.assertion_results.clear();
+                    self.response_request = None;
+                }
+            }
+        }
+    }
+
+    /// The `[[assertions]]` rules configured for the request being executed
+    /// (none for scratch requests).
+    fn response_assertion_rules(&self) -> Vec<String> {
+        let Some((collection_uid, path)) = &self.response_key else {
+            return Vec::new();
+        };
+        let Some((name, _, request_id)) = self.current_collection.as_ref().and_then(|c| get_item_at_path(&c.item, path)) else {
+            return Vec::new();
+        };
+        let collection_name = self
+            .collections
+            .iter()
+            .find(|c| &c.uid == collection_uid)
+            .map(|c| c.name.as_str())
+            .unwrap_or_default();
+        self.config
+            .assertion_rules((collection_uid, collection_name), (request_id.as_deref(), &name))
+    }
+
+    /// Connect `request` as a WebSocket or event stream in the background;
+    /// its messages are picked up by `poll_stream_events`. Replaces any
+    /// stream already open.
+    pub fn open_stream(&mut self, request: Request, kind: StreamKind) {
+        // The stream log replaces the "Performing request..." popup
+        self.loading = false;
+        self.request_executing = false;
+        self.dismiss_stream();
+        let builder = match self.client.stream_request(&request, kind) {
+            Ok(builder) => builder,
+            Err(e) => {
+                let error_msg = format!("{:#}", e);
+                log_error("stream", &error_msg);
+                self.error = Some(error_msg);
+                self.status_message = format!("{}: could not connect", kind.label());
+                return;
+            }
+        };
+        let (events_tx, events) = std::sync::mpsc::channel();
+        let (outgoing, outgoing_rx) = tokio::sync::mpsc::unbounded_channel();
+        let handle = tokio::spawn(crate::api::stream::run_stream(kind, builder, events_tx, outgoing_rx));
+        let url = request.url.to_string();
+        self.stream = Some(StreamSession {
+            kind,
+            url: url.clone(),
+            log: Vec::new(),
+            open: true,
+            scroll_back: 0,
+            events,
+            outgoing: (kind == StreamKind::WebSocket).then_some(outgoing),
+            handle,
+        });
+        self.set_focus(FocusedPane::Response);
+        self.status_message = format!("{}: connecting to {}...", kind.label(), url);
+    }
+
+    /// Move newly arrived stream events into the log (non-blocking).
+    pub fn poll_stream_events(&mut self) {
+        let Some(stream) = &mut self.stream else {
+            return;
+        };
+        let mut status = None;
+        while let Ok(event) = stream.events.try_recv() {
+            match &event {
+                StreamEvent::Connected(_) => {
+                    let hint = if stream.kind == StreamKind::WebSocket { "m: send, c: close" } else { "c: close" };
+                    status = Some(format!("{} connected ({})", stream.kind.label(), hint));
+                }
+                StreamEvent::Closed(reason) => {
+                    stream.open = false;
+                    stream.outgoing = None;
+                    status = Some(format!("{} closed: {} (c to dismiss)", stream.kind.label(), reason));
+                }
+                StreamEvent::Failed(e) => {
+                    stream.open = false;
+                    stream.outgoing = None;
+                    log_error("stream", e);
+                    status = Some(format!("{} failed (c to dismiss)", stream.kind.label()));
+                }
+                StreamEvent::Received(_) | StreamEvent::Sent(_) => {}
+            }
+            let time = chrono::Local::now().format("%H:%M:%S").to_string();
+            stream.log.push((time, event));
+        }
+        if let Some(status) = status {
+            self.status_message = status;
+        }
+    }
+
+    /// Close the open stream, or dismiss the log of one that has ended.
+    pub fn close_stream(&mut self) {
+        let Some(stream) = &mut self.stream else {
+            return;
+        };
+        if !stream.open {
+            self.dismiss_stream();
+            self.update_status_for_pane();
+            return;
+        }
+        if stream.outgoing.take().is_none() {
+            // Event streams have no close handshake; just stop reading
+            stream.handle.abort();
+            stream.open = false;
+            let time = chrono::Local::now().format("%H:%M:%S").to_string();
+            stream.log.push((time, StreamEvent::Closed(String::from("Closed"))));
+        }
+        self.status_message = format!("{} closed (c to dismiss)", stream.kind.label());
+    }
+
+    /// Drop the stream and its log, stopping it if it's still open.
+    fn dismiss_stream(&mut self) {
+        if let Some(stream) = self.stream.take() {
+            stream.handle.abort();
+        }
+    }
+
+    /// Scroll the stream log; positive `delta` goes toward newer messages.
+    pub fn stream_scroll(&mut self, delta: isize) {
+        if let Some(stream) = &mut self.stream {
+            stream.scroll_back = if delta > 0 {
+                stream.scroll_back.saturating_sub(delta as usize)
+            } else {
+                (stream.scroll_back + delta.unsigned_abs()).min(stream.log.len().saturating_sub(1))
+            };
+        }
+    }
+
+    pub fn start_stream_message(&mut self) {
+        match &self.stream {
+            Some(stream) if stream.outgoing.is_some() => {
+                self.stream_input.clear();
+                self.input_mode = InputMode::StreamMessage;
+            }
+            Some(stream) if stream.kind == StreamKind::EventStream => {
+                self.status_message = String::from("Event streams are read-only");
+            }
+            _ => self.status_message = String::from("No open WebSocket to send to"),
+        }
+    }
+
+    pub fn stream_input_char(&mut self, c: char) {
+        self.stream_input.push(c);
+    }
+
+    pub fn stream_input_backspace(&mut self) {
+        self.stream_input.pop();
+    }
+
+    pub fn cancel_stream_message(&mut self) {
+        self.input_mode = InputMode::Normal;
+    }
+
+    /// Send the typed message over the WebSocket, with `{{variables}}` substituted.
+    pub fn send_stream_message(&mut self) {
+        self.input_mode = InputMode::Normal;
+        let typed = std::mem::take(&mut self.stream_input);
+        let message = self.substitute_variables(&typed);
+        let sent = self
+            .stream
+            .as_ref()
+            .and_then(|stream| stream.outgoing.as_ref())
+            .is_some_and(|outgoing| outgoing.send(message).is_ok());
+        if !sent {
+            self.status_message = String::from("WebSocket is closed");
+        }
+    }
+
+    /// Whether the Response pane should say the selected request's last
+    /// execution failed, rather than show nothing.
+    pub fn current_request_failed(&self) -> bool {
+        self.response_failed
+            && self.response.is_none()
+            && self.response_key.as_ref().is_some_and(|(uid, path)| {
+                self.current_collection_uid.as_ref() == Some(uid) && self.current_request_path.as_ref() == Some(path)
+            })
+    }
+
+    /// Set up the JSON tree (or pretty-printed XML) for a response body,
+    /// as its Content-Type (or `r`) says, wrapping it with the headers when
+    /// that view is toggled on.
+    fn build_response_view(&mut self, response: &ExecutedResponse) {
+        let view = self
+            .response_view_override
+            .unwrap_or_else(|| ResponseView::detect(&response.headers, &response.body));
+        self.response_view = view;
+        // Newline-delimited JSON is browsed as an array of its documents
+        let declared = response
+            .headers
+            .iter()
+            .any(|(k, v)| k.eq_ignore_ascii_case("content-type") && crate::util::is_ndjson_content_type(v));
+        let documents = crate::util::ndjson_documents(&response.body, declared).filter(|_| view == ResponseView::Json);
+        self.response_ndjson_count = documents.as_ref().map(Vec::len);
+        let body = match documents {
+            Some(documents) => serde_json::Value::Array(documents).to_string(),
+            None => response.body.clone(),
+        };
+        if self.headers_in_json_viewer {
+            self.json_viewer_state = Some(JsonViewerState::with_headers(&body, &response.headers));
+            self.xml_pretty = None;
+            return;
+        }
+        // A JSON body that doesn't parse falls back to plain text
+        self.json_viewer_state = if view == ResponseView::Json { JsonViewerState::new(&body) } else { None };
+        // XML bodies are reindented for highlighted display
+        self.xml_pretty = if view == ResponseView::Xml {
+            Some(xml_viewer::pretty_print(&response.body))
+        } else {
+            None
+        };
+    }
+
+    /// Step the shown response through the JSON, XML and text views, then
+    /// back to the one its Content-Type picks.
+    pub fn cycle_response_view(&mut self) {
+        let Some(response) = self.response.take() else {
+            self.status_message = String::from("No response to view");
+            return;
+        };
+        self.response_view_override = match self.response_view_override {
+            None => Some(ResponseView::Json),
+            Some(ResponseView::Json) => Some(ResponseView::Xml),
+            Some(ResponseView::Xml) => Some(ResponseView::Text),
+            Some(ResponseView::Text | ResponseView::Binary) => None,
+        };
+        self.build_response_view(&response);
+        self.response = Some(response);
+        self.response_scroll = 0;
+        self.response_search_matches.clear();
+        self.status_message = match self.response_view_override {
+            Some(ResponseView::Json) if self.json_viewer_state.is_none() => {
+                String::from("Response isn't valid JSON - shown as text (r for the next view)")
+            }
+            Some(view) => format!("Viewing response as {} (r for the next view)", view.label()),
+            None => format!("Viewing response as {} (from its Content-Type)", self.response_view.label()),
+        };
+    }
+
+    /// Toggle showing response headers as a `__headers` object in the JSON tree.
+    pub fn toggle_headers_in_json_viewer(&mut self) {
+        self.headers_in_json_viewer = !self.headers_in_json_viewer;
+        if let Some(response) = self.response.take() {
+            self.build_response_view(&response);
+            self.response = Some(response);
+            self.response_scroll = 0;
+        }
+        self.status_message = if self.headers_in_json_viewer {
+            String::from("Headers shown in JSON tree (__headers / __body)")
+        } else {
+            String::from("Headers hidden from JSON tree")
+        };
+    }
+
+    /// Save the shown response as the baseline for its request, so later
+    /// executions are diffed against it.
+    pub fn save_response_as_baseline(&mut self) {
+        let (response, (collection_uid, path)) = match (&self.response, &self.response_key) {
+            (Some(response), Some(key)) => (response, key.clone()),
+            _ => {
+                self.status_message = String::from("Only responses to collection requests can be baselines");
+                return;
             }
+        };
+        self.baselines.set(BaselineEntry {
+            collection_uid,
+            path,
+            status: response.status,
+            body: response.body.clone(),
+            saved_at: chrono::Local::now().format("%Y-%m-%d %H:%M").to_string(),
+        });
+        if let Err(e) = self.baselines.save() {
+            log_error("save_baseline", &e.to_string());
+            self.error = Some(format!("Failed to save baseline: {}", e));
+            return;
+        }
+        self.baseline_diff = Some(Vec::new());
+        self.status_message = String::from("Saved response as baseline");
+    }
+
+    /// The stored baseline for the request the shown response came from.
+    pub fn response_baseline(&self) -> Option<&BaselineEntry> {
+        let (uid, path) = self.response_key.as_ref()?;
+        self.baselines.get(uid, path)
+    }
+
+    /// Diff the shown response against its baseline, storing the result in
+    /// `baseline_diff`. Returns the number of changes, or None without a
+    /// baseline.
+    fn compare_with_baseline(&mut self) -> Option<usize> {
+        let changes = {
+            let baseline = self.response_baseline()?;
+            let response = self.response.as_ref()?;
+            let mut changes = json_diff::diff(
+                &json_diff::parse_body(&baseline.body),
+                &json_diff::parse_body(&response.body),
+            );
+            if baseline.status != response.status {
+                changes.insert(0, JsonChange {
+                    path: String::from("(status)"),
+                    kind: json_diff::ChangeKind::Changed,
+                    old: Some(baseline.status.into()),
+                    new: Some(response.status.into()),
+                });
+            }
+            changes
+        };
+        let count = changes.len();
+        self.baseline_diff = Some(changes);
+        Some(count)
+    }
+
+    pub fn open_baseline_diff(&mut self) {
+        if self.baseline_diff.is_none() && self.compare_with_baseline().is_none() {
+            self.status_message = String::from("No baseline for this response (b to save one)");
+            return;
+        }
+        self.baseline_diff_scroll = 0;
+        self.input_mode = InputMode::BaselineDiff;
+    }
+
+    pub fn close_baseline_diff(&mut self) {
+        self.input_mode = InputMode::Normal;
+    }
+
+    pub fn baseline_diff_scroll_down(&mut self) {
+        self.baseline_diff_scroll = self.baseline_diff_scroll.saturating_add(1);
+    }
+
+    pub fn baseline_diff_scroll_up(&mut self) {
+        self.baseline_diff_scroll = self.baseline_diff_scroll.saturating_sub(1);
+    }
+
+    /// The scratch request to open in the editor: the previous one if any,
+    /// otherwise a blank GET.
+    pub fn scratch_request_for_edit(&self) -> ScratchRequest {
+        self.scratch_request.clone().unwrap_or_default()
+    }
+
+    /// Queue a scratch request for execution. It goes through the same
+    /// confirmation, variable substitution and cancellable execution path
+    /// as collection requests, but is never written to a collection or
+    /// local edits.
+    pub fn queue_scratch_execution(&mut self, scratch: ScratchRequest) {
+        if scratch.url.trim().is_empty() {
+            self.scratch_request = Some(scratch);
+            self.status_message = String::from("Scratch request needs a URL");
+            return;
+        }
+
+        let request = scratch.to_request();
+        self.scratch_request = Some(scratch);
+        let method = if request.method.trim().is_empty() { String::from("GET") } else { request.method.to_uppercase() };
+        if self.needs_execute_confirmation(&method) {
+            self.pending_execute = Some(PendingExecute {
+                method: method.clone(),
+                url: self.substitute_variables(&request.url.to_string()),
+                name: String::from("Scratch request"),
+                scratch: true,
+            });
+            self.input_mode = InputMode::ExecuteConfirm;
+            self.status_message = format!("Confirm {} request? (y/n)", method);
+            return;
+        }
+        self.execute_scratch();
+    }
+
+    /// Execute the stored scratch request.
+    fn execute_scratch(&mut self) {
+        let Some(scratch) = &self.scratch_request else {
+            return;
+        };
+        let resolved = self.resolve_request(&scratch.to_request());
+        self.executing_key = None;
+        self.loading = true;
+        self.request_executing = true;
+        self.status_message = String::from("Executing scratch request...");
+        let timeout = Duration::from_secs(self.default_timeout_secs());
+        self.pending_execution = Some((resolved, timeout, None));
+    }
+
+    /// Values that must never be written to disk: environment/collection
+    /// variables typed `secret` or named like credentials, plus any fetched
+    /// OAuth token.
+    fn secret_values(&self) -> Vec<String> {
+        let collection_vars = self.current_collection.iter().flat_map(|c| c.variable.iter());
+        let env_vars = self.current_environment.iter().flat_map(|e| e.values.iter());
+        let mut secrets: Vec<String> = collection_vars
+            .chain(env_vars)
+            .filter(|v| is_secret_variable(v))
+            .map(|v| v.value.clone())
+            .chain(self.oauth_token.iter().map(|t| t.access_token.clone()))
+            .filter(|v| !v.is_empty())
+            .collect();
+        // Longest first, so a secret containing another is fully masked
+        secrets.sort_by_key(|v| std::cmp::Reverse(v.len()));
+        secrets
+    }
+
+    /// Append an executed request to the request log (when `log_requests`
+    /// is on), with secret values and credential headers redacted.
+    pub fn log_executed_request(&self, request: &Request, result: &Result<ExecutedResponse>, duration: Duration) {
+        if !self.config.settings.log_requests {
+            return;
+        }
+        let secrets = self.secret_values();
+        let redact = |text: &str| {
+            secrets
+                .iter()
+                .fold(text.to_string(), |acc, secret| acc.replace(secret.as_str(), "***"))
+        };
+
+        let headers: Vec<serde_json::Value> = request
+            .header
+            .iter()
+            .filter(|h| !h.disabled.unwrap_or(false) && !h.key.trim().is_empty())
+            .map(|h| {
+                let value = if SENSITIVE_HEADERS.contains(&h.key.to_lowercase().as_str()) {
+                    String::from("***")
+                } else {
+                    redact(&h.value)
+                };
+                serde_json::json!({ "key": h.key, "value": value })
+            })
+            .collect();
+
+        let (status, error) = match result {
+            Ok(response) => (Some(response.status), None),
+            Err(e) => (None, Some(redact(&format!("{:#}", e)))),
+        };
+
+        let entry = serde_json::json!({
+            "timestamp": chrono::Local::now().to_rfc3339(),
+            "method": request.method.to_uppercase(),
+            "url": redact(&request.url.to_string()),
+            "headers": headers,
+            "auth": request.auth.as_ref().map(|a| a.auth_type.clone()),
+            "status": status,
+            "error": error,
+            "duration_ms": duration.as_millis() as u64,
+        });
+        crate::logging::log_request(&entry);
+    }
+
+    /// The shown response and the request that produced it as a Markdown
+    /// report, with secret values and credential headers redacted.
+    fn response_report(&self) -> Option<String> {
+        let response = self.response.as_ref()?;
+        let request = self.response_request.as_ref()?;
+        let header_line = |key: &str, value: &str| {
+            let value = if SENSITIVE_HEADERS.contains(&key.to_lowercase().as_str()) { "***" } else { value };
+            format!("{}: {}\n", key, value)
+        };
+
+        let url = request.url.to_string();
+        let mut sent = format!("{} {}\n", request.method.to_uppercase(), url);
+        for header in request.header.iter().filter(|h| !h.disabled.unwrap_or(false) && !h.key.trim().is_empty()) {
+            sent.push_str(&header_line(&header.key, &header.value));
+        }
+        for (key, value) in crate::api::client::missing_default_headers(request, &self.config.settings.default_headers) {
+            sent.push_str(&header_line(key, value));
+        }
+        if let Some(auth) = &request.auth {
+            sent.push_str(&format!("(auth: {})\n", auth.auth_type));
+        }
+        let body = match request.body.as_ref() {
+            Some(body) if body.is_form_data() => body
+                .formdata
+                .iter()
+                .filter(|f| f.is_enabled())
+                .map(|f| format!("{}={}", f.key, if f.is_file() { String::from("@file") } else { f.value.clone().unwrap_or_default() }))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            Some(body) => match body.graphql() {
+                Some(graphql) => crate::api::client::graphql_payload(graphql)
+                    .map(|payload| pretty_json(&payload.to_string()))
+                    .unwrap_or_else(|_| graphql.query.clone()),
+                None => body.raw.as_deref().map(pretty_json).unwrap_or_default(),
+            },
+            None => String::new(),
+        };
+        if !body.trim().is_empty() {
+            sent.push('\n');
+            sent.push_str(&body);
+        }
+
+        let mut received = format!("HTTP {} {}\n", response.status, response.status_text);
+        for (key, value) in &response.headers {
+            received.push_str(&header_line(key, value));
+        }
+        if !response.body.is_empty() {
+            received.push('\n');
+            received.push_str(&pretty_json(&response.body));
+        }
+
+        let report = format!(
+            "## {} {}\n\n_Captured with LazyPost at {}_\n\n### Request\n\n{}\n### Response\n\n{}",
+            request.method.to_uppercase(),
+            url,
+            chrono::Local::now().format("%Y-%m-%d %H:%M:%S"),
+            crate::util::markdown_code_block("http", &sent),
+            crate::util::markdown_code_block("http", &received),
+        );
+        let secrets = self.secret_values();
+        Some(secrets.iter().fold(report, |acc, secret| acc.replace(secret.as_str(), "***")))
+    }
+
+    /// The response body to open in an external pager, with a file
+    /// extension matching its type so editors highlight it. JSON is
+    /// re-indented.
+    pub fn response_body_for_pager(&self) -> Option<(String, &'static str)> {
+        let response = self.response.as_ref()?;
+        let content_type = response
+            .headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case("content-type"))
+            .map(|(_, v)| v.to_ascii_lowercase())
+            .unwrap_or_default();
+        let extension = if self.response_ndjson_count.is_some() {
+            "ndjson"
+        } else if self.json_viewer_state.is_some() {
+            "json"
+        } else if self.xml_pretty.is_some() {
+            "xml"
+        } else if content_type.contains("html") {
+            "html"
+        } else {
+            "txt"
+        };
+        let body = if extension == "json" { pretty_json(&response.body) } else { response.body.clone() };
+        Some((body, extension))
+    }
+
+    /// `M`: offer to copy or save a report of the shown response; the next
+    /// key picks which.
+    pub fn start_report(&mut self) {
+        if self.response.is_none() || self.response_request.is_none() {
+            self.status_message = String::from("No response to report");
+            return;
+        }
+        self.report_pending = true;
+        self.status_message = String::from("Report: c copy to clipboard, f save to a Markdown file (any other key cancels)");
+    }
+
+    /// Finish an `M` command; any key other than `c`/`f` cancels it.
+    pub fn finish_report(&mut self, key: char) {
+        self.report_pending = false;
+        let Some(report) = self.response_report().filter(|_| matches!(key, 'c' | 'f')) else {
+            self.status_message = String::from("Report cancelled");
+            return;
+        };
+        if key == 'c' {
+            if self.copy_to_clipboard(&report) {
+                self.status_message = String::from("Copied request/response report to clipboard (secrets redacted)");
+            }
+            return;
+        }
+        let name = format!("lazypost-report-{}.md", chrono::Local::now().format("%Y%m%d-%H%M%S"));
+        let path = std::env::current_dir().unwrap_or_default().join(name);
+        match std::fs::write(&path, report) {
+            Ok(()) => self.status_message = format!("Saved report to {}", path.display()),
+            Err(e) => {
+                let error_msg = format!("Failed to write {}: {}", path.display(), e);
+                log_error("save_report", &error_msg);
+                self.error = Some(error_msg);
+                self.status_message = String::from("Report not saved");
+            }
+        }
+    }
+
+    /// `X`: offer to export the highlighted request on its own; the next
+    /// key picks clipboard or file, with `{{variables}}` kept or resolved.
+    pub fn start_request_export(&mut self) {
+        let is_request = self.flat_items.get(self.selected_item_index).is_some_and(|item| item.request.is_some());
+        if !is_request || self.current_request_key().is_none() {
+            self.status_message = String::from("Select a request to export");
+            return;
+        }
+        self.request_export_pending = true;
+        self.status_message = String::from(
+            "Export request: c copy / f save as is, C copy / F save with variables resolved (any other key cancels)",
+        );
+    }
+
+    /// Finish an `X` command; any key other than `c`/`f`/`C`/`F` cancels it.
+    pub fn finish_request_export(&mut self, key: char) {
+        self.request_export_pending = false;
+        if !matches!(key, 'c' | 'f' | 'C' | 'F') {
+            self.status_message = String::from("Export cancelled");
+            return;
+        }
+        let resolved = key.is_ascii_uppercase();
+        let snippet = match self.request_snippet(resolved) {
+            Ok(snippet) => snippet,
             Err(e) => {
-                // Include root cause in error message
                 let error_msg = format!("{:#}", e);
-                log_error("execute_request", &error_msg);
+                log_error("request_snippet", &error_msg);
                 self.error = Some(error_msg);
-                self.status_message = String::from("Request failed");
+                self.status_message = String::from("Request not exported");
+                return;
+            }
+        };
+        let (name, json) = snippet;
+        let what = if resolved { "with variables resolved" } else { "as is" };
+        if key.eq_ignore_ascii_case(&'c') {
+            if self.copy_to_clipboard(&json) {
+                self.status_message = format!("Copied \"{}\" to clipboard {} (import it as a Postman collection)", name, what);
+            }
+            return;
+        }
+        let file = format!("{}.postman_collection.json", crate::util::file_slug(&name));
+        let path = std::env::current_dir().unwrap_or_default().join(file);
+        match std::fs::write(&path, json) {
+            Ok(()) => self.status_message = format!("Saved \"{}\" {} to {}", name, what, path.display()),
+            Err(e) => {
+                let error_msg = format!("Failed to write {}: {}", path.display(), e);
+                log_error("export_request", &error_msg);
+                self.error = Some(error_msg);
+                self.status_message = String::from("Request not exported");
+            }
+        }
+    }
+
+    /// The highlighted request (local edits applied) as a one-request
+    /// Postman v2.1 collection, which Postman and LazyPost import like any
+    /// other. `resolved` substitutes variables and reads its body file.
+    /// Saved examples and the item id are left out.
+    fn request_snippet(&self, resolved: bool) -> Result<(String, String)> {
+        let (uid, path) = self.current_request_key().context("No request selected")?;
+        let collection = self.collection_detail(&uid).context("Collection not loaded")?;
+        let item = request_item_at_path(&collection.item, &path).context("Request not found in the collection")?;
+        let mut item = item.clone();
+        let edit = self.local_edit_in(&uid, &path);
+        if let Some(edit) = &edit {
+            item.name = edit.name.clone();
+        }
+        if resolved {
+            item.request = self.run_step(&uid, collection, item.name.clone(), path, item.request.clone())?.request;
+        } else if let Some(edit) = &edit {
+            item.request = apply_local_edit(&item.request, edit);
+        }
+        item.id = None;
+        item.response.clear();
+        let name = item.name.clone();
+        let snippet = serde_json::json!({
+            "info": {
+                "name": name,
+                "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json"
+            },
+            "item": [item]
+        });
+        let json = serde_json::to_string_pretty(&snippet).context("Failed to serialize the request")?;
+        Ok((name, json))
+    }
+
+    /// Write the loaded collection, local edits applied, as a minimal OpenAPI
+    /// 3.0 document to `<collection-name>.openapi.json` in the current
+    /// directory, replacing one that exists (the status says so).
+    pub fn export_openapi(&mut self) {
+        let (Some(collection), Some(uid)) = (&self.current_collection, self.get_current_collection_uid()) else {
+            self.status_message = String::from("Load a collection to export it");
+            return;
+        };
+        let mut requests = Vec::new();
+        collect_requests_recursive(&collection.item, Vec::new(), &mut requests);
+        let specs: Vec<crate::openapi::SpecRequest> = requests
+            .into_iter()
+            .map(|(name, path, request)| {
+                let folders = folders_along_path(&collection.item, &path).iter().map(|f| f.name.clone()).collect();
+                match self.local_edit_in(&uid, &path) {
+                    Some(edit) => crate::openapi::SpecRequest { name: edit.name.clone(), folders, request: apply_local_edit(&request, &edit) },
+                    None => crate::openapi::SpecRequest { name, folders, request },
+                }
+            })
+            .collect();
+        let name = collection.info.name.clone();
+        let (doc, skipped) = crate::openapi::document(&name, &specs);
+        let path = std::env::current_dir()
+            .unwrap_or_default()
+            .join(format!("{}.openapi.json", crate::util::file_slug(&name)));
+        let existed = path.exists();
+        let written = serde_json::to_string_pretty(&doc)
+            .context("Failed to serialize the OpenAPI document")
+            .and_then(|json| std::fs::write(&path, json).with_context(|| format!("Failed to write {}", path.display())));
+        match written {
+            Ok(()) => {
+                let operations = specs.len() - skipped;
+                let verb = if existed { "Overwrote" } else { "Exported to" };
+                self.status_message = if skipped > 0 {
+                    format!("{} {} with {} operations ({} with a duplicate path and method left out)", verb, path.display(), operations, skipped)
+                } else {
+                    format!("{} {} with {} operations", verb, path.display(), operations)
+                };
+            }
+            Err(e) => {
+                let error_msg = format!("{:#}", e);
+                log_error("export_openapi", &error_msg);
+                self.error = Some(error_msg);
+                self.status_message = String::from("OpenAPI export failed");
+            }
+        }
+    }
+
+    /// Ask for the path of an OpenAPI spec to create a collection from.
+    pub fn start_openapi_import(&mut self) {
+        self.openapi_import = Some(ImportPrompt { path: String::new(), cursor_position: 0 });
+        self.input_mode = InputMode::OpenApiImport;
+        self.status_message = String::from("Path of an OpenAPI 3 JSON or YAML file | Enter: Import | Esc: Cancel");
+    }
+
+    pub fn cancel_openapi_import(&mut self) {
+        self.openapi_import = None;
+        self.input_mode = InputMode::Normal;
+        self.status_message = String::from("Import cancelled");
+    }
+
+    /// Build a collection from the OpenAPI spec at the prompt's path. It is
+    /// created in the selected workspace from the main loop (see
+    /// `apply_openapi_import`), so Esc can cancel it.
+    pub fn confirm_openapi_import(&mut self) {
+        let Some(prompt) = self.openapi_import.take() else {
+            return;
+        };
+        self.input_mode = InputMode::Normal;
+        let path = expand_home(prompt.path.trim());
+        let imported = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))
+            .and_then(|text| crate::openapi::collection(&text));
+        let imported = match imported {
+            Ok(imported) => imported,
+            Err(e) => {
+                let error_msg = format!("{:#}", e);
+                log_error("import_openapi", &error_msg);
+                self.error = Some(error_msg);
+                self.status_message = String::from("OpenAPI import failed");
+                return;
+            }
+        };
+
+        self.loading = true;
+        self.status_message = format!("Creating collection \"{}\"...", imported.name);
+        let workspace_id = self.get_selected_workspace_id();
+        self.pending_import = Some(PendingImport { workspace_id, path, imported });
+    }
+
+    /// Select the collection Postman created for an OpenAPI import.
+    pub fn apply_openapi_import(&mut self, pending: PendingImport, created: Result<CollectionInfo>) {
+        self.loading = false;
+        let PendingImport { path, imported, .. } = pending;
+        match created {
+            Ok(created) => {
+                let uid = created.uid.clone();
+                self.collections.push(created);
+                self.sort_collections();
+                self.flatten_collections();
+                if let Some(index) = self.flat_collections.iter().position(|c| c.uid == uid && !c.is_favorites_folder) {
+                    self.selected_collection_index = index;
+                }
+                self.status_message = format!(
+                    "Created collection \"{}\" with {} requests from {}",
+                    imported.name,
+                    imported.requests,
+                    path.display()
+                );
+            }
+            Err(e) => {
+                let error_msg = format!("{:#}", e);
+                log_error("import_openapi", &error_msg);
+                self.error = Some(error_msg);
+                self.status_message = String::from("Failed to create collection");
+            }
+        }
+    }
+
+    pub fn abort_openapi_import(&mut self) {
+        self.pending_import = None;
+        self.loading = false;
+        self.status_message = String::from("Import cancelled - if it reached Postman, the collection shows up on refresh");
+    }
+
+    /// Reset busy state after the user cancels an in-flight request.
+    pub fn cancel_execution(&mut self) {
+        self.executing_key = None;
+        self.quick_execute = false;
+        self.loading = false;
+        self.request_executing = false;
+        self.status_message = match self.execution_started.take() {
+            Some(started) => format!("Request cancelled after {:.1}s", started.elapsed().as_secs_f64()),
+            None => String::from("Request cancelled"),
+        };
+    }
+
+    /// Collect every request under the selected folder (depth-first, in
+    /// collection order) and ask for confirmation before running them. On
+    /// the Favorites section this runs the collection's favorites; when the
+    /// selection isn't a folder, reopen the last run's results instead.
+    pub fn start_folder_run(&mut self) {
+        let item = match self.flat_items.get(self.selected_item_index) {
+            Some(item) => item,
+            None => return,
+        };
+        if item.path == [usize::MAX] {
+            self.start_favorites_run(false);
+            return;
+        }
+        if !item.is_folder || item.path.contains(&usize::MAX) {
+            self.open_run_results();
+            return;
+        }
+        let folder_name = item.name.clone();
+        let folder_path = item.path.clone();
+
+        let items = match &self.current_collection {
+            Some(collection) => collection.item.clone(),
+            None => return,
+        };
+        let children = match get_folder_items_at_path(&items, &folder_path) {
+            Some(children) => children,
+            None => return,
+        };
+
+        let mut requests = Vec::new();
+        collect_requests_recursive(children, folder_path, &mut requests);
+        if requests.is_empty() {
+            self.status_message = format!("No requests in '{}'", folder_name);
+            return;
+        }
+
+        let (Some(collection_uid), Some(collection)) = (self.current_collection_uid.as_deref(), self.current_collection.as_ref()) else {
+            return;
+        };
+        let steps: Result<Vec<RunStep>> = requests
+            .into_iter()
+            .map(|(name, path, request)| self.run_step(collection_uid, collection, name, path, request))
+            .collect();
+        let steps = match steps {
+            Ok(steps) => steps,
+            Err(e) => {
+                let error_msg = format!("{:#}", e);
+                log_error("read_body_file", &error_msg);
+                self.error = Some(error_msg);
+                self.status_message = String::from("Failed to read body file");
+                return;
+            }
+        };
+
+        self.pending_run_confirm = Some(PendingRun { title: folder_name, steps });
+        self.input_mode = InputMode::RunConfirm;
+        self.status_message = String::from("Run all requests in folder? (y/n)");
+    }
+
+    /// A run step for the request at `path` in `collection` (the loaded one
+    /// or any other): local edits and its body file applied, the body pruned
+    /// as for a single execution, and variables substituted from that
+    /// collection's scopes and the active environment.
+    fn run_step(&self, collection_uid: &str, collection: &CollectionDetail, name: String, path: Vec<usize>, request: Request) -> Result<RunStep> {
+        let edit = self.local_edit_in(collection_uid, &path);
+        let mut request = match &edit {
+            Some(edit) => apply_local_edit(&request, edit),
+            None => request,
+        };
+        if let Some(file) = self.local_edits.get_body_file(collection_uid, &path) {
+            let language = request
+                .body
+                .as_ref()
+                .and_then(|b| b.language())
+                .map(String::from)
+                .or_else(|| edit.and_then(|e| e.body_language));
+            request.body = Some(crate::api::RequestBody::raw(read_body_file(file)?, language));
+        }
+        if request.prunes_body() && !request_item_at_path(&collection.item, &path).is_some_and(|item| item.keeps_body_on_get()) {
+            request.body = None;
+        }
+        let variables = self.variables_in(Some(collection), Some(&path));
+        let timeout = self
+            .local_edits
+            .get_timeout(collection_uid, &path)
+            .unwrap_or_else(|| self.default_timeout_secs());
+        let http_version = self.local_edits.get_http_version(collection_uid, &path);
+        let request_id = request_item_at_path(&collection.item, &path).and_then(|item| item.id.as_deref());
+        let assertions = self.config.assertion_rules((collection_uid, &collection.info.name), (request_id, &name));
+        Ok(RunStep {
+            request: self.resolve_request_with(&request, &variables),
+            name,
+            collection_uid: collection_uid.to_string(),
+            path,
+            timeout: Duration::from_secs(timeout),
+            http_version,
+            assertions,
+        })
+    }
+
+    /// Run every favorited request, from the loaded collection only or from
+    /// all collections. Collections not loaded this session are fetched
+    /// first (`pending_favorites_fetch`), and the run is built once they
+    /// arrive.
+    pub fn start_favorites_run(&mut self, all_collections: bool) {
+        let favorites: Vec<&FavoriteRequest> = self
+            .config
+            .favorite_requests
+            .iter()
+            .filter(|f| all_collections || self.current_collection_uid.as_deref() == Some(f.collection_uid.as_str()))
+            .collect();
+        if favorites.is_empty() {
+            self.status_message = if all_collections {
+                String::from("No favorite requests")
+            } else {
+                String::from("No favorite requests in this collection")
+            };
+            return;
+        }
+        let mut missing: Vec<String> = favorites
+            .iter()
+            .map(|f| f.collection_uid.clone())
+            .filter(|uid| self.collection_detail(uid).is_none() && self.collections.iter().any(|c| &c.uid == uid))
+            .collect();
+        missing.sort();
+        missing.dedup();
+        if missing.is_empty() {
+            self.confirm_favorites_run(all_collections);
+        } else {
+            self.status_message = format!("Fetching {} collections with favorites...", missing.len());
+            self.run_in_progress = Some(format!("favorites (fetching {} collections)", missing.len()));
+            self.pending_favorites_fetch = Some(missing);
+        }
+    }
+
+    /// Cache the collections fetched for a favorites run, then build it.
+    pub fn apply_favorites_fetch(&mut self, fetched: Vec<(String, Result<CollectionDetail>)>) {
+        self.run_in_progress = None;
+        for (uid, result) in fetched {
+            match result {
+                Ok(detail) => {
+                    self.collection_cache.entry(uid).or_insert(detail);
+                }
+                Err(e) => log_error("favorites_run", &format!("{}: {:#}", uid, e)),
+            }
+        }
+        self.confirm_favorites_run(true);
+    }
+
+    /// Build the favorites run from the collections at hand and ask for
+    /// confirmation. Favorites whose collection or request can't be found
+    /// are left out and counted in the status bar.
+    fn confirm_favorites_run(&mut self, all_collections: bool) {
+        let mut missing = 0;
+        let mut steps = Vec::new();
+        for favorite in &self.config.favorite_requests {
+            if !all_collections && self.current_collection_uid.as_deref() != Some(favorite.collection_uid.as_str()) {
+                continue;
+            }
+            let Some(collection) = self.collection_detail(&favorite.collection_uid) else {
+                missing += 1;
+                continue;
+            };
+            let path = favorite
+                .request_id
+                .as_deref()
+                .and_then(|id| find_request_path_by_id(&collection.item, id))
+                .unwrap_or_else(|| favorite.path.clone());
+            let Some((name, Some(request), _)) = get_item_at_path(&collection.item, &path) else {
+                missing += 1;
+                continue;
+            };
+            match self.run_step(&favorite.collection_uid, collection, name, path, request) {
+                Ok(step) => steps.push(step),
+                Err(e) => {
+                    let error_msg = format!("{:#}", e);
+                    log_error("read_body_file", &error_msg);
+                    self.error = Some(error_msg);
+                    self.status_message = String::from("Failed to read body file");
+                    return;
+                }
+            }
+        }
+        if steps.is_empty() {
+            self.status_message = String::from("None of the favorite requests could be found");
+            return;
+        }
+
+        let title = if all_collections {
+            String::from("Favorites")
+        } else {
+            format!("Favorites ({})", self.current_collection.as_ref().map(|c| c.info.name.as_str()).unwrap_or_default())
+        };
+        self.pending_run_confirm = Some(PendingRun { title, steps });
+        self.input_mode = InputMode::RunConfirm;
+        self.status_message = if missing > 0 {
+            format!("Run all favorite requests? (y/n) - {} not found, left out", missing)
+        } else {
+            String::from("Run all favorite requests? (y/n)")
+        };
+    }
+
+    /// Queue the confirmed run for the main loop to execute.
+    pub fn confirm_run(&mut self) {
+        if let Some(run) = self.pending_run_confirm.take() {
+            self.run_in_progress = Some(format!("{} ({} requests)", run.title, run.steps.len()));
+            self.status_message = format!("Running '{}'...", run.title);
+            self.pending_run = Some(run);
+        }
+        self.input_mode = InputMode::Normal;
+    }
+
+    pub fn cancel_run_confirm(&mut self) {
+        self.pending_run_confirm = None;
+        self.input_mode = InputMode::Normal;
+        self.status_message = String::from("Run cancelled");
+    }
+
+    /// Store a finished run's report and show it.
+    pub fn apply_run_report(&mut self, report: RunReport) {
+        self.run_in_progress = None;
+        let passed = report.results.iter().filter(|r| r.passed()).count();
+        self.status_message = format!(
+            "Ran '{}': {}/{} passed{}",
+            report.title,
+            passed,
+            report.results.len(),
+            if report.skipped > 0 {
+                format!(", {} skipped", report.skipped)
+            } else {
+                String::new()
+            }
+        );
+        self.run_report = Some(report);
+        self.run_results_index = 0;
+        self.input_mode = InputMode::RunResults;
+    }
+
+    /// Reset state after the user cancels an in-flight run.
+    pub fn cancel_run(&mut self) {
+        self.run_in_progress = None;
+        self.status_message = String::from("Run cancelled");
+    }
+
+    pub fn open_run_results(&mut self) {
+        if self.run_report.is_some() {
+            self.input_mode = InputMode::RunResults;
+        } else {
+            self.status_message = String::from("Select a folder to run its requests");
+        }
+    }
+
+    pub fn close_run_results(&mut self) {
+        self.input_mode = InputMode::Normal;
+        self.update_status_for_pane();
+    }
+
+    pub fn run_results_up(&mut self) {
+        self.run_results_index = self.run_results_index.saturating_sub(1);
+    }
+
+    pub fn run_results_down(&mut self) {
+        let count = self.run_report.as_ref().map(|r| r.results.len()).unwrap_or(0);
+        if self.run_results_index + 1 < count {
+            self.run_results_index += 1;
+        }
+    }
+
+    /// Close the results and select the highlighted request in the tree,
+    /// loading its collection first if it's another one.
+    pub fn open_run_result_request(&mut self) -> CollectionLoad {
+        let target = self
+            .run_report
+            .as_ref()
+            .and_then(|r| r.results.get(self.run_results_index))
+            .map(|r| (r.collection_uid.clone(), r.path.clone()));
+        self.input_mode = InputMode::Normal;
+        match target {
+            Some((collection_uid, path)) => self.go_to_collection_item(&collection_uid, path, false),
+            None => CollectionLoad::None,
+        }
+    }
+
+    /// Store an edit from the external editor, first asking for confirmation
+    /// when its body looks like JSON but doesn't parse.
+    pub fn submit_local_edit(&mut self, edited: EditableRequest, item_index: usize) {
+        let content_type = self.current_request.as_ref().and_then(|r| {
+            r.header
+                .iter()
+                .find(|h| h.key.eq_ignore_ascii_case("content-type"))
+                .map(|h| h.value.clone())
+        });
+        match json_body_error(&edited.body, content_type.as_deref()) {
+            Some(error) => {
+                self.pending_invalid_body = Some(PendingInvalidBody {
+                    edit: PendingSave { edited, item_index },
+                    error,
+                });
+                self.input_mode = InputMode::InvalidBodyConfirm;
+                self.status_message = String::from("Body is not valid JSON. Store anyway? (y/n)");
             }
+            None => self.store_local_edit(edited, item_index),
         }
     }
 
-    /// Reset busy state after the user cancels an in-flight request.
-    pub fn cancel_execution(&mut self) {
-        self.loading = false;
-        self.request_executing = false;
-        self.status_message = String::from("Request cancelled");
+    pub fn confirm_invalid_body(&mut self) {
+        self.input_mode = InputMode::Normal;
+        if let Some(pending) = self.pending_invalid_body.take() {
+            self.store_local_edit(pending.edit.edited, pending.edit.item_index);
+        }
+    }
+
+    /// Close the prompt and hand back the edit, to reopen it in the editor.
+    pub fn cancel_invalid_body(&mut self) -> Option<PendingSave> {
+        self.input_mode = InputMode::Normal;
+        self.pending_invalid_body.take().map(|pending| pending.edit)
     }
 
-    pub fn store_local_edit(&mut self, edited: EditableRequest, item_index: usize) {
+    pub fn store_local_edit(&mut self, mut edited: EditableRequest, item_index: usize) {
+        let pretty = self.config.settings.prettify_json_bodies
+            .then(|| crate::util::prettify_json(&edited.body))
+            .flatten();
+        if let Some(pretty) = pretty {
+            edited.body = pretty;
+        }
         // Update the preview with the edited request
         self.current_request = Some(Request {
             method: edited.method.clone(),
-            url: if edited.url.is_empty() {
-                RequestUrl::Empty
-            } else {
-                RequestUrl::Simple(edited.url.clone())
-            },
-            header: self.current_request.as_ref().map(|r| r.header.clone()).unwrap_or_default(),
+            url: edited_url(&edited),
+            header: edited.headers.clone().or_else(|| self.current_request.as_ref().map(|r| r.header.clone())).unwrap_or_default(),
             auth: self.current_request.as_ref().and_then(|r| r.auth.clone()),
             body: if edited.body.is_empty() {
                 None
             } else {
-                Some(crate::api::RequestBody {
-                    mode: Some("raw".to_string()),
-                    raw: Some(edited.body.clone()),
-                })
+                Some(crate::api::RequestBody::raw(edited.body.clone(), edited.body_language.clone()))
             },
             description: self.current_request.as_ref().and_then(|r| r.description.clone()),
         });
@@ -1897,14 +5911,20 @@ impl App {
         // Persist to local storage
         if let Some(collection_uid) = self.get_current_collection_uid() {
             let path = self.flat_items[item_index].path.clone();
-            self.local_edits.set_edit(
+            self.local_edits.set_timeout(&collection_uid, &path, edited.timeout_secs);
+            self.local_edits.set_body_file(&collection_uid, &path, edited.body_file.clone());
+            self.local_edits.set_http_version(&collection_uid, &path, edited.http_version);
+            self.local_edits.set_edit(LocalEdit {
                 collection_uid,
                 path,
-                edited.name.clone(),
-                edited.method.clone(),
-                edited.url.clone(),
-                edited.body.clone(),
-            );
+                name: edited.name.clone(),
+                method: edited.method.clone(),
+                url: edited.url.clone(),
+                body: edited.body.clone(),
+                body_language: edited.body_language.clone(),
+                disabled_query: edited.disabled_query.clone(),
+                headers: edited.headers.clone(),
+            });
             if let Err(e) = self.local_edits.save() {
                 log_error("save_local_edit", &e.to_string());
             }
@@ -1934,13 +5954,23 @@ impl App {
 
     /// Get local edit for a request if it exists
     pub fn get_local_edit(&self, path: &[usize]) -> Option<EditableRequest> {
-        let collection_uid = self.get_current_collection_uid()?;
-        let edit = self.local_edits.get_edit(&collection_uid, path)?;
+        self.local_edit_in(&self.get_current_collection_uid()?, path)
+    }
+
+    /// Local edit for a request in any collection.
+    fn local_edit_in(&self, collection_uid: &str, path: &[usize]) -> Option<EditableRequest> {
+        let edit = self.local_edits.get_edit(collection_uid, path)?;
         Some(EditableRequest {
             name: edit.name.clone(),
             method: edit.method.clone(),
             url: edit.url.clone(),
             body: edit.body.clone(),
+            timeout_secs: self.local_edits.get_timeout(collection_uid, path),
+            body_file: self.local_edits.get_body_file(collection_uid, path).map(String::from),
+            body_language: edit.body_language.clone(),
+            http_version: self.local_edits.get_http_version(collection_uid, path),
+            disabled_query: edit.disabled_query.clone(),
+            headers: edit.headers.clone(),
         })
     }
 
@@ -1964,8 +5994,80 @@ impl App {
         }
     }
 
+    /// Queue every local edit for the current collection to be saved to
+    /// Postman in one go. The main loop performs the (cancellable) save.
+    pub fn start_save_all(&mut self) {
+        let collection_uid = match self.get_current_collection_uid() {
+            Some(uid) => uid,
+            None => {
+                self.status_message = String::from("No collection loaded");
+                return;
+            }
+        };
+        let edits: Vec<LocalEdit> = self
+            .local_edits
+            .edits
+            .iter()
+            .filter(|e| e.collection_uid == collection_uid)
+            .cloned()
+            .collect();
+        if edits.is_empty() {
+            self.status_message = String::from("No local edits to save");
+            return;
+        }
+
+        self.status_message = format!("Saving {} edits to Postman...", edits.len());
+        self.pending_save_all = Some(edits);
+        self.input_mode = InputMode::Saving;
+    }
+
+    /// Drop the saved edits from the local store, keep the failed ones for a
+    /// retry, and report a summary like "5 saved, 1 failed".
+    pub fn apply_save_all_report(&mut self, report: SaveAllReport) {
+        self.input_mode = InputMode::Normal;
+
+        for path in &report.saved {
+            self.clear_local_edit(path);
+        }
+        if let Some((_, item_index)) = &self.unsaved_edit {
+            let saved = self
+                .flat_items
+                .get(*item_index)
+                .is_some_and(|item| report.saved.contains(&item.path));
+            if saved {
+                self.unsaved_edit = None;
+            }
+        }
+
+        if let Some(updated) = report.updated {
+            if let Some(uid) = self.current_collection_uid.clone() {
+                self.collection_cache.insert(uid, updated.clone());
+            }
+            self.current_collection = Some(updated);
+            self.flatten_items();
+            if !self.flat_items.is_empty() && self.selected_item_index >= self.flat_items.len() {
+                self.selected_item_index = self.flat_items.len() - 1;
+            }
+            self.update_preview_from_selection();
+        }
+
+        for (name, err) in &report.failed {
+            log_error("save_all", &format!("{}: {}", name, err));
+        }
+        if let Some((name, err)) = report.failed.first() {
+            self.error = Some(format!("Failed to save '{}': {}", name, err));
+        }
+
+        self.status_message = if report.failed.is_empty() {
+            format!("{} saved", report.saved.len())
+        } else {
+            format!("{} saved, {} failed", report.saved.len(), report.failed.len())
+        };
+    }
+
     pub fn cancel_saving(&mut self) {
         self.pending_save = None;
+        self.pending_save_all = None;
         self.input_mode = InputMode::Normal;
         self.status_message = String::from("Save cancelled");
     }
@@ -1979,10 +6081,20 @@ impl App {
         let editable = self.get_local_edit(&item.path).unwrap_or_else(|| EditableRequest {
             name: item.name.clone(),
             method: request.method.clone(),
-            url: request.url.to_string(),
+            url: request.url.send_url(),
             body: request.body.as_ref()
                 .and_then(|b| b.raw.clone())
                 .unwrap_or_default(),
+            timeout_secs: self
+                .get_current_collection_uid()
+                .and_then(|uid| self.local_edits.get_timeout(&uid, &item.path)),
+            body_file: self.body_file(&item.path),
+            body_language: request.body.as_ref().and_then(|b| b.language()).map(String::from),
+            http_version: self
+                .get_current_collection_uid()
+                .and_then(|uid| self.local_edits.get_http_version(&uid, &item.path)),
+            disabled_query: request.url.disabled_query().cloned().collect(),
+            headers: None,
         });
 
         Some((editable, self.selected_item_index))
@@ -2009,7 +6121,8 @@ impl App {
                 self.selected_favorite_index =
                     self.selected_favorite_index.saturating_sub(Self::JUMP_STEP);
             }
-            FocusedPane::Preview | FocusedPane::Response => {}
+            FocusedPane::Preview => self.preview_scroll_up(Self::JUMP_STEP as u16),
+            FocusedPane::Response => {}
         }
     }
 
@@ -2038,7 +6151,82 @@ impl App {
                         (self.selected_favorite_index + Self::JUMP_STEP).min(count - 1);
                 }
             }
-            FocusedPane::Preview | FocusedPane::Response => {}
+            FocusedPane::Preview => self.preview_scroll_down(Self::JUMP_STEP as u16),
+            FocusedPane::Response => {}
+        }
+    }
+
+    /// Jump to the first item of the focused list (`g`).
+    pub fn jump_to_first(&mut self) {
+        match self.focused_pane {
+            FocusedPane::Collections => {
+                self.selected_collection_index = 0;
+            }
+            FocusedPane::Requests => {
+                if !self.flat_items.is_empty() {
+                    self.selected_item_index = 0;
+                    self.update_preview_from_selection();
+                    self.save_last_state();
+                }
+            }
+            FocusedPane::Favorites => {
+                self.selected_favorite_index = 0;
+            }
+            FocusedPane::Preview => self.preview_scroll = 0,
+            FocusedPane::Response => {}
+        }
+    }
+
+    /// Jump to the last item of the focused list (`G`).
+    pub fn jump_to_last(&mut self) {
+        match self.focused_pane {
+            FocusedPane::Collections => {
+                self.selected_collection_index = self.flat_collections.len().saturating_sub(1);
+            }
+            FocusedPane::Requests => {
+                if !self.flat_items.is_empty() {
+                    self.selected_item_index = self.flat_items.len() - 1;
+                    self.update_preview_from_selection();
+                    self.save_last_state();
+                }
+            }
+            FocusedPane::Favorites => {
+                self.selected_favorite_index = self.config.favorite_requests.len().saturating_sub(1);
+            }
+            FocusedPane::Preview => self.preview_scroll = self.preview_max_scroll,
+            FocusedPane::Response => {}
+        }
+    }
+
+    /// Jump to the folder containing the selected item (`p`).
+    pub fn jump_to_parent(&mut self) {
+        match self.focused_pane {
+            FocusedPane::Collections => {
+                // The only nesting here is the Favorites section header.
+                let depth = match self.flat_collections.get(self.selected_collection_index) {
+                    Some(c) if c.depth > 0 => c.depth,
+                    _ => return,
+                };
+                if let Some(idx) = self.flat_collections[..self.selected_collection_index]
+                    .iter()
+                    .rposition(|c| c.depth < depth)
+                {
+                    self.selected_collection_index = idx;
+                }
+            }
+            FocusedPane::Requests => {
+                let path = match self.flat_items.get(self.selected_item_index) {
+                    Some(item) if item.path.len() > 1 => item.path.clone(),
+                    _ => return,
+                };
+                let parent = &path[..path.len() - 1];
+                if let Some(idx) = self.flat_items.iter().position(|item| item.path == parent) {
+                    self.selected_item_index = idx;
+                    self.update_preview_from_selection();
+                    self.save_last_state();
+                }
+            }
+            FocusedPane::Favorites | FocusedPane::Preview | FocusedPane::Response => {}
         }
     }
 
@@ -2061,7 +6249,8 @@ impl App {
                     self.selected_favorite_index -= 1;
                 }
             }
-            FocusedPane::Preview | FocusedPane::Response => {}
+            FocusedPane::Preview => self.preview_scroll_up(1),
+            FocusedPane::Response => {}
         }
     }
 
@@ -2089,8 +6278,89 @@ impl App {
                     self.selected_favorite_index += 1;
                 }
             }
-            FocusedPane::Preview | FocusedPane::Response => {}
+            FocusedPane::Preview => self.preview_scroll_down(1),
+            FocusedPane::Response => {}
+        }
+    }
+
+    /// Scroll the request preview down, stopping once its last line is
+    /// on screen.
+    pub fn preview_scroll_down(&mut self, lines: u16) {
+        self.preview_scroll = self.preview_scroll.saturating_add(lines).min(self.preview_max_scroll);
+    }
+
+    pub fn preview_scroll_up(&mut self, lines: u16) {
+        self.preview_scroll = self.preview_scroll.saturating_sub(lines);
+    }
+
+    /// The first visible row and the number of labelled rows for quick jump
+    /// in the focused list, or None outside the Collections/Requests panes.
+    fn quick_jump_window(&self) -> Option<(usize, usize)> {
+        let (offset, rows, len) = match self.focused_pane {
+            FocusedPane::Collections => (
+                self.collections_scroll,
+                self.collections_view_rows,
+                self.flat_collections.len(),
+            ),
+            FocusedPane::Requests => (
+                self.requests_scroll,
+                self.requests_view_rows,
+                self.flat_items.len(),
+            ),
+            _ => return None,
+        };
+        let count = rows.min(QUICK_JUMP_KEYS.len()).min(len.saturating_sub(offset));
+        Some((offset, count))
+    }
+
+    /// Label every visible row of the focused list with a key to jump to it.
+    pub fn start_quick_jump(&mut self) {
+        match self.quick_jump_window() {
+            Some((_, count)) if count > 0 => {
+                self.input_mode = InputMode::QuickJump;
+                self.status_message = String::from("Jump: press a row's label (Esc to cancel)");
+            }
+            Some(_) => self.status_message = String::from("Nothing to jump to"),
+            None => self.status_message = String::from("Quick jump works in the Collections and Requests panes"),
+        }
+    }
+
+    /// The quick-jump label for row `index` of `pane`, while labels are shown.
+    pub fn quick_jump_label(&self, pane: FocusedPane, index: usize) -> Option<char> {
+        if self.input_mode != InputMode::QuickJump || self.focused_pane != pane {
+            return None;
+        }
+        let (offset, count) = self.quick_jump_window()?;
+        index
+            .checked_sub(offset)
+            .filter(|&row| row < count)
+            .and_then(|row| QUICK_JUMP_KEYS.chars().nth(row))
+    }
+
+    /// Select the row labelled `key`; any other key just cancels.
+    pub fn finish_quick_jump(&mut self, key: char) {
+        self.input_mode = InputMode::Normal;
+        let target = self.quick_jump_window().and_then(|(offset, count)| {
+            QUICK_JUMP_KEYS
+                .chars()
+                .position(|c| c == key)
+                .filter(|&row| row < count)
+                .map(|row| offset + row)
+        });
+        let Some(index) = target else {
+            self.update_status_for_pane();
+            return;
+        };
+        match self.focused_pane {
+            FocusedPane::Collections => self.selected_collection_index = index,
+            FocusedPane::Requests => {
+                self.selected_item_index = index;
+                self.update_preview_from_selection();
+                self.save_last_state();
+            }
+            _ => {}
         }
+        self.update_status_for_pane();
     }
 
     pub fn update_preview_from_selection(&mut self) {
@@ -2099,11 +6369,28 @@ impl App {
                 let request = request.clone();
                 let item_path = item.path.clone();
                 self.set_current_request(request, &item_path);
-                self.response = None;
+                if !self.response_pinned {
+                    self.response = None;
+                }
             }
         }
     }
 
+    /// Pin the response so browsing other requests doesn't clear it, or
+    /// unpin it again.
+    pub fn toggle_response_pin(&mut self) {
+        if !self.response_pinned && self.response.is_none() {
+            self.status_message = String::from("No response to pin");
+            return;
+        }
+        self.response_pinned = !self.response_pinned;
+        self.status_message = if self.response_pinned {
+            String::from("Response pinned - it stays while you browse (P to unpin)")
+        } else {
+            String::from("Response unpinned")
+        };
+    }
+
     pub fn start_new_request_dialog(&mut self) {
         if self.current_collection.is_none() {
             return;
@@ -2140,77 +6427,251 @@ impl App {
         }
     }
 
+    // Editing helpers for the new request dialog and the inline URL editor
+    // The cursor is a byte offset, so it steps over whole characters
     pub fn dialog_input_char(&mut self, c: char) {
+        if let Some((input, cursor)) = self.active_text_input_mut() {
+            input.insert(*cursor, c);
+            *cursor += c.len_utf8();
+        }
+    }
+
+    pub fn dialog_backspace(&mut self) {
+        if let Some((input, cursor)) = self.active_text_input_mut()
+            && let Some((index, _)) = input[..*cursor].char_indices().next_back()
+        {
+            input.remove(index);
+            *cursor = index;
+        }
+    }
+
+    pub fn dialog_move_cursor_left(&mut self) {
+        if let Some((input, cursor)) = self.active_text_input_mut()
+            && let Some((index, _)) = input[..*cursor].char_indices().next_back()
+        {
+            *cursor = index;
+        }
+    }
+
+    pub fn dialog_move_cursor_right(&mut self) {
+        if let Some((input, cursor)) = self.active_text_input_mut()
+            && let Some(c) = input[*cursor..].chars().next()
+        {
+            *cursor += c.len_utf8();
+        }
+    }
+
+    pub fn dialog_next_step(&mut self) -> bool {
         if let Some(dialog) = &mut self.new_request_dialog {
-            let input = match dialog.step {
-                DialogStep::Name => &mut dialog.name,
-                DialogStep::Url => &mut dialog.url,
-            };
-            input.insert(dialog.cursor_position, c);
-            dialog.cursor_position += 1;
+            match dialog.step {
+                DialogStep::Name => {
+                    if dialog.name.trim().is_empty() {
+                        self.status_message = String::from("Name cannot be empty");
+                        return false;
+                    }
+                    dialog.step = DialogStep::Url;
+                    dialog.cursor_position = 0;
+                    self.status_message = String::from("Enter request URL (or leave empty)");
+                    false
+                }
+                DialogStep::Url => {
+                    // Ready to create request
+                    true
+                }
+            }
+        } else {
+            false
+        }
+    }
+
+    pub fn cancel_dialog(&mut self) {
+        self.new_request_dialog = None;
+        self.input_mode = InputMode::Normal;
+        self.status_message = String::from("Use j/k to navigate, Enter to select, e to execute, a to add request");
+    }
+
+    /// Whether the flat item at `index` is an entry in the Favorites section
+    /// (or its header) at the top of the Requests pane, rather than the tree.
+    fn in_favorites_section(&self, index: usize) -> bool {
+        match self.flat_items.first() {
+            Some(first) if first.path == [usize::MAX] => {
+                let tree_start = self.flat_items[1..]
+                    .iter()
+                    .position(|item| item.depth == 0)
+                    .map(|i| i + 1)
+                    .unwrap_or(self.flat_items.len());
+                index < tree_start
+            }
+            _ => false,
+        }
+    }
+
+    /// Move the selected request or folder up (`delta < 0`) or down among its
+    /// siblings. The new order is pushed to Postman from the main loop (see
+    /// `apply_move`), so Esc can cancel it until it is sent.
+    pub fn move_selected_item(&mut self, delta: isize) {
+        if self.flat_items.is_empty() || self.in_favorites_section(self.selected_item_index) {
+            return;
+        }
+        // A sorted list doesn't show the stored order J/K would change
+        if self.request_sort() != RequestSort::Original {
+            self.status_message = String::from("Switch the sort back to original (s) to reorder");
+            return;
+        }
+        let item = &self.flat_items[self.selected_item_index];
+        let (path, name, request_id) = (item.path.clone(), item.name.clone(), item.request_id.clone());
+        let Some((&index, parent)) = path.split_last() else {
+            return;
+        };
+
+        let (collection, collection_uid) = match (&self.current_collection, self.get_current_collection_uid()) {
+            (Some(c), Some(uid)) => (c.clone(), uid),
+            _ => return,
+        };
+
+        let mut items = collection.item.clone();
+        let siblings = match folder_items_mut(&mut items, parent) {
+            Some(siblings) => siblings,
+            None => return,
+        };
+        let target = match index.checked_add_signed(delta) {
+            Some(t) if t < siblings.len() => t,
+            _ => return,
+        };
+        siblings.swap(index, target);
+
+        self.loading = true;
+        self.status_message = String::from("Reordering...");
+        self.pending_move = Some(PendingMove { collection_uid, collection, items, path, name, request_id, target, delta });
+    }
+
+    /// Finish a move once Postman has the new order (`updated` is its copy
+    /// of the collection, when it could be fetched back): remap stored paths
+    /// (favorites, local edits, timeouts, param values) to follow the move.
+    pub fn apply_move(&mut self, pending: PendingMove, updated: Result<Option<CollectionDetail>>) {
+        self.loading = false;
+        let PendingMove { collection_uid, collection, items, path, name, request_id, target, delta } = pending;
+        let updated = match updated {
+            Ok(updated) => updated,
+            Err(e) => {
+                match e.downcast::<CollectionChanged>() {
+                    Ok(changed) => {
+                        let change = BulkChange::Move { path, name, request_id, delta };
+                        self.open_collection_conflict(change, changed.server);
+                    }
+                    Err(e) => {
+                        let error_msg = format!("{:#}", e);
+                        log_error("move_item", &error_msg);
+                        self.error = Some(error_msg);
+                        self.status_message = String::from("Failed to reorder");
+                    }
+                }
+                return;
+            }
+        };
+        let Some((&index, parent)) = path.split_last() else {
+            return;
+        };
+
+        self.remap_paths_after_swap(&collection_uid, parent, index, target);
+
+        // Prefer the server's copy, but the local one is already correct.
+        let updated = updated.unwrap_or(CollectionDetail { item: items, ..collection });
+        self.collection_cache.insert(collection_uid, updated.clone());
+        self.current_collection = Some(updated);
+        self.flatten_items();
+
+        let mut moved_path = parent.to_vec();
+        moved_path.push(target);
+        // Select the moved item in the tree, not its Favorites-section copy.
+        if let Some(pos) = self
+            .flat_items
+            .iter()
+            .enumerate()
+            .position(|(i, item)| item.path == moved_path && !self.in_favorites_section(i))
+        {
+            self.selected_item_index = pos;
+        }
+        self.update_preview_from_selection();
+        self.save_last_state();
+        self.status_message = format!("Moved '{}' {}", name, if delta < 0 { "up" } else { "down" });
+    }
+
+    pub fn cancel_move(&mut self) {
+        self.pending_move = None;
+        self.loading = false;
+        self.status_message = String::from("Reorder cancelled");
+    }
+
+    /// Rewrite every stored request path under `parent` after siblings `a`
+    /// and `b` swapped places.
+    fn remap_paths_after_swap(&mut self, collection_uid: &str, parent: &[usize], a: usize, b: usize) {
+        for fav in &mut self.config.favorite_requests {
+            if fav.collection_uid == collection_uid {
+                remap_swapped_path(&mut fav.path, parent, a, b);
+            }
+        }
+        for edit in &mut self.local_edits.edits {
+            if edit.collection_uid == collection_uid {
+                remap_swapped_path(&mut edit.path, parent, a, b);
+            }
+        }
+        for timeout in &mut self.local_edits.timeouts {
+            if timeout.collection_uid == collection_uid {
+                remap_swapped_path(&mut timeout.path, parent, a, b);
+            }
         }
-    }
-
-    pub fn dialog_backspace(&mut self) {
-        if let Some(dialog) = &mut self.new_request_dialog {
-            let input = match dialog.step {
-                DialogStep::Name => &mut dialog.name,
-                DialogStep::Url => &mut dialog.url,
-            };
-            if dialog.cursor_position > 0 {
-                dialog.cursor_position -= 1;
-                input.remove(dialog.cursor_position);
+        for body_file in &mut self.local_edits.body_files {
+            if body_file.collection_uid == collection_uid {
+                remap_swapped_path(&mut body_file.path, parent, a, b);
             }
         }
-    }
-
-    pub fn dialog_move_cursor_left(&mut self) {
-        if let Some(dialog) = &mut self.new_request_dialog {
-            if dialog.cursor_position > 0 {
-                dialog.cursor_position -= 1;
+        for version in &mut self.local_edits.http_versions {
+            if version.collection_uid == collection_uid {
+                remap_swapped_path(&mut version.path, parent, a, b);
             }
         }
-    }
-
-    pub fn dialog_move_cursor_right(&mut self) {
-        if let Some(dialog) = &mut self.new_request_dialog {
-            let len = match dialog.step {
-                DialogStep::Name => dialog.name.len(),
-                DialogStep::Url => dialog.url.len(),
-            };
-            if dialog.cursor_position < len {
-                dialog.cursor_position += 1;
+        for note in &mut self.local_edits.notes {
+            if note.collection_uid == collection_uid {
+                remap_swapped_path(&mut note.path, parent, a, b);
             }
         }
-    }
-
-    pub fn dialog_next_step(&mut self) -> bool {
-        if let Some(dialog) = &mut self.new_request_dialog {
-            match dialog.step {
-                DialogStep::Name => {
-                    if dialog.name.trim().is_empty() {
-                        self.status_message = String::from("Name cannot be empty");
-                        return false;
-                    }
-                    dialog.step = DialogStep::Url;
-                    dialog.cursor_position = 0;
-                    self.status_message = String::from("Enter request URL (or leave empty)");
-                    false
-                }
-                DialogStep::Url => {
-                    // Ready to create request
-                    true
-                }
+        for entry in &mut self.param_values.entries {
+            if entry.collection_uid == collection_uid {
+                remap_swapped_path(&mut entry.path, parent, a, b);
             }
-        } else {
-            false
         }
-    }
+        for entry in &mut self.baselines.entries {
+            if entry.collection_uid == collection_uid {
+                remap_swapped_path(&mut entry.path, parent, a, b);
+            }
+        }
+        self.expanded_folders = self
+            .expanded_folders
+            .drain()
+            .map(|mut path| {
+                remap_swapped_path(&mut path, parent, a, b);
+                path
+            })
+            .collect();
+        if let Some(path) = &mut self.current_request_path {
+            remap_swapped_path(path, parent, a, b);
+        }
+        // Its flat index is stale after the move; reselecting reloads it.
+        self.unsaved_edit = None;
 
-    pub fn cancel_dialog(&mut self) {
-        self.new_request_dialog = None;
-        self.input_mode = InputMode::Normal;
-        self.status_message = String::from("Use j/k to navigate, Enter to select, e to execute, a to add request");
+        if let Err(e) = self.config.save() {
+            log_error("move_item:config", &e.to_string());
+        }
+        if let Err(e) = self.local_edits.save() {
+            log_error("move_item:local_edits", &e.to_string());
+        }
+        if let Err(e) = self.param_values.save() {
+            log_error("move_item:param_values", &e.to_string());
+        }
+        if let Err(e) = self.baselines.save() {
+            log_error("move_item:baselines", &e.to_string());
+        }
     }
 
     pub async fn create_new_request(&mut self) -> Result<()> {
@@ -2250,7 +6711,7 @@ impl App {
                 url: if dialog.url.is_empty() {
                     RequestUrl::Empty
                 } else {
-                    RequestUrl::Simple(dialog.url)
+                    RequestUrl::Simple(dialog.url.clone())
                 },
                 header: vec![],
                 auth: None,
@@ -2258,14 +6719,16 @@ impl App {
                 description: None,
             },
             response: vec![],
+            event: vec![],
+            protocol_profile_behavior: None,
         });
 
         // Clone and modify the collection's items
         let mut items = collection.item.clone();
         insert_item_at_path(&mut items, &dialog.target_folder_path, new_request);
 
-        // Update the collection via API
-        match self.client.update_collection(&collection_uid, &collection.info, &items).await {
+        // Update the collection via API, unless it changed in Postman meanwhile
+        match self.client.update_collection_checked(&collection_uid, &collection.info, &collection.item, &collection.variable, &items, &collection.variable).await {
             Ok(()) => {
                 // Reload collection to get updated state
                 match self.client.get_collection(&collection_uid).await {
@@ -2286,16 +6749,350 @@ impl App {
             }
             Err(e) => {
                 self.loading = false;
-                let error_msg = e.to_string();
-                log_error("create_new_request:save", &error_msg);
+                match e.downcast::<CollectionChanged>() {
+                    Ok(changed) => {
+                        let change = BulkChange::NewRequest {
+                            name: dialog.name,
+                            url: dialog.url,
+                            folder_path: dialog.target_folder_path,
+                        };
+                        self.open_collection_conflict(change, changed.server);
+                    }
+                    Err(e) => {
+                        let error_msg = e.to_string();
+                        log_error("create_new_request:save", &error_msg);
+                        self.error = Some(error_msg);
+                        self.status_message = String::from("Failed to create request");
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Pick another collection to copy or move the selected request into.
+    pub fn start_transfer(&mut self) {
+        let item = match self.flat_items.get(self.selected_item_index) {
+            Some(item) if item.request.is_some() && !self.in_favorites_section(self.selected_item_index) => item,
+            _ => {
+                self.status_message = String::from("Select a request to copy or move it");
+                return;
+            }
+        };
+        let current_uid = self.get_current_collection_uid();
+        let targets: Vec<CollectionInfo> = self
+            .collections
+            .iter()
+            .filter(|c| Some(&c.uid) != current_uid.as_ref())
+            .cloned()
+            .collect();
+        if targets.is_empty() {
+            self.status_message = String::from("No other collection to copy to");
+            return;
+        }
+        self.transfer_dialog = Some(TransferDialog {
+            path: item.path.clone(),
+            name: item.name.clone(),
+            targets,
+            selected: 0,
+        });
+        self.input_mode = InputMode::TransferSelect;
+        self.status_message = String::from("c/Enter: Copy | m: Move | Esc: Cancel");
+    }
+
+    pub fn transfer_select(&mut self, delta: isize) {
+        if let Some(dialog) = &mut self.transfer_dialog {
+            dialog.selected = dialog
+                .selected
+                .saturating_add_signed(delta)
+                .min(dialog.targets.len().saturating_sub(1));
+        }
+    }
+
+    pub fn cancel_transfer(&mut self) {
+        self.transfer_dialog = None;
+        self.input_mode = InputMode::Normal;
+        self.status_message = String::from("Copy cancelled");
+    }
+
+    /// Add a copy of the request (local edit applied) to the end of the
+    /// chosen collection; with `remove_source`, then take it out of the
+    /// loaded one. Pushed to Postman from the main loop (see
+    /// `apply_transfer`), so Esc can cancel it until it is sent.
+    pub fn confirm_transfer(&mut self, remove_source: bool) {
+        self.input_mode = InputMode::Normal;
+        let Some(dialog) = self.transfer_dialog.take() else {
+            return;
+        };
+        let (Some(collection), Some(collection_uid)) = (self.current_collection.clone(), self.get_current_collection_uid()) else {
+            return;
+        };
+        let Some(source) = request_item_at_path(&collection.item, &dialog.path) else {
+            self.status_message = format!("'{}' is no longer in the collection", dialog.name);
+            return;
+        };
+        let Some(target) = dialog.targets.get(dialog.selected).cloned() else {
+            return;
+        };
+
+        // Postman gives the copy its own id
+        let mut copy = source.clone();
+        copy.id = None;
+        if let Some(edit) = self.local_edit_in(&collection_uid, &dialog.path) {
+            copy.request = apply_local_edit(&copy.request, &edit);
+            copy.name = edit.name;
+        }
+
+        self.loading = true;
+        self.status_message = format!("Copying '{}' to {}...", dialog.name, target.name);
+        self.pending_transfer = Some(PendingTransfer {
+            collection_uid,
+            collection,
+            path: dialog.path,
+            name: dialog.name,
+            copy,
+            target,
+            remove_source,
+        });
+    }
+
+    /// Finish a copy or move once Postman has it. A moved request's
+    /// favorite, note, timeout, body file, HTTP version, param values and
+    /// baseline follow it to the target collection.
+    pub fn apply_transfer(&mut self, pending: PendingTransfer, outcome: TransferOutcome) {
+        self.loading = false;
+        let PendingTransfer { collection_uid, collection, path, name, target, .. } = pending;
+        let copied_index = match outcome.copied {
+            Ok(index) => index,
+            Err(e) => {
+                let error_msg = format!("{:#}", e);
+                log_error("transfer_request:copy", &error_msg);
+                self.error = Some(error_msg);
+                self.status_message = format!("Failed to copy '{}' to {}", name, target.name);
+                return;
+            }
+        };
+        // The cached copy of the target is stale now; it's fetched on next load
+        self.collection_cache.remove(&target.uid);
+
+        let Some(removed) = outcome.removed else {
+            self.status_message = format!("Copied '{}' to {}", name, target.name);
+            return;
+        };
+        match removed {
+            Ok(items) => {
+                self.rehome_moved_request(&collection_uid, &path, &target.uid, vec![copied_index]);
+                self.forget_removed_item(&collection_uid, &path);
+                let updated = CollectionDetail { item: items, ..collection };
+                self.collection_cache.insert(collection_uid, updated.clone());
+                self.current_collection = Some(updated);
+                self.flatten_items();
+                if self.selected_item_index >= self.flat_items.len() {
+                    self.selected_item_index = self.flat_items.len().saturating_sub(1);
+                }
+                self.update_preview_from_selection();
+                self.save_last_state();
+                self.status_message = format!("Moved '{}' to {}", name, target.name);
+            }
+            Err(e) => {
+                let error_msg = match e.downcast::<CollectionChanged>() {
+                    Ok(changed) => {
+                        self.adopt_server_collection(changed.server);
+                        String::from("Collection changed in Postman since it was loaded, so the original wasn't removed. Reloaded the newer version.")
+                    }
+                    Err(e) => format!("{:#}", e),
+                };
+                log_error("transfer_request:remove", &error_msg);
                 self.error = Some(error_msg);
-                self.status_message = String::from("Failed to create request");
+                self.status_message = format!("Copied '{}' to {}, but the original is still here", name, target.name);
+            }
+        }
+    }
+
+    pub fn abort_transfer(&mut self, remove_source: bool) {
+        self.pending_transfer = None;
+        self.loading = false;
+        self.status_message = String::from(if remove_source { "Move cancelled" } else { "Copy cancelled" });
+    }
+
+    /// Re-key what's stored for the request moved from `from` to `to` in the
+    /// `target_uid` collection, so `forget_removed_item` leaves it alone.
+    fn rehome_moved_request(&mut self, collection_uid: &str, from: &[usize], target_uid: &str, to: Vec<usize>) {
+        let moved = |uid: &str, path: &[usize]| uid == collection_uid && path == from;
+        for fav in &mut self.config.favorite_requests {
+            if moved(&fav.collection_uid, &fav.path) {
+                fav.collection_uid = target_uid.to_string();
+                fav.path = to.clone();
+                // The copy has a new Postman id, picked up from its path on load
+                fav.request_id = None;
+            }
+        }
+        for note in &mut self.local_edits.notes {
+            if moved(&note.collection_uid, &note.path) {
+                note.collection_uid = target_uid.to_string();
+                note.path = to.clone();
+            }
+        }
+        for timeout in &mut self.local_edits.timeouts {
+            if moved(&timeout.collection_uid, &timeout.path) {
+                timeout.collection_uid = target_uid.to_string();
+                timeout.path = to.clone();
+            }
+        }
+        for body_file in &mut self.local_edits.body_files {
+            if moved(&body_file.collection_uid, &body_file.path) {
+                body_file.collection_uid = target_uid.to_string();
+                body_file.path = to.clone();
+            }
+        }
+        for version in &mut self.local_edits.http_versions {
+            if moved(&version.collection_uid, &version.path) {
+                version.collection_uid = target_uid.to_string();
+                version.path = to.clone();
+            }
+        }
+        for entry in &mut self.param_values.entries {
+            if moved(&entry.collection_uid, &entry.path) {
+                entry.collection_uid = target_uid.to_string();
+                entry.path = to.clone();
+            }
+        }
+        for entry in &mut self.baselines.entries {
+            if moved(&entry.collection_uid, &entry.path) {
+                entry.collection_uid = target_uid.to_string();
+                entry.path = to.clone();
             }
         }
+    }
+
+    /// Drop everything stored for the item removed from `removed` (and
+    /// beneath it), and shift the paths of its later siblings to follow.
+    fn forget_removed_item(&mut self, collection_uid: &str, removed: &[usize]) {
+        let remap = |uid: &str, path: &mut Vec<usize>| uid != collection_uid || remap_removed_path(path, removed);
+        self.config.favorite_requests.retain_mut(|f| remap(&f.collection_uid, &mut f.path));
+        self.local_edits.edits.retain_mut(|e| remap(&e.collection_uid, &mut e.path));
+        self.local_edits.timeouts.retain_mut(|t| remap(&t.collection_uid, &mut t.path));
+        self.local_edits.body_files.retain_mut(|b| remap(&b.collection_uid, &mut b.path));
+        self.local_edits.http_versions.retain_mut(|v| remap(&v.collection_uid, &mut v.path));
+        self.local_edits.notes.retain_mut(|n| remap(&n.collection_uid, &mut n.path));
+        self.param_values.entries.retain_mut(|e| remap(&e.collection_uid, &mut e.path));
+        self.baselines.entries.retain_mut(|e| remap(&e.collection_uid, &mut e.path));
+        self.expanded_folders = self
+            .expanded_folders
+            .drain()
+            .filter_map(|mut path| remap_removed_path(&mut path, removed).then_some(path))
+            .collect();
+        if self.current_request_path.as_deref().is_some_and(|path| path.starts_with(removed)) {
+            self.current_request_path = None;
+            self.current_request = None;
+        }
+        self.unsaved_edit = None;
+
+        if let Err(e) = self.config.save() {
+            log_error("remove_item:config", &e.to_string());
+        }
+        if let Err(e) = self.local_edits.save() {
+            log_error("remove_item:local_edits", &e.to_string());
+        }
+        if let Err(e) = self.param_values.save() {
+            log_error("remove_item:param_values", &e.to_string());
+        }
+        if let Err(e) = self.baselines.save() {
+            log_error("remove_item:baselines", &e.to_string());
+        }
+    }
+
+    /// Hold back `change` because Postman has a newer version of the
+    /// collection, and show what changed there.
+    fn open_collection_conflict(&mut self, change: BulkChange, server: CollectionDetail) {
+        let summary = match &self.current_collection {
+            Some(collection) => collection_tree_changes(&collection.item, &server.item),
+            None => Vec::new(),
+        };
+        self.collection_conflict = Some(CollectionConflict { change, server, summary });
+        self.input_mode = InputMode::CollectionConflict;
+        self.status_message = String::from("Collection changed in Postman - nothing was overwritten");
+    }
+
+    /// Make the server's version of the collection the loaded one.
+    fn adopt_server_collection(&mut self, server: CollectionDetail) {
+        if let Some(uid) = self.get_current_collection_uid() {
+            self.collection_cache.insert(uid, server.clone());
+        }
+        self.current_collection = Some(server);
+        self.reconcile_request_paths();
+        self.rebuild_variables();
+        self.flatten_items();
+        if self.selected_item_index >= self.flat_items.len() {
+            self.selected_item_index = self.flat_items.len().saturating_sub(1);
+        }
+    }
 
+    /// Load Postman's newer version and apply the held-back change to it.
+    pub async fn reapply_after_conflict(&mut self) -> Result<()> {
+        let Some(conflict) = self.collection_conflict.take() else {
+            self.input_mode = InputMode::Normal;
+            return Ok(());
+        };
+        self.input_mode = InputMode::Normal;
+        self.adopt_server_collection(conflict.server);
+        match conflict.change {
+            BulkChange::NewRequest { name, url, folder_path } => {
+                self.new_request_dialog = Some(NewRequestDialog {
+                    step: DialogStep::Url,
+                    name,
+                    url,
+                    cursor_position: 0,
+                    target_folder_path: folder_path,
+                });
+                self.create_new_request().await?;
+            }
+            BulkChange::Move { path, name, request_id, delta } => {
+                // Find the item again: by id when it has one, else only if
+                // the same name is still at the old path.
+                let items = self.current_collection.as_ref().map(|c| c.item.clone()).unwrap_or_default();
+                let path = match &request_id {
+                    Some(id) => find_request_path_by_id(&items, id),
+                    None => get_item_at_path(&items, &path).filter(|(n, _, _)| *n == name).map(|_| path),
+                };
+                let Some(path) = path else {
+                    self.status_message = format!("'{}' is no longer in the collection", name);
+                    return Ok(());
+                };
+                self.expand_to_path(&path);
+                let index = (0..self.flat_items.len())
+                    .find(|&i| self.flat_items[i].path == path && !self.in_favorites_section(i));
+                if let Some(index) = index {
+                    self.selected_item_index = index;
+                    self.move_selected_item(delta);
+                }
+            }
+        }
         Ok(())
     }
 
+    /// A save refused because the collection changed in Postman: load the
+    /// newer version. The local edit stays, ready to be saved onto it.
+    pub fn apply_save_conflict(&mut self, server: CollectionDetail) {
+        self.adopt_server_collection(server);
+        self.update_preview_from_selection();
+        self.error = Some(String::from(
+            "Collection changed in Postman since it was loaded, so it wasn't overwritten. \
+             Reloaded the newer version; your local edit is kept - press S to save it onto it.",
+        ));
+        self.status_message = String::from("Save skipped - collection changed in Postman");
+    }
+
+    /// Keep Postman's newer version and drop the held-back change.
+    pub fn discard_after_conflict(&mut self) {
+        self.input_mode = InputMode::Normal;
+        if let Some(conflict) = self.collection_conflict.take() {
+            self.adopt_server_collection(conflict.server);
+            self.status_message = String::from("Loaded the newer version from Postman; your change was not applied");
+        }
+    }
+
     // Search methods
     pub fn start_search(&mut self) {
         if matches!(self.focused_pane, FocusedPane::Preview | FocusedPane::Favorites) {
@@ -2359,7 +7156,7 @@ impl App {
                     if flat_col.is_favorites_folder {
                         continue;
                     }
-                    if flat_col.name.to_lowercase().contains(&query) {
+                    if crate::util::search_matches(&flat_col.name, &query) {
                         self.search_matches.push(i);
                     }
                 }
@@ -2513,6 +7310,55 @@ impl App {
         }
     }
 
+    /// Start a `z` + digit "collapse to depth" command.
+    pub fn start_json_depth(&mut self) {
+        if self.json_viewer_state.is_some() {
+            self.json_depth_pending = true;
+            self.status_message = String::from("Collapse to depth: press 1-9");
+        }
+    }
+
+    /// Finish a `z` + digit command; any other key just cancels it.
+    pub fn finish_json_depth(&mut self, key: char) {
+        self.json_depth_pending = false;
+        let Some(depth) = key.to_digit(10).filter(|d| *d > 0) else {
+            self.update_status_for_pane();
+            return;
+        };
+        if let Some(ref mut viewer) = self.json_viewer_state {
+            viewer.expand_to_depth(depth as usize);
+            self.status_message = format!("Expanded to depth {}", depth);
+        }
+    }
+
+    /// Cycle the JSON viewer through all/objects/arrays/strings/numbers.
+    pub fn json_viewer_cycle_type_filter(&mut self) {
+        if let Some(ref mut viewer) = self.json_viewer_state {
+            viewer.cycle_type_filter();
+            self.status_message = viewer.type_filter_status();
+        }
+    }
+
+    pub fn json_viewer_toggle_full_string(&mut self) {
+        if let Some(ref mut viewer) = self.json_viewer_state {
+            self.status_message = match viewer.toggle_full_string() {
+                Some(true) => String::from("Showing the full string"),
+                Some(false) => String::from("String truncated"),
+                None => String::from("Select a string value to show it in full"),
+            };
+        }
+    }
+
+    pub fn json_viewer_toggle_full_strings(&mut self) {
+        if let Some(ref mut viewer) = self.json_viewer_state {
+            self.status_message = if viewer.toggle_full_strings() {
+                String::from("Showing all strings in full")
+            } else {
+                String::from("Long strings truncated")
+            };
+        }
+    }
+
     pub fn json_viewer_collapse_all(&mut self) {
         if let Some(ref mut viewer) = self.json_viewer_state {
             viewer.collapse_all();
@@ -2571,55 +7417,365 @@ impl App {
         }
     }
 
+    // Plain-text response view methods
+
+    /// Body text as displayed in the plain-text response view: the reindented
+    /// XML when available, otherwise the raw body.
+    pub fn response_display_body(&self) -> &str {
+        match (&self.xml_pretty, &self.response) {
+            (Some(xml), _) => xml,
+            (None, Some(response)) => &response.body,
+            (None, None) => "",
+        }
+    }
+
+    /// Number of lines (status, headers section, "Body:" label) rendered above
+    /// the body in the plain-text response view.
+    pub fn response_body_offset(&self) -> usize {
+        let header_count = self.response.as_ref().map(|r| r.headers.len()).unwrap_or(0);
+        5 + header_count
+    }
+
+    fn response_line_count(&self) -> usize {
+        self.response_body_offset() + self.response_display_body().lines().count()
+    }
+
+    pub fn response_scroll_down(&mut self, lines: usize) {
+        let max = self.response_line_count().saturating_sub(1);
+        self.response_scroll = (self.response_scroll + lines).min(max);
+    }
+
+    pub fn response_scroll_up(&mut self, lines: usize) {
+        self.response_scroll = self.response_scroll.saturating_sub(lines);
+    }
+
+    pub fn response_search_start(&mut self) {
+        if self.response.is_none() {
+            return;
+        }
+        self.response_search_query.clear();
+        self.response_search_matches.clear();
+        self.response_match_index = 0;
+        self.input_mode = InputMode::ResponseSearch;
+        self.status_message = String::from("Type to search response, Enter to confirm, Esc to cancel");
+    }
+
+    pub fn response_search_input(&mut self, c: char) {
+        self.response_search_query.push(c);
+        self.update_response_search_matches();
+    }
+
+    pub fn response_search_backspace(&mut self) {
+        self.response_search_query.pop();
+        self.update_response_search_matches();
+    }
+
+    fn update_response_search_matches(&mut self) {
+        self.response_search_matches.clear();
+        self.response_match_index = 0;
+
+        if !self.response_search_query.is_empty() {
+            // ASCII lowercasing keeps byte offsets valid for highlighting.
+            let query = self.response_search_query.to_ascii_lowercase();
+            let matches: Vec<(usize, usize)> = self
+                .response_display_body()
+                .lines()
+                .enumerate()
+                .flat_map(|(line_idx, line)| {
+                    line.to_ascii_lowercase()
+                        .match_indices(&query)
+                        .map(|(offset, _)| (line_idx, offset))
+                        .collect::<Vec<_>>()
+                })
+                .collect();
+            self.response_search_matches = matches;
+        }
+
+        self.jump_to_response_match();
+        self.update_response_search_status();
+    }
+
+    /// Scroll so the current match sits near the top of the viewport.
+    fn jump_to_response_match(&mut self) {
+        if let Some(&(line, _)) = self.response_search_matches.get(self.response_match_index) {
+            self.response_scroll = (self.response_body_offset() + line).saturating_sub(2);
+        }
+    }
+
+    fn update_response_search_status(&mut self) {
+        let match_count = self.response_search_matches.len();
+        if match_count == 0 {
+            if self.response_search_query.is_empty() {
+                self.status_message = String::from("Type to search response, Enter to confirm, Esc to cancel");
+            } else {
+                self.status_message = format!("/{} - No matches", self.response_search_query);
+            }
+        } else {
+            self.status_message = format!(
+                "/{} - Match {}/{}",
+                self.response_search_query,
+                self.response_match_index + 1,
+                match_count
+            );
+        }
+    }
+
+    pub fn response_search_confirm(&mut self) {
+        self.input_mode = InputMode::Normal;
+        if self.response_search_matches.is_empty() {
+            self.update_status_for_pane();
+        }
+    }
+
+    pub fn response_search_cancel(&mut self) {
+        self.response_search_query.clear();
+        self.response_search_matches.clear();
+        self.response_match_index = 0;
+        self.input_mode = InputMode::Normal;
+        self.update_status_for_pane();
+    }
+
+    pub fn response_search_next(&mut self) {
+        let match_count = self.response_search_matches.len();
+        if match_count == 0 {
+            return;
+        }
+        self.response_match_index = (self.response_match_index + 1) % match_count;
+        self.jump_to_response_match();
+        self.update_response_search_status();
+    }
+
+    pub fn response_search_prev(&mut self) {
+        let match_count = self.response_search_matches.len();
+        if match_count == 0 {
+            return;
+        }
+        if self.response_match_index == 0 {
+            self.response_match_index = match_count - 1;
+        } else {
+            self.response_match_index -= 1;
+        }
+        self.jump_to_response_match();
+        self.update_response_search_status();
+    }
+
     /// Copy the currently selected JSON value to clipboard
     pub fn json_viewer_yank(&mut self) {
-        let value = match &self.json_viewer_state {
-            Some(viewer) => viewer.get_selected_value(),
-            None => return,
-        };
-
-        let value = match value {
+        let value = match self.json_viewer_state.as_ref().and_then(|v| v.get_selected_value()) {
             Some(v) => v,
             None => return,
         };
 
-        // Try command-line clipboard tools first (more reliable on Linux)
-        if self.copy_to_clipboard_cli(&value) {
+        if self.copy_to_clipboard(&value) {
             let preview = if value.len() > 50 {
                 format!("{}...", &value[..50])
             } else {
                 value.clone()
             };
             self.status_message = format!("Copied: {}", preview);
+        }
+    }
+
+    /// Copy the selected JSON node with everything beneath it as formatted
+    /// JSON; the whole document when the root (or nothing) is selected.
+    pub fn json_viewer_copy_subtree(&mut self) {
+        let Some((json, whole)) = self.json_viewer_state.as_ref().map(|v| v.selected_subtree_json()) else {
+            self.status_message = String::from("No JSON tree to copy from");
+            return;
+        };
+        if self.copy_to_clipboard(&json) {
+            self.status_message = format!(
+                "Copied {} as JSON ({})",
+                if whole { "document" } else { "subtree" },
+                crate::util::format_bytes(json.len())
+            );
+        }
+    }
+
+    /// Copy the whole response body, whether or not it parsed as JSON.
+    pub fn copy_response_body(&mut self) {
+        let body = match &self.response {
+            Some(response) => response.body.clone(),
+            None => return,
+        };
+        if body.is_empty() {
+            self.status_message = String::from("Response body is empty");
+            return;
+        }
+        if self.copy_to_clipboard(&body) {
+            self.status_message = format!("Copied {} to clipboard", crate::util::format_bytes(body.len()));
+        }
+    }
+
+    /// Whether the current input mode is typing into a text field (and so
+    /// accepts pasted text).
+    pub fn text_field_active(&self) -> bool {
+        match self.input_mode {
+            InputMode::TextInput
+            | InputMode::Search
+            | InputMode::JsonSearch
+            | InputMode::ResponseSearch
+            | InputMode::CommandPalette
+            | InputMode::GlobalFinder
+            | InputMode::ExtraHeaders
+            | InputMode::ApiKeyInput
+            | InputMode::UrlEdit
+            | InputMode::OpenApiImport
+            | InputMode::NoteEdit
+            | InputMode::StreamMessage => true,
+            InputMode::VariablesView => self.editing_variable.is_some() || self.variables_search_active,
+            InputMode::RowsEditor => self.rows_editor.as_ref().is_some_and(|e| e.editing.is_some()),
+            InputMode::ParamsInput => self.params_dialog.as_ref().is_some_and(|d| d.editing),
+            _ => false,
+        }
+    }
+
+    /// Insert `text` into the active text field at its cursor. Every field is
+    /// single-line, so line breaks are dropped.
+    pub fn paste_text(&mut self, text: &str) {
+        if !self.text_field_active() {
             return;
         }
+        let had_newlines = text.trim_end_matches(['\r', '\n']).contains(['\r', '\n']);
+        let chars: Vec<char> = text.chars().filter(|c| *c != '\r' && *c != '\n').collect();
+        for &c in &chars {
+            match self.input_mode {
+                InputMode::TextInput | InputMode::UrlEdit | InputMode::OpenApiImport | InputMode::NoteEdit => self.dialog_input_char(c),
+                InputMode::Search => self.search_input_char(c),
+                InputMode::JsonSearch => self.json_search_input(c),
+                InputMode::ResponseSearch => self.response_search_input(c),
+                InputMode::CommandPalette => self.palette_input(c),
+                InputMode::GlobalFinder => self.finder_input(c),
+                InputMode::ExtraHeaders => self.extra_header_input_char(c),
+                InputMode::ApiKeyInput => self.api_key_input_char(c),
+                InputMode::StreamMessage => self.stream_input_char(c),
+                InputMode::VariablesView if self.editing_variable.is_some() => self.variable_input_char(c),
+                InputMode::VariablesView => self.variables_search_input_char(c),
+                InputMode::ParamsInput => self.params_input_char(c),
+                InputMode::RowsEditor => self.rows_editor_input_char(c),
+                _ => {}
+            }
+        }
+        // Searches report their own match status; don't overwrite it
+        if matches!(self.input_mode, InputMode::TextInput | InputMode::UrlEdit | InputMode::OpenApiImport | InputMode::NoteEdit | InputMode::ExtraHeaders | InputMode::ApiKeyInput | InputMode::VariablesView | InputMode::ParamsInput | InputMode::RowsEditor) {
+            self.status_message = if had_newlines {
+                format!("Pasted {} characters (line breaks removed)", chars.len())
+            } else {
+                format!("Pasted {} characters", chars.len())
+            };
+        }
+    }
+
+    /// Paste the system clipboard into the active text field.
+    pub fn paste_from_clipboard(&mut self) {
+        if let Some(text) = self.read_clipboard() {
+            self.paste_text(&text);
+        }
+    }
+
+    /// Read text from the clipboard: command-line tools first (like
+    /// `copy_to_clipboard`), then arboard.
+    fn read_clipboard(&mut self) -> Option<String> {
+        let tools: [(&str, &[&str]); 3] = [
+            ("wl-paste", &["--no-newline"]),
+            ("xclip", &["-selection", "clipboard", "-o"]),
+            ("xsel", &["--clipboard", "--output"]),
+        ];
+        for (program, args) in tools {
+            let output = std::process::Command::new(program)
+                .args(args)
+                .stdin(std::process::Stdio::null())
+                .stderr(std::process::Stdio::null())
+                .output()
+                .ok()
+                .filter(|o| o.status.success());
+            if let Some(output) = output {
+                return Some(String::from_utf8_lossy(&output.stdout).into_owned());
+            }
+        }
 
-        // Fallback to arboard
         if self.clipboard.is_none() {
             match arboard::Clipboard::new() {
                 Ok(cb) => self.clipboard = Some(cb),
                 Err(e) => {
                     self.error = Some(format!("Clipboard unavailable: {}", e));
-                    return;
+                    return None;
                 }
             }
         }
+        match self.clipboard.as_mut().map(|cb| cb.get_text()) {
+            Some(Ok(text)) => Some(text),
+            Some(Err(e)) => {
+                self.error = Some(format!("Failed to paste: {}", e));
+                None
+            }
+            None => None,
+        }
+    }
 
-        if let Some(ref mut clipboard) = self.clipboard {
-            match clipboard.set_text(&value) {
-                Ok(_) => {
-                    let preview = if value.len() > 50 {
-                        format!("{}...", &value[..50])
-                    } else {
-                        value.clone()
-                    };
-                    self.status_message = format!("Copied: {}", preview);
-                }
+    /// Put `text` on the system clipboard, trying command-line tools first
+    /// (more reliable on Linux) and falling back to arboard. Sets `self.error`
+    /// and returns false when no clipboard is reachable (e.g. over SSH).
+    fn copy_to_clipboard(&mut self, text: &str) -> bool {
+        if self.copy_to_clipboard_cli(text) {
+            return true;
+        }
+
+        if self.clipboard.is_none() {
+            match arboard::Clipboard::new() {
+                Ok(cb) => self.clipboard = Some(cb),
                 Err(e) => {
-                    self.error = Some(format!("Failed to copy: {}", e));
+                    self.error = Some(format!("Clipboard unavailable: {}", e));
+                    return false;
                 }
             }
         }
+
+        match self.clipboard.as_mut().map(|cb| cb.set_text(text)) {
+            Some(Ok(_)) => true,
+            Some(Err(e)) => {
+                self.error = Some(format!("Failed to copy: {}", e));
+                false
+            }
+            None => false,
+        }
+    }
+
+    /// Open the current request's resolved URL in the default browser.
+    pub fn open_in_browser(&mut self) {
+        let url = match &self.current_request {
+            Some(request) => self.substitute_variables(&request.url.to_string()),
+            None => return,
+        };
+        let url = url.trim();
+        if !(url.starts_with("http://") || url.starts_with("https://")) {
+            self.status_message = String::from("Only http(s) URLs can be opened in a browser");
+            return;
+        }
+
+        let (program, args): (&str, &[&str]) = if cfg!(target_os = "macos") {
+            ("open", &[])
+        } else if cfg!(target_os = "windows") {
+            // `cmd /C start` would cut the URL at its first `&`
+            ("rundll32", &["url.dll,FileProtocolHandler"])
+        } else {
+            ("xdg-open", &[])
+        };
+        let launched = std::process::Command::new(program)
+            .args(args)
+            .arg(url)
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .spawn();
+        match launched {
+            Ok(_) => self.status_message = format!("Opened {} in browser", url),
+            Err(e) => {
+                let error_msg = format!("Could not launch a browser ({}): {}", program, e);
+                log_error("open_in_browser", &error_msg);
+                self.error = Some(error_msg);
+            }
+        }
     }
 
     /// Try to copy text using command-line clipboard tools
@@ -2684,6 +7840,214 @@ impl App {
     }
 }
 
+/// If `body` should be JSON (by Content-Type, or because it starts with `{`
+/// or `[`) but fails to parse, a message pointing at the offending line.
+/// `{{variables}}` are stood in for by a number first, since Postman bodies
+/// often use them unquoted.
+fn json_body_error(body: &str, content_type: Option<&str>) -> Option<String> {
+    let trimmed = body.trim_start();
+    let declared_json = content_type.map(|ct| ct.to_lowercase().contains("json")).unwrap_or(false);
+    if trimmed.is_empty() || !(declared_json || trimmed.starts_with('{') || trimmed.starts_with('[')) {
+        return None;
+    }
+
+    let mut candidate = String::with_capacity(body.len());
+    let mut rest = body;
+    while let Some(start) = rest.find("{{") {
+        match rest[start..].find("}}") {
+            Some(len) => {
+                candidate.push_str(&rest[..start]);
+                candidate.push('0');
+                rest = &rest[start + len + 2..];
+            }
+            None => break,
+        }
+    }
+    candidate.push_str(rest);
+
+    serde_json::from_str::<serde_json::Value>(&candidate)
+        .err()
+        .map(|e| format!("Body is not valid JSON (line {}, column {}): {}", e.line(), e.column(), e))
+}
+
+/// A `Key: Value` header line; `None` without a `:`.
+fn parse_header_line(line: &str) -> Option<Header> {
+    let (key, value) = line.split_once(':')?;
+    Some(Header {
+        key: key.trim().to_string(),
+        value: value.trim().to_string(),
+        disabled: None,
+    })
+}
+
+/// Whether a variable holds a credential: typed `secret` or named like one.
+fn is_secret_variable(variable: &Variable) -> bool {
+    const SECRET_HINTS: [&str; 6] = ["secret", "token", "password", "apikey", "api_key", "auth"];
+    let key = variable.key.to_lowercase();
+    variable.var_type.as_deref() == Some("secret") || SECRET_HINTS.iter().any(|h| key.contains(h))
+}
+
+/// Headers whose values are credentials, masked in the request log and in
+/// reports whatever their value.
+const SENSITIVE_HEADERS: [&str; 6] = ["authorization", "proxy-authorization", "cookie", "set-cookie", "x-api-key", "api-key"];
+
+/// `text` re-indented if it is JSON, otherwise unchanged.
+fn pretty_json(text: &str) -> String {
+    serde_json::from_str::<serde_json::Value>(text)
+        .ok()
+        .and_then(|value| serde_json::to_string_pretty(&value).ok())
+        .unwrap_or_else(|| text.to_string())
+}
+
+/// `request` with `extra` headers added, each replacing any header of the
+/// same name (case-insensitively).
+fn with_extra_headers(request: &Request, extra: &[Header]) -> Request {
+    let mut request = request.clone();
+    for header in extra {
+        request.header.retain(|h| !h.key.eq_ignore_ascii_case(&header.key));
+        request.header.push(header.clone());
+    }
+    request
+}
+
+/// An error naming the first enabled form-data file field whose file is
+/// missing, so the request can be stopped before it is sent.
+fn missing_form_file(request: &Request) -> Option<String> {
+    let body = request.body.as_ref().filter(|b| b.is_form_data())?;
+    body.formdata
+        .iter()
+        .filter(|field| field.is_enabled() && field.is_file())
+        .find_map(|field| {
+            let files = field.files();
+            if files.is_empty() {
+                return Some(format!("Form field '{}' has no file selected", field.key));
+            }
+            files
+                .iter()
+                .find(|path| !std::path::Path::new(path).is_file())
+                .map(|path| format!("File not found for form field '{}': {}", field.key, path))
+        })
+}
+
+/// How `server`'s variables differ from `base` (the values last loaded),
+/// one line per variable, marking those also edited in `local`. Values are
+/// left out, as they may be secrets.
+fn environment_changes(base: &[Variable], server: &[Variable], local: &[Variable]) -> Vec<String> {
+    let find = |vars: &[Variable], key: &str| vars.iter().find(|v| v.key == key).cloned();
+    let same = |a: &Variable, b: &Variable| a.value == b.value && a.enabled == b.enabled;
+    let edited_here = |key: &str| match (find(base, key), find(local, key)) {
+        (Some(b), Some(l)) => !same(&b, &l),
+        (None, Some(_)) => true,
+        _ => false,
+    };
+    let mark = |key: &str| if edited_here(key) { " (also edited here)" } else { "" };
+
+    let mut changes = Vec::new();
+    for var in server {
+        match find(base, &var.key) {
+            Some(b) if same(&b, var) => {}
+            Some(_) => changes.push(format!("~ {}{}", var.key, mark(&var.key))),
+            None => changes.push(format!("+ {}{}", var.key, mark(&var.key))),
+        }
+    }
+    for var in base {
+        if find(server, &var.key).is_none() {
+            changes.push(format!("- {}{}", var.key, mark(&var.key)));
+        }
+    }
+    changes
+}
+
+/// `server`'s variables with the local edits (variables in `local` that
+/// differ from `base`, and ones deleted from it) applied on top. A variable
+/// deleted here but edited on the server keeps the server's value.
+fn merge_environment(base: &[Variable], local: &[Variable], server: &[Variable]) -> Vec<Variable> {
+    let same = |a: &Variable, b: &Variable| a.key == b.key && a.value == b.value && a.enabled == b.enabled;
+    let deleted: Vec<&Variable> = base.iter().filter(|b| !local.iter().any(|l| l.key == b.key)).collect();
+    let mut merged: Vec<Variable> = server
+        .iter()
+        .filter(|s| !deleted.iter().any(|d| same(d, s)))
+        .cloned()
+        .collect();
+    for var in local {
+        let unchanged = base.iter().any(|b| same(b, var));
+        if unchanged {
+            continue;
+        }
+        match merged.iter_mut().find(|m| m.key == var.key) {
+            Some(existing) => *existing = var.clone(),
+            None => merged.push(var.clone()),
+        }
+    }
+    merged
+}
+
+/// Contents of a request's body file (`~` expanded).
+fn read_body_file(file: &str) -> Result<String> {
+    let full_path = expand_home(file);
+    std::fs::read_to_string(&full_path).with_context(|| format!("Failed to read body file: {}", full_path.display()))
+}
+
+/// `path` with a leading `~/` expanded to the home directory.
+fn expand_home(path: &str) -> std::path::PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => std::path::PathBuf::from(path),
+    }
+}
+
+/// The request as it looks with a local (unsynced) edit applied on top:
+/// method, URL, body and (when edited) headers come from the edit,
+/// everything else from Postman.
+pub fn apply_local_edit(request: &Request, edit: &EditableRequest) -> Request {
+    Request {
+        method: edit.method.clone(),
+        url: edited_url(edit),
+        header: edit.headers.clone().unwrap_or_else(|| request.header.clone()),
+        auth: request.auth.clone(),
+        // Form-data and GraphQL bodies aren't editable as text; keep them as they are
+        body: if edit.body.is_empty() {
+            request.body.clone().filter(|b| b.is_structured())
+        } else {
+            Some(crate::api::RequestBody::raw(edit.body.clone(), edit.body_language.clone()))
+        },
+        description: request.description.clone(),
+    }
+}
+
+/// The URL of an edited request. With disabled query params it is a
+/// structured URL listing them (so they are saved to Postman as disabled);
+/// otherwise the plain string.
+fn edited_url(edit: &EditableRequest) -> RequestUrl {
+    if edit.url.is_empty() {
+        return RequestUrl::Empty;
+    }
+    if edit.disabled_query.is_empty() {
+        return RequestUrl::Simple(edit.url.clone());
+    }
+    let enabled = crate::util::query_pairs(&edit.url)
+        .into_iter()
+        .map(|(key, value)| QueryParam { key, value, disabled: None });
+    RequestUrl::Complex(UrlDetail {
+        raw: Some(edit.url.clone()),
+        host: None,
+        path: None,
+        query: enabled.chain(edit.disabled_query.iter().cloned()).collect(),
+    })
+}
+
+/// If the cursor sits inside an unclosed `{{`, the byte offset just after
+/// that `{{` (where the partial variable name starts).
+fn open_placeholder(text: &str, cursor: usize) -> Option<usize> {
+    let before = text.get(..cursor)?;
+    let start = before.rfind("{{")? + 2;
+    let partial = &before[start..];
+    if partial.contains("}}") || partial.contains(char::is_whitespace) {
+        return None;
+    }
+    Some(start)
+}
+
 /// Append every unique `{{placeholder}}` key found in `text` to `keys`,
 /// using `seen` to deduplicate across multiple calls. Keys are trimmed and
 /// empty placeholders (`{{}}`) are ignored.
@@ -2710,14 +8074,27 @@ fn extract_placeholders(text: &str, keys: &mut Vec<String>, seen: &mut HashSet<S
     }
 }
 
+/// Number of requests in `items`, including those nested in folders.
+pub fn count_requests(items: &[Item]) -> usize {
+    items
+        .iter()
+        .map(|item| match item {
+            Item::Folder(folder) => count_requests(&folder.item),
+            Item::Request(_) => 1,
+        })
+        .sum()
+}
+
 fn flatten_recursive(
     items: &[Item],
     depth: usize,
     path: Vec<usize>,
     expanded_folders: &HashSet<Vec<usize>>,
+    sort: RequestSort,
     flat_items: &mut Vec<FlatItem>,
 ) {
-    for (i, item) in items.iter().enumerate() {
+    for i in sorted_item_order(items, sort) {
+        let item = &items[i];
         let mut current_path = path.clone();
         current_path.push(i);
 
@@ -2732,9 +8109,10 @@ fn flatten_recursive(
                     request: None,
                     request_id: None,
                     path: current_path.clone(),
+                    request_count: count_requests(&folder.item),
                 });
                 if is_expanded {
-                    flatten_recursive(&folder.item, depth + 1, current_path, expanded_folders, flat_items);
+                    flatten_recursive(&folder.item, depth + 1, current_path, expanded_folders, sort, flat_items);
                 }
             }
             Item::Request(req_item) => {
@@ -2746,13 +8124,54 @@ fn flatten_recursive(
                     request: Some(req_item.request.clone()),
                     request_id: req_item.id.clone(),
                     path: current_path,
+                    request_count: 0,
                 });
             }
         }
     }
 }
 
-fn get_item_at_path(items: &[Item], path: &[usize]) -> Option<(String, Option<Request>, Option<String>)> {
+/// The order to show `items` in. Sorted orders list folders first (by
+/// name), then requests; paths keep the stored indices either way.
+fn sorted_item_order(items: &[Item], sort: RequestSort) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..items.len()).collect();
+    if sort == RequestSort::Original {
+        return order;
+    }
+    const METHODS: [&str; 7] = ["GET", "POST", "PUT", "PATCH", "DELETE", "HEAD", "OPTIONS"];
+    let key = |item: &Item| match item {
+        Item::Folder(folder) => (0, 0, folder.name.to_lowercase()),
+        Item::Request(req_item) => {
+            let method = req_item.request.method.to_uppercase();
+            let rank = match sort {
+                RequestSort::Method => METHODS.iter().position(|m| *m == method).unwrap_or(METHODS.len()),
+                _ => 0,
+            };
+            (1, rank, req_item.name.to_lowercase())
+        }
+    };
+    // Stable, so equal keys keep their stored order
+    order.sort_by_cached_key(|&i| key(&items[i]));
+    order
+}
+
+/// The folders enclosing the item at `path`, outermost first.
+fn folders_along_path<'a>(items: &'a [Item], path: &[usize]) -> Vec<&'a Folder> {
+    let mut folders = Vec::new();
+    let mut items = items;
+    for &idx in path {
+        match items.get(idx) {
+            Some(Item::Folder(folder)) => {
+                folders.push(folder);
+                items = &folder.item;
+            }
+            _ => break,
+        }
+    }
+    folders
+}
+
+pub fn get_item_at_path(items: &[Item], path: &[usize]) -> Option<(String, Option<Request>, Option<String>)> {
     if path.is_empty() {
         return None;
     }
@@ -2782,10 +8201,229 @@ fn get_item_at_path(items: &[Item], path: &[usize]) -> Option<(String, Option<Re
     }
 }
 
+/// The request item at `path`, or None if the path leads elsewhere.
+fn request_item_at_path<'a>(items: &'a [Item], path: &[usize]) -> Option<&'a RequestItem> {
+    let (&index, remaining_path) = path.split_first()?;
+    match items.get(index)? {
+        Item::Request(req_item) if remaining_path.is_empty() => Some(req_item),
+        Item::Folder(folder) => request_item_at_path(&folder.item, remaining_path),
+        Item::Request(_) => None,
+    }
+}
+
+/// Requests that differ between two versions of a collection tree, as
+/// display lines (`+ name` added, `- name` removed, `~ name` changed).
+/// Requests are matched by Postman id, falling back to their path.
+fn collection_tree_changes(old: &[Item], new: &[Item]) -> Vec<String> {
+    fn collect(items: &[Item], path: Vec<usize>, out: &mut Vec<(String, String, serde_json::Value)>) {
+        for (i, item) in items.iter().enumerate() {
+            let mut current_path = path.clone();
+            current_path.push(i);
+            match item {
+                Item::Folder(folder) => collect(&folder.item, current_path, out),
+                Item::Request(req_item) => {
+                    let key = req_item.id.clone().unwrap_or_else(|| format!("{:?}", current_path));
+                    let value = serde_json::to_value(req_item).unwrap_or_default();
+                    out.push((key, req_item.name.clone(), value));
+                }
+            }
+        }
+    }
+
+    let (mut before, mut after) = (Vec::new(), Vec::new());
+    collect(old, Vec::new(), &mut before);
+    collect(new, Vec::new(), &mut after);
+
+    let mut lines = Vec::new();
+    for (key, name, value) in &after {
+        match before.iter().find(|(k, _, _)| k == key) {
+            None => lines.push(format!("+ {}", name)),
+            Some((_, _, old_value)) if old_value != value => lines.push(format!("~ {}", name)),
+            Some(_) => {}
+        }
+    }
+    for (key, name, _) in &before {
+        if !after.iter().any(|(k, _, _)| k == key) {
+            lines.push(format!("- {}", name));
+        }
+    }
+    if lines.is_empty() {
+        lines.push(String::from("~ folders or ordering"));
+    }
+    lines
+}
+
+/// Every folder and request under `items` whose name matches `query` (as
+/// `/` search does) or whose request id starts with it. `owner` is
+/// the collection's (uid, name).
+fn find_items_by_name(
+    items: &[Item],
+    query: &str,
+    owner: (&str, &str),
+    path: &mut Vec<usize>,
+    folders: &mut Vec<String>,
+    found: &mut Vec<FinderMatch>,
+) {
+    let query = query.trim();
+    let found_at = |path: &[usize], folders: &[String], name: &str, method: Option<&str>| FinderMatch {
+        collection_uid: owner.0.to_string(),
+        collection_name: owner.1.to_string(),
+        path: path.to_vec(),
+        folders: folders.to_vec(),
+        name: name.to_string(),
+        is_folder: method.is_none(),
+        method: method.map(String::from),
+    };
+    for (i, item) in items.iter().enumerate() {
+        path.push(i);
+        match item {
+            Item::Folder(folder) => {
+                if crate::util::search_matches(&folder.name, query) {
+                    found.push(found_at(path, folders, &folder.name, None));
+                }
+                folders.push(folder.name.clone());
+                find_items_by_name(&folder.item, query, owner, path, folders, found);
+                folders.pop();
+            }
+            Item::Request(req_item) => {
+                let id_matches = req_item.id.as_deref().is_some_and(|id| id.starts_with(query));
+                if id_matches || crate::util::search_matches(&req_item.name, query) {
+                    found.push(found_at(path, folders, &req_item.name, Some(&req_item.request.method)));
+                }
+            }
+        }
+        path.pop();
+    }
+}
+
+/// What `reconcile_request_path` found for a stored request path.
+#[derive(Debug, PartialEq, Eq)]
+enum StoredPath {
+    Unchanged,
+    /// The path or the recorded id was updated.
+    Updated,
+    /// The stored id is no longer in the collection, so the path is stale.
+    Gone,
+}
+
+/// Re-point a stored `path` at where its request's id now lives in `items`,
+/// or record the id found at `path` when none was stored.
+fn reconcile_request_path(items: &[Item], path: &mut Vec<usize>, request_id: &mut Option<String>) -> StoredPath {
+    match request_id {
+        Some(id) => match find_request_path_by_id(items, id) {
+            Some(new_path) if new_path == *path => StoredPath::Unchanged,
+            Some(new_path) => {
+                *path = new_path;
+                StoredPath::Updated
+            }
+            None => StoredPath::Gone,
+        },
+        None => match get_item_at_path(items, path) {
+            Some((_, _, Some(id))) => {
+                *request_id = Some(id);
+                StoredPath::Updated
+            }
+            _ => StoredPath::Unchanged,
+        },
+    }
+}
+
+/// The path of the request whose Postman id is `id`, searching depth-first.
+fn find_request_path_by_id(items: &[Item], id: &str) -> Option<Vec<usize>> {
+    for (i, item) in items.iter().enumerate() {
+        match item {
+            Item::Request(req_item) if req_item.id.as_deref() == Some(id) => return Some(vec![i]),
+            Item::Request(_) => {}
+            Item::Folder(folder) => {
+                if let Some(mut path) = find_request_path_by_id(&folder.item, id) {
+                    path.insert(0, i);
+                    return Some(path);
+                }
+            }
+        }
+    }
+    None
+}
+
+/// The children of the folder at `path`, or None if it isn't a folder.
+fn get_folder_items_at_path<'a>(items: &'a [Item], path: &[usize]) -> Option<&'a [Item]> {
+    let (&index, remaining_path) = path.split_first()?;
+    match items.get(index)? {
+        Item::Folder(folder) if remaining_path.is_empty() => Some(&folder.item),
+        Item::Folder(folder) => get_folder_items_at_path(&folder.item, remaining_path),
+        Item::Request(_) => None,
+    }
+}
+
+/// Every request under `items` as (name, path, request), depth-first in
+/// collection order. `path` is the path of the folder holding `items`.
+fn collect_requests_recursive(
+    items: &[Item],
+    path: Vec<usize>,
+    requests: &mut Vec<(String, Vec<usize>, Request)>,
+) {
+    for (i, item) in items.iter().enumerate() {
+        let mut current_path = path.clone();
+        current_path.push(i);
+
+        match item {
+            Item::Folder(folder) => {
+                collect_requests_recursive(&folder.item, current_path, requests);
+            }
+            Item::Request(req_item) => {
+                requests.push((req_item.name.clone(), current_path, req_item.request.clone()));
+            }
+        }
+    }
+}
+
 fn insert_item_at_path(items: &mut Vec<Item>, path: &[usize], new_item: Item) {
     insert_item_recursive(items, path, new_item);
 }
 
+/// The mutable child list of the folder at `path` (the root list when `path`
+/// is empty).
+fn folder_items_mut<'a>(items: &'a mut Vec<Item>, path: &[usize]) -> Option<&'a mut Vec<Item>> {
+    let (&index, remaining_path) = match path.split_first() {
+        Some(split) => split,
+        None => return Some(items),
+    };
+    match items.get_mut(index)? {
+        Item::Folder(folder) => folder_items_mut(&mut folder.item, remaining_path),
+        Item::Request(_) => None,
+    }
+}
+
+/// After swapping siblings `a` and `b` under `parent`, rewrite `path` so it
+/// still points at the same item (descendants of either sibling included).
+fn remap_swapped_path(path: &mut [usize], parent: &[usize], a: usize, b: usize) {
+    if path.len() <= parent.len() || !path.starts_with(parent) {
+        return;
+    }
+    let slot = &mut path[parent.len()];
+    if *slot == a {
+        *slot = b;
+    } else if *slot == b {
+        *slot = a;
+    }
+}
+
+/// After the item at `removed` is taken out of its folder, rewrite `path`
+/// so it still points at the same item. False when `path` was the removed
+/// item or beneath it.
+fn remap_removed_path(path: &mut [usize], removed: &[usize]) -> bool {
+    if path.starts_with(removed) {
+        return false;
+    }
+    let Some((&index, parent)) = removed.split_last() else {
+        return true;
+    };
+    if path.len() > parent.len() && path.starts_with(parent) && path[parent.len()] > index {
+        path[parent.len()] -= 1;
+    }
+    true
+}
+
 fn insert_item_recursive(items: &mut Vec<Item>, path: &[usize], new_item: Item) {
     if path.is_empty() {
         items.push(new_item);
@@ -2825,7 +8463,7 @@ fn search_items_recursive(
         match item {
             Item::Folder(folder) => {
                 // Check if folder name matches
-                if folder.name.to_lowercase().contains(query) {
+                if crate::util::search_matches(&folder.name, query) {
                     matches.push(current_path.clone());
                 }
                 // Recurse into folder
@@ -2833,7 +8471,7 @@ fn search_items_recursive(
             }
             Item::Request(req_item) => {
                 // Check if request name matches
-                if req_item.name.to_lowercase().contains(query) {
+                if crate::util::search_matches(&req_item.name, query) {
                     matches.push(current_path);
                 }
             }
@@ -2848,6 +8486,8 @@ fn search_items_recursive(
 pub struct RefreshData {
     pub workspaces: Option<Vec<WorkspaceInfo>>,
     pub collections: std::result::Result<Vec<CollectionInfo>, String>,
+    /// Whether `collections` failed because Postman rejected the API key.
+    pub api_key_rejected: bool,
     pub environments: Option<Vec<EnvironmentInfo>>,
     pub collection_detail: Option<std::result::Result<CollectionDetail, String>>,
     pub environment_detail: Option<std::result::Result<EnvironmentDetail, String>>,
@@ -2865,12 +8505,7 @@ pub async fn fetch_startup_data(
 ) -> RefreshData {
     let (workspaces, collections, environments, collection_detail, environment_detail) = tokio::join!(
         async { client.list_workspaces().await.ok() },
-        async {
-            client
-                .list_collections(workspace_id.as_deref())
-                .await
-                .map_err(|e| e.to_string())
-        },
+        async { client.list_collections(workspace_id.as_deref()).await },
         async { client.list_environments(workspace_id.as_deref()).await.ok() },
         async {
             match collection_uid.as_deref() {
@@ -2886,11 +8521,279 @@ pub async fn fetch_startup_data(
         },
     );
 
+    let api_key_rejected = collections.as_ref().err().is_some_and(is_api_key_error);
     RefreshData {
         workspaces,
-        collections,
+        collections: collections.map_err(|e| e.to_string()),
+        api_key_rejected,
         environments,
         collection_detail,
         environment_detail,
     }
 }
+
+/// Push a J/K move to Postman, then fetch the collection back; `None` when
+/// that fetch failed (the local copy is already correct). Sets `sent` as it
+/// starts writing the new order.
+pub async fn perform_move(client: PostmanClient, pending: &PendingMove, sent: &AtomicBool) -> Result<Option<CollectionDetail>> {
+    let collection = &pending.collection;
+    sent.store(true, Ordering::SeqCst);
+    client
+        .update_collection_checked(&pending.collection_uid, &collection.info, &collection.item, &collection.variable, &pending.items, &collection.variable)
+        .await?;
+    match client.get_collection(&pending.collection_uid).await {
+        Ok(detail) => Ok(Some(detail)),
+        Err(e) => {
+            log_error("move_item:refresh", &e.to_string());
+            Ok(None)
+        }
+    }
+}
+
+/// Add a transfer's copy to the end of its target collection, then, for a
+/// move, take the original out of the source collection. Sets `sent` as it
+/// starts writing the copy.
+pub async fn perform_transfer(client: PostmanClient, pending: &PendingTransfer, sent: &AtomicBool) -> TransferOutcome {
+    let target = &pending.target;
+    let copied = match client.get_collection(&target.uid).await {
+        Ok(detail) => {
+            let mut items = detail.item.clone();
+            items.push(Item::Request(pending.copy.clone()));
+            sent.store(true, Ordering::SeqCst);
+            client
+                .update_collection_checked(&target.uid, &detail.info, &detail.item, &detail.variable, &items, &detail.variable)
+                .await
+                .map(|()| detail.item.len())
+        }
+        Err(e) => Err(e),
+    };
+    if copied.is_err() || !pending.remove_source {
+        return TransferOutcome { copied, removed: None };
+    }
+
+    let collection = &pending.collection;
+    let mut items = collection.item.clone();
+    let (&index, parent) = pending.path.split_last().expect("requests have a non-empty path");
+    if let Some(siblings) = folder_items_mut(&mut items, parent) {
+        siblings.remove(index);
+    }
+    let removed = client
+        .update_collection_checked(&pending.collection_uid, &collection.info, &collection.item, &collection.variable, &items, &collection.variable)
+        .await
+        .map(|()| items);
+    TransferOutcome { copied, removed: Some(removed) }
+}
+
+/// Execute a run's requests one after another, recording status and timing
+/// for each. With `stop_on_failure`, the run ends at the first failing
+/// request and the remainder are reported as skipped.
+pub async fn run_requests(client: PostmanClient, run: PendingRun, stop_on_failure: bool) -> RunReport {
+    let total = run.steps.len();
+    let mut results = Vec::with_capacity(total);
+
+    for step in run.steps {
+        let started = std::time::Instant::now();
+        let outcome = client.execute_request(&step.request, step.timeout, step.http_version).await;
+        let duration_ms = started.elapsed().as_millis();
+
+        let (status, error, assertions) = match outcome {
+            Ok(response) => (Some(response.status), None, crate::assertions::evaluate(&step.assertions, &response)),
+            Err(e) => (None, Some(format!("{:#}", e)), Vec::new()),
+        };
+        let result = RunResult {
+            name: step.name,
+            method: step.request.method.to_uppercase(),
+            collection_uid: step.collection_uid,
+            path: step.path,
+            status,
+            error,
+            duration_ms,
+            assertions,
+        };
+        let failed = !result.passed();
+        results.push(result);
+
+        if failed && stop_on_failure {
+            break;
+        }
+    }
+
+    RunReport {
+        title: run.title,
+        skipped: total - results.len(),
+        results,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_local_edit, collection_tree_changes, edited_url, environment_changes, find_request_path_by_id, merge_environment, reconcile_request_path, sorted_item_order, EditableRequest, StoredPath};
+    use crate::api::{Folder, Header, Item, QueryParam, Request, RequestItem, RequestUrl, Variable};
+    use crate::config::RequestSort;
+
+    fn request(id: &str, name: &str, method: &str) -> Item {
+        Item::Request(RequestItem {
+            id: Some(id.to_string()),
+            name: name.to_string(),
+            request: Request {
+                method: method.to_string(),
+                url: RequestUrl::Simple(format!("https://api.example.com/{}", id)),
+                header: vec![],
+                auth: None,
+                body: None,
+                description: None,
+            },
+            response: vec![],
+            event: vec![],
+            protocol_profile_behavior: None,
+        })
+    }
+
+    fn folder(name: &str, item: Vec<Item>) -> Item {
+        Item::Folder(Folder { name: name.to_string(), item, description: None, variable: vec![] })
+    }
+
+    fn editable(url: &str) -> EditableRequest {
+        EditableRequest {
+            name: String::from("Get user"),
+            method: String::from("GET"),
+            url: url.to_string(),
+            body: String::new(),
+            timeout_secs: None,
+            body_file: None,
+            body_language: None,
+            http_version: None,
+            disabled_query: Vec::new(),
+            headers: None,
+        }
+    }
+
+    #[test]
+    fn disabled_query_params_are_kept_in_a_structured_url() {
+        let mut edit = editable("{{baseUrl}}/users?page=2");
+        assert!(matches!(edited_url(&edit), RequestUrl::Simple(url) if url == "{{baseUrl}}/users?page=2"));
+
+        edit.disabled_query = vec![QueryParam { key: String::from("debug"), value: Some(String::from("1")), disabled: Some(true) }];
+        let RequestUrl::Complex(detail) = edited_url(&edit) else {
+            panic!("expected a structured URL");
+        };
+        assert_eq!(detail.raw.as_deref(), Some("{{baseUrl}}/users?page=2"));
+        let query: Vec<(&str, bool)> = detail.query.iter().map(|q| (q.key.as_str(), q.is_disabled())).collect();
+        assert_eq!(query, [("page", false), ("debug", true)]);
+
+        edit.url.clear();
+        assert!(matches!(edited_url(&edit), RequestUrl::Empty));
+    }
+
+    #[test]
+    fn sorted_orders_put_folders_first_and_keep_ties_stable() {
+        let items = [
+            request("1", "delete user", "DELETE"),
+            folder("Zebra", vec![]),
+            request("2", "Create user", "POST"),
+            request("3", "list users", "GET"),
+            folder("admin", vec![]),
+            request("4", "Another list", "GET"),
+        ];
+        assert_eq!(sorted_item_order(&items, RequestSort::Original), [0, 1, 2, 3, 4, 5]);
+        assert_eq!(sorted_item_order(&items, RequestSort::Name), [4, 1, 5, 2, 0, 3]);
+        assert_eq!(sorted_item_order(&items, RequestSort::Method), [4, 1, 5, 3, 2, 0]);
+    }
+
+    #[test]
+    fn tree_changes_list_added_removed_and_changed_requests() {
+        let old = [folder("Users", vec![request("1", "Get user", "GET"), request("2", "Delete user", "DELETE")]), request("3", "Health", "GET")];
+        let mut changed = request("1", "Get user", "GET");
+        if let Item::Request(item) = &mut changed {
+            item.request.method = String::from("HEAD");
+        }
+        // Moving a request into another folder isn't a change to it
+        let new = [folder("Users", vec![changed]), folder("Ops", vec![request("3", "Health", "GET")]), request("4", "Create user", "POST")];
+        assert_eq!(collection_tree_changes(&old, &new), ["~ Get user", "+ Create user", "- Delete user"]);
+
+        let reordered = [request("3", "Health", "GET"), folder("Users", vec![request("1", "Get user", "GET"), request("2", "Delete user", "DELETE")])];
+        assert_eq!(collection_tree_changes(&old, &reordered), ["~ folders or ordering"]);
+    }
+
+    #[test]
+    fn stored_paths_follow_their_request_ids() {
+        let items = [folder("Users", vec![request("2", "Delete user", "DELETE"), request("1", "Get user", "GET")]), request("3", "Health", "GET")];
+        assert_eq!(find_request_path_by_id(&items, "1"), Some(vec![0, 1]));
+        assert_eq!(find_request_path_by_id(&items, "3"), Some(vec![1]));
+        assert_eq!(find_request_path_by_id(&items, "9"), None);
+
+        // Moved within its folder: the path follows the id
+        let (mut path, mut id) = (vec![0, 0], Some(String::from("1")));
+        assert_eq!(reconcile_request_path(&items, &mut path, &mut id), StoredPath::Updated);
+        assert_eq!(path, [0, 1]);
+        assert_eq!(reconcile_request_path(&items, &mut path, &mut id), StoredPath::Unchanged);
+
+        // Saved before ids were recorded: picks up the id at its path
+        let (mut path, mut id) = (vec![1], None);
+        assert_eq!(reconcile_request_path(&items, &mut path, &mut id), StoredPath::Updated);
+        assert_eq!(id.as_deref(), Some("3"));
+    }
+
+    #[test]
+    fn stored_paths_of_deleted_requests_are_stale() {
+        // "Get user" was at [0, 1]; after deleting it another request sits there
+        let items = [folder("Users", vec![request("2", "Delete user", "DELETE"), request("4", "List users", "GET")])];
+        let (mut path, mut id) = (vec![0, 1], Some(String::from("1")));
+        assert_eq!(reconcile_request_path(&items, &mut path, &mut id), StoredPath::Gone);
+    }
+
+    fn var(key: &str, value: &str) -> Variable {
+        Variable { key: key.to_string(), value: value.to_string(), enabled: None, var_type: None }
+    }
+
+    #[test]
+    fn environment_changes_mark_keys_edited_on_both_sides() {
+        let base = [var("host", "a"), var("token", "t1"), var("old", "x")];
+        let server = [var("host", "b"), var("token", "t1"), var("new", "y")];
+        let local = [var("host", "c"), var("token", "t1"), var("old", "x")];
+        assert_eq!(environment_changes(&base, &server, &local), ["~ host (also edited here)", "+ new", "- old"]);
+        assert!(environment_changes(&base, &base, &local).is_empty());
+    }
+
+    #[test]
+    fn merging_applies_local_edits_on_top_of_the_server() {
+        let base = [var("host", "a"), var("token", "t1")];
+        let server = [var("host", "b"), var("token", "t2"), var("new", "y")];
+        let local = [var("host", "a"), var("token", "mine"), var("added", "z")];
+        let merged: Vec<(String, String)> = merge_environment(&base, &local, &server).into_iter().map(|v| (v.key, v.value)).collect();
+        let expected = [("host", "b"), ("token", "mine"), ("new", "y"), ("added", "z")].map(|(k, v)| (k.to_string(), v.to_string()));
+        assert_eq!(merged, expected);
+    }
+
+    #[test]
+    fn merging_keeps_local_deletions() {
+        let base = [var("host", "a"), var("token", "t1"), var("debug", "1")];
+        let server = [var("host", "a"), var("token", "t2"), var("debug", "1"), var("new", "y")];
+        // Deleted here: `debug` is dropped, but `token` changed on the server so it stays
+        let local = [var("host", "a")];
+        let merged: Vec<(String, String)> = merge_environment(&base, &local, &server).into_iter().map(|v| (v.key, v.value)).collect();
+        let expected = [("host", "a"), ("token", "t2"), ("new", "y")].map(|(k, v)| (k.to_string(), v.to_string()));
+        assert_eq!(merged, expected);
+    }
+
+    #[test]
+    fn local_edit_headers_replace_the_requests_own() {
+        let Item::Request(item) = request("u1", "Get user", "GET") else {
+            unreachable!()
+        };
+        let original = Request {
+            header: vec![Header { key: String::from("Accept"), value: String::from("text/plain"), disabled: None }],
+            ..item.request
+        };
+
+        let mut edit = editable("https://api.example.com/u1");
+        assert_eq!(apply_local_edit(&original, &edit).header, original.header);
+
+        let headers = vec![
+            Header { key: String::from("Accept"), value: String::from("application/json"), disabled: None },
+            Header { key: String::from("X-Trace"), value: String::from("1"), disabled: Some(true) },
+        ];
+        edit.headers = Some(headers.clone());
+        assert_eq!(apply_local_edit(&original, &edit).header, headers);
+    }
+}
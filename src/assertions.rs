@@ -0,0 +1,228 @@
+//! Declarative checks run against a response after a request executes,
+//! configured per request under `[[assertions]]` in config.toml.
+
+use serde_json::Value;
+
+use crate::api::ExecutedResponse;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Comparison {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Comparison {
+    fn parse(op: &str) -> Option<Self> {
+        match op {
+            "==" | "=" => Some(Comparison::Eq),
+            "!=" => Some(Comparison::Ne),
+            "<" => Some(Comparison::Lt),
+            "<=" => Some(Comparison::Le),
+            ">" => Some(Comparison::Gt),
+            ">=" => Some(Comparison::Ge),
+            _ => None,
+        }
+    }
+
+    fn holds(self, left: u16, right: u16) -> bool {
+        match self {
+            Comparison::Eq => left == right,
+            Comparison::Ne => left != right,
+            Comparison::Lt => left < right,
+            Comparison::Le => left <= right,
+            Comparison::Gt => left > right,
+            Comparison::Ge => left >= right,
+        }
+    }
+}
+
+/// One parsed assertion rule.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Rule {
+    /// `status == 200`, `status < 400`, ...
+    Status(Comparison, u16),
+    /// `body contains "ok"`
+    BodyContains(String),
+    /// `header Content-Type exists`
+    HeaderExists(String),
+    /// `json data.id exists`
+    JsonExists(String),
+    /// `json data.items[0].name == "first"` (the value is JSON; bare words
+    /// are compared as strings)
+    JsonEquals(String, Value),
+}
+
+/// The outcome of one rule against a response.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AssertionResult {
+    /// The rule as written in the config.
+    pub rule: String,
+    pub passed: bool,
+    /// Why the rule failed (or couldn't be parsed).
+    pub detail: Option<String>,
+}
+
+/// `text` without one pair of surrounding double quotes, if it has them.
+fn unquote(text: &str) -> &str {
+    text.strip_prefix('"').and_then(|t| t.strip_suffix('"')).unwrap_or(text)
+}
+
+pub fn parse_rule(rule: &str) -> Result<Rule, String> {
+    let rule = rule.trim();
+    let (kind, rest) = rule.split_once(char::is_whitespace).unwrap_or((rule, ""));
+    let rest = rest.trim();
+    match kind.to_ascii_lowercase().as_str() {
+        "status" => {
+            let (op, code) = rest.split_once(char::is_whitespace).ok_or("expected `status <op> <code>`")?;
+            let op = Comparison::parse(op.trim()).ok_or_else(|| format!("unknown comparison `{}`", op.trim()))?;
+            let code = code.trim().parse().map_err(|_| format!("`{}` is not a status code", code.trim()))?;
+            Ok(Rule::Status(op, code))
+        }
+        "body" => match rest.split_once(char::is_whitespace) {
+            Some(("contains", text)) if !text.trim().is_empty() => Ok(Rule::BodyContains(unquote(text.trim()).to_string())),
+            _ => Err(String::from("expected `body contains \"text\"`")),
+        },
+        "header" => match rest.split_whitespace().collect::<Vec<_>>().as_slice() {
+            [name, "exists"] => Ok(Rule::HeaderExists(name.to_string())),
+            _ => Err(String::from("expected `header <name> exists`")),
+        },
+        "json" => {
+            let (path, check) = rest.split_once(char::is_whitespace).ok_or("expected `json <path> exists` or `json <path> == <value>`")?;
+            let check = check.trim();
+            if check == "exists" {
+                return Ok(Rule::JsonExists(path.to_string()));
+            }
+            let expected = check
+                .strip_prefix("==")
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .ok_or("expected `exists` or `== <value>` after the path")?;
+            let expected = serde_json::from_str(expected).unwrap_or_else(|_| Value::String(expected.to_string()));
+            Ok(Rule::JsonEquals(path.to_string(), expected))
+        }
+        _ => Err(format!("unknown rule `{}`", kind)),
+    }
+}
+
+/// The value at a dotted path like `data.items[0].id` (or `items.0.id`).
+/// An empty path or `$` is the whole document.
+pub fn json_path<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    let path = path.trim().trim_start_matches('$').trim_start_matches('.');
+    let mut current = value;
+    for segment in path.split('.').filter(|s| !s.is_empty()) {
+        let (key, indices) = segment.split_once('[').map_or((segment, ""), |(k, i)| (k, i));
+        if !key.is_empty() {
+            current = match current {
+                Value::Array(items) => items.get(key.parse::<usize>().ok()?)?,
+                _ => current.get(key)?,
+            };
+        }
+        for index in indices.split('[').filter(|i| !i.is_empty()) {
+            current = current.get(index.strip_suffix(']')?.parse::<usize>().ok()?)?;
+        }
+    }
+    Some(current)
+}
+
+/// Check every rule against `response`, in order.
+pub fn evaluate(rules: &[String], response: &ExecutedResponse) -> Vec<AssertionResult> {
+    let json: Option<Value> = serde_json::from_str(&response.body).ok();
+    rules
+        .iter()
+        .map(|rule| {
+            let outcome = parse_rule(rule).map(|parsed| check(&parsed, response, json.as_ref()));
+            let (passed, detail) = match outcome {
+                Ok(Ok(())) => (true, None),
+                Ok(Err(detail)) => (false, Some(detail)),
+                Err(e) => (false, Some(format!("invalid rule: {}", e))),
+            };
+            AssertionResult { rule: rule.clone(), passed, detail }
+        })
+        .collect()
+}
+
+fn check(rule: &Rule, response: &ExecutedResponse, json: Option<&Value>) -> Result<(), String> {
+    match rule {
+        Rule::Status(op, code) => {
+            if op.holds(response.status, *code) { Ok(()) } else { Err(format!("got {}", response.status)) }
+        }
+        Rule::BodyContains(text) => {
+            if response.body.contains(text.as_str()) { Ok(()) } else { Err(String::from("not found in body")) }
+        }
+        Rule::HeaderExists(name) => {
+            if response.headers.iter().any(|(k, _)| k.eq_ignore_ascii_case(name)) {
+                Ok(())
+            } else {
+                Err(String::from("header missing"))
+            }
+        }
+        Rule::JsonExists(path) => {
+            let json = json.ok_or("body is not JSON")?;
+            json_path(json, path).map(|_| ()).ok_or_else(|| String::from("path not found"))
+        }
+        Rule::JsonEquals(path, expected) => {
+            let json = json.ok_or("body is not JSON")?;
+            match json_path(json, path) {
+                Some(actual) if actual == expected => Ok(()),
+                Some(actual) => Err(format!("got {}", actual)),
+                None => Err(String::from("path not found")),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{evaluate, json_path, parse_rule, Comparison, Rule};
+    use crate::api::ExecutedResponse;
+    use serde_json::json;
+
+    #[test]
+    fn parses_each_rule_type() {
+        assert_eq!(parse_rule("status == 200"), Ok(Rule::Status(Comparison::Eq, 200)));
+        assert_eq!(parse_rule(" status < 400 "), Ok(Rule::Status(Comparison::Lt, 400)));
+        assert_eq!(parse_rule(r#"body contains "ok""#), Ok(Rule::BodyContains(String::from("ok"))));
+        assert_eq!(parse_rule("header ETag exists"), Ok(Rule::HeaderExists(String::from("ETag"))));
+        assert_eq!(parse_rule("json data.id exists"), Ok(Rule::JsonExists(String::from("data.id"))));
+        assert_eq!(parse_rule("json data.count == 3"), Ok(Rule::JsonEquals(String::from("data.count"), json!(3))));
+        assert_eq!(parse_rule("json data.state == active"), Ok(Rule::JsonEquals(String::from("data.state"), json!("active"))));
+        assert!(parse_rule("status is 200").is_err());
+        assert!(parse_rule("latency < 5").is_err());
+    }
+
+    #[test]
+    fn json_paths_follow_keys_and_indices() {
+        let doc = json!({ "data": { "items": [{ "id": 1 }, { "id": 2 }] } });
+        assert_eq!(json_path(&doc, "data.items[1].id"), Some(&json!(2)));
+        assert_eq!(json_path(&doc, "$.data.items.0.id"), Some(&json!(1)));
+        assert_eq!(json_path(&doc, ""), Some(&doc));
+        assert_eq!(json_path(&doc, "data.missing"), None);
+        assert_eq!(json_path(&doc, "data.items[5]"), None);
+    }
+
+    #[test]
+    fn evaluates_rules_against_a_response() {
+        let response = ExecutedResponse {
+            status: 404,
+            status_text: String::from("Not Found"),
+            headers: vec![(String::from("content-type"), String::from("application/json"))],
+            body: String::from(r#"{"error": "no such user"}"#),
+            redirects: Vec::new(),
+            version: String::from("HTTP/1.1"),
+            truncated_at: None,
+        };
+        let rules: Vec<String> = ["status == 200", "header Content-Type exists", "json error exists", "json data.id exists", "bogus"]
+            .iter()
+            .map(|r| r.to_string())
+            .collect();
+        let results = evaluate(&rules, &response);
+        let passed: Vec<bool> = results.iter().map(|r| r.passed).collect();
+        assert_eq!(passed, vec![false, true, true, false, false]);
+        assert_eq!(results[0].detail.as_deref(), Some("got 404"));
+        assert!(results[4].detail.as_deref().is_some_and(|d| d.starts_with("invalid rule")));
+    }
+}
@@ -14,6 +14,293 @@ pub struct Config {
     pub favorite_requests: Vec<FavoriteRequest>,
     #[serde(default)]
     pub last_state: Option<LastState>,
+    #[serde(default)]
+    pub settings: Settings,
+    /// Environment last used with each collection (collection uid -> environment uid).
+    #[serde(default)]
+    pub collection_environments: std::collections::HashMap<String, String>,
+    #[serde(default)]
+    pub oauth: Option<OAuthConfig>,
+    #[serde(default, skip_serializing_if = "ConfirmExempt::is_empty")]
+    pub confirm_exempt: ConfirmExempt,
+    /// Execution overrides per workspace id.
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    pub workspace_settings: std::collections::HashMap<String, WorkspaceSettings>,
+    /// Recently executed collection requests, most recent first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub recent_requests: Vec<RecentRequest>,
+    /// How each collection's Requests pane is sorted (collection uid -> sort).
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    pub request_sort: std::collections::HashMap<String, RequestSort>,
+    /// Show only names in the Requests pane (no method, favorite or edit
+    /// markers, no folder counts).
+    #[serde(default)]
+    pub compact_requests: bool,
+    /// Response checks run after a request executes, `[[assertions]]`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub assertions: Vec<AssertionSet>,
+}
+
+/// How many executed requests `recent_requests` keeps.
+pub const MAX_RECENT_REQUESTS: usize = 20;
+
+/// Per-workspace overrides of how requests are executed, stored under
+/// `[workspace_settings."<workspace id>"]`. Unset fields fall back to
+/// `[settings]`.
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct WorkspaceSettings {
+    /// Proxy URL; an empty string turns off a global proxy for this workspace.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub insecure_tls: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+}
+
+/// The effective execution settings for one workspace.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExecutionSettings {
+    pub proxy: Option<String>,
+    pub insecure_tls: bool,
+    pub timeout_secs: u64,
+}
+
+/// Requests that run without the y/n confirmation normally shown for
+/// POST/PUT/PATCH/DELETE, stored under `[confirm_exempt]` in config.toml.
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct ConfirmExempt {
+    /// Collection names or uids.
+    #[serde(default)]
+    pub collections: Vec<String>,
+    /// Folder names; every request below a matching folder is exempt.
+    #[serde(default)]
+    pub folders: Vec<String>,
+    /// Resolved URL patterns where `*` matches anything, e.g. `http://localhost:*`.
+    #[serde(default)]
+    pub urls: Vec<String>,
+}
+
+impl ConfirmExempt {
+    pub fn is_empty(&self) -> bool {
+        self.collections.is_empty() && self.folders.is_empty() && self.urls.is_empty()
+    }
+
+    /// Whether a request in `collection` (uid, name), below `folders`, sending
+    /// to `url` skips the confirmation.
+    pub fn is_exempt(&self, collection: (&str, &str), folders: &[&str], url: &str) -> bool {
+        self.collections.iter().any(|c| c == collection.0 || c == collection.1)
+            || self.folders.iter().any(|f| folders.contains(&f.as_str()))
+            || self.urls.iter().any(|pattern| wildcard_match(pattern, url))
+    }
+}
+
+/// Match `text` against `pattern`, where `*` matches any run of characters
+/// (including none) and everything else matches literally.
+pub fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        // No `*` at all: the whole text must match
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(at) => rest = &rest[at + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+/// Display order of the Requests pane. Sorting never changes the order
+/// stored in Postman.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum RequestSort {
+    /// Postman's stored order.
+    #[default]
+    Original,
+    Name,
+    Method,
+}
+
+impl RequestSort {
+    pub fn next(self) -> Self {
+        match self {
+            RequestSort::Original => RequestSort::Name,
+            RequestSort::Name => RequestSort::Method,
+            RequestSort::Method => RequestSort::Original,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            RequestSort::Original => "original order",
+            RequestSort::Name => "name",
+            RequestSort::Method => "method",
+        }
+    }
+}
+
+/// HTTP version executed requests are sent with. `auto` lets the client
+/// negotiate (HTTP/2 over TLS when the server offers it).
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum HttpVersion {
+    #[default]
+    Auto,
+    Http1,
+    Http2,
+}
+
+impl HttpVersion {
+    pub fn label(self) -> &'static str {
+        match self {
+            HttpVersion::Auto => "auto",
+            HttpVersion::Http1 => "HTTP/1.1",
+            HttpVersion::Http2 => "HTTP/2",
+        }
+    }
+}
+
+/// Terminal background the colors are chosen for; `auto` reads it from
+/// `COLORFGBG` and assumes dark when that isn't set.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Background {
+    #[default]
+    Auto,
+    Dark,
+    Light,
+}
+
+/// Assertion rules for one request, stored as `[[assertions]]` in config.toml.
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct AssertionSet {
+    /// The request's name or Postman id.
+    pub request: String,
+    /// Collection name or uid, to tell apart requests with the same name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub collection: Option<String>,
+    /// Rules such as `status == 200` or `json data.id exists`.
+    #[serde(default)]
+    pub rules: Vec<String>,
+}
+
+/// OAuth 2.0 client-credentials grant, stored under `[oauth]` in config.toml.
+/// Values may contain `{{variables}}`, resolved against the active environment.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct OAuthConfig {
+    pub token_url: String,
+    pub client_id: String,
+    pub client_secret: String,
+    #[serde(default)]
+    pub scope: String,
+    /// Variable the fetched `access_token` is exposed as, e.g. `{{access_token}}`.
+    #[serde(default = "default_token_variable")]
+    pub variable: String,
+}
+
+fn default_token_variable() -> String {
+    String::from("access_token")
+}
+
+/// General behaviour settings, stored under `[settings]` in config.toml.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct Settings {
+    /// Stop a folder run at the first failing request (transport error or
+    /// 4xx/5xx status) instead of running the rest.
+    #[serde(default)]
+    pub stop_on_failure: bool,
+    /// Request timeout used when a request has no timeout of its own.
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+    /// Append every executed request (secrets redacted) to `requests.log`.
+    #[serde(default)]
+    pub log_requests: bool,
+    /// Size in bytes at which `error.log`/`requests.log` are rotated.
+    #[serde(default = "default_max_log_bytes")]
+    pub max_log_bytes: u64,
+    /// Largest response body read into memory; anything past it is dropped
+    /// and the response marked truncated.
+    #[serde(default = "default_max_response_bytes")]
+    pub max_response_bytes: u64,
+    /// Proxy for executed requests (Postman API calls don't use it).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+    /// Accept invalid or self-signed TLS certificates on executed requests.
+    #[serde(default)]
+    pub insecure_tls: bool,
+    /// Follow redirects on executed requests (the hops are listed above the
+    /// response). When off, the 3xx response itself is shown.
+    #[serde(default = "default_follow_redirects")]
+    pub follow_redirects: bool,
+    /// Headers added to every executed request that doesn't set a header of
+    /// the same name. Never saved into the collection.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub default_headers: Vec<(String, String)>,
+    /// HTTP version for executed requests, unless a request sets its own.
+    #[serde(default)]
+    pub http_version: HttpVersion,
+    /// Before saving environment variables, check whether the environment
+    /// changed in Postman since it was loaded, and ask before overwriting.
+    #[serde(default = "default_check_environment_conflicts")]
+    pub check_environment_conflicts: bool,
+    /// Pick colors for a light or dark terminal background.
+    #[serde(default)]
+    pub background: Background,
+    /// Re-indent JSON request bodies when a local edit is stored.
+    #[serde(default)]
+    pub prettify_json_bodies: bool,
+    /// Ask before executing any request, not only POST/PUT/PATCH/DELETE.
+    #[serde(default)]
+    pub confirm_all_executions: bool,
+}
+
+fn default_follow_redirects() -> bool {
+    true
+}
+
+fn default_check_environment_conflicts() -> bool {
+    true
+}
+
+fn default_max_log_bytes() -> u64 {
+    5 * 1024 * 1024
+}
+
+fn default_max_response_bytes() -> u64 {
+    50 * 1024 * 1024
+}
+
+fn default_timeout_secs() -> u64 {
+    30
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            stop_on_failure: false,
+            timeout_secs: default_timeout_secs(),
+            log_requests: false,
+            max_log_bytes: default_max_log_bytes(),
+            max_response_bytes: default_max_response_bytes(),
+            proxy: None,
+            insecure_tls: false,
+            follow_redirects: default_follow_redirects(),
+            default_headers: Vec::new(),
+            http_version: HttpVersion::Auto,
+            check_environment_conflicts: true,
+            background: Background::Auto,
+            prettify_json_bodies: false,
+            confirm_all_executions: false,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
@@ -24,6 +311,23 @@ pub struct FavoriteRequest {
     /// before the owning collection has been loaded this session.
     #[serde(default)]
     pub name: String,
+    /// Postman item id. Paths shift when the tree is edited, so when present
+    /// the id is what identifies the request; `path` is refreshed from it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+/// A collection request that was executed, for the recently-executed list.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct RecentRequest {
+    pub collection_uid: String,
+    pub path: Vec<usize>,
+    pub name: String,
+    /// Postman item id, preferred over `path` when reopening.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    /// Local time of the execution, `YYYY-MM-DD HH:MM`.
+    pub executed_at: String,
 }
 
 /// A locally edited request that hasn't been synced to Postman
@@ -36,21 +340,65 @@ pub struct LocalEdit {
     pub url: String,
     #[serde(default)]
     pub body: String,
+    /// Raw body language (`json`, `xml`, ...), sent to Postman as
+    /// `body.options.raw.language`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body_language: Option<String>,
+    /// Query params disabled in the query editor, kept out of `url` but
+    /// saved to Postman as disabled.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub disabled_query: Vec<crate::api::QueryParam>,
+    /// Headers set in the headers editor, replacing the request's own;
+    /// None keeps Postman's.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub headers: Option<Vec<crate::api::Header>>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct LastState {
     pub collection_uid: String,
     pub request_path: Vec<usize>,
+    /// Postman id of the last request, preferred over `request_path`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
     #[serde(default)]
     pub environment_uid: Option<String>,
     #[serde(default)]
     pub workspace_id: Option<String>,
+    /// Pane focused at exit (`collections`, `requests`, `favorites`, ...).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub focused_pane: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct PostmanConfig {
     pub api_key: String,
+    /// Root of the Postman API, for enterprise installations on their own
+    /// domain.
+    #[serde(default = "default_api_base_url")]
+    pub api_base_url: String,
+}
+
+pub const DEFAULT_API_BASE_URL: &str = "https://api.getpostman.com";
+
+fn default_api_base_url() -> String {
+    String::from(DEFAULT_API_BASE_URL)
+}
+
+/// Check that a configured Postman API base URL is a plain https URL (no
+/// query or fragment), so endpoint paths can be appended to it.
+pub fn validate_api_base_url(url: &str) -> Result<(), String> {
+    let parsed = reqwest::Url::parse(url).map_err(|e| format!("api_base_url \"{}\" is not a valid URL: {}", url, e))?;
+    if parsed.scheme() != "https" {
+        return Err(format!("api_base_url \"{}\" must use https", url));
+    }
+    if parsed.host_str().is_none_or(str::is_empty) {
+        return Err(format!("api_base_url \"{}\" has no host", url));
+    }
+    if parsed.query().is_some() || parsed.fragment().is_some() {
+        return Err(format!("api_base_url \"{}\" must not have a query or fragment", url));
+    }
+    Ok(())
 }
 
 /// Postman personal API keys are prefixed with `PMAK-` and are 64 characters
@@ -104,6 +452,9 @@ impl Config {
 
         let config: Config = toml::from_str(&content)
             .with_context(|| "Failed to parse config file")?;
+        validate_api_base_url(&config.postman.api_base_url)
+            .map_err(anyhow::Error::msg)
+            .with_context(|| format!("Invalid [postman] settings in {}", path.display()))?;
 
         Ok(Some(config))
     }
@@ -137,16 +488,44 @@ impl Config {
 
     pub fn new(api_key: String) -> Self {
         Config {
-            postman: PostmanConfig { api_key },
+            postman: PostmanConfig { api_key, api_base_url: default_api_base_url() },
             favorites: Vec::new(),
             favorite_requests: Vec::new(),
             last_state: None,
+            settings: Settings::default(),
+            collection_environments: std::collections::HashMap::new(),
+            oauth: None,
+            confirm_exempt: ConfirmExempt::default(),
+            workspace_settings: std::collections::HashMap::new(),
+            recent_requests: Vec::new(),
+            request_sort: std::collections::HashMap::new(),
+            compact_requests: false,
+            assertions: Vec::new(),
         }
     }
 
-    pub fn add_favorite_request(&mut self, collection_uid: String, path: Vec<usize>, name: String) {
+    /// Proxy, TLS and timeout settings for requests run in `workspace_id`:
+    /// that workspace's overrides on top of `[settings]`.
+    pub fn execution_settings(&self, workspace_id: Option<&str>) -> ExecutionSettings {
+        let overrides = workspace_id.and_then(|id| self.workspace_settings.get(id));
+        let proxy = match overrides.and_then(|o| o.proxy.as_ref()) {
+            Some(proxy) => Some(proxy.clone()),
+            None => self.settings.proxy.clone(),
+        };
+        ExecutionSettings {
+            proxy: proxy.filter(|p| !p.trim().is_empty()),
+            insecure_tls: overrides
+                .and_then(|o| o.insecure_tls)
+                .unwrap_or(self.settings.insecure_tls),
+            timeout_secs: overrides
+                .and_then(|o| o.timeout_secs)
+                .unwrap_or(self.settings.timeout_secs),
+        }
+    }
+
+    pub fn add_favorite_request(&mut self, collection_uid: String, path: Vec<usize>, name: String, request_id: Option<String>) {
         if !self.is_request_favorite(&collection_uid, &path) {
-            self.favorite_requests.push(FavoriteRequest { collection_uid, path, name });
+            self.favorite_requests.push(FavoriteRequest { collection_uid, path, name, request_id });
         }
     }
 
@@ -158,15 +537,59 @@ impl Config {
         self.favorite_requests.iter().any(|f| f.collection_uid == collection_uid && f.path == path)
     }
 
-    pub fn set_last_state(&mut self, collection_uid: String, request_path: Vec<usize>, environment_uid: Option<String>, workspace_id: Option<String>) {
+    /// Put `entry` at the top of the recently-executed list, dropping any
+    /// older entry for the same request and anything past the cap.
+    pub fn record_recent_request(&mut self, entry: RecentRequest) {
+        self.recent_requests.retain(|r| {
+            r.collection_uid != entry.collection_uid
+                || match (&r.request_id, &entry.request_id) {
+                    (Some(a), Some(b)) => a != b,
+                    _ => r.path != entry.path,
+                }
+        });
+        self.recent_requests.insert(0, entry);
+        self.recent_requests.truncate(MAX_RECENT_REQUESTS);
+    }
+
+    /// The assertion rules for a request, from every `[[assertions]]` entry
+    /// naming it. `collection` and `request` are (uid, name) and (id, name).
+    pub fn assertion_rules(&self, collection: (&str, &str), request: (Option<&str>, &str)) -> Vec<String> {
+        self.assertions
+            .iter()
+            .filter(|set| set.request == request.1 || request.0 == Some(set.request.as_str()))
+            .filter(|set| set.collection.as_deref().is_none_or(|c| c == collection.0 || c == collection.1))
+            .flat_map(|set| set.rules.iter().cloned())
+            .collect()
+    }
+
+    pub fn set_last_state(&mut self, collection_uid: String, request_path: Vec<usize>, request_id: Option<String>, environment_uid: Option<String>, workspace_id: Option<String>) {
+        let focused_pane = self.last_state.as_ref().and_then(|s| s.focused_pane.clone());
         self.last_state = Some(LastState {
             collection_uid,
             request_path,
+            request_id,
             environment_uid,
             workspace_id,
+            focused_pane,
         });
     }
 
+    pub fn set_last_focused_pane(&mut self, pane: &str) {
+        match &mut self.last_state {
+            Some(state) => state.focused_pane = Some(pane.to_string()),
+            None => {
+                self.last_state = Some(LastState {
+                    collection_uid: String::new(),
+                    request_path: Vec::new(),
+                    request_id: None,
+                    environment_uid: None,
+                    workspace_id: None,
+                    focused_pane: Some(pane.to_string()),
+                });
+            }
+        }
+    }
+
     pub fn set_last_environment(&mut self, environment_uid: Option<String>) {
         if let Some(ref mut state) = self.last_state {
             state.environment_uid = environment_uid;
@@ -175,12 +598,36 @@ impl Config {
             self.last_state = Some(LastState {
                 collection_uid: String::new(),
                 request_path: Vec::new(),
+                request_id: None,
                 environment_uid: Some(uid),
                 workspace_id: None,
+                focused_pane: None,
             });
         }
     }
 
+    /// Remember the environment chosen while a collection was open. `None`
+    /// ("No Environment") forgets the pairing.
+    pub fn set_collection_environment(&mut self, collection_uid: &str, environment_uid: Option<String>) {
+        match environment_uid {
+            Some(uid) => {
+                self.collection_environments.insert(collection_uid.to_string(), uid);
+            }
+            None => {
+                self.collection_environments.remove(collection_uid);
+            }
+        }
+    }
+
+    /// The environment to use for a collection: the one last paired with it,
+    /// falling back to the globally last-used environment.
+    pub fn environment_for_collection(&self, collection_uid: &str) -> Option<String> {
+        self.collection_environments
+            .get(collection_uid)
+            .cloned()
+            .or_else(|| self.last_state.as_ref().and_then(|s| s.environment_uid.clone()))
+    }
+
     pub fn set_last_workspace(&mut self, workspace_id: Option<String>) {
         if let Some(ref mut state) = self.last_state {
             state.workspace_id = workspace_id;
@@ -193,8 +640,10 @@ impl Config {
             self.last_state = Some(LastState {
                 collection_uid: String::new(),
                 request_path: Vec::new(),
+                request_id: None,
                 environment_uid: None,
                 workspace_id: Some(id),
+                focused_pane: None,
             });
         }
     }
@@ -218,6 +667,52 @@ impl Config {
 #[derive(Debug, Deserialize, Serialize, Default)]
 pub struct LocalEditsStore {
     pub edits: Vec<LocalEdit>,
+    /// Per-request timeouts. Kept apart from `edits` so they survive saving
+    /// the request to Postman (which has no field for them).
+    #[serde(default)]
+    pub timeouts: Vec<RequestTimeout>,
+    /// Per-request body files, kept apart from `edits` for the same reason.
+    #[serde(default)]
+    pub body_files: Vec<RequestBodyFile>,
+    /// Per-request HTTP versions, likewise.
+    #[serde(default)]
+    pub http_versions: Vec<RequestHttpVersion>,
+    /// Per-request notes. Never sent to Postman.
+    #[serde(default)]
+    pub notes: Vec<RequestNote>,
+}
+
+/// A lazypost-only timeout override for one request.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct RequestTimeout {
+    pub collection_uid: String,
+    pub path: Vec<usize>,
+    pub secs: u64,
+}
+
+/// A lazypost-only body for one request, read from `file` at send time
+/// instead of being stored inline.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct RequestBodyFile {
+    pub collection_uid: String,
+    pub path: Vec<usize>,
+    pub file: String,
+}
+
+/// A lazypost-only HTTP version override for one request.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct RequestHttpVersion {
+    pub collection_uid: String,
+    pub path: Vec<usize>,
+    pub version: HttpVersion,
+}
+
+/// A lazypost-only note on one request ("flaky on staging").
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct RequestNote {
+    pub collection_uid: String,
+    pub path: Vec<usize>,
+    pub note: String,
 }
 
 impl LocalEditsStore {
@@ -263,16 +758,9 @@ impl LocalEditsStore {
     }
 
     /// Add or update a local edit
-    pub fn set_edit(&mut self, collection_uid: String, path: Vec<usize>, name: String, method: String, url: String, body: String) {
-        self.edits.retain(|e| !(e.collection_uid == collection_uid && e.path == path));
-        self.edits.push(LocalEdit {
-            collection_uid,
-            path,
-            name,
-            method,
-            url,
-            body,
-        });
+    pub fn set_edit(&mut self, edit: LocalEdit) {
+        self.edits.retain(|e| !(e.collection_uid == edit.collection_uid && e.path == edit.path));
+        self.edits.push(edit);
     }
 
     /// Remove a local edit
@@ -289,6 +777,99 @@ impl LocalEditsStore {
     pub fn has_edit(&self, collection_uid: &str, path: &[usize]) -> bool {
         self.edits.iter().any(|e| e.collection_uid == collection_uid && e.path == path)
     }
+
+    /// Remove every local edit in a collection, returning how many there were.
+    /// Per-request timeouts, body files, HTTP versions and notes are kept.
+    pub fn clear_collection(&mut self, collection_uid: &str) -> usize {
+        let before = self.edits.len();
+        self.edits.retain(|e| e.collection_uid != collection_uid);
+        before - self.edits.len()
+    }
+
+    /// Check if any request in a collection has a local edit
+    pub fn has_edits_in_collection(&self, collection_uid: &str) -> bool {
+        self.edits.iter().any(|e| e.collection_uid == collection_uid)
+    }
+
+    /// Get a request's custom timeout in seconds
+    pub fn get_timeout(&self, collection_uid: &str, path: &[usize]) -> Option<u64> {
+        self.timeouts
+            .iter()
+            .find(|t| t.collection_uid == collection_uid && t.path == path)
+            .map(|t| t.secs)
+    }
+
+    /// Set (or with `None`, clear) a request's custom timeout
+    pub fn set_timeout(&mut self, collection_uid: &str, path: &[usize], secs: Option<u64>) {
+        self.timeouts.retain(|t| !(t.collection_uid == collection_uid && t.path == path));
+        if let Some(secs) = secs {
+            self.timeouts.push(RequestTimeout {
+                collection_uid: collection_uid.to_string(),
+                path: path.to_vec(),
+                secs,
+            });
+        }
+    }
+
+    /// Get a request's own HTTP version
+    pub fn get_http_version(&self, collection_uid: &str, path: &[usize]) -> Option<HttpVersion> {
+        self.http_versions
+            .iter()
+            .find(|v| v.collection_uid == collection_uid && v.path == path)
+            .map(|v| v.version)
+    }
+
+    /// Set (or with `None`, clear) a request's own HTTP version
+    pub fn set_http_version(&mut self, collection_uid: &str, path: &[usize], version: Option<HttpVersion>) {
+        self.http_versions.retain(|v| !(v.collection_uid == collection_uid && v.path == path));
+        if let Some(version) = version {
+            self.http_versions.push(RequestHttpVersion {
+                collection_uid: collection_uid.to_string(),
+                path: path.to_vec(),
+                version,
+            });
+        }
+    }
+
+    /// Get a request's note
+    pub fn get_note(&self, collection_uid: &str, path: &[usize]) -> Option<&str> {
+        self.notes
+            .iter()
+            .find(|n| n.collection_uid == collection_uid && n.path == path)
+            .map(|n| n.note.as_str())
+    }
+
+    /// Set (or with an empty note, clear) a request's note
+    pub fn set_note(&mut self, collection_uid: &str, path: &[usize], note: &str) {
+        self.notes.retain(|n| !(n.collection_uid == collection_uid && n.path == path));
+        if !note.trim().is_empty() {
+            self.notes.push(RequestNote {
+                collection_uid: collection_uid.to_string(),
+                path: path.to_vec(),
+                note: note.trim().to_string(),
+            });
+        }
+    }
+
+    /// Get the file a request's body is read from
+    pub fn get_body_file(&self, collection_uid: &str, path: &[usize]) -> Option<&str> {
+        self.body_files
+            .iter()
+            .find(|b| b.collection_uid == collection_uid && b.path == path)
+            .map(|b| b.file.as_str())
+    }
+
+    /// Set (or with `None`, clear) the file a request's body is read from
+    pub fn set_body_file(&mut self, collection_uid: &str, path: &[usize], file: Option<String>) {
+        self.body_files.retain(|b| !(b.collection_uid == collection_uid && b.path == path));
+        if let Some(file) = file.filter(|f| !f.trim().is_empty()) {
+            self.body_files.push(RequestBodyFile {
+                collection_uid: collection_uid.to_string(),
+                path: path.to_vec(),
+                file,
+            });
+        }
+    }
 }
 
 /// Remembered `{{placeholder}}` values entered in the params dialog, keyed by
@@ -358,6 +939,145 @@ impl ParamValuesStore {
     }
 }
 
+/// A response saved as the reference for regression checks, keyed by request
+/// (stored in ~/.local/share/lazypost/baselines.toml).
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct BaselineEntry {
+    pub collection_uid: String,
+    pub path: Vec<usize>,
+    pub status: u16,
+    pub body: String,
+    pub saved_at: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct BaselineStore {
+    #[serde(default)]
+    pub entries: Vec<BaselineEntry>,
+}
+
+impl BaselineStore {
+    pub fn file_path() -> Result<PathBuf> {
+        Ok(LocalEditsStore::data_dir()?.join("baselines.toml"))
+    }
+
+    pub fn load() -> Result<Self> {
+        let path = Self::file_path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read baselines file: {}", path.display()))?;
+        let store: BaselineStore = toml::from_str(&content)
+            .with_context(|| "Failed to parse baselines file")?;
+        Ok(store)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let data_dir = LocalEditsStore::data_dir()?;
+        fs::create_dir_all(&data_dir)
+            .with_context(|| format!("Failed to create data directory: {}", data_dir.display()))?;
+        let path = Self::file_path()?;
+        let content = toml::to_string_pretty(self)
+            .context("Failed to serialize baselines")?;
+        fs::write(&path, content)
+            .with_context(|| format!("Failed to write baselines file: {}", path.display()))?;
+        Ok(())
+    }
+
+    pub fn get(&self, collection_uid: &str, path: &[usize]) -> Option<&BaselineEntry> {
+        self.entries
+            .iter()
+            .find(|e| e.collection_uid == collection_uid && e.path == path)
+    }
+
+    /// Store `entry`, replacing any earlier baseline for the same request.
+    pub fn set(&mut self, entry: BaselineEntry) {
+        self.entries
+            .retain(|e| !(e.collection_uid == entry.collection_uid && e.path == entry.path));
+        self.entries.push(entry);
+    }
+}
+
+/// How many earlier values are kept per environment variable.
+pub const MAX_VARIABLE_HISTORY: usize = 5;
+
+/// Earlier values of one environment variable, newest first (stored in
+/// ~/.local/share/lazypost/variable_history.toml).
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct VariableHistoryEntry {
+    pub environment_uid: String,
+    pub key: String,
+    #[serde(default)]
+    pub values: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct VariableHistoryStore {
+    #[serde(default)]
+    pub entries: Vec<VariableHistoryEntry>,
+}
+
+impl VariableHistoryStore {
+    pub fn file_path() -> Result<PathBuf> {
+        Ok(LocalEditsStore::data_dir()?.join("variable_history.toml"))
+    }
+
+    pub fn load() -> Result<Self> {
+        let path = Self::file_path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read variable history file: {}", path.display()))?;
+        let store: VariableHistoryStore = toml::from_str(&content)
+            .with_context(|| "Failed to parse variable history file")?;
+        Ok(store)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let data_dir = LocalEditsStore::data_dir()?;
+        fs::create_dir_all(&data_dir)
+            .with_context(|| format!("Failed to create data directory: {}", data_dir.display()))?;
+        let path = Self::file_path()?;
+        let content = toml::to_string_pretty(self)
+            .context("Failed to serialize variable history")?;
+        fs::write(&path, content)
+            .with_context(|| format!("Failed to write variable history file: {}", path.display()))?;
+        Ok(())
+    }
+
+    /// Earlier values of `key` in the environment, newest first.
+    pub fn get(&self, environment_uid: &str, key: &str) -> &[String] {
+        self.entries
+            .iter()
+            .find(|e| e.environment_uid == environment_uid && e.key == key)
+            .map_or(&[], |e| e.values.as_slice())
+    }
+
+    /// Remember `value` as the newest earlier value of `key`, dropping any
+    /// older copy of it and anything past [`MAX_VARIABLE_HISTORY`].
+    pub fn record(&mut self, environment_uid: &str, key: &str, value: String) {
+        let position = self.entries
+            .iter()
+            .position(|e| e.environment_uid == environment_uid && e.key == key);
+        let entry = match position {
+            Some(i) => &mut self.entries[i],
+            None => {
+                self.entries.push(VariableHistoryEntry {
+                    environment_uid: environment_uid.to_string(),
+                    key: key.to_string(),
+                    values: Vec::new(),
+                });
+                self.entries.last_mut().expect("just pushed")
+            }
+        };
+        entry.values.retain(|v| *v != value);
+        entry.values.insert(0, value);
+        entry.values.truncate(MAX_VARIABLE_HISTORY);
+    }
+}
+
 /// On-disk cache of the last-seen workspace/collection/environment lists, used
 /// to paint the UI instantly at startup while fresh data loads in the
 /// background. Only list metadata (names/uids) is cached here — environment
@@ -407,7 +1127,7 @@ impl CacheStore {
 
 #[cfg(test)]
 mod tests {
-    use super::validate_api_key;
+    use super::{validate_api_base_url, validate_api_key, wildcard_match, Config, ConfirmExempt, HttpVersion, LocalEdit, LocalEditsStore, RecentRequest, VariableHistoryStore, WorkspaceSettings, MAX_RECENT_REQUESTS, MAX_VARIABLE_HISTORY};
 
     #[test]
     fn accepts_well_formed_key() {
@@ -431,4 +1151,195 @@ mod tests {
         // The real-world failure: a 10-char truncated paste.
         assert!(validate_api_key("PMAK-1234").is_err());
     }
+
+    #[test]
+    fn api_base_url_must_be_plain_https() {
+        assert!(validate_api_base_url("https://api.getpostman.com").is_ok());
+        assert!(validate_api_base_url("https://postman.example.com/api/").is_ok());
+        assert!(validate_api_base_url("http://postman.example.com").is_err());
+        assert!(validate_api_base_url("postman.example.com").is_err());
+        assert!(validate_api_base_url("https://postman.example.com/?x=1").is_err());
+    }
+
+    #[test]
+    fn collection_environment_falls_back_to_last_used() {
+        let mut config = Config::new(String::new());
+        config.set_last_environment(Some("env-global".to_string()));
+        config.set_collection_environment("col-a", Some("env-staging".to_string()));
+
+        assert_eq!(config.environment_for_collection("col-a").as_deref(), Some("env-staging"));
+        assert_eq!(config.environment_for_collection("col-b").as_deref(), Some("env-global"));
+
+        config.set_collection_environment("col-a", None);
+        assert_eq!(config.environment_for_collection("col-a").as_deref(), Some("env-global"));
+    }
+
+    #[test]
+    fn path_only_favorites_still_load() {
+        let toml = r#"
+            collection_uid = "col-a"
+            path = [0, 2]
+            name = "Get user"
+        "#;
+        let fav: super::FavoriteRequest = toml::from_str(toml).unwrap();
+        assert_eq!(fav.path, vec![0, 2]);
+        assert_eq!(fav.request_id, None);
+    }
+
+    #[test]
+    fn assertion_rules_match_by_name_or_id() {
+        let toml = r#"
+            [postman]
+            api_key = ""
+
+            [[assertions]]
+            request = "Get user"
+            rules = ["status == 200"]
+
+            [[assertions]]
+            request = "req-42"
+            collection = "Users API"
+            rules = ["json data.id exists"]
+        "#;
+        let config: Config = toml::from_str(toml).unwrap();
+        assert_eq!(
+            config.assertion_rules(("col-1", "Users API"), (Some("req-42"), "Get user")),
+            vec!["status == 200", "json data.id exists"]
+        );
+        assert_eq!(config.assertion_rules(("col-2", "Admin"), (Some("req-42"), "Get user")), vec!["status == 200"]);
+        assert!(config.assertion_rules(("col-1", "Users API"), (None, "List users")).is_empty());
+    }
+
+    #[test]
+    fn wildcard_patterns() {
+        assert!(wildcard_match("http://localhost:*", "http://localhost:8080/users"));
+        assert!(wildcard_match("*/test/*", "https://api.example.com/test/reset"));
+        assert!(wildcard_match("https://api.example.com/ping", "https://api.example.com/ping"));
+        assert!(!wildcard_match("https://api.example.com/ping", "https://api.example.com/ping/2"));
+        assert!(!wildcard_match("*.dev/*", "https://api.example.com/x"));
+        assert!(!wildcard_match("ab*ba", "aba"));
+    }
+
+    #[test]
+    fn exempt_by_collection_folder_or_url() {
+        let exempt = ConfirmExempt {
+            collections: vec!["Sandbox".to_string()],
+            folders: vec!["Fixtures".to_string()],
+            urls: vec!["http://localhost*".to_string()],
+        };
+        assert!(exempt.is_exempt(("col-1", "Sandbox"), &[], "https://prod/x"));
+        assert!(exempt.is_exempt(("col-2", "Prod"), &["Admin", "Fixtures"], "https://prod/x"));
+        assert!(exempt.is_exempt(("col-2", "Prod"), &[], "http://localhost:3000/reset"));
+        assert!(!exempt.is_exempt(("col-2", "Prod"), &["Admin"], "https://prod/x"));
+    }
+
+    #[test]
+    fn workspace_settings_override_globals() {
+        let mut config = Config::new(String::new());
+        config.settings.proxy = Some("http://corp-proxy:3128".to_string());
+        config.workspace_settings.insert(
+            "ws-public".to_string(),
+            WorkspaceSettings { proxy: Some(String::new()), insecure_tls: None, timeout_secs: Some(5) },
+        );
+
+        let public = config.execution_settings(Some("ws-public"));
+        assert_eq!(public.proxy, None);
+        assert_eq!(public.timeout_secs, 5);
+
+        let other = config.execution_settings(Some("ws-internal"));
+        assert_eq!(other.proxy.as_deref(), Some("http://corp-proxy:3128"));
+        assert_eq!(other.timeout_secs, 30);
+        assert!(!other.insecure_tls);
+    }
+
+    #[test]
+    fn recent_requests_move_to_front_and_stay_bounded() {
+        let entry = |id: usize| RecentRequest {
+            collection_uid: "col-a".to_string(),
+            path: vec![id],
+            name: format!("Request {}", id),
+            request_id: Some(format!("req-{}", id)),
+            executed_at: String::new(),
+        };
+        let mut config = Config::new(String::new());
+        for id in 0..MAX_RECENT_REQUESTS + 5 {
+            config.record_recent_request(entry(id));
+        }
+        assert_eq!(config.recent_requests.len(), MAX_RECENT_REQUESTS);
+
+        // Re-running a request moves it up instead of duplicating it, even
+        // when its path has shifted.
+        let mut moved = entry(10);
+        moved.path = vec![99];
+        config.record_recent_request(moved);
+        assert_eq!(config.recent_requests.len(), MAX_RECENT_REQUESTS);
+        assert_eq!(config.recent_requests[0].path, vec![99]);
+        assert_eq!(config.recent_requests.iter().filter(|r| r.name == "Request 10").count(), 1);
+    }
+
+    #[test]
+    fn http_version_overrides_are_per_request() {
+        let mut store = LocalEditsStore::default();
+        store.set_http_version("col", &[0, 1], Some(HttpVersion::Http1));
+        store.set_http_version("col", &[0, 1], Some(HttpVersion::Http2));
+        assert_eq!(store.get_http_version("col", &[0, 1]), Some(HttpVersion::Http2));
+        assert_eq!(store.http_versions.len(), 1);
+        assert_eq!(store.get_http_version("col", &[0]), None);
+
+        store.set_http_version("col", &[0, 1], None);
+        assert!(store.http_versions.is_empty());
+    }
+
+    #[test]
+    fn blank_notes_clear_the_note() {
+        let mut store = LocalEditsStore::default();
+        store.set_note("col", &[2], " flaky on staging ");
+        assert_eq!(store.get_note("col", &[2]), Some("flaky on staging"));
+        assert_eq!(store.get_note("other", &[2]), None);
+
+        store.set_note("col", &[2], "  ");
+        assert!(store.notes.is_empty());
+    }
+
+    #[test]
+    fn clearing_a_collection_keeps_other_collections_edits() {
+        let edit = |uid: &str, index: usize| LocalEdit {
+            collection_uid: uid.to_string(),
+            path: vec![index],
+            name: format!("Request {}", index),
+            method: String::from("GET"),
+            url: String::new(),
+            body: String::new(),
+            body_language: None,
+            disabled_query: Vec::new(),
+            headers: None,
+        };
+        let mut store = LocalEditsStore::default();
+        store.set_edit(edit("col-a", 0));
+        store.set_edit(edit("col-a", 1));
+        store.set_edit(edit("col-b", 0));
+        store.set_timeout("col-a", &[0], Some(5));
+
+        assert_eq!(store.clear_collection("col-a"), 2);
+        assert!(!store.has_edits_in_collection("col-a"));
+        assert!(store.has_edit("col-b", &[0]));
+        assert_eq!(store.get_timeout("col-a", &[0]), Some(5));
+        assert_eq!(store.clear_collection("col-a"), 0);
+    }
+
+    #[test]
+    fn variable_history_keeps_the_latest_distinct_values() {
+        let mut store = VariableHistoryStore::default();
+        for value in ["a", "b", "c", "b"] {
+            store.record("env", "host", value.to_string());
+        }
+        assert_eq!(store.get("env", "host"), ["b", "c", "a"]);
+        assert!(store.get("other", "host").is_empty());
+
+        for i in 0..10 {
+            store.record("env", "host", i.to_string());
+        }
+        assert_eq!(store.get("env", "host").len(), MAX_VARIABLE_HISTORY);
+        assert_eq!(store.get("env", "host")[0], "9");
+    }
 }
@@ -1,18 +1,76 @@
 use std::fs::{self, OpenOptions};
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 use chrono::Local;
 
+/// Size at which a log file is rotated; set from `max_log_bytes` at startup.
+static MAX_LOG_BYTES: AtomicU64 = AtomicU64::new(5 * 1024 * 1024);
+
+/// Number of rotated copies kept (`error.log.1`, `error.log.2`).
+const LOG_BACKUPS: usize = 2;
+
+pub fn set_max_log_bytes(bytes: u64) {
+    MAX_LOG_BYTES.store(bytes, Ordering::Relaxed);
+}
+
+/// If `path` has grown past `max_bytes`, shift it to `<name>.1` (pushing
+/// older copies to `.2`, dropping the oldest) so writing starts fresh.
+fn rotate_if_needed(path: &Path, max_bytes: u64) {
+    let size = match fs::metadata(path) {
+        Ok(meta) => meta.len(),
+        Err(_) => return,
+    };
+    if max_bytes == 0 || size < max_bytes {
+        return;
+    }
+
+    let backup = |n: usize| {
+        let mut name = path.as_os_str().to_owned();
+        name.push(format!(".{}", n));
+        PathBuf::from(name)
+    };
+    let _ = fs::remove_file(backup(LOG_BACKUPS));
+    for n in (1..LOG_BACKUPS).rev() {
+        let _ = fs::rename(backup(n), backup(n + 1));
+    }
+    let _ = fs::rename(path, backup(1));
+}
+
 fn log_path() -> Option<PathBuf> {
     dirs::config_dir().map(|p| p.join("lazypost").join("error.log"))
 }
 
+fn request_log_path() -> Option<PathBuf> {
+    dirs::data_local_dir().map(|p| p.join("lazypost").join("requests.log"))
+}
+
+/// Append one executed-request record to `requests.log` in the data dir as a
+/// line of JSON. Callers are responsible for redacting secrets first.
+pub fn log_request(entry: &serde_json::Value) {
+    if let Some(path) = request_log_path() {
+        if let Some(parent) = path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        rotate_if_needed(&path, MAX_LOG_BYTES.load(Ordering::Relaxed));
+
+        if let Ok(mut file) = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+        {
+            let _ = writeln!(file, "{}", entry);
+        }
+    }
+}
+
 pub fn log_error(context: &str, error: &str) {
     if let Some(path) = log_path() {
         // Ensure directory exists
         if let Some(parent) = path.parent() {
             let _ = fs::create_dir_all(parent);
         }
+        rotate_if_needed(&path, MAX_LOG_BYTES.load(Ordering::Relaxed));
 
         if let Ok(mut file) = OpenOptions::new()
             .create(true)
@@ -24,3 +82,33 @@ pub fn log_error(context: &str, error: &str) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::rotate_if_needed;
+    use std::fs;
+
+    #[test]
+    fn rotates_and_keeps_two_backups() {
+        let dir = std::env::temp_dir().join(format!("lazypost-log-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let log = dir.join("error.log");
+
+        for round in ["first", "second", "third"] {
+            fs::write(&log, round).unwrap();
+            rotate_if_needed(&log, 1);
+        }
+
+        assert!(!log.exists());
+        assert_eq!(fs::read_to_string(dir.join("error.log.1")).unwrap(), "third");
+        assert_eq!(fs::read_to_string(dir.join("error.log.2")).unwrap(), "second");
+        assert!(!dir.join("error.log.3").exists());
+
+        // Under the threshold nothing moves
+        fs::write(&log, "small").unwrap();
+        rotate_if_needed(&log, 1024);
+        assert!(log.exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
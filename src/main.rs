@@ -1,22 +1,30 @@
 mod api;
 mod app;
+mod assertions;
 mod config;
 mod logging;
+mod openapi;
+mod palette;
 mod ui;
+mod util;
+mod yaml;
 
 use std::io;
 use std::time::Duration;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::process::Command;
 use std::fs;
 use std::env;
 
 use anyhow::{Context, Result};
 use crossterm::{
-    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    event::{self, DisableBracketedPaste, EnableBracketedPaste, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::prelude::*;
+use serde::{de::DeserializeOwned, Serialize};
 
 use app::{App, EditableRequest, FocusedPane, InputMode};
 use config::{CacheStore, Config};
@@ -25,6 +33,8 @@ use logging::log_error;
 #[tokio::main]
 async fn main() -> Result<()> {
     let config = get_config()?;
+    logging::set_max_log_bytes(config.settings.max_log_bytes);
+    ui::theme::init(config.settings.background);
 
     let mut terminal = setup_terminal()?;
     let result = run_app(&mut terminal, config).await;
@@ -75,7 +85,7 @@ fn get_config() -> Result<Config> {
 fn setup_terminal() -> Result<Terminal<CrosstermBackend<io::Stdout>>> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
+    execute!(stdout, EnterAlternateScreen, EnableBracketedPaste)?;
     let backend = CrosstermBackend::new(stdout);
     let terminal = Terminal::new(backend)?;
     Ok(terminal)
@@ -83,7 +93,7 @@ fn setup_terminal() -> Result<Terminal<CrosstermBackend<io::Stdout>>> {
 
 fn restore_terminal(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<()> {
     disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    execute!(terminal.backend_mut(), DisableBracketedPaste, LeaveAlternateScreen)?;
     terminal.show_cursor()?;
     Ok(())
 }
@@ -103,25 +113,8 @@ async fn perform_save(
     // Try to use individual request endpoint if we have a request_id
     if let Some(ref req_id) = request_id {
         // Build the request object from the edited data
-        let request = api::Request {
-            method: pending.edited.method.clone(),
-            url: if pending.edited.url.is_empty() {
-                api::RequestUrl::Empty
-            } else {
-                api::RequestUrl::Simple(pending.edited.url.clone())
-            },
-            header: flat_item.request.as_ref().map(|r| r.header.clone()).unwrap_or_default(),
-            auth: flat_item.request.as_ref().and_then(|r| r.auth.clone()),
-            body: if pending.edited.body.is_empty() {
-                None
-            } else {
-                Some(api::RequestBody {
-                    mode: Some("raw".to_string()),
-                    raw: Some(pending.edited.body.clone()),
-                })
-            },
-            description: flat_item.request.as_ref().and_then(|r| r.description.clone()),
-        };
+        let original = flat_item.request.as_ref().context("Selected item is not a request")?;
+        let request = app::apply_local_edit(original, &pending.edited);
 
         // Use individual endpoint - avoids validation errors from other requests
         client.update_request(&collection_uid, req_id, &pending.edited.name, &request).await?;
@@ -129,7 +122,7 @@ async fn perform_save(
         // Fall back to bulk update if no request_id available
         let mut items = collection.item.clone();
         update_request_at_path(&mut items, &path, &pending.edited)?;
-        client.update_collection(&collection_uid, &collection.info, &items).await?;
+        client.update_collection_checked(&collection_uid, &collection.info, &collection.item, &collection.variable, &items, &collection.variable).await?;
     }
 
     // Reload collection to get updated state
@@ -138,6 +131,80 @@ async fn perform_save(
     Ok((updated, pending.edited.name))
 }
 
+/// Push a batch of local edits to Postman one at a time. A failure doesn't
+/// stop the batch: it is recorded and the remaining edits are still attempted.
+async fn perform_save_all(
+    client: api::PostmanClient,
+    collection_uid: String,
+    collection: Option<api::CollectionDetail>,
+    edits: Vec<config::LocalEdit>,
+) -> Result<app::SaveAllReport> {
+    let collection = collection.context("No collection loaded")?;
+    let mut items = collection.item.clone();
+    let mut saved = Vec::new();
+    let mut failed = Vec::new();
+
+    for edit in edits {
+        let edited = EditableRequest {
+            name: edit.name.clone(),
+            method: edit.method.clone(),
+            url: edit.url.clone(),
+            body: edit.body.clone(),
+            timeout_secs: None,
+            body_file: None,
+            body_language: edit.body_language.clone(),
+            http_version: None,
+            disabled_query: edit.disabled_query.clone(),
+            headers: edit.headers.clone(),
+        };
+
+        let outcome = match app::get_item_at_path(&items, &edit.path) {
+            Some((_, Some(request), Some(request_id))) => {
+                let request = app::apply_local_edit(&request, &edited);
+                let result = client.update_request(&collection_uid, &request_id, &edited.name, &request).await;
+                // Keep the working copy in step, so a later bulk update
+                // neither reverts this request nor sees it as a conflict
+                if result.is_ok()
+                    && let Err(e) = update_request_at_path(&mut items, &edit.path, &edited)
+                {
+                    log_error("save_all:working_copy", &e.to_string());
+                }
+                result
+            }
+            Some((_, Some(_), None)) => {
+                // No request id: fall back to a bulk update, keeping the
+                // working copy unchanged if Postman rejects it.
+                let mut updated_items = items.clone();
+                match update_request_at_path(&mut updated_items, &edit.path, &edited) {
+                    Ok(()) => {
+                        let result = client.update_collection_checked(&collection_uid, &collection.info, &items, &collection.variable, &updated_items, &collection.variable).await;
+                        if result.is_ok() {
+                            items = updated_items;
+                        }
+                        result
+                    }
+                    Err(e) => Err(e),
+                }
+            }
+            _ => Err(anyhow::anyhow!("Request no longer exists in the collection")),
+        };
+
+        match outcome {
+            Ok(()) => saved.push(edit.path),
+            Err(e) => failed.push((edit.name, format!("{:#}", e))),
+        }
+    }
+
+    // Reload to pick up the saved state; a failed reload isn't a failed save.
+    let updated = if saved.is_empty() {
+        None
+    } else {
+        client.get_collection(&collection_uid).await.ok()
+    };
+
+    Ok(app::SaveAllReport { saved, failed, updated })
+}
+
 /// Drive a spawned async task to completion while keeping the UI responsive and
 /// letting the user press Esc to cancel. Returns `Some(value)` on completion,
 /// or `None` if the task was cancelled or aborted.
@@ -145,14 +212,29 @@ async fn run_cancellable<T: Send + 'static>(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     app: &mut App,
     handle: tokio::task::JoinHandle<T>,
+) -> Result<Option<T>> {
+    run_cancellable_until_sent(terminal, app, handle, &AtomicBool::new(false)).await
+}
+
+/// `run_cancellable` for a task that writes to Postman: once it sets `sent`,
+/// Esc is ignored so the local tree can follow what Postman now has.
+async fn run_cancellable_until_sent<T: Send + 'static>(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    app: &mut App,
+    handle: tokio::task::JoinHandle<T>,
+    sent: &AtomicBool,
 ) -> Result<Option<T>> {
     loop {
         // Poll for Esc to cancel the in-flight task
         if event::poll(Duration::from_millis(50))? {
             if let Event::Key(key) = event::read()? {
                 if key.kind == KeyEventKind::Press && key.code == KeyCode::Esc {
-                    handle.abort();
-                    return Ok(None);
+                    if sent.load(Ordering::SeqCst) {
+                        app.status_message = String::from("Already sent to Postman - finishing...");
+                    } else {
+                        handle.abort();
+                        return Ok(None);
+                    }
                 }
             }
         }
@@ -165,16 +247,37 @@ async fn run_cancellable<T: Send + 'static>(
             };
         }
 
-        // Keep the loading popup visible while we wait
+        // Keep the loading popup visible (and its spinner moving) while we wait
+        app.tick();
         terminal.draw(|frame| ui::render(frame, app))?;
     }
 }
 
 
+/// Fetch the workspaces, collections and environments (plus the last-used
+/// collection and environment) in the background, as at startup.
+fn spawn_startup_refresh(app: &App) -> std::sync::mpsc::Receiver<app::RefreshData> {
+    let (refresh_tx, refresh_rx) = std::sync::mpsc::channel();
+    let (ws_id, coll_uid, env_uid) = match &app.config.last_state {
+        Some(s) => (
+            s.workspace_id.clone(),
+            if s.collection_uid.is_empty() { None } else { Some(s.collection_uid.clone()) },
+            app.config.environment_for_collection(&s.collection_uid),
+        ),
+        None => (None, None, None),
+    };
+    let client = app.client.clone();
+    tokio::spawn(async move {
+        let data = crate::app::fetch_startup_data(client, ws_id, coll_uid, env_uid).await;
+        let _ = refresh_tx.send(data);
+    });
+    refresh_rx
+}
+
 /// Kick off a non-blocking background refresh of the currently-loaded
 /// collection. The result is polled by the main loop, so the user can keep
 /// browsing and using the (cached) requests while it fetches.
-fn spawn_collection_refresh(app: &mut App) {
+fn spawn_collection_refresh(app: &mut App, reload: bool) {
     let uid = match &app.current_collection_uid {
         Some(uid) => uid.clone(),
         None => return,
@@ -185,7 +288,58 @@ fn spawn_collection_refresh(app: &mut App) {
     tokio::spawn(async move {
         let _ = tx.send(client.get_collection(&fetch_uid).await);
     });
-    app.pending_collection_refresh = Some(app::CollectionRefresh { uid, rx });
+    app.pending_collection_refresh = Some(app::CollectionRefresh { uid, reload, rx });
+}
+
+/// Fetch collection/environment counts for every workspace that doesn't have
+/// them yet, in the background. Each result is sent as soon as it arrives so
+/// the popup fills in row by row.
+fn spawn_workspace_counts(app: &mut App) {
+    if app.pending_workspace_counts.is_some() {
+        return;
+    }
+    let ids = app.workspaces_missing_counts();
+    if ids.is_empty() {
+        return;
+    }
+    let (tx, rx) = std::sync::mpsc::channel();
+    let client = app.client.clone();
+    tokio::spawn(async move {
+        for id in ids {
+            let result = client.get_workspace_counts(&id).await;
+            if tx.send((id, result)).is_err() {
+                break;
+            }
+        }
+    });
+    app.pending_workspace_counts = Some(rx);
+}
+
+/// Fetch every collection the global finder can't search yet, one after
+/// another in the background. Each is sent as it arrives so the matches
+/// fill in while the user types.
+fn spawn_finder_fetch(app: &mut App) {
+    if app.pending_finder_fetch.is_some() {
+        return;
+    }
+    let uids = app.collections_missing_detail();
+    if uids.is_empty() {
+        return;
+    }
+    if let Some(finder) = &mut app.finder {
+        finder.loading = uids.len();
+    }
+    let (tx, rx) = std::sync::mpsc::channel();
+    let client = app.client.clone();
+    tokio::spawn(async move {
+        for uid in uids {
+            let result = client.get_collection(&uid).await;
+            if tx.send((uid, result)).is_err() {
+                break;
+            }
+        }
+    });
+    app.pending_finder_fetch = Some(rx);
 }
 
 fn update_request_at_path(
@@ -193,7 +347,7 @@ fn update_request_at_path(
     path: &[usize],
     edited: &app::EditableRequest,
 ) -> Result<()> {
-    use api::{Item, RequestBody, RequestUrl};
+    use api::Item;
 
     if path.is_empty() {
         anyhow::bail!("Empty path");
@@ -210,20 +364,7 @@ fn update_request_at_path(
         match &mut items[index] {
             Item::Request(req_item) => {
                 req_item.name = edited.name.clone();
-                req_item.request.method = edited.method.clone();
-                req_item.request.url = if edited.url.is_empty() {
-                    RequestUrl::Empty
-                } else {
-                    RequestUrl::Simple(edited.url.clone())
-                };
-                req_item.request.body = if edited.body.is_empty() {
-                    None
-                } else {
-                    Some(RequestBody {
-                        mode: Some("raw".to_string()),
-                        raw: Some(edited.body.clone()),
-                    })
-                };
+                req_item.request = app::apply_local_edit(&req_item.request, edited);
                 Ok(())
             }
             Item::Folder(_) => {
@@ -240,10 +381,69 @@ fn update_request_at_path(
     }
 }
 
-fn edit_request_in_editor(
+/// The next key to handle: a command chosen in the palette (replayed as its
+/// key binding) or else a key event from the terminal.
+fn next_key(app: &mut App) -> Result<Option<KeyEvent>> {
+    if let Some(index) = app.pending_palette_command.take() {
+        let command = &palette::COMMANDS[index];
+        return Ok(Some(KeyEvent::new(command.code, command.modifiers)));
+    }
+    match event::read()? {
+        Event::Key(key) => Ok(Some(key)),
+        // Bracketed paste: the terminal delivers the whole text at once
+        Event::Paste(text) => {
+            app.paste_text(&text);
+            Ok(None)
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Leave the alternate screen and raw mode so an external program can use
+/// the terminal.
+fn suspend_tui(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<()> {
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), DisableBracketedPaste, LeaveAlternateScreen)?;
+    terminal.show_cursor()?;
+    Ok(())
+}
+
+/// Take the terminal back after `suspend_tui`.
+fn resume_tui(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<()> {
+    enable_raw_mode()?;
+    execute!(terminal.backend_mut(), EnterAlternateScreen, EnableBracketedPaste)?;
+    terminal.hide_cursor()?;
+    terminal.clear()?;
+    Ok(())
+}
+
+/// Open `content` in `$PAGER` (else `$EDITOR`/`$VISUAL`, else `less`) from a
+/// temp file with the given extension, suspending the TUI until it exits.
+fn view_in_pager(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, content: &str, extension: &str) -> Result<()> {
+    let temp_file = env::temp_dir().join(format!("lazypost_response.{}", extension));
+    fs::write(&temp_file, content).context("Failed to write temp file")?;
+
+    let viewer = ["PAGER", "EDITOR", "VISUAL"]
+        .iter()
+        .find_map(|var| env::var(var).ok().filter(|v| !v.trim().is_empty()))
+        .unwrap_or_else(|| "less".to_string());
+    // Commands may carry arguments, e.g. `less -R` or `code --wait`
+    let mut parts = viewer.split_whitespace();
+    let program = parts.next().unwrap_or("less");
+
+    suspend_tui(terminal)?;
+    let status = Command::new(program).args(parts).arg(&temp_file).status();
+    resume_tui(terminal)?;
+    fs::remove_file(&temp_file).ok();
+
+    status.with_context(|| format!("Failed to run {}", viewer))?;
+    Ok(())
+}
+
+fn edit_request_in_editor<T: Serialize + DeserializeOwned>(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
-    request: &EditableRequest,
-) -> Result<Option<EditableRequest>> {
+    request: &T,
+) -> Result<Option<T>> {
     // Create temp file with request data
     let temp_dir = env::temp_dir();
     let temp_file = temp_dir.join("lazypost_edit.toml");
@@ -254,10 +454,7 @@ fn edit_request_in_editor(
     fs::write(&temp_file, &content)
         .context("Failed to write temp file")?;
 
-    // Exit TUI mode
-    disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
-    terminal.show_cursor()?;
+    suspend_tui(terminal)?;
 
     // Get editor from environment
     let editor = env::var("EDITOR")
@@ -270,11 +467,7 @@ fn edit_request_in_editor(
         .status()
         .context(format!("Failed to run editor: {}", editor))?;
 
-    // Re-enter TUI mode
-    enable_raw_mode()?;
-    execute!(terminal.backend_mut(), EnterAlternateScreen)?;
-    terminal.hide_cursor()?;
-    terminal.clear()?;
+    resume_tui(terminal)?;
 
     if !status.success() {
         fs::remove_file(&temp_file).ok();
@@ -293,7 +486,7 @@ fn edit_request_in_editor(
     }
 
     // Parse edited content
-    let edited: EditableRequest = toml::from_str(&edited_content)
+    let edited: T = toml::from_str(&edited_content)
         .context("Failed to parse edited request. Check TOML syntax.")?;
 
     Ok(Some(edited))
@@ -304,6 +497,7 @@ async fn run_app(
     config: Config,
 ) -> Result<()> {
     let mut app = App::new(config);
+    app.apply_execution_settings();
 
     // Paint instantly from the on-disk cache (no network). Fresh data is
     // fetched in the background below and applied when it arrives.
@@ -315,23 +509,11 @@ async fn run_app(
     terminal.draw(|frame| ui::render(frame, &mut app))?;
 
     // Kick off the background refresh: a single batch of concurrent requests.
-    let (refresh_tx, refresh_rx) = std::sync::mpsc::channel();
-    let (ws_id, coll_uid, env_uid) = match &app.config.last_state {
-        Some(s) => (
-            s.workspace_id.clone(),
-            if s.collection_uid.is_empty() { None } else { Some(s.collection_uid.clone()) },
-            s.environment_uid.clone(),
-        ),
-        None => (None, None, None),
-    };
-    let client = app.client.clone();
-    tokio::spawn(async move {
-        let data = crate::app::fetch_startup_data(client, ws_id, coll_uid, env_uid).await;
-        let _ = refresh_tx.send(data);
-    });
+    let mut refresh_rx = spawn_startup_refresh(&app);
 
     let mut refreshed = false;
     loop {
+        app.tick();
         terminal.draw(|frame| ui::render(frame, &mut app))?;
 
         // Apply the background refresh result once it arrives (non-blocking).
@@ -348,8 +530,9 @@ async fn run_app(
             match refresh.rx.try_recv() {
                 Ok(result) => {
                     let uid = refresh.uid.clone();
+                    let reload = refresh.reload;
                     app.pending_collection_refresh = None;
-                    app.apply_collection_refresh(uid, result);
+                    app.apply_collection_refresh(uid, result, reload);
                 }
                 Err(std::sync::mpsc::TryRecvError::Empty) => {}
                 Err(std::sync::mpsc::TryRecvError::Disconnected) => {
@@ -358,20 +541,110 @@ async fn run_app(
             }
         }
 
-        if event::poll(Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
+        // Log messages from an open WebSocket / event stream
+        app.poll_stream_events();
+
+        // Fill in workspace counts as they arrive
+        if let Some(rx) = &app.pending_workspace_counts {
+            let mut received = Vec::new();
+            let mut finished = false;
+            loop {
+                match rx.try_recv() {
+                    Ok(entry) => received.push(entry),
+                    Err(std::sync::mpsc::TryRecvError::Empty) => break,
+                    Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                        finished = true;
+                        break;
+                    }
+                }
+            }
+            for (id, result) in received {
+                app.apply_workspace_counts(id, result);
+            }
+            if finished {
+                app.pending_workspace_counts = None;
+            }
+        }
+
+        // Search collections fetched for the global finder as they arrive
+        if let Some(rx) = &app.pending_finder_fetch {
+            let mut received = Vec::new();
+            let mut finished = false;
+            loop {
+                match rx.try_recv() {
+                    Ok(entry) => received.push(entry),
+                    Err(std::sync::mpsc::TryRecvError::Empty) => break,
+                    Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                        finished = true;
+                        break;
+                    }
+                }
+            }
+            for (uid, result) in received {
+                app.apply_finder_collection(uid, result);
+            }
+            if finished {
+                app.pending_finder_fetch = None;
+            }
+        }
+
+        if app.pending_palette_command.is_some() || event::poll(Duration::from_millis(100))? {
+            if let Some(key) = next_key(&mut app)? {
                 if key.kind != KeyEventKind::Press {
                     continue;
                 }
 
+                // Second key of the report prompt (copy or save)
+                if app.report_pending {
+                    let choice = match key.code {
+                        KeyCode::Char(c) => c,
+                        _ => ' ',
+                    };
+                    app.finish_report(choice);
+                    continue;
+                }
+
+                // Second key of the request export prompt (copy or save)
+                if app.request_export_pending {
+                    let choice = match key.code {
+                        KeyCode::Char(c) => c,
+                        _ => ' ',
+                    };
+                    app.finish_request_export(choice);
+                    continue;
+                }
+
+                // Second key of `z` + digit (collapse the JSON tree to a depth)
+                if app.json_depth_pending {
+                    let digit = match key.code {
+                        KeyCode::Char(c) => c,
+                        _ => ' ',
+                    };
+                    app.finish_json_depth(digit);
+                    continue;
+                }
+
+                // Ctrl+v pastes the clipboard into whichever text field is active
+                if key.code == KeyCode::Char('v')
+                    && key.modifiers.contains(KeyModifiers::CONTROL)
+                    && app.text_field_active()
+                {
+                    app.paste_from_clipboard();
+                    continue;
+                }
+
                 match app.input_mode {
                     InputMode::Normal => {
                         match key.code {
                             KeyCode::Char('q')
                                 if key.modifiers.contains(KeyModifiers::CONTROL) =>
                             {
+                                app.save_focused_pane();
                                 return Ok(());
                             }
+                            KeyCode::Esc => {
+                                app.clear_error();
+                            }
                             // Pane switching with number keys
                             KeyCode::Char('1') => {
                                 app.set_focus(FocusedPane::Collections);
@@ -386,31 +659,48 @@ async fn run_app(
                                 app.set_focus(FocusedPane::Preview);
                             }
                             KeyCode::Char('5') => {
-                                if app.response.is_some() {
+                                if app.response.is_some() || app.stream.is_some() {
                                     app.set_focus(FocusedPane::Response);
                                 }
                             }
                             // Navigation
                             KeyCode::Char('j') | KeyCode::Down => {
-                                if app.focused_pane == FocusedPane::Response && app.json_viewer_state.is_some() {
+                                if app.focused_pane == FocusedPane::Response && app.stream.is_some() {
+                                    app.stream_scroll(1);
+                                } else if app.focused_pane == FocusedPane::Response && app.json_viewer_state.is_some() {
                                     app.json_viewer_down();
+                                } else if app.focused_pane == FocusedPane::Response {
+                                    app.response_scroll_down(1);
                                 } else {
                                     app.move_down();
                                 }
                             }
                             KeyCode::Char('k') | KeyCode::Up => {
-                                if app.focused_pane == FocusedPane::Response && app.json_viewer_state.is_some() {
+                                if app.focused_pane == FocusedPane::Response && app.stream.is_some() {
+                                    app.stream_scroll(-1);
+                                } else if app.focused_pane == FocusedPane::Response && app.json_viewer_state.is_some() {
                                     app.json_viewer_up();
+                                } else if app.focused_pane == FocusedPane::Response {
+                                    app.response_scroll_up(1);
                                 } else {
                                     app.move_up();
                                 }
                             }
+                            // Scroll the request preview a page at a time
+                            KeyCode::PageDown if app.focused_pane == FocusedPane::Preview => {
+                                app.preview_scroll_down(app.preview_view_rows.max(1));
+                            }
+                            KeyCode::PageUp if app.focused_pane == FocusedPane::Preview => {
+                                app.preview_scroll_up(app.preview_view_rows.max(1));
+                            }
                             // Half-page jump down/up (vim Ctrl-D / Ctrl-U)
                             KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                                 if app.focused_pane == FocusedPane::Response && app.json_viewer_state.is_some() {
                                     for _ in 0..10 {
                                         app.json_viewer_down();
                                     }
+                                } else if app.focused_pane == FocusedPane::Response {
+                                    app.response_scroll_down(10);
                                 } else {
                                     app.jump_down();
                                 }
@@ -420,10 +710,45 @@ async fn run_app(
                                     for _ in 0..10 {
                                         app.json_viewer_up();
                                     }
+                                } else if app.focused_pane == FocusedPane::Response {
+                                    app.response_scroll_up(10);
                                 } else {
                                     app.jump_up();
                                 }
                             }
+                            // Move the selected request/folder among its siblings
+                            KeyCode::Char('J') if app.focused_pane == FocusedPane::Requests => {
+                                app.move_selected_item(1);
+                            }
+                            KeyCode::Char('K') if app.focused_pane == FocusedPane::Requests => {
+                                app.move_selected_item(-1);
+                            }
+                            // Jump to first / last item, or to the parent folder
+                            KeyCode::Char('g') => {
+                                app.jump_to_first();
+                            }
+                            KeyCode::Char('G') => {
+                                app.jump_to_last();
+                            }
+                            // Command palette
+                            KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                                app.open_palette();
+                            }
+                            KeyCode::Char(':') => {
+                                app.open_palette();
+                            }
+                            // Find a request or folder in any collection
+                            KeyCode::Char('f') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                                app.open_global_finder();
+                                spawn_finder_fetch(&mut app);
+                            }
+                            KeyCode::Char('p') => {
+                                app.jump_to_parent();
+                            }
+                            // Toggle, add, edit or delete the request's headers
+                            KeyCode::Char('h') if app.focused_pane == FocusedPane::Preview => {
+                                app.start_header_editor();
+                            }
                             // JSON viewer collapse/expand (Response pane only)
                             KeyCode::Char('h') | KeyCode::Left => {
                                 if app.focused_pane == FocusedPane::Response && app.json_viewer_state.is_some() {
@@ -446,19 +771,76 @@ async fn run_app(
                                     app.json_viewer_expand_all();
                                 }
                             }
+                            // z + digit: expand the JSON tree to that depth only
+                            KeyCode::Char('z') if app.focused_pane == FocusedPane::Response => {
+                                app.start_json_depth();
+                            }
+                            // Show the response as JSON, XML or text regardless of its Content-Type
+                            KeyCode::Char('r') if app.focused_pane == FocusedPane::Response && !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                                app.cycle_response_view();
+                            }
+                            // Narrow the JSON tree to one value type
+                            KeyCode::Char('t') if app.focused_pane == FocusedPane::Response => {
+                                app.json_viewer_cycle_type_filter();
+                            }
                             // JSON viewer yank (copy) to clipboard
-                            KeyCode::Char('y') => {
-                                if app.focused_pane == FocusedPane::Response && app.json_viewer_state.is_some() {
+                            KeyCode::Char('y') if app.focused_pane == FocusedPane::Response => {
+                                if app.json_viewer_state.is_some() {
                                     app.json_viewer_yank();
+                                } else {
+                                    app.copy_response_body();
                                 }
                             }
+                            KeyCode::Char('Y') if app.focused_pane == FocusedPane::Response => {
+                                app.copy_response_body();
+                            }
+                            // Copy the selected JSON node and everything beneath it
+                            KeyCode::Char('C') if app.focused_pane == FocusedPane::Response => {
+                                app.json_viewer_copy_subtree();
+                            }
+                            // Copy or move the selected request to another collection
+                            KeyCode::Char('M') if app.focused_pane == FocusedPane::Requests => {
+                                app.start_transfer();
+                            }
+                            // Copy or save a request/response report
+                            KeyCode::Char('M') if app.focused_pane == FocusedPane::Response => {
+                                app.start_report();
+                            }
+                            // Export the highlighted request as a one-request collection
+                            KeyCode::Char('X') if matches!(app.focused_pane, FocusedPane::Requests | FocusedPane::Preview) => {
+                                app.start_request_export();
+                            }
+                            // WebSocket / event stream: send a message, close
+                            KeyCode::Char('m') if app.focused_pane == FocusedPane::Response => {
+                                app.start_stream_message();
+                            }
+                            KeyCode::Char('c') if app.focused_pane == FocusedPane::Response && app.stream.is_some() => {
+                                app.close_stream();
+                            }
+                            // Long JSON strings: full text for the selected one, or all
+                            KeyCode::Char('W') if app.focused_pane == FocusedPane::Response => {
+                                app.json_viewer_toggle_full_string();
+                            }
+                            KeyCode::Char('X') if app.focused_pane == FocusedPane::Response => {
+                                app.json_viewer_toggle_full_strings();
+                            }
+                            KeyCode::Char('i') if app.focused_pane == FocusedPane::Response => {
+                                app.toggle_headers_in_json_viewer();
+                            }
+                            // Baseline responses for regression checks
+                            KeyCode::Char('b') if app.focused_pane == FocusedPane::Response => {
+                                app.save_response_as_baseline();
+                            }
+                            KeyCode::Char('B') if app.focused_pane == FocusedPane::Response => {
+                                app.open_baseline_diff();
+                            }
                             // Enter key behavior depends on focused pane
                             KeyCode::Enter => match app.focused_pane {
                                 FocusedPane::Collections => {
                                     // Cached collections display instantly and refresh in the
                                     // background; uncached ones use the blocking fetch below.
                                     if app.start_collection_load() == app::CollectionLoad::Cached {
-                                        spawn_collection_refresh(&mut app);
+                                        spawn_collection_refresh(&mut app, false);
                                     }
                                 }
                                 FocusedPane::Requests => {
@@ -474,7 +856,7 @@ async fn run_app(
                                     // Load the owning collection (cached or fetched) and
                                     // jump to the favorited request.
                                     if app.start_favorite_load() == app::CollectionLoad::Cached {
-                                        spawn_collection_refresh(&mut app);
+                                        spawn_collection_refresh(&mut app, false);
                                     }
                                 }
                                 FocusedPane::Preview => {
@@ -504,13 +886,28 @@ async fn run_app(
                                     }
                                 }
                             }
+                            // Open the response body in a pager or editor
+                            KeyCode::Char('U') if app.focused_pane == FocusedPane::Response => {
+                                match app.response_body_for_pager() {
+                                    Some((content, extension)) => match view_in_pager(terminal, &content, extension) {
+                                        Ok(()) => {}
+                                        Err(e) => {
+                                            let error_msg = format!("{:#}", e);
+                                            log_error("view_in_pager", &error_msg);
+                                            app.error = Some(error_msg);
+                                            app.status_message = String::from("Could not open the response");
+                                        }
+                                    },
+                                    None => app.status_message = String::from("No response to open"),
+                                }
+                            }
                             // Edit request in external editor
                             KeyCode::Char('E') => {
                                 if let Some((request, item_index)) = app.get_current_request_for_edit() {
                                     match edit_request_in_editor(terminal, &request) {
                                         Ok(Some(edited)) => {
                                             // Store edit locally - user can press S to save to Postman
-                                            app.store_local_edit(edited, item_index);
+                                            app.submit_local_edit(edited, item_index);
                                         }
                                         Ok(None) => {
                                             app.status_message = String::from("Edit cancelled or no changes");
@@ -524,14 +921,113 @@ async fn run_app(
                                     }
                                 }
                             }
+                            // Fetch an OAuth token now (client-credentials, from config)
+                            KeyCode::Char('T') => {
+                                app.start_token_fetch(false);
+                            }
+                            // Compose and send a one-off request in the editor
+                            KeyCode::Char('o') => {
+                                let scratch = app.scratch_request_for_edit();
+                                match edit_request_in_editor(terminal, &scratch) {
+                                    Ok(Some(edited)) => {
+                                        app.queue_scratch_execution(edited);
+                                    }
+                                    Ok(None) if app.scratch_request.is_some() => {
+                                        // Unchanged: resend the previous scratch request
+                                        app.queue_scratch_execution(scratch);
+                                    }
+                                    Ok(None) => {
+                                        app.status_message = String::from("Scratch request cancelled");
+                                    }
+                                    Err(e) => {
+                                        let error_msg = e.to_string();
+                                        log_error("edit_scratch_request", &error_msg);
+                                        app.error = Some(error_msg);
+                                        app.status_message = String::from("Scratch request failed");
+                                    }
+                                }
+                            }
+                            // Save every local edit in the collection to Postman
+                            KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                                app.start_save_all();
+                            }
+                            // Note on the selected request, kept locally
+                            KeyCode::Char('n') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                                app.start_note_edit();
+                            }
+                            // Cycle the Requests pane sort (original/name/method)
+                            KeyCode::Char('s') if app.focused_pane == FocusedPane::Requests => {
+                                app.cycle_request_sort();
+                            }
+                            // Names only vs. method, favorite and edit markers
+                            KeyCode::Char('c') if app.focused_pane == FocusedPane::Requests => {
+                                app.toggle_compact_requests();
+                            }
                             // Save unsaved edits to Postman
                             KeyCode::Char('S') => {
                                 app.start_saving_edit();
                             }
+                            // Cycle the raw body language (sets the Content-Type)
+                            KeyCode::Char('C') if app.focused_pane == FocusedPane::Preview => {
+                                app.cycle_body_language();
+                            }
+                            // Jump to where the request's next {{variable}} is defined
+                            KeyCode::Char(']') if app.focused_pane == FocusedPane::Preview => {
+                                app.go_to_variable_definition();
+                            }
+                            // One-shot headers for the next execution
+                            KeyCode::Char('+') => {
+                                app.open_extra_headers();
+                            }
+                            KeyCode::Char('O') if app.focused_pane == FocusedPane::Preview => {
+                                app.open_in_browser();
+                            }
+                            // Export the collection as an OpenAPI document
+                            KeyCode::Char('O') if app.focused_pane == FocusedPane::Requests => {
+                                app.export_openapi();
+                            }
+                            // Show the resolved request without sending it
+                            KeyCode::Char('d') if app.focused_pane == FocusedPane::Preview => {
+                                app.open_dry_run();
+                            }
+                            // Read-only view of the request's pre-request/test scripts
+                            KeyCode::Char('x') if app.focused_pane == FocusedPane::Preview => {
+                                app.open_script_view();
+                            }
+                            // Quick execute: run the highlighted request and stay here
+                            KeyCode::Char('x') if app.focused_pane == FocusedPane::Requests => {
+                                app.quick_execute();
+                            }
+                            // Create a collection from an OpenAPI spec
+                            KeyCode::Char('I') if app.focused_pane == FocusedPane::Collections => {
+                                app.start_openapi_import();
+                            }
+                            // Discard every local edit in the highlighted collection
+                            KeyCode::Char('D') if app.focused_pane == FocusedPane::Collections => {
+                                app.start_clear_edits_confirmation();
+                            }
                             // Discard local edit for the selected request
                             KeyCode::Char('D') => {
                                 app.start_delete_edit_confirmation();
                             }
+                            // Run every request in the selected folder (or reopen
+                            // the last run's results)
+                            KeyCode::Char('R') if app.focused_pane == FocusedPane::Requests => {
+                                app.start_folder_run();
+                            }
+                            // Run every favorite request across collections
+                            KeyCode::Char('R') if app.focused_pane == FocusedPane::Favorites => {
+                                app.start_favorites_run(true);
+                            }
+                            // Re-fetch the loaded collection from Postman
+                            KeyCode::Char('R') => {
+                                app.start_collection_reload();
+                                spawn_collection_refresh(&mut app, true);
+                            }
+                            KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                                app.start_collection_reload();
+                                spawn_collection_refresh(&mut app, true);
+                            }
                             // Add new request
                             KeyCode::Char('a') => {
                                 app.start_new_request_dialog();
@@ -540,10 +1036,15 @@ async fn run_app(
                             KeyCode::Char('f') => {
                                 app.toggle_favorite();
                             }
+                            KeyCode::Char('F') => {
+                                app.toggle_folder_favorites();
+                            }
                             // Search
                             KeyCode::Char('/') => {
                                 if app.focused_pane == FocusedPane::Response && app.json_viewer_state.is_some() {
                                     app.json_search_start();
+                                } else if app.focused_pane == FocusedPane::Response {
+                                    app.response_search_start();
                                 } else {
                                     app.start_search();
                                 }
@@ -551,6 +1052,8 @@ async fn run_app(
                             KeyCode::Char('n') => {
                                 if app.focused_pane == FocusedPane::Response && app.json_viewer_state.is_some() {
                                     app.json_search_next();
+                                } else if app.focused_pane == FocusedPane::Response {
+                                    app.response_search_next();
                                 } else {
                                     app.next_match();
                                 }
@@ -558,6 +1061,8 @@ async fn run_app(
                             KeyCode::Char('N') => {
                                 if app.focused_pane == FocusedPane::Response && app.json_viewer_state.is_some() {
                                     app.json_search_prev();
+                                } else if app.focused_pane == FocusedPane::Response {
+                                    app.response_search_prev();
                                 } else {
                                     app.prev_match();
                                 }
@@ -583,13 +1088,45 @@ async fn run_app(
                             KeyCode::Char('v') => {
                                 app.open_environment_popup();
                             }
+                            // Switch to the next/previous environment directly
+                            KeyCode::Char('>') => {
+                                app.cycle_environment(1).await;
+                            }
+                            KeyCode::Char('<') => {
+                                app.cycle_environment(-1).await;
+                            }
                             // Variables view
                             KeyCode::Char('V') => {
                                 app.open_variables_popup();
                             }
+                            // Label the visible rows and jump to one with a keypress
+                            KeyCode::Char('\'') => {
+                                app.start_quick_jump();
+                            }
+                            // Recently executed requests
+                            KeyCode::Char(';') => {
+                                app.open_recent_requests();
+                            }
+                            // Edit the selected request's query params in a form
+                            KeyCode::Char('P') if app.focused_pane == FocusedPane::Preview => {
+                                app.start_query_editor();
+                            }
+                            // Preview variables as written or resolved
+                            KeyCode::Char('s') if app.focused_pane == FocusedPane::Preview => {
+                                app.toggle_preview_resolved();
+                            }
+                            // Edit the selected request's URL in place
+                            KeyCode::Char('u') if app.focused_pane == FocusedPane::Preview => {
+                                app.start_url_edit();
+                            }
+                            // Keep the response while browsing other requests
+                            KeyCode::Char('P') => {
+                                app.toggle_response_pin();
+                            }
                             // Workspace selection
                             KeyCode::Char('w') => {
                                 app.open_workspace_popup();
+                                spawn_workspace_counts(&mut app);
                             }
                             _ => {}
                         }
@@ -604,6 +1141,16 @@ async fn run_app(
                                     app.create_new_request().await?;
                                 }
                             }
+                            // Complete a {{variable}} name
+                            KeyCode::Tab => {
+                                app.accept_completion();
+                            }
+                            KeyCode::Up => {
+                                app.completion_move(-1);
+                            }
+                            KeyCode::Down => {
+                                app.completion_move(1);
+                            }
                             KeyCode::Backspace => {
                                 app.dialog_backspace();
                             }
@@ -619,56 +1166,171 @@ async fn run_app(
                             _ => {}
                         }
                     }
-                    InputMode::Search => {
+                    InputMode::UrlEdit => {
                         match key.code {
                             KeyCode::Esc => {
-                                app.cancel_search();
+                                app.cancel_url_edit();
                             }
                             KeyCode::Enter => {
-                                app.confirm_search();
+                                app.confirm_url_edit();
+                            }
+                            KeyCode::Tab => {
+                                app.accept_completion();
+                            }
+                            KeyCode::Up => {
+                                app.completion_move(-1);
+                            }
+                            KeyCode::Down => {
+                                app.completion_move(1);
                             }
                             KeyCode::Backspace => {
-                                app.search_backspace();
+                                app.dialog_backspace();
+                            }
+                            KeyCode::Left => {
+                                app.dialog_move_cursor_left();
+                            }
+                            KeyCode::Right => {
+                                app.dialog_move_cursor_right();
                             }
                             KeyCode::Char(c) => {
-                                app.search_input_char(c);
+                                app.dialog_input_char(c);
                             }
                             _ => {}
                         }
                     }
-                    InputMode::JsonSearch => {
+                    InputMode::TransferSelect => {
                         match key.code {
-                            KeyCode::Esc => {
-                                app.json_search_cancel();
+                            KeyCode::Esc | KeyCode::Char('q') => {
+                                app.cancel_transfer();
                             }
-                            KeyCode::Enter => {
-                                app.json_search_confirm();
+                            KeyCode::Char('j') | KeyCode::Down => {
+                                app.transfer_select(1);
                             }
-                            KeyCode::Backspace => {
-                                app.json_search_backspace();
+                            KeyCode::Char('k') | KeyCode::Up => {
+                                app.transfer_select(-1);
                             }
-                            KeyCode::Char(c) => {
-                                app.json_search_input(c);
+                            KeyCode::Char('c') | KeyCode::Enter => {
+                                app.confirm_transfer(false);
+                            }
+                            KeyCode::Char('m') => {
+                                app.confirm_transfer(true);
                             }
                             _ => {}
                         }
                     }
-                    InputMode::Saving => {
-                        // Handled separately below with select!
-                    }
-                    InputMode::EnvironmentSelect => {
+                    InputMode::NoteEdit => {
                         match key.code {
                             KeyCode::Esc => {
-                                app.close_environment_popup();
+                                app.cancel_note_edit();
                             }
                             KeyCode::Enter => {
-                                app.confirm_environment_selection().await;
-                            }
-                            KeyCode::Char('j') | KeyCode::Down => {
-                                app.environment_popup_down();
+                                app.confirm_note_edit();
                             }
-                            KeyCode::Char('k') | KeyCode::Up => {
-                                app.environment_popup_up();
+                            KeyCode::Backspace => {
+                                app.dialog_backspace();
+                            }
+                            KeyCode::Left => {
+                                app.dialog_move_cursor_left();
+                            }
+                            KeyCode::Right => {
+                                app.dialog_move_cursor_right();
+                            }
+                            KeyCode::Char(c) => {
+                                app.dialog_input_char(c);
+                            }
+                            _ => {}
+                        }
+                    }
+                    InputMode::OpenApiImport => {
+                        match key.code {
+                            KeyCode::Esc => {
+                                app.cancel_openapi_import();
+                            }
+                            KeyCode::Enter => {
+                                app.confirm_openapi_import();
+                            }
+                            KeyCode::Backspace => {
+                                app.dialog_backspace();
+                            }
+                            KeyCode::Left => {
+                                app.dialog_move_cursor_left();
+                            }
+                            KeyCode::Right => {
+                                app.dialog_move_cursor_right();
+                            }
+                            KeyCode::Char(c) => {
+                                app.dialog_input_char(c);
+                            }
+                            _ => {}
+                        }
+                    }
+                    InputMode::Search => {
+                        match key.code {
+                            KeyCode::Esc => {
+                                app.cancel_search();
+                            }
+                            KeyCode::Enter => {
+                                app.confirm_search();
+                            }
+                            KeyCode::Backspace => {
+                                app.search_backspace();
+                            }
+                            KeyCode::Char(c) => {
+                                app.search_input_char(c);
+                            }
+                            _ => {}
+                        }
+                    }
+                    InputMode::JsonSearch => {
+                        match key.code {
+                            KeyCode::Esc => {
+                                app.json_search_cancel();
+                            }
+                            KeyCode::Enter => {
+                                app.json_search_confirm();
+                            }
+                            KeyCode::Backspace => {
+                                app.json_search_backspace();
+                            }
+                            KeyCode::Char(c) => {
+                                app.json_search_input(c);
+                            }
+                            _ => {}
+                        }
+                    }
+                    InputMode::ResponseSearch => {
+                        match key.code {
+                            KeyCode::Esc => {
+                                app.response_search_cancel();
+                            }
+                            KeyCode::Enter => {
+                                app.response_search_confirm();
+                            }
+                            KeyCode::Backspace => {
+                                app.response_search_backspace();
+                            }
+                            KeyCode::Char(c) => {
+                                app.response_search_input(c);
+                            }
+                            _ => {}
+                        }
+                    }
+                    InputMode::Saving => {
+                        // Handled separately below with select!
+                    }
+                    InputMode::EnvironmentSelect => {
+                        match key.code {
+                            KeyCode::Esc => {
+                                app.close_environment_popup();
+                            }
+                            KeyCode::Enter => {
+                                app.confirm_environment_selection().await;
+                            }
+                            KeyCode::Char('j') | KeyCode::Down => {
+                                app.environment_popup_down();
+                            }
+                            KeyCode::Char('k') | KeyCode::Up => {
+                                app.environment_popup_up();
                             }
                             _ => {}
                         }
@@ -683,6 +1345,15 @@ async fn run_app(
                                 KeyCode::Enter => {
                                     app.confirm_variable_edit();
                                 }
+                                KeyCode::Tab => {
+                                    app.accept_completion();
+                                }
+                                KeyCode::Up => {
+                                    app.completion_move(-1);
+                                }
+                                KeyCode::Down => {
+                                    app.completion_move(1);
+                                }
                                 KeyCode::Backspace => {
                                     app.variable_backspace();
                                 }
@@ -714,6 +1385,18 @@ async fn run_app(
                                 }
                                 _ => {}
                             }
+                        } else if app.variables_export_confirm {
+                            match key.code {
+                                KeyCode::Char('f') => {
+                                    app.export_variables_to_file();
+                                }
+                                KeyCode::Char('c') => {
+                                    app.copy_variables_as_exports();
+                                }
+                                _ => {
+                                    app.cancel_variables_export();
+                                }
+                            }
                         } else {
                             // Navigation mode
                             match key.code {
@@ -737,9 +1420,21 @@ async fn run_app(
                                 KeyCode::Char('s') => {
                                     app.save_variables_to_postman().await;
                                 }
+                                KeyCode::Char('t') => {
+                                    app.start_token_fetch(false);
+                                }
                                 KeyCode::Char('/') => {
                                     app.start_variables_search();
                                 }
+                                KeyCode::Char('x') => {
+                                    app.start_variables_export();
+                                }
+                                KeyCode::Char('h') => {
+                                    app.cycle_variable_history();
+                                }
+                                KeyCode::Tab => {
+                                    app.toggle_variables_scope();
+                                }
                                 _ => {}
                             }
                         }
@@ -765,9 +1460,10 @@ async fn run_app(
                         match key.code {
                             KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => {
                                 // User confirmed, execute the request
-                                app.pending_execute = None;
-                                app.input_mode = InputMode::Normal;
-                                app.queue_execution();
+                                app.confirm_execute();
+                            }
+                            KeyCode::Char('a') | KeyCode::Char('A') => {
+                                app.confirm_execute_always();
                             }
                             KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
                                 app.cancel_execute_confirmation();
@@ -786,6 +1482,325 @@ async fn run_app(
                             _ => {}
                         }
                     }
+                    InputMode::RowsEditor => {
+                        let editing = app.rows_editor.as_ref().is_some_and(|e| e.editing.is_some());
+                        if editing {
+                            match key.code {
+                                KeyCode::Enter | KeyCode::Esc => {
+                                    app.rows_editor_stop_edit();
+                                }
+                                KeyCode::Tab | KeyCode::BackTab => {
+                                    app.rows_editor_switch_field();
+                                }
+                                KeyCode::Left => {
+                                    app.rows_editor_cursor_left();
+                                }
+                                KeyCode::Right => {
+                                    app.rows_editor_cursor_right();
+                                }
+                                KeyCode::Backspace => {
+                                    app.rows_editor_backspace();
+                                }
+                                KeyCode::Char(c) => {
+                                    app.rows_editor_input_char(c);
+                                }
+                                _ => {}
+                            }
+                        } else {
+                            match key.code {
+                                KeyCode::Esc => {
+                                    app.cancel_rows_editor();
+                                }
+                                KeyCode::Enter => {
+                                    app.confirm_rows_editor();
+                                }
+                                KeyCode::Up | KeyCode::Char('k') => {
+                                    app.rows_editor_up();
+                                }
+                                KeyCode::Down | KeyCode::Char('j') => {
+                                    app.rows_editor_down();
+                                }
+                                KeyCode::Char('a') => {
+                                    app.rows_editor_add();
+                                }
+                                KeyCode::Char('d') | KeyCode::Delete => {
+                                    app.rows_editor_delete();
+                                }
+                                KeyCode::Char(' ') => {
+                                    app.rows_editor_toggle();
+                                }
+                                KeyCode::Char('n') => {
+                                    app.rows_editor_edit(app::RowField::Key);
+                                }
+                                KeyCode::Char('e') | KeyCode::Tab => {
+                                    app.rows_editor_edit(app::RowField::Value);
+                                }
+                                _ => {}
+                            }
+                        }
+                    }
+                    InputMode::ClearEditsConfirm => {
+                        match key.code {
+                            KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => {
+                                app.confirm_clear_edits();
+                            }
+                            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                                app.cancel_clear_edits();
+                            }
+                            _ => {}
+                        }
+                    }
+                    InputMode::CommandPalette => {
+                        match key.code {
+                            KeyCode::Esc => {
+                                app.close_palette();
+                            }
+                            KeyCode::Enter => {
+                                app.confirm_palette();
+                            }
+                            KeyCode::Up => {
+                                app.palette_move(-1);
+                            }
+                            KeyCode::Down | KeyCode::Tab => {
+                                app.palette_move(1);
+                            }
+                            KeyCode::Backspace => {
+                                app.palette_backspace();
+                            }
+                            KeyCode::Char(c) => {
+                                app.palette_input(c);
+                            }
+                            _ => {}
+                        }
+                    }
+                    InputMode::GlobalFinder => {
+                        match key.code {
+                            KeyCode::Esc => {
+                                app.close_global_finder();
+                            }
+                            KeyCode::Enter => {
+                                let load = app.confirm_global_finder();
+                                if load == app::CollectionLoad::Cached {
+                                    spawn_collection_refresh(&mut app, false);
+                                }
+                            }
+                            KeyCode::Up => {
+                                app.finder_move(-1);
+                            }
+                            KeyCode::Down | KeyCode::Tab => {
+                                app.finder_move(1);
+                            }
+                            KeyCode::Backspace => {
+                                app.finder_backspace();
+                            }
+                            KeyCode::Char(c) => {
+                                app.finder_input(c);
+                            }
+                            _ => {}
+                        }
+                    }
+                    InputMode::ExtraHeaders => {
+                        match key.code {
+                            KeyCode::Esc => {
+                                app.close_extra_headers();
+                            }
+                            KeyCode::Enter => {
+                                app.submit_extra_header();
+                            }
+                            KeyCode::Backspace => {
+                                app.extra_header_backspace();
+                            }
+                            KeyCode::Char(c) => {
+                                app.extra_header_input_char(c);
+                            }
+                            _ => {}
+                        }
+                    }
+                    InputMode::RecentRequests => {
+                        match key.code {
+                            KeyCode::Esc | KeyCode::Char(';') => {
+                                app.close_recent_requests();
+                            }
+                            KeyCode::Enter => {
+                                let load = app.start_recent_load();
+                                if load == app::CollectionLoad::Cached {
+                                    spawn_collection_refresh(&mut app, false);
+                                }
+                            }
+                            KeyCode::Char('j') | KeyCode::Down => {
+                                app.recent_requests_down();
+                            }
+                            KeyCode::Char('k') | KeyCode::Up => {
+                                app.recent_requests_up();
+                            }
+                            _ => {}
+                        }
+                    }
+                    InputMode::ApiKeyInput => {
+                        match key.code {
+                            KeyCode::Esc => {
+                                app.cancel_api_key_prompt();
+                            }
+                            KeyCode::Enter => {
+                                // Retry the startup load with the new key
+                                let accepted = app.submit_api_key();
+                                if accepted {
+                                    refresh_rx = spawn_startup_refresh(&app);
+                                    refreshed = false;
+                                }
+                            }
+                            KeyCode::Backspace => {
+                                app.api_key_backspace();
+                            }
+                            KeyCode::Char(c) => {
+                                app.api_key_input_char(c);
+                            }
+                            _ => {}
+                        }
+                    }
+                    InputMode::InvalidBodyConfirm => {
+                        match key.code {
+                            KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => {
+                                app.confirm_invalid_body();
+                            }
+                            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                                if let Some(pending) = app.cancel_invalid_body() {
+                                    match edit_request_in_editor(terminal, &pending.edited) {
+                                        Ok(Some(edited)) => app.submit_local_edit(edited, pending.item_index),
+                                        Ok(None) => app.status_message = String::from("Edit discarded"),
+                                        Err(e) => {
+                                            let error_msg = e.to_string();
+                                            log_error("edit_request_in_editor", &error_msg);
+                                            app.error = Some(error_msg);
+                                            app.status_message = String::from("Edit failed");
+                                        }
+                                    }
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                    InputMode::DryRun => {
+                        match key.code {
+                            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('d') => {
+                                app.close_dry_run();
+                            }
+                            KeyCode::Char('j') | KeyCode::Down => {
+                                app.dry_run_scroll_down();
+                            }
+                            KeyCode::Char('k') | KeyCode::Up => {
+                                app.dry_run_scroll_up();
+                            }
+                            _ => {}
+                        }
+                    }
+                    InputMode::StreamMessage => {
+                        match key.code {
+                            KeyCode::Esc => {
+                                app.cancel_stream_message();
+                            }
+                            KeyCode::Enter => {
+                                app.send_stream_message();
+                            }
+                            KeyCode::Backspace => {
+                                app.stream_input_backspace();
+                            }
+                            KeyCode::Char(c) => {
+                                app.stream_input_char(c);
+                            }
+                            _ => {}
+                        }
+                    }
+                    InputMode::CollectionConflict => {
+                        match key.code {
+                            KeyCode::Char('r') | KeyCode::Enter => {
+                                app.reapply_after_conflict().await?;
+                            }
+                            KeyCode::Esc | KeyCode::Char('n') => {
+                                app.discard_after_conflict();
+                            }
+                            _ => {}
+                        }
+                    }
+                    InputMode::EnvironmentConflict => {
+                        match key.code {
+                            KeyCode::Char('m') | KeyCode::Enter => {
+                                app.resolve_environment_conflict(true).await;
+                            }
+                            KeyCode::Char('o') => {
+                                app.resolve_environment_conflict(false).await;
+                            }
+                            KeyCode::Esc | KeyCode::Char('n') => {
+                                app.cancel_environment_conflict();
+                            }
+                            _ => {}
+                        }
+                    }
+                    InputMode::QuickJump => {
+                        match key.code {
+                            KeyCode::Char(c) => app.finish_quick_jump(c),
+                            _ => app.finish_quick_jump(' '),
+                        }
+                    }
+                    InputMode::ScriptView => {
+                        match key.code {
+                            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('x') => {
+                                app.close_script_view();
+                            }
+                            KeyCode::Char('j') | KeyCode::Down => {
+                                app.script_view_scroll_down();
+                            }
+                            KeyCode::Char('k') | KeyCode::Up => {
+                                app.script_view_scroll_up();
+                            }
+                            _ => {}
+                        }
+                    }
+                    InputMode::BaselineDiff => {
+                        match key.code {
+                            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('B') => {
+                                app.close_baseline_diff();
+                            }
+                            KeyCode::Char('j') | KeyCode::Down => {
+                                app.baseline_diff_scroll_down();
+                            }
+                            KeyCode::Char('k') | KeyCode::Up => {
+                                app.baseline_diff_scroll_up();
+                            }
+                            _ => {}
+                        }
+                    }
+                    InputMode::RunConfirm => {
+                        match key.code {
+                            KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => {
+                                app.confirm_run();
+                            }
+                            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                                app.cancel_run_confirm();
+                            }
+                            _ => {}
+                        }
+                    }
+                    InputMode::RunResults => {
+                        match key.code {
+                            KeyCode::Esc | KeyCode::Char('q') => {
+                                app.close_run_results();
+                            }
+                            KeyCode::Enter => {
+                                let load = app.open_run_result_request();
+                                if load == app::CollectionLoad::Cached {
+                                    spawn_collection_refresh(&mut app, false);
+                                }
+                            }
+                            KeyCode::Char('j') | KeyCode::Down => {
+                                app.run_results_down();
+                            }
+                            KeyCode::Char('k') | KeyCode::Up => {
+                                app.run_results_up();
+                            }
+                            _ => {}
+                        }
+                    }
                     InputMode::ParamsInput => {
                         let editing = app.params_dialog.as_ref().map(|d| d.editing).unwrap_or(false);
                         if editing {
@@ -794,6 +1809,17 @@ async fn run_app(
                                 KeyCode::Enter | KeyCode::Esc => {
                                     app.params_stop_edit();
                                 }
+                                // While {{variable}} completions are showing, Tab
+                                // accepts one and Up/Down pick between them
+                                KeyCode::Tab if app.has_completion() => {
+                                    app.accept_completion();
+                                }
+                                KeyCode::Up if app.has_completion() => {
+                                    app.completion_move(-1);
+                                }
+                                KeyCode::Down if app.has_completion() => {
+                                    app.completion_move(1);
+                                }
                                 KeyCode::Up | KeyCode::BackTab => {
                                     app.params_stop_edit();
                                     app.params_up();
@@ -871,16 +1897,150 @@ async fn run_app(
             }
         }
 
+        // Loading a collection may have switched to its remembered environment
+        if app.pending_environment_load {
+            app.load_pending_environment().await;
+        }
+
+        // If an OAuth token is needed, fetch it first (a waiting request is
+        // queued by apply_token_result and picked up just below)
+        if let Some(fetch) = app.pending_token_fetch.take() {
+            let client = app.client.clone();
+            let then_execute = fetch.then_execute;
+            let handle = tokio::spawn(async move {
+                client
+                    .fetch_client_credentials_token(&fetch.token_url, &fetch.client_id, &fetch.client_secret, &fetch.scope)
+                    .await
+            });
+            match run_cancellable(terminal, &mut app, handle).await? {
+                Some(result) => app.apply_token_result(result, then_execute),
+                None => app.cancel_token_fetch(),
+            }
+        }
+
+        // WebSocket and event-stream requests stay open and log into the
+        // Response pane instead of waiting for a single response
+        let stream_kind = app.pending_execution.as_ref().and_then(|(resolved, _, _)| api::stream::stream_kind(resolved));
+        if let Some(kind) = stream_kind
+            && let Some((resolved, _, _)) = app.pending_execution.take()
+        {
+            app.open_stream(resolved, kind);
+        }
+
         // If a request is queued, execute it in the background so Esc can cancel
-        if let Some(resolved) = app.pending_execution.take() {
+        if let Some((resolved, timeout, version)) = app.pending_execution.take() {
             let client = app.client.clone();
-            let handle = tokio::spawn(async move { client.execute_request(&resolved).await });
+            let request = resolved.clone();
+            let started = std::time::Instant::now();
+            app.execution_started = Some(started);
+            let handle = tokio::spawn(async move { client.execute_request(&request, timeout, version).await });
             match run_cancellable(terminal, &mut app, handle).await? {
-                Some(result) => app.apply_execution_result(result),
+                Some(result) => {
+                    app.log_executed_request(&resolved, &result, started.elapsed());
+                    app.apply_execution_result(resolved, result);
+                }
                 None => app.cancel_execution(),
             }
         }
 
+        // Fetch the collections a favorites run needs, then build the run
+        if let Some(uids) = app.pending_favorites_fetch.take() {
+            let client = app.client.clone();
+            let handle = tokio::spawn(async move {
+                let mut fetched = Vec::with_capacity(uids.len());
+                for uid in uids {
+                    let result = client.get_collection(&uid).await;
+                    fetched.push((uid, result));
+                }
+                fetched
+            });
+            match run_cancellable(terminal, &mut app, handle).await? {
+                Some(fetched) => app.apply_favorites_fetch(fetched),
+                None => app.cancel_run(),
+            }
+        }
+
+        // If a folder run is queued, execute it in the background so Esc can cancel
+        if let Some(run) = app.pending_run.take() {
+            let client = app.client.clone();
+            let stop_on_failure = app.config.settings.stop_on_failure;
+            let handle = tokio::spawn(async move { app::run_requests(client, run, stop_on_failure).await });
+            match run_cancellable(terminal, &mut app, handle).await? {
+                Some(report) => app.apply_run_report(report),
+                None => app.cancel_run(),
+            }
+        }
+
+        // If a J/K move is queued, push it in the background so Esc can
+        // cancel it until it's sent
+        if let Some(pending) = app.pending_move.take() {
+            let client = app.client.clone();
+            let sent = Arc::new(AtomicBool::new(false));
+            let task_sent = Arc::clone(&sent);
+            let handle = tokio::spawn(async move {
+                let result = app::perform_move(client, &pending, &task_sent).await;
+                (pending, result)
+            });
+            match run_cancellable_until_sent(terminal, &mut app, handle, &sent).await? {
+                Some((pending, result)) => app.apply_move(pending, result),
+                None => app.cancel_move(),
+            }
+        }
+
+        // If a copy or move is queued, push it in the background so Esc can
+        // cancel it until it's sent
+        if let Some(pending) = app.pending_transfer.take() {
+            let client = app.client.clone();
+            let remove_source = pending.remove_source;
+            let sent = Arc::new(AtomicBool::new(false));
+            let task_sent = Arc::clone(&sent);
+            let handle = tokio::spawn(async move {
+                let outcome = app::perform_transfer(client, &pending, &task_sent).await;
+                (pending, outcome)
+            });
+            match run_cancellable_until_sent(terminal, &mut app, handle, &sent).await? {
+                Some((pending, outcome)) => app.apply_transfer(pending, outcome),
+                None => app.abort_transfer(remove_source),
+            }
+        }
+
+        // If an OpenAPI import is queued, create it in the background so Esc can cancel
+        if let Some(pending) = app.pending_import.take() {
+            let client = app.client.clone();
+            let handle = tokio::spawn(async move {
+                let imported = &pending.imported;
+                let created = client
+                    .create_collection(pending.workspace_id.as_deref(), &imported.name, &imported.items, &imported.variables)
+                    .await;
+                (pending, created)
+            });
+            match run_cancellable(terminal, &mut app, handle).await? {
+                Some((pending, created)) => app.apply_openapi_import(pending, created),
+                None => app.abort_openapi_import(),
+            }
+        }
+
+        // If a batch save is queued, push each edit in the background so Esc can cancel
+        if let Some(edits) = app.pending_save_all.take() {
+            let client = app.client.clone();
+            let collection_uid = app.get_current_collection_uid().unwrap_or_default();
+            let collection = app.current_collection.clone();
+            let handle = tokio::spawn(async move {
+                perform_save_all(client, collection_uid, collection, edits).await
+            });
+            match run_cancellable(terminal, &mut app, handle).await? {
+                Some(Ok(report)) => app.apply_save_all_report(report),
+                Some(Err(e)) => {
+                    let error_msg = e.to_string();
+                    log_error("save_all", &error_msg);
+                    app.error = Some(error_msg);
+                    app.status_message = String::from("Failed to save changes");
+                    app.input_mode = InputMode::Normal;
+                }
+                None => app.cancel_saving(),
+            }
+        }
+
         // If in saving mode, perform the save with cancellation support
         if app.input_mode == InputMode::Saving {
             if let Some(pending) = app.pending_save.take() {
@@ -924,6 +2084,7 @@ async fn run_app(
                     }
 
                     // Redraw UI
+                    app.tick();
                     terminal.draw(|frame| ui::render(frame, &mut app))?;
                 };
 
@@ -940,12 +2101,17 @@ async fn run_app(
                             app.status_message = format!("Saved '{}'", request_name);
                             app.update_preview_from_selection();
                         }
-                        Err(e) => {
-                            let error_msg = e.to_string();
-                            log_error("save_request", &error_msg);
-                            app.error = Some(error_msg);
-                            app.status_message = String::from("Failed to save changes");
-                        }
+                        Err(e) => match e.downcast::<api::client::CollectionChanged>() {
+                            // The local edit is still stored, so it can be saved
+                            // again onto the newer version
+                            Ok(changed) => app.apply_save_conflict(changed.server),
+                            Err(e) => {
+                                let error_msg = e.to_string();
+                                log_error("save_request", &error_msg);
+                                app.error = Some(error_msg);
+                                app.status_message = String::from("Failed to save changes");
+                            }
+                        },
                     }
                     app.input_mode = InputMode::Normal;
                 }
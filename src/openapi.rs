@@ -0,0 +1,519 @@
+//! A minimal OpenAPI 3.0 document built from a Postman collection: one
+//! operation per path and method, with query, path and header parameters
+//! and the request body as an example. Good enough to seed a spec, not a
+//! faithful translation. The reverse, `collection`, seeds a collection from
+//! a spec the same way.
+
+use anyhow::{Context, Result};
+use serde_json::{json, Map, Value};
+
+use crate::api::{content_type_for_language, Folder, Header, Item, Request, RequestBody, RequestItem, RequestUrl, Variable};
+
+/// A request to describe: its name, the names of its enclosing folders
+/// (outermost first) and the request itself, local edits applied.
+pub struct SpecRequest {
+    pub name: String,
+    pub folders: Vec<String>,
+    pub request: Request,
+}
+
+/// Headers OpenAPI describes elsewhere (`requestBody`, security schemes)
+/// and forbids as header parameters.
+const RESERVED_HEADERS: &[&str] = &["accept", "content-type", "authorization"];
+
+/// The OpenAPI document for `requests`, and how many were left out because
+/// an earlier request already has the same path and method.
+pub fn document(title: &str, requests: &[SpecRequest]) -> (Value, usize) {
+    let mut servers: Vec<String> = Vec::new();
+    let mut paths = Map::new();
+    let mut skipped = 0;
+
+    for spec in requests {
+        let url = spec.request.url.send_url();
+        let (server, path) = split_url(&url);
+        if !server.is_empty() && !servers.contains(&server) {
+            servers.push(server);
+        }
+        let method = spec.request.method.to_lowercase();
+        let method = if method.is_empty() { String::from("get") } else { method };
+        let operations = paths
+            .entry(path.clone())
+            .or_insert_with(|| Value::Object(Map::new()))
+            .as_object_mut()
+            .expect("path items are objects");
+        if operations.contains_key(&method) {
+            skipped += 1;
+            continue;
+        }
+        operations.insert(method, operation(spec, &path, &url));
+    }
+
+    let mut doc = json!({
+        "openapi": "3.0.3",
+        "info": { "title": title, "version": "1.0.0" },
+        "paths": paths,
+    });
+    if !servers.is_empty() {
+        doc["servers"] = servers.into_iter().map(|url| json!({ "url": url })).collect();
+    }
+    (doc, skipped)
+}
+
+fn operation(spec: &SpecRequest, path: &str, url: &str) -> Value {
+    let mut parameters: Vec<Value> = path_parameters(path)
+        .into_iter()
+        .map(|name| json!({ "name": name, "in": "path", "required": true, "schema": { "type": "string" } }))
+        .collect();
+    parameters.extend(crate::util::query_pairs(url).into_iter().map(|(key, value)| {
+        let mut param = json!({ "name": key, "in": "query", "schema": { "type": "string" } });
+        if let Some(value) = value {
+            param["example"] = Value::String(value);
+        }
+        param
+    }));
+    parameters.extend(
+        spec.request
+            .header
+            .iter()
+            .filter(|h| h.disabled != Some(true) && !RESERVED_HEADERS.contains(&h.key.to_lowercase().as_str()))
+            .map(|h| json!({ "name": h.key, "in": "header", "schema": { "type": "string" }, "example": h.value })),
+    );
+
+    let mut op = json!({
+        "summary": spec.name,
+        "responses": { "default": { "description": "Response" } },
+    });
+    if let Some(folder) = spec.folders.last() {
+        op["tags"] = json!([folder]);
+    }
+    if !parameters.is_empty() {
+        op["parameters"] = Value::Array(parameters);
+    }
+    if let Some(body) = request_body(&spec.request) {
+        op["requestBody"] = body;
+    }
+    op
+}
+
+fn request_body(request: &Request) -> Option<Value> {
+    let body = request.body.as_ref()?;
+    if !body.formdata.is_empty() {
+        let properties: Map<String, Value> = body
+            .formdata
+            .iter()
+            .filter(|f| f.disabled != Some(true))
+            .map(|f| {
+                let schema = if f.field_type.as_deref() == Some("file") {
+                    json!({ "type": "string", "format": "binary" })
+                } else {
+                    json!({ "type": "string", "example": f.value.clone().unwrap_or_default() })
+                };
+                (f.key.clone(), schema)
+            })
+            .collect();
+        return Some(json!({
+            "content": { "multipart/form-data": { "schema": { "type": "object", "properties": properties } } }
+        }));
+    }
+    if let Some(graphql) = &body.graphql {
+        return Some(json!({ "content": { "application/json": { "example": graphql } } }));
+    }
+    let raw = body.raw.as_deref().filter(|raw| !raw.trim().is_empty())?;
+    let declared = request
+        .header
+        .iter()
+        .find(|h| h.disabled != Some(true) && h.key.eq_ignore_ascii_case("content-type"))
+        .map(|h| h.value.split(';').next().unwrap_or_default().trim().to_string());
+    let parsed = serde_json::from_str::<Value>(raw).ok();
+    let media_type = declared
+        .or_else(|| body.language().and_then(content_type_for_language).map(String::from))
+        .unwrap_or_else(|| String::from(if parsed.is_some() { "application/json" } else { "text/plain" }));
+    let example = parsed.unwrap_or_else(|| Value::String(raw.to_string()));
+    Some(json!({ "content": { media_type: { "example": example } } }))
+}
+
+/// Split a request URL into its server (scheme and host, or a leading
+/// `{{variable}}`) and an OpenAPI path, with `:name` and `{{name}}`
+/// segments turned into `{name}` templates.
+fn split_url(url: &str) -> (String, String) {
+    let url = url.split(['?', '#']).next().unwrap_or_default();
+    let (server, rest) = match url.find("://") {
+        Some(scheme_end) => {
+            let host_end = url[scheme_end + 3..].find('/').map_or(url.len(), |i| scheme_end + 3 + i);
+            url.split_at(host_end)
+        }
+        None => url.split_at(url.find('/').unwrap_or(url.len())),
+    };
+    let segments: Vec<String> = rest
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .map(|segment| {
+            if let Some(name) = segment.strip_prefix(':') {
+                format!("{{{}}}", name)
+            } else if let Some(name) = segment.strip_prefix("{{").and_then(|s| s.strip_suffix("}}")) {
+                format!("{{{}}}", name)
+            } else {
+                segment.to_string()
+            }
+        })
+        .collect();
+    (server.to_string(), format!("/{}", segments.join("/")))
+}
+
+/// Names of the `{name}` templates in an OpenAPI path.
+fn path_parameters(path: &str) -> Vec<&str> {
+    path.split('/')
+        .filter_map(|segment| segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')))
+        .collect()
+}
+
+/// HTTP methods an OpenAPI path item can hold operations for.
+const METHODS: &[&str] = &["get", "put", "post", "delete", "options", "head", "patch", "trace"];
+
+/// How deep `$ref`s are followed when building an example body, so a
+/// schema that refers to itself still ends.
+const MAX_SCHEMA_DEPTH: usize = 8;
+
+/// A collection seeded from an OpenAPI 3 document: its name, the requests
+/// (one per operation, in a folder per first tag), and a `baseUrl` variable
+/// set to the first server the request URLs start with.
+#[derive(Debug)]
+pub struct ImportedCollection {
+    pub name: String,
+    pub items: Vec<Item>,
+    pub variables: Vec<Variable>,
+    /// Number of requests created.
+    pub requests: usize,
+}
+
+/// Build a collection from the OpenAPI document in `text` (JSON or YAML).
+pub fn collection(text: &str) -> Result<ImportedCollection> {
+    let spec: Value = match serde_json::from_str(text) {
+        Ok(spec) => spec,
+        Err(_) => crate::yaml::parse(text).context("Not an OpenAPI JSON or YAML document")?,
+    };
+    // An unquoted `openapi: 3.0` reads as a number in YAML
+    let version = match spec.get("openapi") {
+        Some(Value::Number(n)) => Some(n.to_string()),
+        other => other.and_then(Value::as_str).map(str::to_string),
+    };
+    if version.is_none_or(|v| !v.starts_with('3')) {
+        anyhow::bail!("Only OpenAPI 3 documents can be imported");
+    }
+    let paths = spec.get("paths").and_then(Value::as_object).context("The document has no paths")?;
+
+    let mut root: Vec<Item> = Vec::new();
+    let mut folders: Vec<Folder> = Vec::new();
+    let mut requests = 0;
+    for (path, path_item) in paths {
+        let shared = path_item.get("parameters").and_then(Value::as_array).cloned().unwrap_or_default();
+        for method in METHODS {
+            let Some(operation) = path_item.get(*method) else {
+                continue;
+            };
+            let item = Item::Request(request_item(&spec, path, method, operation, &shared));
+            requests += 1;
+            match operation.pointer("/tags/0").and_then(Value::as_str) {
+                Some(tag) => match folders.iter_mut().find(|f| f.name == tag) {
+                    Some(folder) => folder.item.push(item),
+                    None => folders.push(Folder {
+                        name: tag.to_string(),
+                        item: vec![item],
+                        description: None,
+                        variable: Vec::new(),
+                    }),
+                },
+                None => root.push(item),
+            }
+        }
+    }
+    if requests == 0 {
+        anyhow::bail!("The document has no operations");
+    }
+
+    let variables = spec
+        .pointer("/servers/0/url")
+        .and_then(Value::as_str)
+        .map(|url| {
+            vec![Variable {
+                key: String::from("baseUrl"),
+                value: url.trim_end_matches('/').to_string(),
+                enabled: None,
+                var_type: None,
+            }]
+        })
+        .unwrap_or_default();
+    let mut items: Vec<Item> = folders.into_iter().map(Item::Folder).collect();
+    items.extend(root);
+    let name = spec.pointer("/info/title").and_then(Value::as_str).unwrap_or("Imported API").to_string();
+    Ok(ImportedCollection { name, items, variables, requests })
+}
+
+fn request_item(spec: &Value, path: &str, method: &str, operation: &Value, shared: &[Value]) -> RequestItem {
+    // Operation parameters override path-level ones with the same name and location
+    let own: Vec<Value> = operation
+        .get("parameters")
+        .and_then(Value::as_array)
+        .map(|params| params.iter().map(|p| resolve(spec, p).clone()).collect())
+        .unwrap_or_default();
+    let mut parameters: Vec<Value> = shared
+        .iter()
+        .map(|p| resolve(spec, p).clone())
+        .filter(|p| !own.iter().any(|o| o["name"] == p["name"] && o["in"] == p["in"]))
+        .collect();
+    parameters.extend(own);
+
+    let mut query = Vec::new();
+    let mut header = Vec::new();
+    for param in &parameters {
+        let Some(name) = param.get("name").and_then(Value::as_str) else {
+            continue;
+        };
+        let example = parameter_example(spec, param);
+        match param.get("in").and_then(Value::as_str) {
+            Some("query") => query.push(format!("{}={}", name, example)),
+            Some("header") => header.push(Header { key: name.to_string(), value: example, disabled: None }),
+            _ => {}
+        }
+    }
+
+    let mut url = format!("{{{{baseUrl}}}}{}", postman_path(path));
+    if !query.is_empty() {
+        url = format!("{}?{}", url, query.join("&"));
+    }
+
+    let body = operation
+        .get("requestBody")
+        .map(|body| resolve(spec, body))
+        .and_then(|body| body.get("content").and_then(Value::as_object))
+        .and_then(|content| {
+            let (media_type, media) = content
+                .iter()
+                .find(|(media_type, _)| media_type.contains("json"))
+                .or_else(|| content.iter().next())?;
+            let example = media
+                .get("example")
+                .or_else(|| media.pointer("/examples").and_then(Value::as_object).and_then(|e| e.values().next()).map(|e| resolve(spec, e)).and_then(|e| e.get("value")))
+                .cloned()
+                .or_else(|| media.get("schema").map(|schema| schema_example(spec, schema, 0)));
+            header.push(Header { key: String::from("Content-Type"), value: media_type.clone(), disabled: None });
+            let (raw, language) = match example {
+                Some(Value::String(text)) => (text, None),
+                Some(value) if media_type.contains("json") => (serde_json::to_string_pretty(&value).unwrap_or_default(), Some("json")),
+                Some(value) => (value.to_string(), None),
+                None => (String::new(), None),
+            };
+            Some(RequestBody::raw(raw, language.map(String::from)))
+        });
+
+    let name = operation
+        .get("summary")
+        .or_else(|| operation.get("operationId"))
+        .and_then(Value::as_str)
+        .map(String::from)
+        .unwrap_or_else(|| format!("{} {}", method.to_uppercase(), path));
+    RequestItem {
+        id: None,
+        name,
+        request: Request {
+            method: method.to_uppercase(),
+            url: RequestUrl::Simple(url),
+            header,
+            auth: None,
+            body,
+            description: operation.get("description").cloned(),
+        },
+        response: Vec::new(),
+        event: Vec::new(),
+        protocol_profile_behavior: None,
+    }
+}
+
+/// Follow a local `$ref` (`#/components/...`); anything else is returned as is.
+fn resolve<'a>(spec: &'a Value, value: &'a Value) -> &'a Value {
+    value
+        .get("$ref")
+        .and_then(Value::as_str)
+        .and_then(|reference| reference.strip_prefix('#'))
+        .and_then(|pointer| spec.pointer(pointer))
+        .unwrap_or(value)
+}
+
+/// An OpenAPI path with its `{name}` templates as `{{name}}` variables.
+fn postman_path(path: &str) -> String {
+    path.split('/')
+        .map(|segment| match segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+            Some(name) => format!("{{{{{}}}}}", name),
+            None => segment.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// A parameter's example value as text: its own example, its schema's, or
+/// the schema's default. Empty when there is none.
+fn parameter_example(spec: &Value, param: &Value) -> String {
+    let schema = param.get("schema").map(|schema| resolve(spec, schema));
+    let example = param
+        .get("example")
+        .or_else(|| schema.and_then(|s| s.get("example")))
+        .or_else(|| schema.and_then(|s| s.get("default")));
+    match example {
+        Some(Value::String(text)) => text.clone(),
+        Some(Value::Null) | None => String::new(),
+        Some(value) => value.to_string(),
+    }
+}
+
+/// An example value shaped like `schema`: its example or default when it
+/// has one, otherwise a placeholder per type.
+fn schema_example(spec: &Value, schema: &Value, depth: usize) -> Value {
+    let schema = resolve(spec, schema);
+    if let Some(example) = schema.get("example").or_else(|| schema.get("default")) {
+        return example.clone();
+    }
+    if depth >= MAX_SCHEMA_DEPTH {
+        return Value::Null;
+    }
+    if let Some(first) = ["allOf", "oneOf", "anyOf"].iter().find_map(|key| schema.pointer(&format!("/{}/0", key))) {
+        return schema_example(spec, first, depth + 1);
+    }
+    if let Some(value) = schema.pointer("/enum/0") {
+        return value.clone();
+    }
+    match schema.get("type").and_then(Value::as_str) {
+        Some("array") => json!([schema.get("items").map_or(Value::Null, |items| schema_example(spec, items, depth + 1))]),
+        Some("string") => Value::String(String::new()),
+        Some("integer") | Some("number") => json!(0),
+        Some("boolean") => json!(false),
+        _ => match schema.get("properties").and_then(Value::as_object) {
+            Some(properties) => properties
+                .iter()
+                .map(|(key, property)| (key.clone(), schema_example(spec, property, depth + 1)))
+                .collect::<Map<_, _>>()
+                .into(),
+            None => Value::Null,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{collection, document, split_url, SpecRequest};
+    use crate::api::{Item, QueryParam, Request, RequestUrl, UrlDetail};
+
+    fn request(method: &str, url: &str) -> SpecRequest {
+        SpecRequest {
+            name: format!("{} {}", method, url),
+            folders: vec![String::from("Users")],
+            request: Request {
+                method: method.to_string(),
+                url: RequestUrl::Simple(url.to_string()),
+                header: vec![],
+                auth: None,
+                body: None,
+                description: None,
+            },
+        }
+    }
+
+    #[test]
+    fn urls_split_into_server_and_templated_path() {
+        assert_eq!(
+            split_url("https://api.example.com/users/:id/posts?page=1"),
+            (String::from("https://api.example.com"), String::from("/users/{id}/posts"))
+        );
+        assert_eq!(
+            split_url("{{baseUrl}}/users/{{userId}}"),
+            (String::from("{{baseUrl}}"), String::from("/users/{userId}"))
+        );
+        assert_eq!(split_url("https://api.example.com"), (String::from("https://api.example.com"), String::from("/")));
+    }
+
+    #[test]
+    fn requests_are_grouped_by_path_and_method() {
+        let requests = [
+            request("GET", "{{baseUrl}}/users/:id?expand=posts"),
+            request("DELETE", "{{baseUrl}}/users/:id"),
+            request("GET", "{{baseUrl}}/users/:id"),
+        ];
+        let (doc, skipped) = document("Users API", &requests);
+        assert_eq!(skipped, 1);
+        assert_eq!(doc["servers"][0]["url"], "{{baseUrl}}");
+        let get = &doc["paths"]["/users/{id}"]["get"];
+        assert_eq!(get["tags"][0], "Users");
+        assert_eq!(get["parameters"][0]["in"], "path");
+        assert_eq!(get["parameters"][1]["name"], "expand");
+        assert!(doc["paths"]["/users/{id}"]["delete"].is_object());
+    }
+
+    #[test]
+    fn disabled_query_params_are_left_out() {
+        let mut spec = request("GET", "");
+        spec.request.url = RequestUrl::Complex(UrlDetail {
+            raw: Some(String::from("{{baseUrl}}/users?page=1&debug=true")),
+            host: None,
+            path: None,
+            query: vec![
+                QueryParam { key: String::from("page"), value: Some(String::from("1")), disabled: None },
+                QueryParam { key: String::from("debug"), value: Some(String::from("true")), disabled: Some(true) },
+            ],
+        });
+        let (doc, _) = document("Users API", &[spec]);
+        let parameters = doc["paths"]["/users"]["get"]["parameters"].as_array().unwrap();
+        assert_eq!(parameters.len(), 1);
+        assert_eq!(parameters[0]["name"], "page");
+    }
+
+    #[test]
+    fn specs_import_as_folders_per_tag() {
+        let spec = r##"{
+            "openapi": "3.0.3",
+            "info": { "title": "Users API" },
+            "servers": [{ "url": "https://api.example.com/" }],
+            "paths": {
+                "/users/{id}": {
+                    "parameters": [{ "name": "id", "in": "path", "required": true }],
+                    "get": {
+                        "tags": ["Users"],
+                        "summary": "Get user",
+                        "parameters": [{ "name": "expand", "in": "query", "example": "posts" }]
+                    },
+                    "put": {
+                        "tags": ["Users"],
+                        "requestBody": { "content": { "application/json": { "schema": { "$ref": "#/components/schemas/User" } } } }
+                    }
+                },
+                "/health": { "get": { "operationId": "health" } }
+            },
+            "components": { "schemas": { "User": { "properties": { "name": { "type": "string" }, "age": { "type": "integer" } } } } }
+        }"##;
+        let imported = collection(spec).unwrap();
+        assert_eq!(imported.name, "Users API");
+        assert_eq!(imported.requests, 3);
+        assert_eq!(imported.variables[0].value, "https://api.example.com");
+        let Item::Folder(users) = &imported.items[0] else {
+            panic!("expected the Users folder first");
+        };
+        let Item::Request(get) = &users.item[0] else {
+            panic!("expected a request");
+        };
+        assert_eq!(get.name, "Get user");
+        assert_eq!(get.request.url.send_url(), "{{baseUrl}}/users/{{id}}?expand=posts");
+        let Item::Request(put) = &users.item[1] else {
+            panic!("expected a request");
+        };
+        let body: serde_json::Value = serde_json::from_str(put.request.body.as_ref().unwrap().raw.as_deref().unwrap()).unwrap();
+        assert_eq!(body, serde_json::json!({ "name": "", "age": 0 }));
+        assert!(matches!(&imported.items[1], Item::Request(health) if health.name == "health"));
+        assert!(collection(r#"{ "swagger": "2.0", "paths": {} }"#).is_err());
+    }
+
+    #[test]
+    fn yaml_specs_import_like_json() {
+        let spec = "openapi: 3.0\ninfo:\n  title: Health\npaths:\n  /health:\n    get:\n      operationId: health\n";
+        let imported = collection(spec).unwrap();
+        assert_eq!(imported.name, "Health");
+        assert!(matches!(&imported.items[0], Item::Request(health) if health.name == "health"));
+    }
+}
@@ -0,0 +1,122 @@
+use crossterm::event::{KeyCode, KeyModifiers};
+
+use crate::app::FocusedPane;
+
+/// An action offered by the command palette. Running one focuses `pane`
+/// (when set) and replays `key` through the normal key handler, so every
+/// command behaves exactly like its key binding.
+pub struct PaletteCommand {
+    pub name: &'static str,
+    /// Key binding shown next to the name.
+    pub keys: &'static str,
+    pub pane: Option<FocusedPane>,
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+const fn command(
+    name: &'static str,
+    keys: &'static str,
+    pane: Option<FocusedPane>,
+    code: KeyCode,
+    modifiers: KeyModifiers,
+) -> PaletteCommand {
+    PaletteCommand { name, keys, pane, code, modifiers }
+}
+
+const NONE: KeyModifiers = KeyModifiers::NONE;
+const CTRL: KeyModifiers = KeyModifiers::CONTROL;
+
+pub const COMMANDS: &[PaletteCommand] = &[
+    command("Execute request", "e", Some(FocusedPane::Preview), KeyCode::Char('e'), NONE),
+    command("Quick execute highlighted request", "x", Some(FocusedPane::Requests), KeyCode::Char('x'), NONE),
+    command("Dry run (show resolved request)", "d", Some(FocusedPane::Preview), KeyCode::Char('d'), NONE),
+    command("Toggle resolved variables in preview", "s", Some(FocusedPane::Preview), KeyCode::Char('s'), NONE),
+    command("Add headers for the next run", "+", None, KeyCode::Char('+'), NONE),
+    command("Cycle body language (Content-Type)", "C", Some(FocusedPane::Preview), KeyCode::Char('C'), NONE),
+    command("Go to variable definition", "]", Some(FocusedPane::Preview), KeyCode::Char(']'), NONE),
+    command("View pre-request and test scripts", "x", Some(FocusedPane::Preview), KeyCode::Char('x'), NONE),
+    command("Open URL in browser", "O", Some(FocusedPane::Preview), KeyCode::Char('O'), NONE),
+    command("Edit request in editor", "E", Some(FocusedPane::Preview), KeyCode::Char('E'), NONE),
+    command("Edit query params", "P", Some(FocusedPane::Preview), KeyCode::Char('P'), NONE),
+    command("Edit headers (toggle, add, delete)", "h", Some(FocusedPane::Preview), KeyCode::Char('h'), NONE),
+    command("Export request (clipboard or file)", "X", Some(FocusedPane::Preview), KeyCode::Char('X'), NONE),
+    command("Export collection as OpenAPI", "O", Some(FocusedPane::Requests), KeyCode::Char('O'), NONE),
+    command("Import OpenAPI spec as collection", "I", Some(FocusedPane::Collections), KeyCode::Char('I'), NONE),
+    command("View response as JSON/XML/text", "r", Some(FocusedPane::Response), KeyCode::Char('r'), NONE),
+    command("Save local edit to Postman", "S", Some(FocusedPane::Preview), KeyCode::Char('S'), NONE),
+    command("Discard local edit", "D", Some(FocusedPane::Preview), KeyCode::Char('D'), NONE),
+    command("Save all local edits", "Ctrl+s", None, KeyCode::Char('s'), CTRL),
+    command("Edit note on request", "Ctrl+n", None, KeyCode::Char('n'), CTRL),
+    command("Discard all local edits in collection", "D", Some(FocusedPane::Collections), KeyCode::Char('D'), NONE),
+    command("Add request", "a", Some(FocusedPane::Requests), KeyCode::Char('a'), NONE),
+    command("Run folder", "R", Some(FocusedPane::Requests), KeyCode::Char('R'), NONE),
+    command("Run all favorite requests", "R", Some(FocusedPane::Favorites), KeyCode::Char('R'), NONE),
+    command("Move item down", "J", Some(FocusedPane::Requests), KeyCode::Char('J'), NONE),
+    command("Move item up", "K", Some(FocusedPane::Requests), KeyCode::Char('K'), NONE),
+    command("Copy or move request to another collection", "M", Some(FocusedPane::Requests), KeyCode::Char('M'), NONE),
+    command("Sort requests (original/name/method)", "s", Some(FocusedPane::Requests), KeyCode::Char('s'), NONE),
+    command("Toggle compact request list", "c", Some(FocusedPane::Requests), KeyCode::Char('c'), NONE),
+    command("Toggle favorite", "f", None, KeyCode::Char('f'), NONE),
+    command("Toggle favorites for folder", "F", Some(FocusedPane::Requests), KeyCode::Char('F'), NONE),
+    command("Scratch request", "o", None, KeyCode::Char('o'), NONE),
+    command("Recently executed requests", ";", None, KeyCode::Char(';'), NONE),
+    command("Find request in all collections", "Ctrl+f", None, KeyCode::Char('f'), CTRL),
+    command("Reload collection from Postman", "Ctrl+r", None, KeyCode::Char('r'), CTRL),
+    command("Search current list", "/", None, KeyCode::Char('/'), NONE),
+    command("Jump to parent folder", "p", None, KeyCode::Char('p'), NONE),
+    command("Quick jump to a visible row", "'", None, KeyCode::Char('\''), NONE),
+    command("Copy response body", "Y", Some(FocusedPane::Response), KeyCode::Char('Y'), NONE),
+    command("Copy JSON subtree as formatted JSON", "C", Some(FocusedPane::Response), KeyCode::Char('C'), NONE),
+    command("Copy or save request/response report", "M", Some(FocusedPane::Response), KeyCode::Char('M'), NONE),
+    command("Open response in pager/editor", "U", Some(FocusedPane::Response), KeyCode::Char('U'), NONE),
+    command("Toggle headers in JSON tree", "i", Some(FocusedPane::Response), KeyCode::Char('i'), NONE),
+    command("Filter JSON tree by value type", "t", Some(FocusedPane::Response), KeyCode::Char('t'), NONE),
+    command("Pin/unpin response while browsing", "P", Some(FocusedPane::Response), KeyCode::Char('P'), NONE),
+    command("Show selected JSON string in full", "W", Some(FocusedPane::Response), KeyCode::Char('W'), NONE),
+    command("Show all JSON strings in full", "X", Some(FocusedPane::Response), KeyCode::Char('X'), NONE),
+    command("Save response as baseline", "b", Some(FocusedPane::Response), KeyCode::Char('b'), NONE),
+    command("Compare response with baseline", "B", Some(FocusedPane::Response), KeyCode::Char('B'), NONE),
+    command("Select environment", "v", None, KeyCode::Char('v'), NONE),
+    command("Next environment", ">", None, KeyCode::Char('>'), NONE),
+    command("Previous environment", "<", None, KeyCode::Char('<'), NONE),
+    command("View environment variables", "V", None, KeyCode::Char('V'), NONE),
+    command("Fetch OAuth token", "T", None, KeyCode::Char('T'), NONE),
+    command("Select workspace", "w", None, KeyCode::Char('w'), NONE),
+    command("Go to Collections", "1", None, KeyCode::Char('1'), NONE),
+    command("Go to Requests", "2", None, KeyCode::Char('2'), NONE),
+    command("Go to Favorites", "3", None, KeyCode::Char('3'), NONE),
+    command("Go to Preview", "4", None, KeyCode::Char('4'), NONE),
+    command("Go to Response", "5", None, KeyCode::Char('5'), NONE),
+    command("Dismiss error", "Esc", None, KeyCode::Esc, NONE),
+    command("Quit", "Ctrl+q", None, KeyCode::Char('q'), CTRL),
+];
+
+/// Indices into `COMMANDS` of the entries matching `query`, matched like
+/// `/` search.
+pub fn filter(query: &str) -> Vec<usize> {
+    COMMANDS
+        .iter()
+        .enumerate()
+        .filter(|(_, cmd)| crate::util::search_matches(cmd.name, query))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{filter, COMMANDS};
+
+    #[test]
+    fn filters_like_request_search() {
+        let names = |query: &str| filter(query).into_iter().map(|i| COMMANDS[i].name).collect::<Vec<_>>();
+        assert!(names("FOLD").contains(&"Run folder"));
+        assert!(names("run folder").contains(&"Run folder"));
+        assert!(!names("folder run").contains(&"Run folder"));
+    }
+
+    #[test]
+    fn empty_query_lists_every_command() {
+        assert_eq!(filter("").len(), COMMANDS.len());
+    }
+}
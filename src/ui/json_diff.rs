@@ -0,0 +1,128 @@
+use serde_json::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// One difference between a baseline and a new JSON document. `path` uses
+/// dotted keys and `[i]` indices, e.g. `data.items[2].id`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonChange {
+    pub path: String,
+    pub kind: ChangeKind,
+    pub old: Option<Value>,
+    pub new: Option<Value>,
+}
+
+/// Parse a response body for diffing; NDJSON compares as an array of its
+/// documents, and bodies that aren't JSON compare as a single string.
+pub fn parse_body(body: &str) -> Value {
+    serde_json::from_str(body)
+        .ok()
+        .or_else(|| crate::util::ndjson_documents(body, false).map(Value::Array))
+        .unwrap_or_else(|| Value::String(body.to_string()))
+}
+
+/// Structural diff of two JSON values. Objects are compared key by key and
+/// arrays index by index; anything else (including a type change) is a
+/// `Changed` at that path.
+pub fn diff(old: &Value, new: &Value) -> Vec<JsonChange> {
+    let mut changes = Vec::new();
+    diff_at(String::new(), old, new, &mut changes);
+    changes
+}
+
+fn diff_at(path: String, old: &Value, new: &Value, changes: &mut Vec<JsonChange>) {
+    match (old, new) {
+        (Value::Object(a), Value::Object(b)) => {
+            for (key, old_value) in a {
+                let child = join_key(&path, key);
+                match b.get(key) {
+                    Some(new_value) => diff_at(child, old_value, new_value, changes),
+                    None => changes.push(JsonChange {
+                        path: child,
+                        kind: ChangeKind::Removed,
+                        old: Some(old_value.clone()),
+                        new: None,
+                    }),
+                }
+            }
+            for (key, new_value) in b {
+                if !a.contains_key(key) {
+                    changes.push(JsonChange {
+                        path: join_key(&path, key),
+                        kind: ChangeKind::Added,
+                        old: None,
+                        new: Some(new_value.clone()),
+                    });
+                }
+            }
+        }
+        (Value::Array(a), Value::Array(b)) => {
+            for i in 0..a.len().max(b.len()) {
+                let child = format!("{}[{}]", path, i);
+                match (a.get(i), b.get(i)) {
+                    (Some(x), Some(y)) => diff_at(child, x, y, changes),
+                    (Some(x), None) => changes.push(JsonChange {
+                        path: child,
+                        kind: ChangeKind::Removed,
+                        old: Some(x.clone()),
+                        new: None,
+                    }),
+                    (None, Some(y)) => changes.push(JsonChange {
+                        path: child,
+                        kind: ChangeKind::Added,
+                        old: None,
+                        new: Some(y.clone()),
+                    }),
+                    (None, None) => {}
+                }
+            }
+        }
+        _ if old != new => changes.push(JsonChange {
+            path: if path.is_empty() { String::from("(root)") } else { path },
+            kind: ChangeKind::Changed,
+            old: Some(old.clone()),
+            new: Some(new.clone()),
+        }),
+        _ => {}
+    }
+}
+
+fn join_key(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{diff, ChangeKind};
+    use serde_json::json;
+
+    #[test]
+    fn reports_added_removed_and_changed_fields() {
+        let old = json!({"id": 1, "name": "a", "tags": ["x", "y"], "meta": {"v": 1}});
+        let new = json!({"id": 1, "name": "b", "tags": ["x"], "meta": {"v": 1, "w": 2}});
+        let changes: Vec<_> = diff(&old, &new).into_iter().map(|c| (c.path, c.kind)).collect();
+        assert_eq!(
+            changes,
+            vec![
+                ("meta.w".to_string(), ChangeKind::Added),
+                ("name".to_string(), ChangeKind::Changed),
+                ("tags[1]".to_string(), ChangeKind::Removed),
+            ]
+        );
+    }
+
+    #[test]
+    fn identical_documents_have_no_changes() {
+        let doc = json!([{"a": null}, 2, "three"]);
+        assert!(diff(&doc, &doc).is_empty());
+    }
+}
@@ -1,7 +1,9 @@
 use ratatui::style::{Color, Style};
-use ratatui::text::{Line, Span};
+use ratatui::text::{Line, Span, Text};
 use serde_json::Value;
+use std::collections::HashSet;
 use tui_tree_widget::{TreeItem, TreeState};
+use crate::ui::theme;
 
 /// Segment of a path to a JSON node
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -14,6 +16,28 @@ pub enum JsonPathSegment {
 /// Unique identifier for a JSON tree node
 pub type JsonNodeId = Vec<JsonPathSegment>;
 
+/// A node's path as JSONPath, e.g. `$.data.items[0]["content-type"]`
+pub fn json_path_string(path: &[JsonPathSegment]) -> String {
+    let mut out = String::from("$");
+    for segment in path {
+        match segment {
+            JsonPathSegment::Root => {}
+            JsonPathSegment::Index(i) => out.push_str(&format!("[{}]", i)),
+            JsonPathSegment::Key(key) => {
+                let plain = key.chars().next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+                    && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+                if plain {
+                    out.push('.');
+                    out.push_str(key);
+                } else {
+                    out.push_str(&format!("[{}]", Value::String(key.clone())));
+                }
+            }
+        }
+    }
+    out
+}
+
 /// Color scheme for JSON syntax highlighting
 pub struct JsonColors {
     pub key: Color,
@@ -28,6 +52,18 @@ pub struct JsonColors {
 
 impl Default for JsonColors {
     fn default() -> Self {
+        if theme::is_light() {
+            return Self {
+                key: Color::Blue,
+                string: Color::Green,
+                number: Color::Magenta,
+                boolean: Color::Red,
+                null: Color::DarkGray,
+                bracket: Color::Black,
+                colon: Color::Black,
+                match_highlight: Color::Black,
+            };
+        }
         Self {
             key: Color::Cyan,
             string: Color::Green,
@@ -41,6 +77,49 @@ impl Default for JsonColors {
     }
 }
 
+/// Which kind of node the viewer narrows down to (cycled with `t`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TypeFilter {
+    #[default]
+    All,
+    Objects,
+    Arrays,
+    Strings,
+    Numbers,
+}
+
+impl TypeFilter {
+    pub fn next(self) -> Self {
+        match self {
+            TypeFilter::All => TypeFilter::Objects,
+            TypeFilter::Objects => TypeFilter::Arrays,
+            TypeFilter::Arrays => TypeFilter::Strings,
+            TypeFilter::Strings => TypeFilter::Numbers,
+            TypeFilter::Numbers => TypeFilter::All,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TypeFilter::All => "all",
+            TypeFilter::Objects => "objects",
+            TypeFilter::Arrays => "arrays",
+            TypeFilter::Strings => "strings",
+            TypeFilter::Numbers => "numbers",
+        }
+    }
+
+    fn matches(self, value: &Value) -> bool {
+        match self {
+            TypeFilter::All => false,
+            TypeFilter::Objects => value.is_object(),
+            TypeFilter::Arrays => value.is_array(),
+            TypeFilter::Strings => value.is_string(),
+            TypeFilter::Numbers => value.is_number(),
+        }
+    }
+}
+
 /// State for the interactive JSON viewer
 pub struct JsonViewerState {
     /// Tree widget state for navigation (uses path segments, not full paths)
@@ -55,8 +134,20 @@ pub struct JsonViewerState {
     pub current_match_index: usize,
     /// Color scheme
     pub colors: JsonColors,
+    /// Active value-type filter
+    pub type_filter: TypeFilter,
+    /// Nodes of the filtered type, highlighted like search matches
+    pub type_matches: HashSet<JsonNodeId>,
+    /// Show every string value in full instead of truncated
+    pub full_strings: bool,
+    /// String nodes shown in full while `full_strings` is off
+    full_string_nodes: HashSet<JsonNodeId>,
+    /// Width of the tree area, for wrapping strings shown in full (0 = unknown)
+    pub wrap_width: usize,
     /// Whether we need to expand all on next render
     needs_expand: bool,
+    /// Whether `json` is the `{"__headers", "__body"}` wrapper (see `with_headers`)
+    wraps_headers: bool,
 }
 
 impl JsonViewerState {
@@ -71,12 +162,57 @@ impl JsonViewerState {
             search_matches: Vec::new(),
             current_match_index: 0,
             colors: JsonColors::default(),
+            type_filter: TypeFilter::All,
+            type_matches: HashSet::new(),
+            full_strings: false,
+            full_string_nodes: HashSet::new(),
+            wrap_width: 0,
             needs_expand: true, // Expand on first render
+            wraps_headers: false,
         };
 
         Some(state)
     }
 
+    /// A viewer over `{"__headers": {...}, "__body": ...}` so response headers
+    /// can be browsed, searched and yanked like the body. Repeated headers
+    /// (e.g. `Set-Cookie`) become arrays; a body that isn't JSON is shown as
+    /// a string.
+    pub fn with_headers(body: &str, headers: &[(String, String)]) -> Self {
+        let mut header_map = serde_json::Map::new();
+        for (key, value) in headers {
+            let value = Value::String(value.clone());
+            match header_map.get_mut(key) {
+                Some(Value::Array(values)) => values.push(value),
+                Some(existing) => *existing = Value::Array(vec![existing.take(), value]),
+                None => {
+                    header_map.insert(key.clone(), value);
+                }
+            }
+        }
+        let body = serde_json::from_str(body).unwrap_or_else(|_| Value::String(body.to_string()));
+
+        let mut root = serde_json::Map::new();
+        root.insert(String::from("__headers"), Value::Object(header_map));
+        root.insert(String::from("__body"), body);
+
+        Self {
+            tree_state: TreeState::default(),
+            json: Value::Object(root),
+            search_query: String::new(),
+            search_matches: Vec::new(),
+            current_match_index: 0,
+            colors: JsonColors::default(),
+            type_filter: TypeFilter::All,
+            type_matches: HashSet::new(),
+            full_strings: false,
+            full_string_nodes: HashSet::new(),
+            wrap_width: 0,
+            needs_expand: true,
+            wraps_headers: true,
+        }
+    }
+
     /// Move selection up
     pub fn up(&mut self) {
         self.tree_state.key_up();
@@ -119,6 +255,17 @@ impl JsonViewerState {
         self.tree_state.open(vec![JsonPathSegment::Root]);
     }
 
+    /// Expand the first `depth` levels below the root and collapse everything
+    /// deeper, e.g. depth 1 shows only the top-level keys.
+    pub fn expand_to_depth(&mut self, depth: usize) {
+        let mut paths: Vec<JsonNodeId> = Vec::new();
+        self.collect_expandable_paths(&self.json.clone(), vec![JsonPathSegment::Root], &mut paths);
+        self.tree_state.close_all();
+        for path in paths.into_iter().filter(|p| p.len() <= depth.max(1)) {
+            self.tree_state.open(path);
+        }
+    }
+
     /// Collect all accumulated paths to expandable nodes (objects and arrays)
     fn collect_expandable_paths(&self, value: &Value, path: JsonNodeId, paths: &mut Vec<JsonNodeId>) {
         match value {
@@ -142,6 +289,85 @@ impl JsonViewerState {
         }
     }
 
+    /// Switch to the next type filter: everything is collapsed except the
+    /// paths leading to nodes of that type, and the first one is selected.
+    /// Back at `All`, the whole tree is expanded again.
+    pub fn cycle_type_filter(&mut self) {
+        self.type_filter = self.type_filter.next();
+        self.type_matches.clear();
+        if self.type_filter == TypeFilter::All {
+            self.expand_all();
+            return;
+        }
+
+        let mut matches = Vec::new();
+        Self::collect_type_matches(&self.json, vec![JsonPathSegment::Root], self.type_filter, &mut matches);
+        self.collapse_all();
+        for path in &matches {
+            for i in 1..path.len() {
+                self.tree_state.open(path[..i].to_vec());
+            }
+        }
+        if let Some(first) = matches.first() {
+            self.tree_state.select(first.clone());
+        }
+        self.type_matches = matches.into_iter().collect();
+    }
+
+    /// Collect the paths of all non-root nodes matching `filter`
+    fn collect_type_matches(value: &Value, path: JsonNodeId, filter: TypeFilter, matches: &mut Vec<JsonNodeId>) {
+        if path.len() > 1 && filter.matches(value) {
+            matches.push(path.clone());
+        }
+        match value {
+            Value::Object(map) => {
+                for (key, val) in map {
+                    let mut child_path = path.clone();
+                    child_path.push(JsonPathSegment::Key(key.clone()));
+                    Self::collect_type_matches(val, child_path, filter, matches);
+                }
+            }
+            Value::Array(arr) => {
+                for (i, val) in arr.iter().enumerate() {
+                    let mut child_path = path.clone();
+                    child_path.push(JsonPathSegment::Index(i));
+                    Self::collect_type_matches(val, child_path, filter, matches);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Show the selected string value in full, or truncate it again.
+    /// Returns whether it is now shown in full; None when no string is selected.
+    pub fn toggle_full_string(&mut self) -> Option<bool> {
+        let selected = self.tree_state.selected().to_vec();
+        if !self.get_value_at_path(&self.json, &selected)?.is_string() {
+            return None;
+        }
+        if self.full_string_nodes.remove(&selected) {
+            return Some(self.full_strings);
+        }
+        self.full_string_nodes.insert(selected);
+        Some(true)
+    }
+
+    /// Show every string value in full, or go back to truncating them (the
+    /// default, which keeps huge arrays of long strings cheap to draw).
+    pub fn toggle_full_strings(&mut self) -> bool {
+        self.full_strings = !self.full_strings;
+        self.full_string_nodes.clear();
+        self.full_strings
+    }
+
+    /// Status text for the active type filter
+    pub fn type_filter_status(&self) -> String {
+        match self.type_filter {
+            TypeFilter::All => String::from("Type filter: all"),
+            filter => format!("Type filter: {} ({} found)", filter.label(), self.type_matches.len()),
+        }
+    }
+
     /// Start a new search
     pub fn start_search(&mut self) {
         self.search_query.clear();
@@ -293,6 +519,12 @@ impl JsonViewerState {
         }
     }
 
+    /// JSONPath of the selected node, for the breadcrumb
+    pub fn selected_path_string(&self) -> Option<String> {
+        let selected = self.tree_state.selected();
+        (!selected.is_empty()).then(|| json_path_string(selected))
+    }
+
     /// Get the JSON value at the currently selected path
     pub fn get_selected_value(&self) -> Option<String> {
         let selected = self.tree_state.selected();
@@ -301,12 +533,36 @@ impl JsonViewerState {
         }
 
         // Walk the JSON tree following the path
-        let value = self.get_value_at_path(&self.json, &selected)?;
+        let value = match selected.len() {
+            1 => self.document().clone(),
+            _ => self.get_value_at_path(&self.json, &selected)?,
+        };
 
         // Serialize to pretty JSON
         Some(serde_json::to_string_pretty(&value).unwrap_or_else(|_| value.to_string()))
     }
 
+    /// The selected node and everything beneath it as pretty JSON, and
+    /// whether that is the whole document (the root or nothing selected).
+    pub fn selected_subtree_json(&self) -> (String, bool) {
+        let selected = self.tree_state.selected();
+        let value = match selected.len() {
+            0 | 1 => self.document().clone(),
+            _ => self.get_value_at_path(&self.json, selected).unwrap_or_else(|| self.document().clone()),
+        };
+        let json = serde_json::to_string_pretty(&value).unwrap_or_else(|_| value.to_string());
+        (json, selected.len() <= 1)
+    }
+
+    /// The response body's JSON, without the `__headers` wrapper when the
+    /// headers are shown in the tree.
+    fn document(&self) -> &Value {
+        match &self.json {
+            Value::Object(root) if self.wraps_headers => root.get("__body").unwrap_or(&Value::Null),
+            json => json,
+        }
+    }
+
     fn get_value_at_path(&self, value: &Value, path: &[JsonPathSegment]) -> Option<Value> {
         if path.is_empty() {
             return Some(value.clone());
@@ -373,15 +629,20 @@ impl JsonViewerState {
                 vec![TreeItem::new(local_id, label, children).expect("valid tree item")]
             }
             _ => {
-                let label = self.format_value_label(value, key, is_match);
+                let label: Text<'static> = if value.is_string() && (self.full_strings || self.full_string_nodes.contains(&path)) {
+                    self.format_full_string_label(value, key, is_match, path.len())
+                } else {
+                    self.format_value_label(value, key, is_match).into()
+                };
                 vec![TreeItem::new(local_id, label, vec![]).expect("valid tree item")]
             }
         }
     }
 
-    /// Check if a path is a search match
+    /// Check if a path is a search match (or of the filtered type)
     fn is_search_match(&self, path: &JsonNodeId) -> bool {
-        !self.search_query.is_empty() && self.search_matches.contains(path)
+        (!self.search_query.is_empty() && self.search_matches.contains(path))
+            || self.type_matches.contains(path)
     }
 
     /// Format a label for an object node
@@ -430,6 +691,42 @@ impl JsonViewerState {
         Line::from(spans)
     }
 
+    /// Label for a string value shown in full, wrapped to the tree width.
+    /// `depth` is the node's path length, used to account for its indent.
+    fn format_full_string_label(&self, value: &Value, key: Option<&str>, is_match: bool, depth: usize) -> Text<'static> {
+        let text = format!("\"{}\"", value.as_str().unwrap_or_default());
+        let style = if is_match {
+            Style::default().fg(self.colors.match_highlight).bg(Color::Yellow)
+        } else {
+            Style::default().fg(self.colors.string)
+        };
+        let key_style = if is_match { style } else { Style::default().fg(self.colors.key) };
+
+        let width = match self.wrap_width {
+            0 => usize::MAX,
+            w => w.saturating_sub(depth * 2 + 2).max(20),
+        };
+        let key_width = key.map_or(0, |k| k.chars().count() + 2);
+        let mut chars = text.chars();
+        let mut lines = Vec::new();
+        let mut first = vec![];
+        if let Some(k) = key {
+            first.push(Span::styled(k.to_string(), key_style));
+            first.push(Span::styled(": ", Style::default().fg(self.colors.colon)));
+        }
+        let first_chunk: String = chars.by_ref().take(width.saturating_sub(key_width).max(10)).collect();
+        first.push(Span::styled(first_chunk, style));
+        lines.push(Line::from(first));
+        loop {
+            let chunk: String = chars.by_ref().take(width).collect();
+            if chunk.is_empty() {
+                break;
+            }
+            lines.push(Line::from(Span::styled(chunk, style)));
+        }
+        Text::from(lines)
+    }
+
     /// Format a label for a primitive value
     fn format_value_label(&self, value: &Value, key: Option<&str>, is_match: bool) -> Line<'static> {
         let mut spans = Vec::new();
@@ -448,17 +745,17 @@ impl JsonViewerState {
 
         let (value_str, color) = match value {
             Value::String(s) => {
-                let display = if s.len() > 50 {
-                    format!("\"{}...\"", &s[..47])
-                } else {
-                    format!("\"{}\"", s)
+                // Cut on a character boundary, not mid-way through a UTF-8 sequence
+                let display = match s.char_indices().nth(47) {
+                    Some((cut, _)) if s.chars().nth(50).is_some() => format!("\"{}...\"", &s[..cut]),
+                    _ => format!("\"{}\"", s),
                 };
                 (display, self.colors.string)
             }
             Value::Number(n) => (n.to_string(), self.colors.number),
             Value::Bool(b) => (b.to_string(), self.colors.boolean),
             Value::Null => ("null".to_string(), self.colors.null),
-            _ => ("?".to_string(), Color::White),
+            _ => ("?".to_string(), theme::text()),
         };
 
         if is_match {
@@ -2,17 +2,33 @@ use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Wrap},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
     Frame,
 };
 use tui_tree_widget::Tree;
 
-use crate::app::{App, DialogStep, FocusedPane, InputMode};
+use crate::api::client::missing_default_headers;
+use crate::api::stream::{StreamEvent, StreamKind};
+use crate::app::{count_requests, App, DialogStep, FocusedPane, InputMode, RowField, RowsTarget, StreamSession, VariablesScope};
+use crate::config::RequestSort;
+use crate::ui::theme;
+use crate::ui::xml_viewer::{self, XmlColors};
+use crate::util::{ellipsize, scroll_offset, ResponseView};
 
 const FOCUSED_COLOR: Color = Color::Green;
-const UNFOCUSED_COLOR: Color = Color::White;
+
+/// Smallest terminal the panes and popups fit in; below it only a notice
+/// is drawn.
+const MIN_WIDTH: u16 = 60;
+const MIN_HEIGHT: u16 = 16;
 
 pub fn render(frame: &mut Frame, app: &mut App) {
+    let area = frame.area();
+    if area.width < MIN_WIDTH || area.height < MIN_HEIGHT {
+        render_too_small(frame, area);
+        return;
+    }
+
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([Constraint::Min(0), Constraint::Length(3)])
@@ -35,9 +51,25 @@ pub fn render(frame: &mut Frame, app: &mut App) {
         render_new_request_dialog(frame, app);
     }
 
+    if app.input_mode == InputMode::TransferSelect {
+        render_transfer_popup(frame, app);
+    }
+
+    if app.input_mode == InputMode::NoteEdit {
+        render_note_edit(frame, app);
+    }
+
+    if app.input_mode == InputMode::OpenApiImport {
+        render_openapi_import(frame, app);
+    }
+
+    if app.input_mode == InputMode::UrlEdit {
+        render_url_edit(frame, app);
+    }
+
     // Render saving popup if active
     if app.input_mode == InputMode::Saving {
-        render_saving_popup(frame);
+        render_saving_popup(frame, app);
     }
 
     // Render environment selection popup if active
@@ -66,8 +98,13 @@ pub fn render(frame: &mut Frame, app: &mut App) {
     }
 
     // Render request executing popup if executing
-    if app.request_executing {
-        render_request_executing_popup(frame);
+    if app.request_executing || app.token_fetching {
+        render_request_executing_popup(frame, app);
+    }
+
+    // Render folder run progress popup while a run is in flight
+    if app.run_in_progress.is_some() {
+        render_run_progress_popup(frame, app);
     }
 
     // Render JSON search overlay if in JSON search mode
@@ -75,6 +112,11 @@ pub fn render(frame: &mut Frame, app: &mut App) {
         render_json_search_overlay(frame, app);
     }
 
+    // Render response text search overlay if searching a plain-text response
+    if app.input_mode == InputMode::ResponseSearch {
+        render_response_search_overlay(frame, app);
+    }
+
     // Render execute confirmation popup if active
     if app.input_mode == InputMode::ExecuteConfirm {
         render_execute_confirm_popup(frame, app);
@@ -85,6 +127,78 @@ pub fn render(frame: &mut Frame, app: &mut App) {
         render_delete_edit_confirm_popup(frame, app);
     }
 
+    if app.input_mode == InputMode::ClearEditsConfirm {
+        render_clear_edits_confirm_popup(frame, app);
+    }
+
+    // Render invalid-JSON-body confirmation popup if active
+    if app.input_mode == InputMode::InvalidBodyConfirm {
+        render_invalid_body_popup(frame, app);
+    }
+
+    // Render folder run confirmation / results if active
+    if app.input_mode == InputMode::RunConfirm {
+        render_run_confirm_popup(frame, app);
+    }
+    if app.input_mode == InputMode::RunResults {
+        render_run_results_popup(frame, app);
+    }
+
+    // Render dry run overlay if active
+    if app.input_mode == InputMode::DryRun {
+        render_dry_run_popup(frame, app);
+    }
+
+    // Render the read-only script viewer if active
+    if app.input_mode == InputMode::ScriptView {
+        render_script_view_popup(frame, app);
+    }
+
+    // Render the concurrent-change warning for a held-back bulk update
+    if app.input_mode == InputMode::EnvironmentConflict {
+        render_environment_conflict_popup(frame, app);
+    }
+
+    if app.input_mode == InputMode::CollectionConflict {
+        render_collection_conflict_popup(frame, app);
+    }
+
+    // Render baseline comparison if active
+    if app.input_mode == InputMode::BaselineDiff {
+        render_baseline_diff_popup(frame, app);
+    }
+
+    // Render the one-shot headers overlay if active
+    if app.input_mode == InputMode::ExtraHeaders {
+        render_extra_headers_popup(frame, app);
+    }
+
+    // Render the recently-executed list if active
+    if app.input_mode == InputMode::RecentRequests {
+        render_recent_requests_popup(frame, app);
+    }
+
+    // Render the API key re-entry prompt if Postman rejected the key
+    if app.input_mode == InputMode::ApiKeyInput {
+        render_api_key_popup(frame, app);
+    }
+
+    // Offer {{variable}} completions for whichever text field is being typed in
+    render_completion_popup(frame, app);
+
+    // Render command palette if active
+    if app.input_mode == InputMode::CommandPalette {
+        render_command_palette(frame, app);
+    }
+
+    if app.input_mode == InputMode::GlobalFinder {
+        render_global_finder(frame, app);
+    }
+
+    if app.input_mode == InputMode::RowsEditor {
+        render_rows_editor(frame, app);
+    }
+
     // Render parameter input dialog if active
     if app.input_mode == InputMode::ParamsInput {
         render_params_dialog(frame, app);
@@ -115,9 +229,11 @@ fn render_main_layout(frame: &mut Frame, app: &mut App, area: Rect) {
     let requests_area = left_vertical[1];
     let favorites_area = left_vertical[2];
 
-    render_collections_pane(frame, app, collections_area);
-    render_requests_pane(frame, app, requests_area);
-    render_favorites_pane(frame, app, favorites_area);
+    let clipped = [
+        render_collections_pane(frame, app, collections_area),
+        render_requests_pane(frame, app, requests_area),
+        render_favorites_pane(frame, app, favorites_area),
+    ];
 
     // Split right area if we have a response
     if app.response.is_some() {
@@ -131,13 +247,31 @@ fn render_main_layout(frame: &mut Frame, app: &mut App, area: Rect) {
     } else {
         render_preview_pane(frame, app, right_area);
     }
+
+    // The focused list's selected row, drawn in full over the panes to its
+    // right when the narrow column clips it
+    if let Some((row, line)) = clipped.into_iter().flatten().next() {
+        let row = row.intersection(frame.area());
+        frame.render_widget(Clear, row);
+        frame.render_widget(Paragraph::new(line), row);
+    }
+}
+
+/// The on-screen row and full text of a list pane's selected row, when the
+/// pane is too narrow to show all of it.
+fn clipped_row(area: Rect, row: usize, line: Line<'static>) -> Option<(Rect, Line<'static>)> {
+    let width = line.width() as u16;
+    if width <= area.width.saturating_sub(2) || row as u16 + 2 >= area.height {
+        return None;
+    }
+    Some((Rect::new(area.x + 1, area.y + 1 + row as u16, width, 1), line))
 }
 
 fn get_border_color(app: &App, pane: FocusedPane) -> Color {
     if app.focused_pane == pane {
         FOCUSED_COLOR
     } else {
-        UNFOCUSED_COLOR
+        theme::text()
     }
 }
 
@@ -149,13 +283,14 @@ fn get_title_with_number(title: &str, number: u8, focused: bool) -> String {
     }
 }
 
-fn render_collections_pane(frame: &mut Frame, app: &mut App, area: Rect) {
+fn render_collections_pane(frame: &mut Frame, app: &mut App, area: Rect) -> Option<(Rect, Line<'static>)> {
     let is_focused = app.focused_pane == FocusedPane::Collections;
     let is_searching = app.input_mode == InputMode::Search
         && !app.search_query.is_empty()
         && is_focused;
 
-    let items: Vec<ListItem> = app
+    // Indices of the rows in the (filtered) list
+    let shown: Vec<usize> = app
         .flat_collections
         .iter()
         .enumerate()
@@ -171,13 +306,32 @@ fn render_collections_pane(frame: &mut Frame, app: &mut App, area: Rect) {
                 true
             }
         })
-        .map(|(i, flat_col)| {
+        .map(|(i, _)| i)
+        .collect();
+
+    // Scroll just far enough to keep the selection in view, then build only
+    // the rows that fit, so huge lists cost no more to draw than short ones.
+    let selected_pos = shown.iter().position(|&i| i == app.selected_collection_index);
+    let rows = area.height.saturating_sub(2) as usize;
+    app.collections_view_rows = rows;
+    app.collections_scroll = scroll_offset(app.collections_scroll, selected_pos, rows, shown.len());
+
+    let mut selected_line = None;
+    let items: Vec<ListItem> = shown
+        .iter()
+        .skip(app.collections_scroll)
+        .take(rows)
+        .map(|&i| {
+            let flat_col = &app.flat_collections[i];
             let is_favorite = !flat_col.is_favorites_folder && app.config.is_favorite(&flat_col.uid);
+            let has_local_edits = !flat_col.is_favorites_folder && app.local_edits.has_edits_in_collection(&flat_col.uid);
             let style = if i == app.selected_collection_index {
                 Style::default()
                     .bg(Color::DarkGray)
                     .fg(Color::White)
                     .add_modifier(Modifier::BOLD)
+            } else if has_local_edits {
+                Style::default().fg(Color::Magenta)
             } else if flat_col.is_favorites_folder {
                 Style::default().fg(Color::Yellow)
             } else if is_favorite {
@@ -200,10 +354,30 @@ fn render_collections_pane(frame: &mut Frame, app: &mut App, area: Rect) {
                 ("  ".to_string(), flat_col.name.clone())
             };
 
-            ListItem::new(Line::from(vec![Span::styled(
-                format!("{}{}{}", indent, icon, name),
-                style,
-            )]))
+            let prefix = format!("{}{}", indent, icon);
+            let count_suffix = if flat_col.is_favorites_folder {
+                String::new()
+            } else {
+                let count = app.collection_request_count(&flat_col.uid)
+                    .map(|n| format!(" ({})", n))
+                    .unwrap_or_default();
+                if has_local_edits { format!("{} ~", count) } else { count }
+            };
+            let mut line = if is_searching {
+                highlight_name_match(prefix, &name, count_suffix, &app.search_query, style)
+            } else {
+                Line::from(vec![Span::styled(
+                    format!("{}{}{}", prefix, name, count_suffix),
+                    style,
+                )])
+            };
+            if let Some(label) = app.quick_jump_label(FocusedPane::Collections, i) {
+                line.spans.insert(0, quick_jump_span(label));
+            }
+            if i == app.selected_collection_index {
+                selected_line = Some(line.clone());
+            }
+            ListItem::new(line)
         })
         .collect();
 
@@ -213,25 +387,6 @@ fn render_collections_pane(frame: &mut Frame, app: &mut App, area: Rect) {
         "Collections".to_string()
     };
 
-    // Position of the selected collection within the (filtered) list, so the
-    // view scrolls to keep it visible when the list is taller than the pane.
-    let selected_pos = app
-        .flat_collections
-        .iter()
-        .enumerate()
-        .filter(|(i, flat_col)| {
-            if is_searching {
-                if flat_col.is_favorites_folder {
-                    false
-                } else {
-                    app.search_matches.contains(i)
-                }
-            } else {
-                true
-            }
-        })
-        .position(|(i, _)| i == app.selected_collection_index);
-
     let border_color = get_border_color(app, FocusedPane::Collections);
     let block = Block::default()
         .borders(Borders::ALL)
@@ -239,19 +394,62 @@ fn render_collections_pane(frame: &mut Frame, app: &mut App, area: Rect) {
         .title(get_title_with_number(&title, 1, is_focused));
 
     let list = List::new(items).block(block);
-    // Reuse the persistent scroll offset; only update which row is selected so
-    // the view stays put until the selection reaches an edge.
-    app.collections_list_state.select(selected_pos);
-    frame.render_stateful_widget(list, area, &mut app.collections_list_state);
+    let row = selected_pos.map(|pos| pos - app.collections_scroll);
+    let mut state = ListState::default().with_selected(row);
+    frame.render_stateful_widget(list, area, &mut state);
+    clipped_row(area, row?, selected_line.filter(|_| is_focused)?)
+}
+
+/// A quick-jump label drawn at the start of a list row.
+fn quick_jump_span(label: char) -> Span<'static> {
+    Span::styled(
+        format!("[{}]", label),
+        Style::default().fg(Color::Black).bg(Color::Yellow).add_modifier(Modifier::BOLD),
+    )
+}
+
+/// Render a list row with every case-insensitive occurrence of `query` in
+/// `name` emphasized, so it's clear why the row matched the search.
+fn highlight_name_match(prefix: String, name: &str, suffix: String, query: &str, style: Style) -> Line<'static> {
+    let match_style = style
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+
+    let mut spans = vec![Span::styled(prefix, style)];
+    // ASCII lowercasing keeps byte offsets aligned with the original name.
+    let haystack = name.to_ascii_lowercase();
+    let needle = query.to_ascii_lowercase();
+    let mut last = 0;
+    if !needle.is_empty() {
+        for (start, _) in haystack.match_indices(&needle) {
+            if start < last {
+                continue;
+            }
+            if start > last {
+                spans.push(Span::styled(name[last..start].to_string(), style));
+            }
+            let end = start + needle.len();
+            spans.push(Span::styled(name[start..end].to_string(), match_style));
+            last = end;
+        }
+    }
+    if last < name.len() {
+        spans.push(Span::styled(name[last..].to_string(), style));
+    }
+    if !suffix.is_empty() {
+        spans.push(Span::styled(suffix, style));
+    }
+    Line::from(spans)
 }
 
-fn render_requests_pane(frame: &mut Frame, app: &mut App, area: Rect) {
+fn render_requests_pane(frame: &mut Frame, app: &mut App, area: Rect) -> Option<(Rect, Line<'static>)> {
     let is_focused = app.focused_pane == FocusedPane::Requests;
     let is_searching = app.input_mode == InputMode::Search
         && !app.search_query.is_empty()
         && is_focused;
 
-    let items: Vec<ListItem> = app
+    // Indices of the rows in the (filtered) list
+    let shown: Vec<usize> = app
         .flat_items
         .iter()
         .enumerate()
@@ -263,7 +461,23 @@ fn render_requests_pane(frame: &mut Frame, app: &mut App, area: Rect) {
                 true
             }
         })
-        .map(|(i, item)| {
+        .map(|(i, _)| i)
+        .collect();
+
+    // As in the Collections pane, only the rows in view are built
+    let selected_pos = shown.iter().position(|&i| i == app.selected_item_index);
+    let rows = area.height.saturating_sub(2) as usize;
+    app.requests_view_rows = rows;
+    app.requests_scroll = scroll_offset(app.requests_scroll, selected_pos, rows, shown.len());
+
+    let compact = app.config.compact_requests;
+    let mut selected_line = None;
+    let items: Vec<ListItem> = shown
+        .iter()
+        .skip(app.requests_scroll)
+        .take(rows)
+        .map(|&i| {
+            let item = &app.flat_items[i];
             let is_favorite = app.is_request_favorite(&item.path);
             let has_local_edit = !item.is_folder && app.has_local_edit(&item.path);
             let indent = if is_searching {
@@ -276,7 +490,7 @@ fn render_requests_pane(frame: &mut Frame, app: &mut App, area: Rect) {
             } else {
                 ">"
             };
-            let method_prefix = if !item.is_folder {
+            let method_prefix = if !item.is_folder && !compact {
                 if let Some(req) = &item.request {
                     format!("[{}] ", req.method)
                 } else {
@@ -285,8 +499,16 @@ fn render_requests_pane(frame: &mut Frame, app: &mut App, area: Rect) {
             } else {
                 String::new()
             };
-            let favorite_prefix = if is_favorite { "* " } else { "" };
-            let modified_suffix = if has_local_edit { " ~" } else { "" };
+            let favorite_prefix = if is_favorite && !compact { "* " } else { "" };
+            let modified_suffix = if compact {
+                String::new()
+            } else if item.is_folder {
+                format!(" ({})", item.request_count)
+            } else if has_local_edit {
+                String::from(" ~")
+            } else {
+                String::new()
+            };
 
             let style = if i == app.selected_item_index {
                 Style::default()
@@ -303,34 +525,36 @@ fn render_requests_pane(frame: &mut Frame, app: &mut App, area: Rect) {
                 Style::default()
             };
 
-            ListItem::new(Line::from(vec![Span::styled(
-                format!("{}{} {}{}{}{}", indent, icon, favorite_prefix, method_prefix, item.name, modified_suffix),
-                style,
-            )]))
+            let prefix = format!("{}{} {}{}", indent, icon, favorite_prefix, method_prefix);
+            let mut line = if is_searching {
+                highlight_name_match(prefix, &item.name, modified_suffix, &app.search_query, style)
+            } else {
+                Line::from(vec![Span::styled(
+                    format!("{}{}{}", prefix, item.name, modified_suffix),
+                    style,
+                )])
+            };
+            if let Some(label) = app.quick_jump_label(FocusedPane::Requests, i) {
+                line.spans.insert(0, quick_jump_span(label));
+            }
+            if i == app.selected_item_index {
+                selected_line = Some(line.clone());
+            }
+            ListItem::new(line)
         })
         .collect();
 
     let title = if is_searching {
         format!("Requests ({} matches)", app.search_match_paths.len())
+    } else if let Some(collection) = &app.current_collection {
+        match app.request_sort() {
+            RequestSort::Original => format!("Requests ({})", count_requests(&collection.item)),
+            sort => format!("Requests ({}, by {})", count_requests(&collection.item), sort.label()),
+        }
     } else {
         "Requests".to_string()
     };
 
-    // Position of the selected request within the (filtered) list, so the view
-    // scrolls to keep it visible when the list is taller than the pane.
-    let selected_pos = app
-        .flat_items
-        .iter()
-        .enumerate()
-        .filter(|(_, item)| {
-            if is_searching {
-                app.search_match_paths.contains(&item.path)
-            } else {
-                true
-            }
-        })
-        .position(|(i, _)| i == app.selected_item_index);
-
     let border_color = get_border_color(app, FocusedPane::Requests);
     let block = Block::default()
         .borders(Borders::ALL)
@@ -338,13 +562,13 @@ fn render_requests_pane(frame: &mut Frame, app: &mut App, area: Rect) {
         .title(get_title_with_number(&title, 2, is_focused));
 
     let list = List::new(items).block(block);
-    // Reuse the persistent scroll offset; only update which row is selected so
-    // the view stays put until the selection reaches an edge.
-    app.requests_list_state.select(selected_pos);
-    frame.render_stateful_widget(list, area, &mut app.requests_list_state);
+    let row = selected_pos.map(|pos| pos - app.requests_scroll);
+    let mut state = ListState::default().with_selected(row);
+    frame.render_stateful_widget(list, area, &mut state);
+    clipped_row(area, row?, selected_line.filter(|_| is_focused)?)
 }
 
-fn render_favorites_pane(frame: &mut Frame, app: &mut App, area: Rect) {
+fn render_favorites_pane(frame: &mut Frame, app: &mut App, area: Rect) -> Option<(Rect, Line<'static>)> {
     let is_focused = app.focused_pane == FocusedPane::Favorites;
     let entries = app.favorite_entries();
 
@@ -354,7 +578,7 @@ fn render_favorites_pane(frame: &mut Frame, app: &mut App, area: Rect) {
         app.selected_favorite_index = entries.len() - 1;
     }
 
-    let items: Vec<ListItem> = entries
+    let lines: Vec<Line> = entries
         .iter()
         .enumerate()
         .map(|(i, entry)| {
@@ -378,12 +602,14 @@ fn render_favorites_pane(frame: &mut Frame, app: &mut App, area: Rect) {
                 .map(|m| format!("[{}] ", m))
                 .unwrap_or_default();
 
-            ListItem::new(Line::from(vec![
+            Line::from(vec![
                 Span::styled(format!("* {}{}", method_prefix, entry.name), name_style),
                 Span::styled(format!(" ({})", entry.collection_name), collection_style),
-            ]))
+            ])
         })
         .collect();
+    let selected_line = lines.get(app.selected_favorite_index).cloned();
+    let items: Vec<ListItem> = lines.into_iter().map(ListItem::new).collect();
 
     let border_color = get_border_color(app, FocusedPane::Favorites);
     let block = Block::default()
@@ -400,16 +626,19 @@ fn render_favorites_pane(frame: &mut Frame, app: &mut App, area: Rect) {
     let list = List::new(items).block(block);
     app.favorites_list_state.select(selected_pos);
     frame.render_stateful_widget(list, area, &mut app.favorites_list_state);
+    let row = selected_pos? - app.favorites_list_state.offset();
+    clipped_row(area, row, selected_line.filter(|_| is_focused)?)
 }
 
-fn render_preview_pane(frame: &mut Frame, app: &App, area: Rect) {
+fn render_preview_pane(frame: &mut Frame, app: &mut App, area: Rect) {
     let is_focused = app.focused_pane == FocusedPane::Preview;
     let border_color = get_border_color(app, FocusedPane::Preview);
-    let has_local_edit = app.has_unsaved_edit();
 
-    if let Some(request) = &app.current_request {
-        let param_count = app.current_request_params().len();
-        render_request_preview(frame, request, area, border_color, is_focused, has_local_edit, param_count);
+    if let Some(request) = app.current_request.clone() {
+        let (rows, max_scroll) = render_request_preview(frame, app, &request, area, border_color, is_focused);
+        app.preview_view_rows = rows;
+        app.preview_max_scroll = max_scroll;
+        app.preview_scroll = app.preview_scroll.min(max_scroll);
     } else {
         let block = Block::default()
             .borders(Borders::ALL)
@@ -426,13 +655,149 @@ fn render_response_pane(frame: &mut Frame, app: &mut App, area: Rect) {
     let is_focused = app.focused_pane == FocusedPane::Response;
     let border_color = get_border_color(app, FocusedPane::Response);
 
-    if let Some(response) = app.response.clone() {
+    if let Some(stream) = &app.stream {
+        render_stream_log(frame, app, stream, area, border_color, is_focused);
+    } else if let Some(response) = app.response.clone() {
+        let area = if response.redirects.is_empty() {
+            area
+        } else {
+            let height = (response.redirects.len() as u16 + 2).min(area.height / 3).max(3);
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(height), Constraint::Min(0)])
+                .split(area);
+            render_redirect_chain(frame, &response.redirects, chunks[0]);
+            chunks[1]
+        };
+        let area = if app.assertion_results.is_empty() {
+            area
+        } else {
+            let height = (app.assertion_results.len() as u16 + 2).min(area.height / 3).max(3);
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(height), Constraint::Min(0)])
+                .split(area);
+            render_assertion_results(frame, app, chunks[0]);
+            chunks[1]
+        };
         render_response(frame, app, &response, area, border_color, is_focused);
+    } else if app.current_request_failed() {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(border_color))
+            .title(get_title_with_number("Response", 5, is_focused));
+        let content = vec![
+            Line::from(Span::styled("(no response — request failed)", Style::default().fg(Color::Red))),
+            Line::from(""),
+            Line::from(Span::styled("See the error for details; e to retry", Style::default().fg(Color::DarkGray))),
+        ];
+        frame.render_widget(Paragraph::new(content).block(block), area);
     }
 }
 
-fn render_request_preview(frame: &mut Frame, request: &crate::api::Request, area: Rect, border_color: Color, is_focused: bool, has_local_edit: bool, param_count: usize) {
-    let url = request.url.to_string();
+/// The redirects followed before the response, numbered in order.
+fn render_redirect_chain(frame: &mut Frame, redirects: &[crate::api::RedirectHop], area: Rect) {
+    let dim = Style::default().fg(Color::DarkGray);
+    let lines: Vec<Line> = redirects
+        .iter()
+        .enumerate()
+        .map(|(i, hop)| {
+            Line::from(vec![
+                Span::styled(format!("{}. ", i + 1), dim),
+                Span::styled(format!("{} ", hop.status), Style::default().fg(Color::Blue)),
+                Span::raw(hop.url.clone()),
+                Span::styled(format!(" → {}", hop.location), dim),
+            ])
+        })
+        .collect();
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Blue))
+        .title(format!(" Redirects ({}) ", redirects.len()));
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// The executed request's assertions as a pass/fail checklist, failures
+/// first so they stay visible when the list is cut short.
+fn render_assertion_results(frame: &mut Frame, app: &App, area: Rect) {
+    let results = &app.assertion_results;
+    let passed = results.iter().filter(|r| r.passed).count();
+    let color = if passed == results.len() { Color::Green } else { Color::Red };
+    let mut ordered: Vec<_> = results.iter().collect();
+    ordered.sort_by_key(|r| r.passed);
+    let lines: Vec<Line> = ordered
+        .into_iter()
+        .map(|result| {
+            let (mark, style) = if result.passed {
+                ("✓", Style::default().fg(Color::Green))
+            } else {
+                ("✗", Style::default().fg(Color::Red))
+            };
+            let mut spans = vec![Span::styled(format!("{} ", mark), style), Span::raw(result.rule.clone())];
+            if let Some(detail) = &result.detail {
+                spans.push(Span::styled(format!("  ({})", detail), Style::default().fg(Color::DarkGray)));
+            }
+            Line::from(spans)
+        })
+        .collect();
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(color))
+        .title(format!(" Assertions {}/{} passed ", passed, results.len()));
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// The log of a WebSocket or event stream, newest at the bottom, with the
+/// message being typed (if any) on the last line.
+fn render_stream_log(frame: &mut Frame, app: &App, stream: &StreamSession, area: Rect, border_color: Color, is_focused: bool) {
+    let dim = Style::default().fg(Color::DarkGray);
+    let mut lines: Vec<Line> = Vec::new();
+    for (time, event) in &stream.log {
+        let (marker, text, style) = match event {
+            StreamEvent::Connected(status) => ("•", format!("Connected to {} ({})", stream.url, status), Style::default().fg(Color::Green)),
+            StreamEvent::Received(text) => ("←", text.clone(), Style::default()),
+            StreamEvent::Sent(text) => ("→", text.clone(), Style::default().fg(Color::Cyan)),
+            StreamEvent::Closed(reason) => ("•", reason.clone(), Style::default().fg(Color::Yellow)),
+            StreamEvent::Failed(e) => ("✗", e.clone(), Style::default().fg(Color::Red)),
+        };
+        for (i, line) in text.lines().enumerate() {
+            let prefix = if i == 0 { format!("{} {} ", time, marker) } else { " ".repeat(time.len() + 3) };
+            lines.push(Line::from(vec![Span::styled(prefix, dim), Span::styled(line.to_string(), style)]));
+        }
+    }
+    if stream.log.is_empty() {
+        lines.push(Line::from(Span::styled(format!("Connecting to {}...", stream.url), dim)));
+    }
+
+    let typing = app.input_mode == InputMode::StreamMessage;
+    let rows = (area.height.saturating_sub(2) as usize).saturating_sub(usize::from(typing));
+    let end = lines.len().saturating_sub(stream.scroll_back);
+    let start = end.saturating_sub(rows);
+    let mut content: Vec<Line> = lines.drain(start..end).collect();
+    if typing {
+        content.push(Line::from(vec![
+            Span::styled("> ", Style::default().fg(Color::Cyan)),
+            Span::raw(format!("{}_", app.stream_input)),
+        ]));
+    }
+
+    let state = if stream.open { "open" } else { "closed" };
+    let scrolled = if stream.scroll_back > 0 { format!(", {} lines back", stream.scroll_back) } else { String::new() };
+    let title = format!("Response ({} {}, {} messages{})", stream.kind.label(), state, stream.log.len(), scrolled);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(border_color))
+        .title(get_title_with_number(&title, 5, is_focused));
+    frame.render_widget(Paragraph::new(content).block(block), area);
+}
+
+/// Draws the preview scrolled by `app.preview_scroll`; returns the rows
+/// inside the border and the furthest scroll that still shows content.
+fn render_request_preview(frame: &mut Frame, app: &App, request: &crate::api::Request, area: Rect, border_color: Color, is_focused: bool) -> (u16, u16) {
+    let has_local_edit = app.has_unsaved_edit();
+    let param_count = app.current_request_params().len();
+    let (timeout_secs, custom_timeout) = app.current_request_timeout();
+    let url = request.url.send_url();
     let headers_text: String = request
         .header
         .iter()
@@ -440,24 +805,106 @@ fn render_request_preview(frame: &mut Frame, request: &crate::api::Request, area
         .collect::<Vec<_>>()
         .join("\n");
 
-    let body_text = request
-        .body
-        .as_ref()
-        .and_then(|b| b.raw.clone())
-        .unwrap_or_else(|| String::from("(no body)"));
+    let graphql = request.body.as_ref().and_then(|b| b.graphql());
+    let body_text = match (app.current_body_file(), request.body.as_ref().filter(|b| b.is_form_data())) {
+        (Some(file), _) => format!("(from file: {})", file),
+        (None, Some(body)) => form_data_lines(&body.formdata).join("\n"),
+        (None, None) if graphql.is_some() => graphql.map(graphql_lines).unwrap_or_default().join("\n"),
+        (None, None) => request
+            .body
+            .as_ref()
+            .and_then(|b| b.raw.clone())
+            .unwrap_or_else(|| String::from("(no body)")),
+    };
 
-    let content = format!(
-        "Method: {}\n\nURL: {}\n\nHeaders:\n{}\n\nBody:\n{}",
+    let summary = format!(
+        "Method: {}\n\nURL: {}\n\nTimeout: {}s ({})\n",
         request.method,
         url,
+        timeout_secs,
+        if custom_timeout { "custom" } else { "default" },
+    );
+    // One-shot headers from `+` are listed after the request's own, then
+    // the configured defaults that neither of them overrides
+    let mut with_extra = request.clone();
+    with_extra.header.extend(app.extra_headers.iter().cloned());
+    let headers_text = std::iter::once(headers_text)
+        .filter(|text| !text.is_empty())
+        .chain(app.extra_headers.iter().map(|h| format!("+ {}: {} (next run only)", h.key, h.value)))
+        .chain(
+            missing_default_headers(&with_extra, &app.config.settings.default_headers)
+                .into_iter()
+                .map(|(key, value)| format!("= {}: {} (default header)", key, value)),
+        )
+        .collect::<Vec<_>>()
+        .join("\n");
+    let body_label = match app.current_body_language() {
+        Some(language) => format!("Body ({}):", language),
+        None if request.body.as_ref().is_some_and(|b| b.is_form_data()) => String::from("Body (form-data):"),
+        None if graphql.is_some() => String::from("Body (GraphQL):"),
+        None => String::from("Body:"),
+    };
+    let body_label = if app.current_body_pruned() {
+        let method = if request.method.trim().is_empty() { String::from("GET") } else { request.method.to_uppercase() };
+        format!("{} - not sent with {} (enable disableBodyPruning in Postman to send it):", body_label.trim_end_matches(':'), method)
+    } else {
+        body_label
+    };
+    let details = format!(
+        "\nHeaders:\n{}\n\n{}\n{}",
         if headers_text.is_empty() {
             "(none)".to_string()
         } else {
             headers_text
         },
+        body_label,
         body_text
     );
 
+    // The resolved view substitutes everything but the pane's own labels,
+    // and highlights the placeholders that are left
+    let shown = |text: &str| if app.preview_resolved { app.substitute_for_preview(text) } else { text.to_string() };
+    let line = |text: &str| if app.preview_resolved { highlight_placeholders(text) } else { Line::from(text.to_string()) };
+    let summary = shown(&summary);
+    let details = shown(&details);
+    let mut content: Vec<Line> = summary.lines().map(line).collect();
+    // Disabled query params are left out of the URL above; list them dimmed
+    // under it
+    let disabled_query: Vec<String> = request
+        .url
+        .disabled_query()
+        .map(|p| match &p.value {
+            Some(value) => format!("{}={}", p.key, value),
+            None => p.key.clone(),
+        })
+        .collect();
+    if !disabled_query.is_empty() {
+        content.insert(
+            3,
+            Line::styled(format!("Disabled query params: {}", shown(&disabled_query.join("&"))), Style::default().fg(Color::DarkGray)),
+        );
+    }
+    // The local note goes first, where it can't be missed
+    if let Some(note) = app.current_note() {
+        content.insert(0, Line::from(""));
+        content.insert(0, Line::styled(format!("Note: {}", note), Style::default().fg(Color::Yellow)));
+    }
+    content.push(Line::from(""));
+    content.push(variables_line(&app.current_request_variables()));
+    let scripts = app.current_request_scripts();
+    if !scripts.is_empty() {
+        let listed = scripts
+            .iter()
+            .map(|(label, lines)| format!("{} ({} lines)", label, lines.len()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        content.push(Line::from(vec![
+            Span::raw(format!("Scripts: {} ", listed)),
+            Span::styled("(x to view)", Style::default().fg(Color::DarkGray)),
+        ]));
+    }
+    content.extend(details.lines().map(line));
+
     let title = if has_local_edit {
         "Request ~ (not synced to Postman)"
     } else {
@@ -480,35 +927,178 @@ fn render_request_preview(frame: &mut Frame, request: &crate::api::Request, area
             Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
         ));
     }
+    if app.preview_resolved {
+        title_spans.push(Span::styled("resolved ", Style::default().fg(Color::Cyan)));
+    }
+
+    // Wrapped height of the content, so scrolling stops at its last line
+    let paragraph = Paragraph::new(content).wrap(Wrap { trim: true });
+    let rows = area.height.saturating_sub(2);
+    let content_height = paragraph.line_count(area.width.saturating_sub(2).max(1));
+    let max_scroll = u16::try_from(content_height.saturating_sub(rows as usize)).unwrap_or(u16::MAX);
+    let scroll = app.preview_scroll.min(max_scroll);
+    if max_scroll > 0 {
+        title_spans.push(Span::styled(
+            format!("{}/{} ", scroll, max_scroll),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
 
     let block = Block::default()
         .borders(Borders::ALL)
         .border_style(Style::default().fg(border_color))
         .title(Line::from(title_spans));
 
-    let paragraph = Paragraph::new(content)
-        .block(block)
-        .wrap(Wrap { trim: true });
+    let paragraph = paragraph.block(block).scroll((scroll, 0));
 
     frame.render_widget(paragraph, area);
+    (rows, max_scroll)
 }
 
-fn render_response(frame: &mut Frame, app: &mut App, response: &crate::api::ExecutedResponse, area: Rect, border_color: Color, is_focused: bool) {
-    let status_color = if response.status >= 200 && response.status < 300 {
-        Color::Green
-    } else if response.status >= 400 {
-        Color::Red
-    } else {
-        Color::Yellow
-    };
+/// A preview line with the `{{placeholders}}` left after substitution
+/// highlighted: red when undefined, yellow for dynamic ones (`{{$guid}}`)
+/// that get a value when the request is sent.
+fn highlight_placeholders(text: &str) -> Line<'static> {
+    let mut spans = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start..].find("}}").map(|end| end + 2) else {
+            break;
+        };
+        let placeholder = &rest[start..start + len];
+        let color = if placeholder[2..].starts_with('$') { Color::Yellow } else { Color::Red };
+        spans.push(Span::raw(rest[..start].to_string()));
+        spans.push(Span::styled(placeholder.to_string(), Style::default().fg(color).add_modifier(Modifier::BOLD)));
+        rest = &rest[start + len..];
+    }
+    spans.push(Span::raw(rest.to_string()));
+    Line::from(spans)
+}
+
+/// The query as written, then the variables pretty-printed when they're
+/// valid JSON (as-is otherwise).
+fn graphql_lines(body: &crate::api::GraphqlBody) -> Vec<String> {
+    let mut lines: Vec<String> = body.query.lines().map(String::from).collect();
+    if lines.is_empty() {
+        lines.push(String::from("(empty query)"));
+    }
+    if let Some(variables) = body.variables.as_deref().filter(|v| !v.trim().is_empty()) {
+        let pretty = serde_json::from_str::<serde_json::Value>(variables)
+            .ok()
+            .and_then(|value| serde_json::to_string_pretty(&value).ok())
+            .unwrap_or_else(|| variables.to_string());
+        lines.push(String::new());
+        lines.push(String::from("Variables:"));
+        lines.extend(pretty.lines().map(String::from));
+    }
+    lines
+}
+
+/// One line per multipart field: `key: value` for text, `key: @path` for
+/// each file, with disabled fields marked.
+fn form_data_lines(fields: &[crate::api::FormField]) -> Vec<String> {
+    if fields.is_empty() {
+        return vec![String::from("(no fields)")];
+    }
+    let mut lines = Vec::new();
+    for field in fields {
+        let disabled = if field.disabled.unwrap_or(false) { " (disabled)" } else { "" };
+        if !field.is_file() {
+            lines.push(format!("{}: {}{}", field.key, field.value.as_deref().unwrap_or(""), disabled));
+            continue;
+        }
+        let files = field.files();
+        if files.is_empty() {
+            lines.push(format!("{}: (file not selected){}", field.key, disabled));
+        }
+        for path in files {
+            lines.push(format!("{}: @{} (file){}", field.key, path, disabled));
+        }
+    }
+    lines
+}
+
+/// `Variables: a, b, c` with each name green when the current scope defines
+/// it and red when it's missing.
+fn variables_line(variables: &[(String, bool)]) -> Line<'static> {
+    let mut spans = vec![Span::raw("Variables: ")];
+    if variables.is_empty() {
+        spans.push(Span::styled("(none)", Style::default().fg(Color::DarkGray)));
+    }
+    for (i, (name, resolved)) in variables.iter().enumerate() {
+        if i > 0 {
+            spans.push(Span::raw(", "));
+        }
+        let color = if *resolved { Color::Green } else { Color::Red };
+        spans.push(Span::styled(name.clone(), Style::default().fg(color)));
+    }
+    let missing = variables.iter().filter(|(_, resolved)| !resolved).count();
+    if missing > 0 {
+        spans.push(Span::styled(
+            format!("  ({} missing)", missing),
+            Style::default().fg(Color::Red),
+        ));
+    }
+    Line::from(spans)
+}
+
+/// The category name and display color for an HTTP status code.
+fn status_category(status: u16) -> (&'static str, Color) {
+    match status {
+        100..=199 => ("Informational", Color::Gray),
+        200..=299 => ("Success", Color::Green),
+        300..=399 => ("Redirect", Color::Blue),
+        400..=499 => ("Client Error", Color::LightRed),
+        500..=599 => ("Server Error", Color::Red),
+        _ => ("Unknown", Color::Yellow),
+    }
+}
+
+/// `Status: 404 Not Found (Client Error) · HTTP/1.1 · 1.2 KB`, with the code and category colored
+/// by status class, and a warning when the body was truncated. Shared by the
+/// JSON and plain-text response views.
+fn status_line_spans(response: &crate::api::ExecutedResponse) -> Vec<Span<'static>> {
+    let (category, color) = status_category(response.status);
+    let reason = response.status_text.split_whitespace().skip(1).collect::<Vec<_>>().join(" ");
+    let mut spans = vec![
+        Span::raw("Status: "),
+        Span::styled(
+            format!("{}", response.status),
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(format!(" {} ", reason)),
+        Span::styled(format!("({})", category), Style::default().fg(color)),
+        Span::styled(
+            format!(" · {} · {}", response.version, crate::util::format_bytes(response.body.len())),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ];
+    if let Some(cap) = response.truncated_at {
+        spans.push(Span::styled(
+            format!(" · response exceeded {}, truncated (max_response_bytes)", crate::util::format_bytes(cap)),
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ));
+    }
+    spans
+}
 
+fn render_response(frame: &mut Frame, app: &mut App, response: &crate::api::ExecutedResponse, area: Rect, border_color: Color, is_focused: bool) {
+    let ndjson_title = app.response_ndjson_count.map(|n| format!("Response (NDJSON, {} documents)", n));
     let title = if app.loading {
         "Response (loading...)"
+    } else if let Some(title) = ndjson_title.as_deref().filter(|_| app.json_viewer_state.is_some()) {
+        title
     } else if app.json_viewer_state.is_some() {
         "Response (JSON)"
+    } else if app.xml_pretty.is_some() {
+        "Response (XML)"
+    } else if app.response_view == ResponseView::Binary {
+        "Response (binary)"
     } else {
         "Response"
     };
+    let pinned_title = format!("{} [pinned, P to unpin]", title);
+    let title = if app.response_pinned { pinned_title.as_str() } else { title };
 
     let block = Block::default()
         .borders(Borders::ALL)
@@ -524,15 +1114,9 @@ fn render_response(frame: &mut Frame, app: &mut App, response: &crate::api::Exec
             .split(area);
 
         // Render status line at top
-        let status_line = Line::from(vec![
-            Span::raw("Status: "),
-            Span::styled(
-                format!("{}", response.status),
-                Style::default().fg(status_color).add_modifier(Modifier::BOLD),
-            ),
-            Span::raw(format!(" {} | j/k: nav | h/l: collapse/expand | /: search | y: copy",
-                response.status_text.split_whitespace().skip(1).collect::<Vec<_>>().join(" "))),
-        ]);
+        let mut status_spans = status_line_spans(response);
+        status_spans.push(Span::raw(" | j/k: nav | h/l: collapse/expand | /: search | y: copy"));
+        let status_line = Line::from(status_spans);
 
         let status_block = Block::default()
             .borders(Borders::TOP | Borders::LEFT | Borders::RIGHT)
@@ -545,56 +1129,117 @@ fn render_response(frame: &mut Frame, app: &mut App, response: &crate::api::Exec
         // Expand all nodes on first render
         viewer_state.maybe_expand_all();
 
-        // Render the JSON tree
+        // Render the JSON tree; strings shown in full wrap at its inner width
+        viewer_state.wrap_width = chunks[1].width.saturating_sub(2) as usize;
         let tree_items = viewer_state.build_tree_items();
+        // Breadcrumb of the selected node, cut from the left when too long
+        let mut tree_block = Block::default()
+            .borders(Borders::BOTTOM | Borders::LEFT | Borders::RIGHT)
+            .border_style(Style::default().fg(border_color));
+        if let Some(path) = viewer_state.selected_path_string() {
+            let room = chunks[1].width.saturating_sub(6) as usize;
+            let count = path.chars().count();
+            let path = if count > room {
+                format!("…{}", path.chars().skip(count + 1 - room).collect::<String>())
+            } else {
+                path
+            };
+            tree_block = tree_block.title_bottom(Line::from(Span::styled(format!(" {} ", path), Style::default().fg(Color::Cyan))));
+        }
         let tree = Tree::new(&tree_items)
             .expect("valid tree")
-            .block(Block::default()
-                .borders(Borders::BOTTOM | Borders::LEFT | Borders::RIGHT)
-                .border_style(Style::default().fg(border_color)))
+            .block(tree_block)
             .highlight_style(Style::default().bg(Color::DarkGray));
 
         frame.render_stateful_widget(tree, chunks[1], &mut viewer_state.tree_state);
     } else {
         // Fall back to plain text display for non-JSON responses
-        let headers_text: String = response
-            .headers
-            .iter()
-            .map(|(k, v)| format!("{}: {}", k, v))
-            .collect::<Vec<_>>()
-            .join("\n");
-
-        let body_preview = if response.body.len() > 2000 {
-            format!("{}...\n\n(truncated)", &response.body[..2000])
-        } else {
-            response.body.clone()
-        };
-
-        let content = vec![
-            Line::from(vec![
-                Span::raw("Status: "),
-                Span::styled(
-                    format!("{}", response.status),
-                    Style::default().fg(status_color).add_modifier(Modifier::BOLD),
-                ),
-                Span::raw(format!(" {}", response.status_text.split_whitespace().skip(1).collect::<Vec<_>>().join(" "))),
-            ]),
+        let mut content = vec![
+            Line::from(status_line_spans(response)),
             Line::from(""),
             Line::from(Span::styled("Headers:", Style::default().add_modifier(Modifier::BOLD))),
-            Line::from(headers_text),
-            Line::from(""),
-            Line::from(Span::styled("Body:", Style::default().add_modifier(Modifier::BOLD))),
-            Line::from(body_preview),
         ];
+        content.extend(response.headers.iter().map(|(k, v)| Line::from(format!("{}: {}", k, v))));
+        content.push(Line::from(""));
+        content.push(Line::from(Span::styled("Body:", Style::default().add_modifier(Modifier::BOLD))));
+        let binary = app.response_view == ResponseView::Binary && !response.body.is_empty();
+        if response.body.is_empty() {
+            // Tell a genuinely empty body (e.g. 204 No Content) from a failure
+            content.push(Line::from(Span::styled("(empty body)", Style::default().fg(Color::DarkGray))));
+        } else if binary {
+            let content_type = response
+                .headers
+                .iter()
+                .find(|(k, _)| k.eq_ignore_ascii_case("content-type"))
+                .map_or("no Content-Type", |(_, v)| v.as_str());
+            content.push(Line::from(Span::styled(
+                format!("(binary content, {}, {} - r to show it as text)", content_type, crate::util::format_bytes(response.body.len())),
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+
+        // Only build the body lines that can be on screen; `response_scroll`
+        // counts logical lines from the top (status line included).
+        let visible = area.height as usize;
+        let body_skip = app.response_scroll.saturating_sub(content.len());
+        let header_skip = app.response_scroll.min(content.len());
+        let content: Vec<Line> = content.into_iter().skip(header_skip).collect();
+
+        // XML is shown reindented and colorized; keep its leading whitespace.
+        let is_xml = app.xml_pretty.is_some();
+        let colors = XmlColors::default();
+        let query_len = app.response_search_query.len();
+        let current_match = app.response_search_matches.get(app.response_match_index).copied();
+        let body_lines = app
+            .response_display_body()
+            .lines()
+            .enumerate()
+            .skip(body_skip)
+            .take(if binary { 0 } else { visible })
+            .map(|(line_idx, line)| {
+                let hits: Vec<usize> = app
+                    .response_search_matches
+                    .iter()
+                    .filter(|(l, _)| *l == line_idx)
+                    .map(|(_, offset)| *offset)
+                    .collect();
+                if !hits.is_empty() {
+                    highlight_search_hits(line, &hits, query_len, current_match.filter(|(l, _)| *l == line_idx).map(|(_, o)| o))
+                } else if is_xml {
+                    xml_viewer::highlight_line(line, &colors)
+                } else {
+                    Line::from(line.to_string())
+                }
+            });
+        let content: Vec<Line> = content.into_iter().chain(body_lines).collect();
 
         let paragraph = Paragraph::new(content)
             .block(block)
-            .wrap(Wrap { trim: true });
+            .wrap(Wrap { trim: !is_xml });
 
         frame.render_widget(paragraph, area);
     }
 }
 
+/// Render a line of plain text with search hits highlighted; the current
+/// match (if on this line) stands out from the others.
+fn highlight_search_hits(line: &str, hits: &[usize], len: usize, current: Option<usize>) -> Line<'static> {
+    let mut spans = Vec::new();
+    let mut pos = 0;
+    for &start in hits {
+        if start < pos {
+            continue;
+        }
+        let end = (start + len).min(line.len());
+        spans.push(Span::raw(line[pos..start].to_string()));
+        let bg = if Some(start) == current { Color::LightRed } else { Color::Yellow };
+        spans.push(Span::styled(line[start..end].to_string(), Style::default().fg(Color::Black).bg(bg)));
+        pos = end;
+    }
+    spans.push(Span::raw(line[pos..].to_string()));
+    Line::from(spans)
+}
+
 fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
     let status_style = if app.error.is_some() {
         Style::default().fg(Color::Red)
@@ -606,6 +1251,8 @@ fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
 
     let status_text = if let Some(error) = &app.error {
         format!("Error: {}", error)
+    } else if app.loading {
+        format!("{} {}", app.spinner(), app.status_message)
     } else {
         app.status_message.clone()
     };
@@ -615,45 +1262,79 @@ fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
         InputMode::TextInput => "Enter: Next/Submit | Esc: Cancel",
         InputMode::Search => "Enter: Confirm | Esc: Cancel | Type to search",
         InputMode::JsonSearch => "Enter: Confirm | Esc: Cancel | n/N: Next/Prev match | Type to search",
+        InputMode::ResponseSearch => "Enter: Confirm | Esc: Cancel | Type to search",
         InputMode::Saving => "Esc: Cancel",
-        InputMode::ExecuteConfirm => "y/Enter: Execute | n/Esc: Cancel",
-        InputMode::DeleteEditConfirm => "y/Enter: Discard | n/Esc: Cancel",
+        InputMode::ExecuteConfirm => "y/Enter: Execute | a: Don't ask again for this collection | n/Esc: Cancel",
+        InputMode::DeleteEditConfirm | InputMode::ClearEditsConfirm => "y/Enter: Discard | n/Esc: Cancel",
+        InputMode::InvalidBodyConfirm => "y/Enter: Store anyway | n/Esc: Back to editor",
+        InputMode::ExtraHeaders => "Enter: Add header (empty: done) | Backspace on empty: Remove last | Esc: Done",
+        InputMode::RunConfirm => "y/Enter: Run | n/Esc: Cancel",
+        InputMode::RunResults => "j/k: Nav | Enter: Open request | Esc: Close",
+        InputMode::DryRun => "j/k: Scroll | Esc: Close",
+        InputMode::BaselineDiff => "j/k: Scroll | Esc: Close",
+        InputMode::CommandPalette => "Type to filter | Up/Down: Select | Enter: Run | Esc: Close",
+        InputMode::GlobalFinder => "Type a name or request id | Up/Down: Select | Enter: Open | Esc: Close",
+        InputMode::UrlEdit => "Enter: Store | Tab: Complete variable | Esc: Cancel",
+        InputMode::OpenApiImport => "Enter: Import | Esc: Cancel",
+        InputMode::NoteEdit => "Enter: Store | Esc: Cancel",
+        InputMode::TransferSelect => "j/k: Select collection | c/Enter: Copy | m: Move | Esc: Cancel",
+        InputMode::RowsEditor => if app.rows_editor.as_ref().is_some_and(|e| e.editing.is_some()) {
+            "Type to edit | Tab: Key/value | Enter/Esc: Done"
+        } else {
+            "j/k: Row | n: Key | e: Value | a: Add | d: Delete | Space: On/off | Enter: Apply | Esc: Cancel"
+        },
         InputMode::ParamsInput => if app.params_dialog.as_ref().map(|d| d.editing).unwrap_or(false) {
             "Type to edit | Ctrl+U: Clear | Enter/Esc: Done"
         } else {
             "j/k: Field | e: Edit | r: Replace | Enter: Send | Esc: Cancel"
         },
         InputMode::EnvironmentSelect => "j/k: Nav | Enter: Select | Esc: Cancel",
+        InputMode::RecentRequests => "j/k: Nav | Enter: Open request | Esc: Close",
+        InputMode::StreamMessage => "Enter: Send | Ctrl+v: Paste | Esc: Cancel",
+        InputMode::CollectionConflict => "r/Enter: Reload and reapply my change | Esc: Reload only",
+        InputMode::EnvironmentConflict => "m/Enter: Merge | o: Overwrite Postman's changes | Esc: Cancel",
+        InputMode::QuickJump => "Press a row's label to jump | Any other key: Cancel",
+        InputMode::ScriptView => "j/k: Scroll | Esc/x: Close (scripts are not run)",
+        InputMode::ApiKeyInput => "Enter: Save key and retry | Ctrl+v: Paste | Esc: Keep current key",
         InputMode::VariablesView => if app.editing_variable.is_some() {
             "Enter: Confirm | Esc: Cancel | Type to edit"
         } else if app.variables_search_active {
             "Enter: Confirm | Esc: Cancel | Type to search"
+        } else if app.variables_export_confirm {
+            "f: Write .env file | c: Copy export lines | Esc: Cancel"
         } else {
-            "j/k: Nav | Enter: Edit | /: Search | s: Save | Esc: Close"
+            "j/k: Nav | Enter: Edit | Tab: Environment/Collection | h: Earlier value | /: Search | s: Save | t: Fetch token | x: Export | Esc: Close"
         },
         InputMode::WorkspaceSelect => "j/k: Nav | Enter: Select | Esc: Cancel",
         InputMode::Normal => {
             let has_unsaved = app.has_unsaved_edit();
             match (app.focused_pane, has_env, has_unsaved) {
-                (FocusedPane::Collections, true, _) => "1-5: Pane | j/k: Nav | Enter: Load | f: Fav | v: Env | V: Vars | Ctrl+q: Quit",
-                (FocusedPane::Collections, false, _) => "1-5: Pane | j/k: Nav | Enter: Load | f: Fav | v: Env | Ctrl+q: Quit",
-                (FocusedPane::Requests, true, _) => "1-5: Pane | j/k: Nav | Enter: Select | e: Exec | a: Add | f: Fav | v: Env | V: Vars | Ctrl+q: Quit",
-                (FocusedPane::Requests, false, _) => "1-5: Pane | j/k: Nav | Enter: Select | e: Exec | a: Add | f: Fav | v: Env | Ctrl+q: Quit",
-                (FocusedPane::Favorites, true, _) => "1-5: Pane | j/k: Nav | Enter: Open | f: Unfav | v: Env | V: Vars | Ctrl+q: Quit",
-                (FocusedPane::Favorites, false, _) => "1-5: Pane | j/k: Nav | Enter: Open | f: Unfav | v: Env | Ctrl+q: Quit",
-                (FocusedPane::Preview, true, true) => "1-5: Pane | e: Exec | E: Edit | S: Save* | D: Discard | v: Env | V: Vars | Ctrl+q: Quit",
-                (FocusedPane::Preview, true, false) => "1-5: Pane | e: Exec | E: Edit | v: Env | V: Vars | Ctrl+q: Quit",
-                (FocusedPane::Preview, false, true) => "1-5: Pane | e: Exec | E: Edit | S: Save* | D: Discard | v: Env | Ctrl+q: Quit",
-                (FocusedPane::Preview, false, false) => "1-5: Pane | e: Exec | E: Edit | v: Env | Ctrl+q: Quit",
+                (FocusedPane::Collections, true, _) => "1-5: Pane | j/k: Nav | Enter: Load | f: Fav | D: Discard edits | I: Import | v: Env | V: Vars | Ctrl+q: Quit",
+                (FocusedPane::Collections, false, _) => "1-5: Pane | j/k: Nav | Enter: Load | f: Fav | D: Discard edits | I: Import | v: Env | Ctrl+q: Quit",
+                (FocusedPane::Requests, true, _) => "1-5: Pane | j/k: Nav | Enter: Select | e: Exec | x: Quick exec | R: Run folder | a: Add | M: Copy to | s: Sort | c: Compact | O: OpenAPI | f: Fav | v: Env | V: Vars | Ctrl+q: Quit",
+                (FocusedPane::Requests, false, _) => "1-5: Pane | j/k: Nav | Enter: Select | e: Exec | x: Quick exec | R: Run folder | a: Add | M: Copy to | s: Sort | c: Compact | O: OpenAPI | f: Fav | v: Env | Ctrl+q: Quit",
+                (FocusedPane::Favorites, true, _) => "1-5: Pane | j/k: Nav | Enter: Open | f: Unfav | R: Run all | v: Env | V: Vars | Ctrl+q: Quit",
+                (FocusedPane::Favorites, false, _) => "1-5: Pane | j/k: Nav | Enter: Open | f: Unfav | R: Run all | v: Env | Ctrl+q: Quit",
+                (FocusedPane::Preview, true, true) => "1-5: Pane | j/k: Scroll | e: Exec | d: Dry run | s: Resolve | E: Edit | u: URL | P: Query | h: Headers | X: Export | S: Save* | D: Discard | v: Env | V: Vars | Ctrl+q: Quit",
+                (FocusedPane::Preview, true, false) => "1-5: Pane | j/k: Scroll | e: Exec | d: Dry run | s: Resolve | E: Edit | u: URL | P: Query | h: Headers | X: Export | v: Env | V: Vars | Ctrl+q: Quit",
+                (FocusedPane::Preview, false, true) => "1-5: Pane | j/k: Scroll | e: Exec | d: Dry run | s: Resolve | E: Edit | u: URL | P: Query | h: Headers | X: Export | S: Save* | D: Discard | v: Env | Ctrl+q: Quit",
+                (FocusedPane::Preview, false, false) => "1-5: Pane | j/k: Scroll | e: Exec | d: Dry run | s: Resolve | E: Edit | u: URL | P: Query | h: Headers | X: Export | v: Env | Ctrl+q: Quit",
+                (FocusedPane::Response, _, _) if app.stream.as_ref().is_some_and(|s| !s.open) => {
+                    "j/k: Scroll | c: Dismiss log | e: Reconnect (in Preview) | Ctrl+q: Quit"
+                }
+                (FocusedPane::Response, _, _) if app.stream.as_ref().is_some_and(|s| s.kind == StreamKind::WebSocket) => {
+                    "j/k: Scroll | m: Send message | c: Close connection | Ctrl+q: Quit"
+                }
+                (FocusedPane::Response, _, _) if app.stream.is_some() => "j/k: Scroll | c: Close stream | Ctrl+q: Quit",
                 (FocusedPane::Response, true, _) => if app.json_viewer_state.is_some() {
-                    "j/k: Nav | h/l: Fold | H/L: Fold All | /: Search | n/N: Match | y: Copy | v: Env | Ctrl+q: Quit"
+                    "j/k: Nav | h/l: Fold | H/L: Fold All | /: Search | n/N: Match | y: Copy | C: Subtree | Y: Copy body | r: View as | M: Report | U: Pager | b: Baseline | B: Compare | P: Pin | v: Env | Ctrl+q: Quit"
                 } else {
-                    "1-5: Pane | v: Env | V: Vars | Ctrl+q: Quit"
+                    "j/k: Scroll | /: Search | n/N: Match | y: Copy body | 1-5: Pane | r: View as | M: Report | U: Pager | b: Baseline | B: Compare | P: Pin | v: Env | V: Vars | Ctrl+q: Quit"
                 },
                 (FocusedPane::Response, false, _) => if app.json_viewer_state.is_some() {
-                    "j/k: Nav | h/l: Fold | H/L: Fold All | /: Search | n/N: Match | y: Copy | v: Env | Ctrl+q: Quit"
+                    "j/k: Nav | h/l: Fold | H/L: Fold All | /: Search | n/N: Match | y: Copy | C: Subtree | Y: Copy body | r: View as | M: Report | U: Pager | b: Baseline | B: Compare | P: Pin | v: Env | Ctrl+q: Quit"
                 } else {
-                    "1-5: Pane | v: Env | Ctrl+q: Quit"
+                    "j/k: Scroll | /: Search | n/N: Match | y: Copy body | 1-5: Pane | r: View as | M: Report | U: Pager | b: Baseline | B: Compare | P: Pin | v: Env | Ctrl+q: Quit"
                 },
             }
         },
@@ -664,9 +1345,27 @@ fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
         .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
         .split(area);
 
+    let mut status_title = vec![Span::raw(" Status ")];
+    for (label, rate_limit) in [
+        ("API", app.client.api_rate_limit()),
+        ("Target", app.response_rate_limit),
+    ] {
+        if let Some(rate_limit) = rate_limit {
+            status_title.push(rate_limit_span(label, &rate_limit));
+        }
+    }
+    // Age of the last fetch from Postman, to flag stale data
+    if let Some(refreshed) = app.last_refresh {
+        let age = (chrono::Local::now() - refreshed).num_seconds();
+        status_title.push(Span::styled(
+            format!("[updated {} at {}] ", crate::util::format_age(age), refreshed.format("%H:%M")),
+            Style::default().fg(if age >= 3600 { Color::Yellow } else { Color::DarkGray }),
+        ));
+    }
+
     let status = Paragraph::new(status_text)
         .style(status_style)
-        .block(Block::default().borders(Borders::ALL).title(" Status "));
+        .block(Block::default().borders(Borders::ALL).title(Line::from(status_title)));
 
     let keys = Paragraph::new(keybindings)
         .style(Style::default().fg(Color::Cyan))
@@ -676,6 +1375,21 @@ fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_widget(keys, chunks[1]);
 }
 
+/// `[API 12/300]`-style rate-limit badge for the status bar title, red when
+/// the remaining budget is low.
+fn rate_limit_span(label: &str, rate_limit: &crate::api::RateLimit) -> Span<'static> {
+    let text = match rate_limit.limit {
+        Some(limit) => format!("[{} {}/{}] ", label, rate_limit.remaining, limit),
+        None => format!("[{} {} left] ", label, rate_limit.remaining),
+    };
+    let style = if rate_limit.is_low() {
+        Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(Color::DarkGray)
+    };
+    Span::styled(text, style)
+}
+
 fn render_new_request_dialog(frame: &mut Frame, app: &App) {
     let dialog = match &app.new_request_dialog {
         Some(d) => d,
@@ -727,14 +1441,149 @@ fn render_new_request_dialog(frame: &mut Frame, app: &App) {
     let block = Block::default()
         .borders(Borders::ALL)
         .title(format!(" New Request - {} ", step_indicator))
-        .style(Style::default().bg(Color::Black));
+        .style(Style::default().bg(theme::popup_background()));
 
     let paragraph = Paragraph::new(content).block(block);
 
     frame.render_widget(paragraph, dialog_area);
 }
 
-fn render_saving_popup(frame: &mut Frame) {
+fn render_url_edit(frame: &mut Frame, app: &App) {
+    let Some(edit) = &app.url_edit else {
+        return;
+    };
+    render_line_prompt(
+        frame,
+        " Edit URL ",
+        None,
+        (&edit.url, edit.cursor_position),
+        " Enter: Store as local edit | Tab: Complete {{variable}} | Esc: Cancel",
+        100,
+    );
+}
+
+/// A centred single-line text prompt with a cursor marker, an optional
+/// label above the input and a key hint below it.
+fn render_line_prompt(
+    frame: &mut Frame,
+    title: &str,
+    label: Option<&str>,
+    (text, cursor): (&str, usize),
+    hint: &str,
+    max_width: u16,
+) {
+    let area = frame.area();
+
+    let dialog_width = max_width.min(area.width.saturating_sub(4)).max(40);
+    let dialog_height = 8u16;
+    let x = area.width.saturating_sub(dialog_width) / 2;
+    let y = area.height.saturating_sub(dialog_height) / 2;
+
+    let dialog_area = Rect::new(x, y, dialog_width.min(area.width), dialog_height.min(area.height));
+
+    frame.render_widget(Clear, dialog_area);
+
+    let input_with_cursor = if cursor >= text.len() {
+        format!("{}_", text)
+    } else {
+        let (before, after) = text.split_at(cursor);
+        format!("{}|{}", before, after)
+    };
+
+    let mut content = Vec::new();
+    if let Some(label) = label {
+        content.push(Line::from(Span::styled(format!(" {}", label), Style::default().fg(Color::DarkGray))));
+    }
+    content.push(Line::from(format!(" {}", input_with_cursor)));
+    content.push(Line::from(""));
+    content.push(Line::from(Span::styled(hint.to_string(), Style::default().fg(Color::DarkGray))));
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title.to_string())
+        .style(Style::default().bg(theme::popup_background()));
+
+    let paragraph = Paragraph::new(content).block(block).wrap(Wrap { trim: false });
+
+    frame.render_widget(paragraph, dialog_area);
+}
+
+fn render_too_small(frame: &mut Frame, area: Rect) {
+    let content = vec![
+        Line::styled("Terminal too small", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+        Line::from(format!("{}x{}, needs {}x{}", area.width, area.height, MIN_WIDTH, MIN_HEIGHT)),
+        Line::styled("Enlarge the window to continue", Style::default().fg(Color::DarkGray)),
+    ];
+    let height = (content.len() as u16).min(area.height);
+    let y = area.y + area.height.saturating_sub(height) / 2;
+    let paragraph = Paragraph::new(content).alignment(ratatui::layout::Alignment::Center).wrap(Wrap { trim: true });
+    frame.render_widget(paragraph, Rect::new(area.x, y, area.width, height));
+}
+
+fn render_transfer_popup(frame: &mut Frame, app: &App) {
+    let Some(dialog) = &app.transfer_dialog else {
+        return;
+    };
+    let area = frame.area();
+
+    let max_name_len = dialog.targets.iter().map(|c| c.name.chars().count()).max().unwrap_or(10);
+    let popup_width = ((max_name_len + 6) as u16).clamp(40, 70).min(area.width);
+    let popup_height = ((dialog.targets.len() + 4) as u16).min(20).min(area.height);
+    let x = area.width.saturating_sub(popup_width) / 2;
+    let y = area.height.saturating_sub(popup_height) / 2;
+    let popup_area = Rect::new(x, y, popup_width, popup_height);
+
+    frame.render_widget(Clear, popup_area);
+
+    let items: Vec<ListItem> = dialog
+        .targets
+        .iter()
+        .map(|c| ListItem::new(Line::from(c.name.clone())))
+        .collect();
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" Copy '{}' to ", ellipsize(&dialog.name, 40)))
+        .title_bottom(Line::styled(" c/Enter: Copy | m: Move | Esc ", Style::default().fg(Color::DarkGray)))
+        .border_style(Style::default().fg(Color::Magenta))
+        .style(Style::default().bg(theme::popup_background()));
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(Style::default().bg(Color::DarkGray).fg(Color::White).add_modifier(Modifier::BOLD));
+    let mut state = ListState::default().with_selected(Some(dialog.selected));
+    frame.render_stateful_widget(list, popup_area, &mut state);
+}
+
+fn render_note_edit(frame: &mut Frame, app: &App) {
+    let Some(edit) = &app.note_edit else {
+        return;
+    };
+    render_line_prompt(
+        frame,
+        " Note ",
+        None,
+        (&edit.note, edit.cursor_position),
+        " Enter: Store (on this machine only, empty removes it) | Esc: Cancel",
+        80,
+    );
+}
+
+fn render_openapi_import(frame: &mut Frame, app: &App) {
+    let Some(prompt) = &app.openapi_import else {
+        return;
+    };
+    render_line_prompt(
+        frame,
+        " Import OpenAPI ",
+        Some("OpenAPI 3 spec (JSON or YAML) to create a collection from:"),
+        (&prompt.path, prompt.cursor_position),
+        " Enter: Import into the selected workspace | Esc: Cancel",
+        80,
+    );
+}
+
+fn render_saving_popup(frame: &mut Frame, app: &App) {
     let area = frame.area();
 
     let popup_width = 40u16;
@@ -749,7 +1598,7 @@ fn render_saving_popup(frame: &mut Frame) {
     let content = vec![
         Line::from(""),
         Line::from(Span::styled(
-            "  Saving changes to Postman...",
+            format!("  {} Saving changes to Postman...", app.spinner()),
             Style::default().add_modifier(Modifier::BOLD),
         )),
         Line::from(""),
@@ -763,7 +1612,7 @@ fn render_saving_popup(frame: &mut Frame) {
         .borders(Borders::ALL)
         .title(" Saving ")
         .border_style(Style::default().fg(Color::Yellow))
-        .style(Style::default().bg(Color::Black));
+        .style(Style::default().bg(theme::popup_background()));
 
     let paragraph = Paragraph::new(content).block(block);
 
@@ -866,18 +1715,367 @@ fn render_environment_popup(frame: &mut Frame, app: &App) {
         .borders(Borders::ALL)
         .title(" Environment ")
         .border_style(Style::default().fg(Color::Cyan))
-        .style(Style::default().bg(Color::Black));
+        .style(Style::default().bg(theme::popup_background()));
 
     let list = List::new(items).block(block);
     frame.render_widget(list, popup_area);
 }
 
+fn render_completion_popup(frame: &mut Frame, app: &App) {
+    let candidates = app.completion_candidates();
+    if candidates.is_empty() {
+        return;
+    }
+
+    const MAX_SHOWN: usize = 8;
+    let area = frame.area();
+    let selected = app.completion_index % candidates.len();
+    let offset = selected.saturating_sub(MAX_SHOWN - 1);
+
+    let width = candidates.iter().map(|c| c.len()).max().unwrap_or(0).max(20) as u16 + 4;
+    let height = candidates.len().min(MAX_SHOWN) as u16 + 2;
+    // Just below the centered dialogs the text inputs live in
+    let x = area.width.saturating_sub(width) / 2;
+    let y = (area.height / 2 + 4).min(area.height.saturating_sub(height));
+    let popup_area = Rect::new(x, y, width.min(area.width), height.min(area.height));
+
+    frame.render_widget(Clear, popup_area);
+
+    let items: Vec<ListItem> = candidates
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(MAX_SHOWN)
+        .map(|(i, name)| {
+            let style = if i == selected {
+                Style::default().bg(Color::DarkGray).fg(Color::White).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::Yellow)
+            };
+            ListItem::new(Line::from(Span::styled(name.clone(), style)))
+        })
+        .collect();
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Variables ")
+        .title_bottom(Line::from(" Tab: Insert ").right_aligned())
+        .border_style(Style::default().fg(Color::Yellow))
+        .style(Style::default().bg(theme::popup_background()));
+
+    frame.render_widget(List::new(items).block(block), popup_area);
+}
+
+fn render_extra_headers_popup(frame: &mut Frame, app: &App) {
+    let area = frame.area();
+    let popup_width = 60u16.min(area.width.saturating_sub(4));
+    let popup_height = ((app.extra_headers.len().max(1) + 5) as u16).min(area.height.saturating_sub(4));
+    let x = (area.width.saturating_sub(popup_width)) / 2;
+    let y = (area.height.saturating_sub(popup_height)) / 3;
+    let popup_area = Rect::new(x, y, popup_width, popup_height).intersection(area);
+
+    frame.render_widget(Clear, popup_area);
+
+    let dim = Style::default().fg(Color::DarkGray);
+    let mut content = Vec::new();
+    if app.extra_headers.is_empty() {
+        content.push(Line::from(Span::styled("(none yet)", dim)));
+    }
+    for header in &app.extra_headers {
+        content.push(Line::from(vec![
+            Span::styled(format!("{}: ", header.key), Style::default().fg(Color::Cyan)),
+            Span::raw(header.value.clone()),
+        ]));
+    }
+    content.push(Line::from(""));
+    content.push(Line::from(vec![
+        Span::styled("> ", Style::default().fg(Color::Cyan)),
+        Span::raw(&app.extra_header_input),
+        Span::styled("_", dim),
+    ]));
+    content.push(Line::from(Span::styled("Key: Value — sent with the next run only", dim)));
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Extra Headers ")
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(theme::popup_background()));
+
+    frame.render_widget(Paragraph::new(content).block(block), popup_area);
+}
+
+fn render_api_key_popup(frame: &mut Frame, app: &App) {
+    let area = frame.area();
+    let popup_width = 70u16.min(area.width.saturating_sub(4));
+    let popup_height = 9u16.min(area.height.saturating_sub(4));
+    let x = (area.width.saturating_sub(popup_width)) / 2;
+    let y = (area.height.saturating_sub(popup_height)) / 3;
+    let popup_area = Rect::new(x, y, popup_width, popup_height).intersection(area);
+
+    frame.render_widget(Clear, popup_area);
+
+    let dim = Style::default().fg(Color::DarkGray);
+    // Mask the key, keeping the last few characters to tell keys apart
+    let len = app.api_key_input.chars().count();
+    let shown: String = app.api_key_input.chars().skip(len.saturating_sub(4)).collect();
+    let masked = format!("{}{}", "*".repeat(len.saturating_sub(4)), shown);
+    let content = vec![
+        Line::from(Span::styled(
+            app.api_key_error.clone().unwrap_or_default(),
+            Style::default().fg(Color::Red),
+        )),
+        Line::from(""),
+        Line::from("Enter a new Postman API key:"),
+        Line::from(vec![
+            Span::styled("> ", Style::default().fg(Color::Cyan)),
+            Span::raw(masked),
+            Span::styled("_", dim),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled("https://web.postman.co/settings/me/api-keys", dim)),
+    ];
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" API Key ")
+        .border_style(Style::default().fg(Color::Red))
+        .style(Style::default().bg(theme::popup_background()));
+
+    frame.render_widget(
+        Paragraph::new(content).block(block).wrap(Wrap { trim: true }),
+        popup_area,
+    );
+}
+
+fn render_global_finder(frame: &mut Frame, app: &App) {
+    let finder = match &app.finder {
+        Some(f) => f,
+        None => return,
+    };
+
+    let area = frame.area();
+    let popup_width = 80u16.min(area.width.saturating_sub(4));
+    let popup_height = ((finder.matches.len().max(1) + 4) as u16).min(area.height.saturating_sub(4));
+    let x = (area.width.saturating_sub(popup_width)) / 2;
+    let y = (area.height.saturating_sub(popup_height)) / 3;
+    let popup_area = Rect::new(x, y, popup_width, popup_height).intersection(area);
+
+    frame.render_widget(Clear, popup_area);
+
+    let title = if finder.loading > 0 {
+        format!(" Find in all collections (fetching {} more) ", finder.loading)
+    } else {
+        String::from(" Find in all collections ")
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(theme::popup_background()));
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(2), Constraint::Min(0)])
+        .split(inner);
+
+    let input = Paragraph::new(vec![
+        Line::from(vec![
+            Span::styled("> ", Style::default().fg(Color::Cyan)),
+            Span::raw(&finder.query),
+            Span::styled("_", Style::default().fg(Color::DarkGray)),
+        ]),
+        Line::from(""),
+    ]);
+    frame.render_widget(input, chunks[0]);
+
+    if finder.matches.is_empty() {
+        let hint = if finder.query.trim().is_empty() {
+            "Type a request or folder name"
+        } else {
+            "No matching requests or folders"
+        };
+        frame.render_widget(Paragraph::new(Span::styled(hint, Style::default().fg(Color::DarkGray))), chunks[1]);
+        return;
+    }
+
+    // Keep the selection in view when the list is taller than the popup.
+    let visible = chunks[1].height as usize;
+    let offset = finder.selected.saturating_sub(visible.saturating_sub(1));
+    let width = chunks[1].width as usize;
+
+    let items: Vec<ListItem> = finder
+        .matches
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(visible)
+        .map(|(i, m)| {
+            let label = match &m.method {
+                Some(method) => format!("{:<6} ", method.to_uppercase()),
+                None => String::from("[dir]  "),
+            };
+            let trail: String = m.folders.iter().map(|f| format!("{} / ", f)).collect();
+            let used = label.chars().count() + trail.chars().count() + m.name.chars().count();
+            let padding = width.saturating_sub(used + m.collection_name.chars().count()).max(1);
+            let style = if i == finder.selected {
+                Style::default().bg(Color::DarkGray).fg(Color::White).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(label, style.fg(Color::Green)),
+                Span::styled(trail, style.fg(Color::Gray)),
+                Span::styled(m.name.as_str(), style),
+                Span::styled(" ".repeat(padding), style),
+                Span::styled(m.collection_name.as_str(), style.fg(Color::Yellow)),
+            ]))
+        })
+        .collect();
+
+    frame.render_widget(List::new(items), chunks[1]);
+}
+
+fn render_command_palette(frame: &mut Frame, app: &App) {
+    let palette = match &app.palette {
+        Some(p) => p,
+        None => return,
+    };
+
+    let area = frame.area();
+    let popup_width = 60u16.min(area.width.saturating_sub(4));
+    let popup_height = ((palette.matches.len().max(1) + 4) as u16).min(area.height.saturating_sub(4));
+    let x = (area.width.saturating_sub(popup_width)) / 2;
+    let y = (area.height.saturating_sub(popup_height)) / 3;
+    let popup_area = Rect::new(x, y, popup_width, popup_height).intersection(area);
+
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Commands ")
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(theme::popup_background()));
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(2), Constraint::Min(0)])
+        .split(inner);
+
+    let input = Paragraph::new(vec![
+        Line::from(vec![
+            Span::styled("> ", Style::default().fg(Color::Cyan)),
+            Span::raw(&palette.query),
+            Span::styled("_", Style::default().fg(Color::DarkGray)),
+        ]),
+        Line::from(""),
+    ]);
+    frame.render_widget(input, chunks[0]);
+
+    if palette.matches.is_empty() {
+        let empty = Paragraph::new(Span::styled("No matching commands", Style::default().fg(Color::DarkGray)));
+        frame.render_widget(empty, chunks[1]);
+        return;
+    }
+
+    // Keep the selection in view when the list is taller than the popup.
+    let visible = chunks[1].height as usize;
+    let offset = palette.selected.saturating_sub(visible.saturating_sub(1));
+    let width = chunks[1].width as usize;
+
+    let items: Vec<ListItem> = palette
+        .matches
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(visible)
+        .map(|(i, &index)| {
+            let command = &crate::palette::COMMANDS[index];
+            let padding = width.saturating_sub(command.name.len() + command.keys.len());
+            let style = if i == palette.selected {
+                Style::default().bg(Color::DarkGray).fg(Color::White).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(command.name, style),
+                Span::styled(" ".repeat(padding), style),
+                Span::styled(command.keys, style.fg(Color::Yellow)),
+            ]))
+        })
+        .collect();
+
+    frame.render_widget(List::new(items), chunks[1]);
+}
+
+fn render_recent_requests_popup(frame: &mut Frame, app: &App) {
+    let recent = &app.config.recent_requests;
+    let area = frame.area();
+    let popup_width = 70u16.min(area.width.saturating_sub(4));
+    let popup_height = ((recent.len() + 2) as u16).min(area.height.saturating_sub(4));
+    let x = (area.width.saturating_sub(popup_width)) / 2;
+    let y = (area.height.saturating_sub(popup_height)) / 3;
+    let popup_area = Rect::new(x, y, popup_width, popup_height).intersection(area);
+
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Recently executed ")
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(theme::popup_background()));
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    // Keep the selection in view when the list is taller than the popup.
+    let visible = inner.height as usize;
+    let offset = app.recent_index.saturating_sub(visible.saturating_sub(1));
+    let width = inner.width as usize;
+
+    let items: Vec<ListItem> = recent
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(visible)
+        .map(|(i, entry)| {
+            let collection_name = app
+                .collections
+                .iter()
+                .find(|c| c.uid == entry.collection_uid)
+                .map(|c| c.name.as_str())
+                .unwrap_or("Unknown collection");
+            let label = format!("{} ({})", entry.name, collection_name);
+            let padding = width.saturating_sub(label.chars().count() + entry.executed_at.len());
+            let style = if i == app.recent_index {
+                Style::default().bg(Color::DarkGray).fg(Color::White).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(label, style),
+                Span::styled(" ".repeat(padding), style),
+                Span::styled(entry.executed_at.as_str(), style.fg(Color::Gray)),
+            ]))
+        })
+        .collect();
+
+    frame.render_widget(List::new(items), inner);
+}
+
 fn render_workspace_popup(frame: &mut Frame, app: &App) {
     let area = frame.area();
 
     // Calculate popup size based on content
-    let max_name_len = app.workspaces.iter()
-        .map(|w| w.name.len())
+    let labels: Vec<(String, Option<String>)> = app
+        .workspaces
+        .iter()
+        .map(|w| (w.name.clone(), workspace_counts_label(app, &w.id)))
+        .collect();
+    let max_name_len = labels.iter()
+        .map(|(name, counts)| name.len() + counts.as_ref().map_or(0, |c| c.len() + 2))
         .max()
         .unwrap_or(10)
         .max("All Workspaces".len());
@@ -909,31 +2107,47 @@ fn render_workspace_popup(frame: &mut Frame, app: &App) {
     items.push(ListItem::new(Line::from(Span::styled("All Workspaces", all_ws_style))));
 
     // Workspace options
-    for (i, ws) in app.workspaces.iter().enumerate() {
-        let style = if app.workspace_popup_index == i + 1 {
+    for (i, (name, counts)) in labels.iter().enumerate() {
+        let selected = app.workspace_popup_index == i + 1;
+        let style = if selected {
             Style::default().bg(Color::DarkGray).fg(Color::White).add_modifier(Modifier::BOLD)
         } else {
             Style::default()
         };
-        items.push(ListItem::new(Line::from(Span::styled(&ws.name, style))));
+        let mut spans = vec![Span::styled(name.clone(), style)];
+        if let Some(counts) = counts {
+            let counts_style = if selected { style } else { Style::default().fg(Color::DarkGray) };
+            spans.push(Span::styled(format!("  {}", counts), counts_style));
+        }
+        items.push(ListItem::new(Line::from(spans)));
     }
 
     let block = Block::default()
         .borders(Borders::ALL)
         .title(" Workspace ")
         .border_style(Style::default().fg(Color::Magenta))
-        .style(Style::default().bg(Color::Black));
+        .style(Style::default().bg(theme::popup_background()));
 
     let list = List::new(items).block(block);
     frame.render_widget(list, popup_area);
 }
 
+/// "3 col · 2 env" once a workspace's counts have loaded, "…" while they're
+/// still being fetched, and nothing if fetching failed.
+fn workspace_counts_label(app: &App, workspace_id: &str) -> Option<String> {
+    match app.workspace_counts.get(workspace_id) {
+        Some(counts) => Some(format!("{} col · {} env", counts.collections, counts.environments)),
+        None if app.pending_workspace_counts.is_some() => Some(String::from("…")),
+        None => None,
+    }
+}
+
 fn render_workspace_loading_popup(frame: &mut Frame, app: &App) {
     let area = frame.area();
 
     let ws_name = app.workspace_loading.as_deref().unwrap_or("workspace");
     let text = format!("Loading workspace: {}", ws_name);
-    let popup_width = (text.len() + 6).max(30) as u16;
+    let popup_width = (text.len() + 8).max(30) as u16;
     let popup_height = 5u16;
 
     let x = (area.width.saturating_sub(popup_width)) / 2;
@@ -946,7 +2160,7 @@ fn render_workspace_loading_popup(frame: &mut Frame, app: &App) {
     let content = vec![
         Line::from(""),
         Line::from(Span::styled(
-            format!("  {}  ", text),
+            format!("  {} {}  ", app.spinner(), text),
             Style::default().add_modifier(Modifier::BOLD),
         )),
         Line::from(""),
@@ -956,7 +2170,7 @@ fn render_workspace_loading_popup(frame: &mut Frame, app: &App) {
         .borders(Borders::ALL)
         .title(" Loading ")
         .border_style(Style::default().fg(Color::Magenta))
-        .style(Style::default().bg(Color::Black));
+        .style(Style::default().bg(theme::popup_background()));
 
     let paragraph = Paragraph::new(content).block(block);
 
@@ -968,7 +2182,7 @@ fn render_collection_loading_popup(frame: &mut Frame, app: &App) {
 
     let col_name = app.collection_loading.as_deref().unwrap_or("collection");
     let text = format!("Loading: {}", col_name);
-    let popup_width = (text.len() + 6).max(30) as u16;
+    let popup_width = (text.len() + 8).max(30) as u16;
     let popup_height = 5u16;
 
     let x = (area.width.saturating_sub(popup_width)) / 2;
@@ -981,7 +2195,7 @@ fn render_collection_loading_popup(frame: &mut Frame, app: &App) {
     let content = vec![
         Line::from(""),
         Line::from(Span::styled(
-            format!("  {}  ", text),
+            format!("  {} {}  ", app.spinner(), text),
             Style::default().add_modifier(Modifier::BOLD),
         )),
         Line::from(""),
@@ -992,18 +2206,23 @@ fn render_collection_loading_popup(frame: &mut Frame, app: &App) {
         .title(" Loading ")
         .title_bottom(Line::from(" Esc: Cancel ").right_aligned())
         .border_style(Style::default().fg(Color::Green))
-        .style(Style::default().bg(Color::Black));
+        .style(Style::default().bg(theme::popup_background()));
 
     let paragraph = Paragraph::new(content).block(block);
 
     frame.render_widget(paragraph, popup_area);
 }
 
-fn render_request_executing_popup(frame: &mut Frame) {
+fn render_request_executing_popup(frame: &mut Frame, app: &App) {
     let area = frame.area();
 
-    let text = "Performing request...";
-    let popup_width = (text.len() + 6).max(30) as u16;
+    // A hanging endpoint shows as a climbing counter, next to the way out
+    let text = match app.execution_started.map(|started| started.elapsed().as_secs()) {
+        _ if app.token_fetching => String::from("Fetching OAuth token..."),
+        Some(secs) if secs > 0 => format!("Performing request... {}s", secs),
+        _ => String::from("Performing request..."),
+    };
+    let popup_width = (text.len() + 8).max(30) as u16;
     let popup_height = 5u16;
 
     let x = (area.width.saturating_sub(popup_width)) / 2;
@@ -1016,7 +2235,7 @@ fn render_request_executing_popup(frame: &mut Frame) {
     let content = vec![
         Line::from(""),
         Line::from(Span::styled(
-            format!("  {}  ", text),
+            format!("  {} {}  ", app.spinner(), text),
             Style::default().add_modifier(Modifier::BOLD),
         )),
         Line::from(""),
@@ -1027,7 +2246,7 @@ fn render_request_executing_popup(frame: &mut Frame) {
         .title(" Request ")
         .title_bottom(Line::from(" Esc: Cancel ").right_aligned())
         .border_style(Style::default().fg(Color::Yellow))
-        .style(Style::default().bg(Color::Black));
+        .style(Style::default().bg(theme::popup_background()));
 
     let paragraph = Paragraph::new(content).block(block);
 
@@ -1079,7 +2298,7 @@ fn render_params_dialog(frame: &mut Frame, app: &App) {
             Style::default().fg(Color::Cyan)
         };
         let value_style = if is_selected {
-            Style::default().fg(Color::White).add_modifier(Modifier::BOLD)
+            Style::default().fg(theme::text()).add_modifier(Modifier::BOLD)
         } else {
             Style::default()
         };
@@ -1108,37 +2327,121 @@ fn render_params_dialog(frame: &mut Frame, app: &App) {
         .borders(Borders::ALL)
         .title(" Request Parameters ")
         .border_style(Style::default().fg(Color::Green))
-        .style(Style::default().bg(Color::Black));
+        .style(Style::default().bg(theme::popup_background()));
 
     let paragraph = Paragraph::new(lines).block(block);
     frame.render_widget(paragraph, popup_area);
 }
 
-fn render_variables_popup(frame: &mut Frame, app: &App) {
-    let area = frame.area();
-
-    let variables = app.get_variables_for_display();
-
-    // Calculate popup size
-    let max_key_len = variables.iter()
-        .map(|(_, k, _, _)| k.len())
-        .max()
-        .unwrap_or(10);
-    let max_val_len = variables.iter()
-        .map(|(_, _, v, _)| v.len())
-        .max()
-        .unwrap_or(10)
-        .min(40); // Cap value display length
-
-    let popup_width = (max_key_len + max_val_len + 10).max(50).min(80) as u16;
-    let extra_lines = if app.variables_search_active || !app.variables_search_query.is_empty() { 1 } else { 0 };
-    let popup_height = (variables.len() + 5 + extra_lines).max(8).min(20) as u16;
+fn render_rows_editor(frame: &mut Frame, app: &App) {
+    let Some(editor) = &app.rows_editor else {
+        return;
+    };
 
+    let area = frame.area();
+    let popup_width = 76u16.min(area.width.saturating_sub(4));
+    let popup_height = ((editor.rows.len().max(1) + 6).min(24) as u16).min(area.height);
     let x = (area.width.saturating_sub(popup_width)) / 2;
     let y = (area.height.saturating_sub(popup_height)) / 2;
+    let popup_area = Rect::new(x, y, popup_width.min(area.width), popup_height.min(area.height));
 
-    let popup_area = Rect::new(
-        x,
+    frame.render_widget(Clear, popup_area);
+
+    // The field being typed into, with a cursor marker
+    let with_cursor = |text: &str| {
+        let cursor = editor.cursor_position.min(text.len());
+        if cursor >= text.len() {
+            format!("{}_", text)
+        } else {
+            let (before, after) = text.split_at(cursor);
+            format!("{}|{}", before, after)
+        }
+    };
+
+    let mut lines: Vec<Line> = vec![Line::from("")];
+    let (title, empty, separator) = match editor.target {
+        RowsTarget::QueryParams => (" Query Parameters ", "   No query params - press a to add one", " = "),
+        RowsTarget::Headers => (" Headers ", "   No headers - press a to add one", ": "),
+    };
+    if editor.rows.is_empty() {
+        lines.push(Line::from(Span::styled(empty, Style::default().fg(Color::DarkGray))));
+    }
+    for (i, row) in editor.rows.iter().enumerate() {
+        let is_selected = i == editor.selected;
+        let editing = editor.editing.filter(|_| is_selected);
+        let key = match editing {
+            Some(RowField::Key) => with_cursor(&row.key),
+            _ => row.key.clone(),
+        };
+        let value = match editing {
+            Some(RowField::Value) => with_cursor(row.value.as_deref().unwrap_or_default()),
+            _ => row.value.clone().unwrap_or_default(),
+        };
+        let base = if row.disabled {
+            Style::default().fg(Color::DarkGray)
+        } else {
+            Style::default()
+        };
+        let key_style = if is_selected {
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+        } else if row.disabled {
+            base
+        } else {
+            Style::default().fg(Color::Cyan)
+        };
+        let value_style = if is_selected { base.add_modifier(Modifier::BOLD) } else { base };
+        lines.push(Line::from(vec![
+            Span::styled(if is_selected { " > " } else { "   " }, key_style),
+            Span::styled(if row.disabled { "[ ] " } else { "[x] " }, base),
+            Span::styled(key, key_style),
+            Span::styled(separator, base),
+            Span::styled(value, value_style),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    let hint = if editor.editing.is_some() {
+        " Type to edit | Tab: Key/value | Enter/Esc: Done"
+    } else {
+        " n: Key | e: Value | a: Add | d: Delete | Space: On/off | Enter: Apply"
+    };
+    lines.push(Line::from(Span::styled(hint, Style::default().fg(Color::DarkGray))));
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .border_style(Style::default().fg(Color::Green))
+        .style(Style::default().bg(theme::popup_background()));
+
+    frame.render_widget(Paragraph::new(lines).block(block), popup_area);
+}
+
+fn render_variables_popup(frame: &mut Frame, app: &App) {
+    let area = frame.area();
+
+    let variables = app.get_variables_for_display();
+
+    // Calculate popup size
+    let max_key_len = variables.iter()
+        .map(|(_, k, _, _)| k.len())
+        .max()
+        .unwrap_or(10);
+    let max_val_len = variables.iter()
+        .map(|(_, _, v, _)| v.len())
+        .max()
+        .unwrap_or(10)
+        .min(40); // Cap value display length
+
+    let popup_width = (max_key_len + max_val_len + 10).max(50).min(80) as u16;
+    let extra_lines = if app.variables_search_active || !app.variables_search_query.is_empty() { 1 } else { 0 };
+    let extra_lines = extra_lines + if app.variables_export_confirm { 2 } else { 0 };
+    let popup_height = (variables.len() + 5 + extra_lines).max(8).min(20) as u16;
+
+    let x = (area.width.saturating_sub(popup_width)) / 2;
+    let y = (area.height.saturating_sub(popup_height)) / 2;
+
+    let popup_area = Rect::new(
+        x,
         y,
         popup_width.min(area.width),
         popup_height.min(area.height),
@@ -1169,8 +2472,12 @@ fn render_variables_popup(frame: &mut Frame, app: &App) {
                 Style::default().fg(Color::DarkGray),
             )));
         } else {
+            let empty = match app.variables_scope {
+                VariablesScope::Environment => "  No variables in environment",
+                VariablesScope::Collection => "  No variables in collection",
+            };
             lines.push(Line::from(Span::styled(
-                "  No variables in environment",
+                empty,
                 Style::default().fg(Color::DarkGray),
             )));
         }
@@ -1195,11 +2502,7 @@ fn render_variables_popup(frame: &mut Frame, app: &App) {
                 }
             } else {
                 // Truncate long values
-                if value.len() > 40 {
-                    format!("{}...", &value[..37])
-                } else {
-                    value.clone()
-                }
+                ellipsize(value, 40)
             };
 
             let base_style = if !enabled {
@@ -1222,14 +2525,29 @@ fn render_variables_popup(frame: &mut Frame, app: &App) {
 
     // Add help text at the bottom
     lines.push(Line::from(""));
-    let help_text = if app.editing_variable.is_some() {
+    if app.variables_export_confirm {
+        lines.push(Line::from(Span::styled(
+            "Export writes every enabled value unmasked, secrets included.",
+            Style::default().fg(Color::Yellow),
+        )));
+        // The prompt is the confirmation, so it says when `f` replaces a file
+        let path = app.variables_export_path();
+        let write = if path.exists() { "Overwrite" } else { "Write" };
+        lines.push(Line::from(Span::styled(
+            format!("f: {} {} | c: Copy export lines | Esc: Cancel", write, path.display()),
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+        )));
+    }
+    let help_text = if app.variables_export_confirm {
+        ""
+    } else if app.editing_variable.is_some() {
         "Enter: Confirm | Esc: Cancel"
     } else if app.variables_search_active {
         "Enter: Confirm | Esc: Cancel | Type to search"
     } else if app.variables_modified {
-        "Enter: Edit | /: Search | s: Save* | Esc: Close"
+        "Enter: Edit | /: Search | s: Save* | Tab: Scope | x: Export | Esc: Close"
     } else {
-        "Enter: Edit | /: Search | s: Save | Esc: Close"
+        "Enter: Edit | /: Search | s: Save | Tab: Scope | x: Export | Esc: Close"
     };
     lines.push(Line::from(Span::styled(
         help_text,
@@ -1237,24 +2555,22 @@ fn render_variables_popup(frame: &mut Frame, app: &App) {
     )));
 
     let title = if app.variables_modified {
-        format!(" Variables ({}) * ", app.get_current_environment_name())
+        format!(" Variables ({}) * ", app.variables_scope_label())
     } else {
-        format!(" Variables ({}) ", app.get_current_environment_name())
+        format!(" Variables ({}) ", app.variables_scope_label())
     };
 
     let block = Block::default()
         .borders(Borders::ALL)
         .title(title)
         .border_style(Style::default().fg(Color::Cyan))
-        .style(Style::default().bg(Color::Black));
+        .style(Style::default().bg(theme::popup_background()));
 
     let paragraph = Paragraph::new(lines).block(block);
     frame.render_widget(paragraph, popup_area);
 }
 
 fn render_json_search_overlay(frame: &mut Frame, app: &App) {
-    let area = frame.area();
-
     // Get the search query from the JSON viewer state
     let search_query = app.json_viewer_state
         .as_ref()
@@ -1266,6 +2582,34 @@ fn render_json_search_overlay(frame: &mut Frame, app: &App) {
         .map(|v| v.search_status())
         .unwrap_or_default();
 
+    let display_text = if search_query.is_empty() {
+        "/_".to_string()
+    } else {
+        format!("{}_", search_status)
+    };
+    render_search_overlay(frame, " Search JSON ", display_text);
+}
+
+fn render_response_search_overlay(frame: &mut Frame, app: &App) {
+    let display_text = if app.response_search_query.is_empty() {
+        "/_".to_string()
+    } else if app.response_search_matches.is_empty() {
+        format!("/{} (no matches)_", app.response_search_query)
+    } else {
+        format!(
+            "/{} ({}/{})_",
+            app.response_search_query,
+            app.response_match_index + 1,
+            app.response_search_matches.len()
+        )
+    };
+    render_search_overlay(frame, " Search Response ", display_text);
+}
+
+/// A one-line search input box just above the status bar.
+fn render_search_overlay(frame: &mut Frame, title: &str, display_text: String) {
+    let area = frame.area();
+
     let popup_width = 50u16.min(area.width.saturating_sub(4));
     let popup_height = 3u16;
 
@@ -1273,16 +2617,10 @@ fn render_json_search_overlay(frame: &mut Frame, app: &App) {
     let x = (area.width.saturating_sub(popup_width)) / 2;
     let y = area.height.saturating_sub(popup_height + 4); // Above status bar
 
-    let popup_area = Rect::new(x, y, popup_width, popup_height);
+    let popup_area = Rect::new(x, y, popup_width, popup_height).intersection(area);
 
     frame.render_widget(Clear, popup_area);
 
-    let display_text = if search_query.is_empty() {
-        "/_".to_string()
-    } else {
-        format!("{}_", search_status)
-    };
-
     let content = vec![
         Line::from(Span::styled(
             display_text,
@@ -1292,9 +2630,9 @@ fn render_json_search_overlay(frame: &mut Frame, app: &App) {
 
     let block = Block::default()
         .borders(Borders::ALL)
-        .title(" Search JSON ")
+        .title(title)
         .border_style(Style::default().fg(Color::Yellow))
-        .style(Style::default().bg(Color::Black));
+        .style(Style::default().bg(theme::popup_background()));
 
     let paragraph = Paragraph::new(content).block(block);
     frame.render_widget(paragraph, popup_area);
@@ -1309,11 +2647,7 @@ fn render_execute_confirm_popup(frame: &mut Frame, app: &App) {
     let area = frame.area();
 
     // Calculate popup size based on content
-    let url_display = if pending.url.len() > 50 {
-        format!("{}...", &pending.url[..47])
-    } else {
-        pending.url.clone()
-    };
+    let url_display = ellipsize(&pending.url, 50);
 
     let popup_width = 60u16.min(area.width.saturating_sub(4));
     let popup_height = 9u16;
@@ -1330,7 +2664,7 @@ fn render_execute_confirm_popup(frame: &mut Frame, app: &App) {
         "DELETE" => Color::Red,
         "POST" => Color::Green,
         "PUT" | "PATCH" => Color::Yellow,
-        _ => Color::White,
+        _ => theme::text(),
     };
 
     let content = vec![
@@ -1351,7 +2685,7 @@ fn render_execute_confirm_popup(frame: &mut Frame, app: &App) {
         ]),
         Line::from(""),
         Line::from(Span::styled(
-            "  [y/Enter] Yes   [n/Esc] No",
+            "  [y/Enter] Yes   [a] Always (this session)   [n/Esc] No",
             Style::default().fg(Color::Cyan),
         )),
     ];
@@ -1365,13 +2699,65 @@ fn render_execute_confirm_popup(frame: &mut Frame, app: &App) {
         .borders(Borders::ALL)
         .title(" Confirm Request ")
         .border_style(Style::default().fg(title_color))
-        .style(Style::default().bg(Color::Black));
+        .style(Style::default().bg(theme::popup_background()));
 
     let paragraph = Paragraph::new(content).block(block);
 
     frame.render_widget(paragraph, popup_area);
 }
 
+fn render_clear_edits_confirm_popup(frame: &mut Frame, app: &App) {
+    let Some(pending) = &app.pending_clear_edits else {
+        return;
+    };
+
+    let area = frame.area();
+    let name_display = if pending.collection_name.chars().count() > 44 {
+        format!("{}...", pending.collection_name.chars().take(41).collect::<String>())
+    } else {
+        pending.collection_name.clone()
+    };
+
+    let popup_width = 60u16.min(area.width.saturating_sub(4));
+    let popup_height = 9u16;
+    let x = (area.width.saturating_sub(popup_width)) / 2;
+    let y = (area.height.saturating_sub(popup_height)) / 2;
+    let popup_area = Rect::new(x, y, popup_width.min(area.width), popup_height.min(area.height));
+
+    frame.render_widget(Clear, popup_area);
+
+    let content = vec![
+        Line::from(""),
+        Line::from(format!(
+            "  Discard all {} local edit{}?",
+            pending.count,
+            if pending.count == 1 { "" } else { "s" }
+        )),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("  Collection: ", Style::default().fg(Color::DarkGray)),
+            Span::raw(name_display),
+        ]),
+        Line::from(Span::styled(
+            "  Every request reverts to the version from Postman.",
+            Style::default().fg(Color::DarkGray),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            "  [y/Enter] Yes   [n/Esc] No",
+            Style::default().fg(Color::Cyan),
+        )),
+    ];
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Discard All Local Edits ")
+        .border_style(Style::default().fg(Color::Red))
+        .style(Style::default().bg(theme::popup_background()));
+
+    frame.render_widget(Paragraph::new(content).block(block), popup_area);
+}
+
 fn render_delete_edit_confirm_popup(frame: &mut Frame, app: &App) {
     let pending = match &app.pending_delete_edit {
         Some(p) => p,
@@ -1380,11 +2766,7 @@ fn render_delete_edit_confirm_popup(frame: &mut Frame, app: &App) {
 
     let area = frame.area();
 
-    let name_display = if pending.name.len() > 50 {
-        format!("{}...", &pending.name[..47])
-    } else {
-        pending.name.clone()
-    };
+    let name_display = ellipsize(&pending.name, 50);
 
     let popup_width = 60u16.min(area.width.saturating_sub(4));
     let popup_height = 9u16;
@@ -1419,9 +2801,539 @@ fn render_delete_edit_confirm_popup(frame: &mut Frame, app: &App) {
         .borders(Borders::ALL)
         .title(" Discard Local Edit ")
         .border_style(Style::default().fg(Color::Red))
-        .style(Style::default().bg(Color::Black));
+        .style(Style::default().bg(theme::popup_background()));
 
     let paragraph = Paragraph::new(content).block(block);
 
     frame.render_widget(paragraph, popup_area);
 }
+
+fn render_invalid_body_popup(frame: &mut Frame, app: &App) {
+    let pending = match &app.pending_invalid_body {
+        Some(p) => p,
+        None => return,
+    };
+
+    let area = frame.area();
+
+    let popup_width = 70u16.min(area.width.saturating_sub(4));
+    let popup_height = 10u16;
+
+    let x = (area.width.saturating_sub(popup_width)) / 2;
+    let y = (area.height.saturating_sub(popup_height)) / 2;
+
+    let popup_area = Rect::new(x, y, popup_width.min(area.width), popup_height.min(area.height));
+
+    frame.render_widget(Clear, popup_area);
+
+    let content = vec![
+        Line::from(""),
+        Line::from(Span::styled(format!("  {}", pending.error), Style::default().fg(Color::Yellow))),
+        Line::from(""),
+        Line::from(Span::styled(
+            "  The server will likely reject it when the request is sent.",
+            Style::default().fg(Color::DarkGray),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            "  [y/Enter] Store anyway   [n/Esc] Back to editor",
+            Style::default().fg(Color::Cyan),
+        )),
+    ];
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Invalid JSON Body ")
+        .border_style(Style::default().fg(Color::Yellow))
+        .style(Style::default().bg(theme::popup_background()));
+
+    let paragraph = Paragraph::new(content).block(block).wrap(Wrap { trim: false });
+
+    frame.render_widget(paragraph, popup_area);
+}
+
+fn render_run_confirm_popup(frame: &mut Frame, app: &App) {
+    let pending = match &app.pending_run_confirm {
+        Some(p) => p,
+        None => return,
+    };
+
+    let area = frame.area();
+
+    let title_display = ellipsize(&pending.title, 40);
+    let destructive = pending
+        .steps
+        .iter()
+        .filter(|step| App::is_destructive_method(&step.request.method))
+        .count();
+
+    let popup_width = 60u16.min(area.width.saturating_sub(4));
+    let popup_height = 9u16;
+
+    let x = (area.width.saturating_sub(popup_width)) / 2;
+    let y = (area.height.saturating_sub(popup_height)) / 2;
+
+    let popup_area = Rect::new(x, y, popup_width.min(area.width), popup_height.min(area.height));
+
+    frame.render_widget(Clear, popup_area);
+
+    let mode = if app.config.settings.stop_on_failure {
+        "stops at first failure"
+    } else {
+        "runs all, even after failures"
+    };
+
+    let content = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::raw("  Run "),
+            Span::styled(
+                format!("{} requests", pending.steps.len()),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(format!(" in '{}'?", title_display)),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("  {} modify data (POST/PUT/PATCH/DELETE)", destructive),
+            Style::default().fg(if destructive > 0 { Color::Yellow } else { Color::DarkGray }),
+        )),
+        Line::from(Span::styled(
+            format!("  Mode: {}", mode),
+            Style::default().fg(Color::DarkGray),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            "  [y/Enter] Yes   [n/Esc] No",
+            Style::default().fg(Color::Cyan),
+        )),
+    ];
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Run ")
+        .border_style(Style::default().fg(Color::Yellow))
+        .style(Style::default().bg(theme::popup_background()));
+
+    let paragraph = Paragraph::new(content).block(block);
+
+    frame.render_widget(paragraph, popup_area);
+}
+
+fn render_run_progress_popup(frame: &mut Frame, app: &App) {
+    let area = frame.area();
+
+    let run_name = app.run_in_progress.as_deref().unwrap_or("folder");
+    let text = format!("Running: {}", run_name);
+    let popup_width = (text.len() + 8).max(30) as u16;
+    let popup_height = 5u16;
+
+    let x = (area.width.saturating_sub(popup_width)) / 2;
+    let y = (area.height.saturating_sub(popup_height)) / 2;
+
+    let popup_area = Rect::new(x, y, popup_width.min(area.width), popup_height.min(area.height));
+
+    frame.render_widget(Clear, popup_area);
+
+    let content = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("  {} {}  ", app.spinner(), text),
+            Style::default().add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+    ];
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Run ")
+        .title_bottom(Line::from(" Esc: Cancel ").right_aligned())
+        .border_style(Style::default().fg(Color::Yellow))
+        .style(Style::default().bg(theme::popup_background()));
+
+    let paragraph = Paragraph::new(content).block(block);
+
+    frame.render_widget(paragraph, popup_area);
+}
+
+fn render_run_results_popup(frame: &mut Frame, app: &mut App) {
+    let report = match &app.run_report {
+        Some(r) => r,
+        None => return,
+    };
+
+    let area = frame.area();
+
+    let popup_width = 80u16.min(area.width.saturating_sub(4));
+    let popup_height = ((report.results.len() + 5) as u16).clamp(8, area.height.saturating_sub(4).max(8));
+
+    let x = (area.width.saturating_sub(popup_width)) / 2;
+    let y = (area.height.saturating_sub(popup_height)) / 2;
+
+    let popup_area = Rect::new(x, y, popup_width.min(area.width), popup_height.min(area.height));
+
+    frame.render_widget(Clear, popup_area);
+
+    // Name each request's collection when the run spans several
+    let several_collections = report.results.iter().any(|r| r.collection_uid != report.results[0].collection_uid);
+    let mut items: Vec<ListItem> = report
+        .results
+        .iter()
+        .enumerate()
+        .map(|(i, result)| {
+            let (mark, outcome, color) = match (&result.error, result.status) {
+                (Some(err), _) => ("x", err.lines().next().unwrap_or("error").to_string(), Color::Red),
+                (None, Some(status)) if result.passed() => ("+", status.to_string(), Color::Green),
+                (None, Some(status)) => ("x", status.to_string(), Color::Red),
+                (None, None) => ("?", String::from("-"), Color::DarkGray),
+            };
+            let mut spans = vec![
+                Span::styled(format!(" {} ", mark), Style::default().fg(color).add_modifier(Modifier::BOLD)),
+                Span::styled(format!("{:<6} ", result.method), Style::default().fg(Color::Cyan)),
+                Span::raw(format!("{} ", result.name)),
+                Span::styled(outcome, Style::default().fg(color)),
+                Span::styled(format!(" ({} ms)", result.duration_ms), Style::default().fg(Color::DarkGray)),
+            ];
+            if !result.assertions.is_empty() {
+                let held = result.assertions.iter().filter(|a| a.passed).count();
+                let color = if held == result.assertions.len() { Color::Green } else { Color::Red };
+                spans.push(Span::styled(format!(" {}/{} assertions", held, result.assertions.len()), Style::default().fg(color)));
+            }
+            if several_collections {
+                let collection = app.collections.iter().find(|c| c.uid == result.collection_uid).map(|c| c.name.as_str());
+                spans.push(Span::styled(format!("  {}", collection.unwrap_or("?")), Style::default().fg(Color::DarkGray)));
+            }
+            let row = Line::from(spans);
+            let style = if i == app.run_results_index {
+                Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            ListItem::new(row).style(style)
+        })
+        .collect();
+
+    if report.skipped > 0 {
+        items.push(ListItem::new(Line::from(Span::styled(
+            format!("   ... {} skipped after failure", report.skipped),
+            Style::default().fg(Color::DarkGray),
+        ))));
+    }
+
+    let passed = report.results.iter().filter(|r| r.passed()).count();
+    let border_color = if passed == report.results.len() && report.skipped == 0 {
+        Color::Green
+    } else {
+        Color::Red
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" Run: {} - {}/{} passed ", report.title, passed, report.results.len()))
+        .title_bottom(Line::from(" Enter: Open | Esc: Close ").right_aligned())
+        .border_style(Style::default().fg(border_color))
+        .style(Style::default().bg(theme::popup_background()));
+
+    let mut state = ratatui::widgets::ListState::default();
+    state.select(Some(app.run_results_index));
+    let list = List::new(items).block(block);
+    frame.render_stateful_widget(list, popup_area, &mut state);
+}
+
+fn render_collection_conflict_popup(frame: &mut Frame, app: &App) {
+    let conflict = match &app.collection_conflict {
+        Some(c) => c,
+        None => return,
+    };
+
+    let action = match &conflict.change {
+        crate::app::BulkChange::NewRequest { name, .. } => format!("Creating '{}'", name),
+        crate::app::BulkChange::Move { name, .. } => format!("Moving '{}'", name),
+    };
+    let footer = vec![
+        Line::from(format!("{} would overwrite those changes.", action)),
+        Line::from(vec![
+            Span::styled("r", Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
+            Span::raw(": reload and reapply  "),
+            Span::styled("Esc", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
+            Span::raw(": reload only"),
+        ]),
+    ];
+    render_conflict_popup(frame, " Collection Changed ", "collection", &conflict.summary, footer);
+}
+
+fn render_environment_conflict_popup(frame: &mut Frame, app: &App) {
+    let Some(conflict) = &app.environment_conflict else {
+        return;
+    };
+
+    let footer = vec![
+        Line::from("Saving your variables would overwrite those changes."),
+        Line::from(Span::styled("Merge keeps them and applies your edits on top.", Style::default().fg(Color::DarkGray))),
+        Line::from(vec![
+            Span::styled("m", Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
+            Span::raw(": merge  "),
+            Span::styled("o", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
+            Span::raw(": overwrite  "),
+            Span::styled("Esc", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+            Span::raw(": cancel"),
+        ]),
+    ];
+    render_conflict_popup(frame, " Environment Changed ", "environment", &conflict.summary, footer);
+}
+
+/// A save refused because the `subject` (collection or environment)
+/// changed in Postman: what changed there, then `footer` with the choices.
+fn render_conflict_popup(frame: &mut Frame, title: &str, subject: &str, summary: &[String], footer: Vec<Line<'static>>) {
+    const MAX_SHOWN: usize = 10;
+    let area = frame.area();
+    let shown = summary.len().min(MAX_SHOWN);
+    let popup_width = 64u16.min(area.width.saturating_sub(4));
+    let popup_height = ((shown + footer.len() + 6) as u16).min(area.height.saturating_sub(2));
+    let x = (area.width.saturating_sub(popup_width)) / 2;
+    let y = (area.height.saturating_sub(popup_height)) / 3;
+    let popup_area = Rect::new(x, y, popup_width, popup_height).intersection(area);
+
+    frame.render_widget(Clear, popup_area);
+
+    let mut content = vec![
+        Line::from(Span::styled(
+            format!("The {} changed in Postman since it was loaded:", subject),
+            Style::default().fg(Color::Yellow),
+        )),
+        Line::from(""),
+    ];
+    content.extend(summary.iter().take(MAX_SHOWN).map(|line| Line::from(format!("  {}", line))));
+    if summary.len() > MAX_SHOWN {
+        content.push(Line::from(Span::styled(
+            format!("  ... and {} more", summary.len() - MAX_SHOWN),
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+    content.push(Line::from(""));
+    content.extend(footer);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title.to_string())
+        .border_style(Style::default().fg(Color::Yellow))
+        .style(Style::default().bg(theme::popup_background()));
+
+    frame.render_widget(Paragraph::new(content).block(block).wrap(Wrap { trim: false }), popup_area);
+}
+
+fn render_baseline_diff_popup(frame: &mut Frame, app: &App) {
+    use crate::ui::json_diff::ChangeKind;
+
+    let changes = match &app.baseline_diff {
+        Some(c) => c,
+        None => return,
+    };
+
+    let area = frame.area();
+
+    let popup_width = (area.width * 4 / 5).max(40).min(area.width);
+    let popup_height = (area.height * 4 / 5).max(10).min(area.height);
+
+    let x = (area.width.saturating_sub(popup_width)) / 2;
+    let y = (area.height.saturating_sub(popup_height)) / 2;
+
+    let popup_area = Rect::new(x, y, popup_width, popup_height).intersection(area);
+
+    frame.render_widget(Clear, popup_area);
+
+    let dim = Style::default().fg(Color::DarkGray);
+    let mut content: Vec<Line> = Vec::new();
+    if let Some(baseline) = app.response_baseline() {
+        content.push(Line::from(Span::styled(
+            format!("Baseline saved {} (status {})", baseline.saved_at, baseline.status),
+            dim,
+        )));
+        content.push(Line::from(""));
+    }
+
+    if changes.is_empty() {
+        content.push(Line::from(Span::styled(
+            "No differences - the response matches the baseline",
+            Style::default().fg(Color::Green),
+        )));
+    }
+    for change in changes {
+        let show = |v: &Option<serde_json::Value>| v.as_ref().map(|v| v.to_string()).unwrap_or_default();
+        let line = match change.kind {
+            ChangeKind::Added => Line::from(vec![
+                Span::styled(format!("+ {}: ", change.path), Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
+                Span::styled(show(&change.new), Style::default().fg(Color::Green)),
+            ]),
+            ChangeKind::Removed => Line::from(vec![
+                Span::styled(format!("- {}: ", change.path), Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
+                Span::styled(show(&change.old), Style::default().fg(Color::Red)),
+            ]),
+            ChangeKind::Changed => Line::from(vec![
+                Span::styled(format!("~ {}: ", change.path), Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+                Span::styled(show(&change.old), Style::default().fg(Color::Red)),
+                Span::styled(" -> ", dim),
+                Span::styled(show(&change.new), Style::default().fg(Color::Green)),
+            ]),
+        };
+        content.push(line);
+    }
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" Compare with Baseline ({} changes) ", changes.len()))
+        .title_bottom(Line::from(" j/k: Scroll | Esc: Close ").right_aligned())
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(theme::popup_background()));
+
+    let paragraph = Paragraph::new(content)
+        .block(block)
+        .wrap(Wrap { trim: false })
+        .scroll((app.baseline_diff_scroll, 0));
+
+    frame.render_widget(paragraph, popup_area);
+}
+
+fn render_script_view_popup(frame: &mut Frame, app: &App) {
+    let area = frame.area();
+    let popup_width = (area.width * 4 / 5).max(40).min(area.width);
+    let popup_height = (area.height * 4 / 5).max(10).min(area.height);
+    let x = (area.width.saturating_sub(popup_width)) / 2;
+    let y = (area.height.saturating_sub(popup_height)) / 2;
+    let popup_area = Rect::new(x, y, popup_width, popup_height).intersection(area);
+
+    frame.render_widget(Clear, popup_area);
+
+    let label = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+    let mut content = Vec::new();
+    for (i, (name, lines)) in app.current_request_scripts().into_iter().enumerate() {
+        if i > 0 {
+            content.push(Line::from(""));
+        }
+        content.push(Line::from(Span::styled(name, label)));
+        content.extend(lines.into_iter().map(|l| Line::from(format!("  {}", l))));
+    }
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Scripts (read-only, not run) ")
+        .title_bottom(Line::from(" j/k: Scroll | Esc: Close ").right_aligned())
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(theme::popup_background()));
+
+    let paragraph = Paragraph::new(content)
+        .block(block)
+        .wrap(Wrap { trim: false })
+        .scroll((app.script_view_scroll, 0));
+
+    frame.render_widget(paragraph, popup_area);
+}
+
+fn render_dry_run_popup(frame: &mut Frame, app: &App) {
+    let request = match &app.dry_run {
+        Some(r) => r,
+        None => return,
+    };
+
+    let area = frame.area();
+
+    let popup_width = (area.width * 4 / 5).max(40).min(area.width);
+    let popup_height = (area.height * 4 / 5).max(10).min(area.height);
+
+    let x = (area.width.saturating_sub(popup_width)) / 2;
+    let y = (area.height.saturating_sub(popup_height)) / 2;
+
+    let popup_area = Rect::new(x, y, popup_width, popup_height).intersection(area);
+
+    frame.render_widget(Clear, popup_area);
+
+    let label = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+    let dim = Style::default().fg(Color::DarkGray);
+
+    let mut content = vec![Line::from(vec![
+        Span::styled(format!("{} ", request.method.to_uppercase()), label),
+        Span::raw(request.url.to_string()),
+    ])];
+
+    content.push(Line::from(""));
+    content.push(Line::from(Span::styled("Headers", label)));
+    let mut any_header = false;
+    for header in &request.header {
+        if header.key.trim().is_empty() {
+            continue;
+        }
+        any_header = true;
+        if header.disabled.unwrap_or(false) {
+            content.push(Line::from(Span::styled(
+                format!("  {}: {} (disabled, not sent)", header.key, header.value),
+                dim,
+            )));
+        } else {
+            content.push(Line::from(format!("  {}: {}", header.key, header.value)));
+        }
+    }
+    for (key, value) in missing_default_headers(request, &app.config.settings.default_headers) {
+        any_header = true;
+        content.push(Line::from(vec![
+            Span::raw(format!("  {}: {}", key, value)),
+            Span::styled("  (default header)", dim),
+        ]));
+    }
+    if let Some(auth_line) = request.auth.as_ref().and_then(crate::api::client::describe_auth) {
+        any_header = true;
+        content.push(Line::from(vec![
+            Span::raw(format!("  {}", auth_line)),
+            Span::styled("  (from auth)", dim),
+        ]));
+    }
+    if !any_header {
+        content.push(Line::from(Span::styled("  (none)", dim)));
+    }
+
+    content.push(Line::from(""));
+    content.push(Line::from(Span::styled("Body", label)));
+    let form_data = request.body.as_ref().filter(|b| b.is_form_data());
+    let graphql = request.body.as_ref().and_then(|b| b.graphql());
+    match request.body.as_ref().and_then(|b| b.raw.as_deref()) {
+        _ if form_data.is_some() => {
+            let fields = form_data.map(|b| b.formdata.as_slice()).unwrap_or_default();
+            content.extend(form_data_lines(fields).into_iter().map(|l| Line::from(format!("  {}", l))));
+        }
+        _ if graphql.is_some() => {
+            let lines = graphql.map(graphql_lines).unwrap_or_default();
+            content.extend(lines.into_iter().map(|l| Line::from(format!("  {}", l))));
+        }
+        Some(raw) if !raw.is_empty() => {
+            content.extend(raw.lines().map(|l| Line::from(format!("  {}", l))));
+        }
+        _ => content.push(Line::from(Span::styled("  (no body)", dim))),
+    }
+
+    // Flag placeholders that are still unresolved after substitution
+    let url = request.url.to_string();
+    let unresolved = url.contains("{{")
+        || request.header.iter().any(|h| h.value.contains("{{") || h.key.contains("{{"))
+        || request.body.as_ref().and_then(|b| b.raw.as_deref()).is_some_and(|b| b.contains("{{"))
+        || graphql.is_some_and(|g| g.query.contains("{{") || g.variables.as_deref().is_some_and(|v| v.contains("{{")));
+    if unresolved {
+        content.push(Line::from(""));
+        content.push(Line::from(Span::styled(
+            "Warning: some {{variables}} are still unresolved",
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+        )));
+    }
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Dry Run (not sent) ")
+        .title_bottom(Line::from(" j/k: Scroll | Esc: Close ").right_aligned())
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(theme::popup_background()));
+
+    let paragraph = Paragraph::new(content)
+        .block(block)
+        .wrap(Wrap { trim: false })
+        .scroll((app.dry_run_scroll, 0));
+
+    frame.render_widget(paragraph, popup_area);
+}
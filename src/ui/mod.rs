@@ -1,5 +1,8 @@
+pub mod json_diff;
 pub mod json_viewer;
 pub mod layout;
+pub mod theme;
+pub mod xml_viewer;
 
 pub use json_viewer::JsonViewerState;
 pub use layout::*;
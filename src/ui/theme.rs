@@ -0,0 +1,60 @@
+//! Colors that depend on whether the terminal has a light or dark
+//! background. The choice is made once at startup from the `background`
+//! setting, detecting it from `COLORFGBG` when set to `auto`.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use ratatui::style::Color;
+
+use crate::config::Background;
+
+static LIGHT_BACKGROUND: AtomicBool = AtomicBool::new(false);
+
+pub fn init(setting: Background) {
+    let light = match setting {
+        Background::Light => true,
+        Background::Dark => false,
+        Background::Auto => std::env::var("COLORFGBG")
+            .ok()
+            .and_then(|value| light_from_colorfgbg(&value))
+            .unwrap_or(false),
+    };
+    LIGHT_BACKGROUND.store(light, Ordering::Relaxed);
+}
+
+pub fn is_light() -> bool {
+    LIGHT_BACKGROUND.load(Ordering::Relaxed)
+}
+
+/// Whether a `COLORFGBG` value (`"fg;bg"`, or `"fg;default;bg"` in some
+/// terminals) names a light background: ANSI white (7) or a bright color
+/// other than bright black (8). None when it can't be read.
+pub fn light_from_colorfgbg(value: &str) -> Option<bool> {
+    let background: u8 = value.rsplit(';').next()?.trim().parse().ok()?;
+    Some(background == 7 || (9..=15).contains(&background))
+}
+
+/// Plain text and unfocused borders.
+pub fn text() -> Color {
+    if is_light() { Color::Black } else { Color::White }
+}
+
+/// Background of popups, which would otherwise show the panes through.
+pub fn popup_background() -> Color {
+    if is_light() { Color::Reset } else { Color::Black }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::light_from_colorfgbg;
+
+    #[test]
+    fn reads_the_background_from_colorfgbg() {
+        assert_eq!(light_from_colorfgbg("15;0"), Some(false));
+        assert_eq!(light_from_colorfgbg("0;15"), Some(true));
+        assert_eq!(light_from_colorfgbg("0;default;7"), Some(true));
+        assert_eq!(light_from_colorfgbg("7;8"), Some(false));
+        assert_eq!(light_from_colorfgbg("default;default"), None);
+        assert_eq!(light_from_colorfgbg(""), None);
+    }
+}
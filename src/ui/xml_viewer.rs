@@ -0,0 +1,240 @@
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+
+use crate::ui::theme;
+
+/// Color scheme for XML syntax highlighting
+pub struct XmlColors {
+    pub tag: Color,
+    pub attr_name: Color,
+    pub attr_value: Color,
+    pub text: Color,
+    pub punctuation: Color,
+    pub comment: Color,
+}
+
+impl Default for XmlColors {
+    fn default() -> Self {
+        if theme::is_light() {
+            return Self {
+                tag: Color::Blue,
+                attr_name: Color::Magenta,
+                attr_value: Color::Green,
+                text: Color::Black,
+                punctuation: Color::DarkGray,
+                comment: Color::DarkGray,
+            };
+        }
+        Self {
+            tag: Color::Cyan,
+            attr_name: Color::Yellow,
+            attr_value: Color::Green,
+            text: Color::White,
+            punctuation: Color::DarkGray,
+            comment: Color::DarkGray,
+        }
+    }
+}
+
+/// A lexical piece of an XML document: markup (`<...>`) or the text between.
+#[derive(Debug, PartialEq)]
+enum XmlToken<'a> {
+    Open(&'a str),
+    Close(&'a str),
+    /// Self-closing tags, declarations, comments, CDATA, doctype.
+    Standalone(&'a str),
+    Text(&'a str),
+}
+
+fn tokenize(input: &str) -> Vec<XmlToken<'_>> {
+    let mut tokens = Vec::new();
+    let mut rest = input;
+
+    while !rest.is_empty() {
+        if !rest.starts_with('<') {
+            let end = rest.find('<').unwrap_or(rest.len());
+            let text = rest[..end].trim();
+            if !text.is_empty() {
+                tokens.push(XmlToken::Text(text));
+            }
+            rest = &rest[end..];
+            continue;
+        }
+
+        // Comments and CDATA may contain `>`, so they need their own terminators.
+        let terminator = if rest.starts_with("<!--") {
+            "-->"
+        } else if rest.starts_with("<![CDATA[") {
+            "]]>"
+        } else {
+            ">"
+        };
+        let end = match rest.find(terminator) {
+            Some(i) => i + terminator.len(),
+            None => rest.len(),
+        };
+        let markup = &rest[..end];
+
+        let token = if markup.starts_with("</") {
+            XmlToken::Close(markup)
+        } else if markup.starts_with("<?") || markup.starts_with("<!") || markup.ends_with("/>") {
+            XmlToken::Standalone(markup)
+        } else {
+            XmlToken::Open(markup)
+        };
+        tokens.push(token);
+        rest = &rest[end..];
+    }
+
+    tokens
+}
+
+/// Reindent an XML document two spaces per level. Elements that contain only
+/// text are kept on one line (`<name>value</name>`). Malformed input is
+/// formatted on a best-effort basis rather than rejected.
+pub fn pretty_print(input: &str) -> String {
+    let tokens = tokenize(input);
+    let mut lines: Vec<String> = Vec::new();
+    let mut depth: usize = 0;
+    let mut i = 0;
+
+    while i < tokens.len() {
+        let indent = "  ".repeat(depth);
+        match tokens[i] {
+            XmlToken::Open(open) => {
+                // Collapse <a>text</a> and <a></a> onto a single line.
+                match (tokens.get(i + 1), tokens.get(i + 2)) {
+                    (Some(XmlToken::Text(text)), Some(XmlToken::Close(close))) => {
+                        lines.push(format!("{}{}{}{}", indent, open, text, close));
+                        i += 3;
+                        continue;
+                    }
+                    (Some(XmlToken::Close(close)), _) => {
+                        lines.push(format!("{}{}{}", indent, open, close));
+                        i += 2;
+                        continue;
+                    }
+                    _ => {
+                        lines.push(format!("{}{}", indent, open));
+                        depth += 1;
+                    }
+                }
+            }
+            XmlToken::Close(close) => {
+                depth = depth.saturating_sub(1);
+                lines.push(format!("{}{}", "  ".repeat(depth), close));
+            }
+            XmlToken::Standalone(markup) | XmlToken::Text(markup) => {
+                lines.push(format!("{}{}", indent, markup));
+            }
+        }
+        i += 1;
+    }
+
+    lines.join("\n")
+}
+
+/// Colorize one line of (pretty-printed) XML: tag names, attribute names and
+/// values, text content and comments each get their own color.
+pub fn highlight_line(line: &str, colors: &XmlColors) -> Line<'static> {
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    let mut rest = line;
+
+    while !rest.is_empty() {
+        if !rest.starts_with('<') {
+            let end = rest.find('<').unwrap_or(rest.len());
+            spans.push(Span::styled(rest[..end].to_string(), Style::default().fg(colors.text)));
+            rest = &rest[end..];
+            continue;
+        }
+
+        if rest.starts_with("<!--") || rest.starts_with("<![CDATA[") || rest.starts_with("<!") {
+            let terminator = if rest.starts_with("<!--") {
+                "-->"
+            } else if rest.starts_with("<![CDATA[") {
+                "]]>"
+            } else {
+                ">"
+            };
+            let end = rest.find(terminator).map(|i| i + terminator.len()).unwrap_or(rest.len());
+            spans.push(Span::styled(rest[..end].to_string(), Style::default().fg(colors.comment)));
+            rest = &rest[end..];
+            continue;
+        }
+
+        let end = rest.find('>').map(|i| i + 1).unwrap_or(rest.len());
+        highlight_tag(&rest[..end], colors, &mut spans);
+        rest = &rest[end..];
+    }
+
+    Line::from(spans)
+}
+
+/// Split a single `<tag attr="value">` into styled spans.
+fn highlight_tag(tag: &str, colors: &XmlColors, spans: &mut Vec<Span<'static>>) {
+    let punct = Style::default().fg(colors.punctuation);
+
+    let open_len = if tag.starts_with("</") || tag.starts_with("<?") { 2 } else { 1 };
+    spans.push(Span::styled(tag[..open_len].to_string(), punct));
+
+    let close_len = if tag.ends_with("/>") || tag.ends_with("?>") {
+        2
+    } else if tag.ends_with('>') {
+        1
+    } else {
+        0
+    }
+    .min(tag.len() - open_len);
+    let inner = &tag[open_len..tag.len() - close_len];
+
+    let name_end = inner.find(char::is_whitespace).unwrap_or(inner.len());
+    spans.push(Span::styled(inner[..name_end].to_string(), Style::default().fg(colors.tag)));
+
+    // Attributes: walk `name="value"` pairs, tolerating odd spacing.
+    let mut attrs = &inner[name_end..];
+    while !attrs.is_empty() {
+        let ws_len = attrs.len() - attrs.trim_start().len();
+        if ws_len > 0 {
+            spans.push(Span::raw(attrs[..ws_len].to_string()));
+            attrs = &attrs[ws_len..];
+            continue;
+        }
+
+        let eq = attrs.find('=').unwrap_or(attrs.len());
+        let name_len = attrs[..eq].find(char::is_whitespace).unwrap_or(eq);
+        spans.push(Span::styled(attrs[..name_len].to_string(), Style::default().fg(colors.attr_name)));
+        attrs = &attrs[name_len..];
+
+        if let Some(after_eq) = attrs.strip_prefix('=') {
+            spans.push(Span::styled("=", punct));
+            attrs = after_eq;
+            let quote = attrs.chars().next().filter(|c| *c == '"' || *c == '\'');
+            let value_len = match quote {
+                Some(q) => attrs[1..].find(q).map(|i| i + 2).unwrap_or(attrs.len()),
+                None => attrs.find(char::is_whitespace).unwrap_or(attrs.len()),
+            };
+            spans.push(Span::styled(attrs[..value_len].to_string(), Style::default().fg(colors.attr_value)));
+            attrs = &attrs[value_len..];
+        }
+    }
+
+    spans.push(Span::styled(tag[tag.len() - close_len..].to_string(), punct));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::pretty_print;
+
+    #[test]
+    fn indents_nested_elements() {
+        let xml = r#"<?xml version="1.0"?><a><b x="1"><c>hi</c></b><d/></a>"#;
+        let expected = "<?xml version=\"1.0\"?>\n<a>\n  <b x=\"1\">\n    <c>hi</c>\n  </b>\n  <d/>\n</a>";
+        assert_eq!(pretty_print(xml), expected);
+    }
+
+    #[test]
+    fn keeps_comments_with_angle_brackets_intact() {
+        let xml = "<a><!-- x > y --><b></b></a>";
+        assert_eq!(pretty_print(xml), "<a>\n  <!-- x > y -->\n  <b></b>\n</a>");
+    }
+}
@@ -0,0 +1,582 @@
+/// Byte count as a short human-readable size: "512 B", "4.2 KB", "1.3 MB".
+pub fn format_bytes(bytes: usize) -> String {
+    const KB: f64 = 1024.0;
+    const MB: f64 = KB * 1024.0;
+    const GB: f64 = MB * 1024.0;
+    let b = bytes as f64;
+    if b < KB {
+        format!("{} B", bytes)
+    } else if b < MB {
+        format!("{:.1} KB", b / KB)
+    } else if b < GB {
+        format!("{:.1} MB", b / MB)
+    } else {
+        format!("{:.1} GB", b / GB)
+    }
+}
+
+/// `text` cut to at most `max` characters, ending in `...` when shortened.
+/// Counts characters, so multi-byte text is never split mid-character.
+pub fn ellipsize(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+    let kept: String = text.chars().take(max.saturating_sub(3)).collect();
+    format!("{}...", kept)
+}
+
+/// Whether `name` matches a search query: the query appears in it,
+/// case-insensitively. Shared by `/` search, the palette and the finder.
+pub fn search_matches(name: &str, query: &str) -> bool {
+    name.to_lowercase().contains(&query.to_lowercase())
+}
+
+/// `name` as a lowercase file name stem: runs of anything but ASCII letters
+/// and digits become `-`.
+pub fn file_slug(name: &str) -> String {
+    let slug: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '-' })
+        .collect();
+    slug.split('-').filter(|part| !part.is_empty()).collect::<Vec<_>>().join("-")
+}
+
+/// Postman's built-in `{{$name}}` variables that lazypost can generate.
+pub const DYNAMIC_VARIABLES: &[&str] = &["$guid", "$timestamp", "$isoTimestamp", "$randomInt", "$randomUUID"];
+
+/// A fresh value for the dynamic variable `name` (including the `$`), or
+/// None if it isn't one lazypost knows how to generate.
+pub fn dynamic_variable(name: &str) -> Option<String> {
+    match name {
+        "$guid" | "$randomUUID" => Some(uuid::Uuid::new_v4().to_string()),
+        "$timestamp" => Some(chrono::Utc::now().timestamp().to_string()),
+        "$isoTimestamp" => Some(chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true)),
+        // Postman's range: 0 to 1000 inclusive
+        "$randomInt" => Some((uuid::Uuid::new_v4().as_u128() % 1001).to_string()),
+        _ => None,
+    }
+}
+
+/// Replace each known `{{$name}}` in `text` with a freshly generated value,
+/// so two `{{$guid}}`s get two different ids. Unknown ones are left as is.
+pub fn substitute_dynamic_variables(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{$") {
+        result.push_str(&rest[..start]);
+        let after_open = &rest[start + 2..];
+        let value = after_open
+            .find("}}")
+            .and_then(|end| dynamic_variable(&after_open[..end]).map(|value| (end, value)));
+        match value {
+            Some((end, value)) => {
+                result.push_str(&value);
+                rest = &after_open[end + 2..];
+            }
+            None => {
+                result.push_str("{{");
+                rest = after_open;
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// `key` as a shell variable name: anything other than letters, digits and
+/// `_` becomes `_`, and a leading digit gets a `_` prefix.
+pub fn env_var_name(key: &str) -> String {
+    let name: String = key
+        .trim()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' })
+        .collect();
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{}", name)
+    } else {
+        name
+    }
+}
+
+/// A `.env` line: the value double-quoted, with backslashes, quotes,
+/// control characters and shell expansions (`$`, backticks) escaped.
+pub fn dotenv_line(key: &str, value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    for c in value.chars() {
+        match c {
+            '\\' => quoted.push_str("\\\\"),
+            '"' => quoted.push_str("\\\""),
+            '$' => quoted.push_str("\\$"),
+            '`' => quoted.push_str("\\`"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c => quoted.push(c),
+        }
+    }
+    format!("{}=\"{}\"", env_var_name(key), quoted)
+}
+
+/// An `export` line for a POSIX shell, single-quoted so nothing in the
+/// value is expanded.
+pub fn shell_export_line(key: &str, value: &str) -> String {
+    format!("export {}='{}'", env_var_name(key), value.replace('\'', "'\\''"))
+}
+
+/// The first row to show so that row `selected` of a `len`-row list stays
+/// inside a `rows`-tall viewport, moving from `offset` only as far as needed.
+pub fn scroll_offset(offset: usize, selected: Option<usize>, rows: usize, len: usize) -> usize {
+    let offset = offset.min(len.saturating_sub(rows));
+    match selected {
+        Some(selected) if selected < offset => selected,
+        Some(selected) if rows > 0 && selected >= offset + rows => selected + 1 - rows,
+        _ => offset,
+    }
+}
+
+/// `text` as a fenced Markdown code block, with a fence longer than any run
+/// of backticks inside it so the block can't be closed early.
+pub fn markdown_code_block(language: &str, text: &str) -> String {
+    let longest = text
+        .split(|c| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or(0);
+    let fence = "`".repeat(longest.max(2) + 1);
+    format!("{}{}\n{}\n{}\n", fence, language, text.trim_end_matches('\n'), fence)
+}
+
+/// The documents of a newline-delimited JSON body: one JSON value per
+/// non-blank line. `declared` is whether the Content-Type says NDJSON; without
+/// it, a body only counts when it has several lines and isn't a single JSON
+/// document itself.
+pub fn ndjson_documents(body: &str, declared: bool) -> Option<Vec<serde_json::Value>> {
+    let lines: Vec<&str> = body.lines().map(str::trim).filter(|l| !l.is_empty()).collect();
+    if lines.is_empty() || (!declared && (lines.len() < 2 || serde_json::from_str::<serde_json::Value>(body).is_ok())) {
+        return None;
+    }
+    lines.into_iter().map(|line| serde_json::from_str(line).ok()).collect()
+}
+
+/// Whether a Content-Type header value is newline-delimited JSON.
+pub fn is_ndjson_content_type(content_type: &str) -> bool {
+    let media_type = content_type.split(';').next().unwrap_or_default().trim().to_ascii_lowercase();
+    matches!(media_type.as_str(), "application/x-ndjson" | "application/ndjson" | "application/jsonl" | "application/x-jsonlines")
+}
+
+/// `body` re-indented with two spaces per level if it is a single JSON
+/// document, else None. Keys keep their order and numbers their spelling,
+/// as only the whitespace between tokens is rewritten.
+pub fn prettify_json(body: &str) -> Option<String> {
+    serde_json::from_str::<serde_json::Value>(body).ok()?;
+    let mut out = String::with_capacity(body.len() * 2);
+    let mut depth = 0usize;
+    let mut chars = body.trim().chars().peekable();
+    let newline = |out: &mut String, depth: usize| {
+        out.push('\n');
+        out.push_str(&"  ".repeat(depth));
+    };
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                out.push(c);
+                while let Some(c) = chars.next() {
+                    out.push(c);
+                    match c {
+                        '\\' => out.extend(chars.next()),
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+            '{' | '[' => {
+                while chars.next_if(|c| c.is_whitespace()).is_some() {}
+                out.push(c);
+                if let Some(close) = chars.next_if(|&next| next == '}' || next == ']') {
+                    out.push(close);
+                } else {
+                    depth += 1;
+                    newline(&mut out, depth);
+                }
+            }
+            '}' | ']' => {
+                depth = depth.saturating_sub(1);
+                newline(&mut out, depth);
+                out.push(c);
+            }
+            ',' => {
+                out.push(c);
+                newline(&mut out, depth);
+            }
+            ':' => out.push_str(": "),
+            c if c.is_whitespace() => {}
+            c => out.push(c),
+        }
+    }
+    Some(out)
+}
+
+/// How a response body is shown in the Response pane.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResponseView {
+    Json,
+    Xml,
+    Text,
+    /// Not shown; a notice with the content type and size instead.
+    Binary,
+}
+
+impl ResponseView {
+    /// The view for a response, from its `Content-Type` when it names a
+    /// known kind, else from the body: JSON if it parses, XML if it starts
+    /// with `<`, binary if it has NULs or undecodable bytes.
+    pub fn detect(headers: &[(String, String)], body: &str) -> Self {
+        let content_type = headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case("content-type"))
+            .map(|(_, v)| v.split(';').next().unwrap_or_default().trim().to_ascii_lowercase());
+        match content_type.as_deref() {
+            Some(ct) if ct.contains("json") => ResponseView::Json,
+            Some(ct) if ct.contains("xml") => ResponseView::Xml,
+            // HTML also starts with `<` but isn't well-formed enough to reindent
+            Some(ct) if ct.starts_with("text/") || ct.contains("javascript") || ct.ends_with("x-www-form-urlencoded") => {
+                ResponseView::Text
+            }
+            Some(ct)
+                if ["image/", "audio/", "video/", "font/"].iter().any(|p| ct.starts_with(p))
+                    || matches!(ct, "application/octet-stream" | "application/pdf" | "application/zip" | "application/gzip") =>
+            {
+                ResponseView::Binary
+            }
+            _ if body.contains(['\0', '\u{FFFD}']) => ResponseView::Binary,
+            _ if serde_json::from_str::<serde_json::Value>(body).is_ok() || ndjson_documents(body, false).is_some() => {
+                ResponseView::Json
+            }
+            _ if body.trim_start().starts_with('<') => ResponseView::Xml,
+            _ => ResponseView::Text,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ResponseView::Json => "JSON",
+            ResponseView::Xml => "XML",
+            ResponseView::Text => "text",
+            ResponseView::Binary => "binary",
+        }
+    }
+}
+
+/// `url` without the query parameters in `removed` (key and value; a `None`
+/// value matches `key` and `key=`). Each entry drops one matching pair, so
+/// a repeated key that is only partly removed keeps its other occurrences.
+pub fn remove_query_params(url: &str, removed: &[(&str, Option<&str>)]) -> String {
+    let (rest, fragment) = match url.split_once('#') {
+        Some((rest, fragment)) => (rest, Some(fragment)),
+        None => (url, None),
+    };
+    let Some((base, query)) = rest.split_once('?') else {
+        return url.to_string();
+    };
+    let mut pairs: Vec<&str> = query.split('&').collect();
+    for (key, value) in removed {
+        let position = pairs.iter().position(|pair| {
+            let (k, v) = pair.split_once('=').unwrap_or((pair, ""));
+            k == *key && v == value.unwrap_or_default()
+        });
+        if let Some(position) = position {
+            pairs.remove(position);
+        }
+    }
+    let mut result = base.to_string();
+    if !pairs.is_empty() {
+        result.push('?');
+        result.push_str(&pairs.join("&"));
+    }
+    if let Some(fragment) = fragment {
+        result.push('#');
+        result.push_str(fragment);
+    }
+    result
+}
+
+/// How long ago something happened, for the status bar: `just now`,
+/// `12m ago`, `3h ago`, `2d ago`.
+pub fn format_age(seconds: i64) -> String {
+    match seconds {
+        ..60 => String::from("just now"),
+        60..3600 => format!("{}m ago", seconds / 60),
+        3600..86400 => format!("{}h ago", seconds / 3600),
+        _ => format!("{}d ago", seconds / 86400),
+    }
+}
+
+/// The query parameters of `url` in order, decoded, as (key, value); a
+/// pair without `=` has no value.
+pub fn query_pairs(url: &str) -> Vec<(String, Option<String>)> {
+    let without_fragment = url.split('#').next().unwrap_or_default();
+    let Some((_, query)) = without_fragment.split_once('?') else {
+        return Vec::new();
+    };
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .filter_map(|pair| {
+            let (key, value) = url::form_urlencoded::parse(pair.as_bytes()).next()?;
+            Some((key.into_owned(), pair.contains('=').then(|| value.into_owned())))
+        })
+        .collect()
+}
+
+/// `text` form-urlencoded for a query string, leaving `{{variable}}`
+/// placeholders as written so they still resolve.
+fn encode_query_component(text: &str) -> String {
+    let mut encoded = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start..].find("}}") else {
+            break;
+        };
+        let end = start + len + 2;
+        encoded.extend(url::form_urlencoded::byte_serialize(&rest.as_bytes()[..start]));
+        encoded.push_str(&rest[start..end]);
+        rest = &rest[end..];
+    }
+    encoded.extend(url::form_urlencoded::byte_serialize(rest.as_bytes()));
+    encoded
+}
+
+/// `url` with its query string replaced by `pairs` (encoded; dropped when
+/// empty), keeping any `#fragment`.
+pub fn with_query(url: &str, pairs: &[(String, Option<String>)]) -> String {
+    let (rest, fragment) = match url.split_once('#') {
+        Some((rest, fragment)) => (rest, Some(fragment)),
+        None => (url, None),
+    };
+    let mut result = rest.split('?').next().unwrap_or_default().to_string();
+    let query: Vec<String> = pairs
+        .iter()
+        .map(|(key, value)| match value {
+            Some(value) => format!("{}={}", encode_query_component(key), encode_query_component(value)),
+            None => encode_query_component(key),
+        })
+        .collect();
+    if !query.is_empty() {
+        result.push('?');
+        result.push_str(&query.join("&"));
+    }
+    if let Some(fragment) = fragment {
+        result.push('#');
+        result.push_str(fragment);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{dotenv_line, dynamic_variable, ellipsize, env_var_name, file_slug, format_age, format_bytes, is_ndjson_content_type, markdown_code_block, ndjson_documents, prettify_json, query_pairs, remove_query_params, scroll_offset, shell_export_line, substitute_dynamic_variables, with_query, ResponseView};
+
+    #[test]
+    fn response_view_follows_content_type_then_body() {
+        let ct = |v: &str| vec![(String::from("Content-Type"), v.to_string())];
+        assert_eq!(ResponseView::detect(&ct("application/problem+json"), "{}"), ResponseView::Json);
+        assert_eq!(ResponseView::detect(&ct("text/plain; charset=utf-8"), r#"{"a": 1}"#), ResponseView::Text);
+        assert_eq!(ResponseView::detect(&ct("text/xml; charset=utf-8"), "not even markup"), ResponseView::Xml);
+        assert_eq!(ResponseView::detect(&ct("text/html"), "<html></html>"), ResponseView::Text);
+        assert_eq!(ResponseView::detect(&ct("image/png"), "\u{FFFD}PNG"), ResponseView::Binary);
+        assert_eq!(ResponseView::detect(&ct("application/x-custom"), "<a/>"), ResponseView::Xml);
+        assert_eq!(ResponseView::detect(&[], "[1, 2]"), ResponseView::Json);
+        assert_eq!(ResponseView::detect(&[], "  <soap:Envelope/>"), ResponseView::Xml);
+        assert_eq!(ResponseView::detect(&[], "ok"), ResponseView::Text);
+        assert_eq!(ResponseView::detect(&[], "\0\u{1}"), ResponseView::Binary);
+    }
+
+    #[test]
+    fn long_text_is_ellipsized_by_characters() {
+        assert_eq!(ellipsize("short", 10), "short");
+        assert_eq!(ellipsize("https://example.com/users", 10), "https:/...");
+        assert_eq!(ellipsize("ééééééé", 5), "éé...");
+    }
+
+    #[test]
+    fn names_become_file_slugs() {
+        assert_eq!(file_slug("Users API (v2)"), "users-api-v2");
+        assert_eq!(file_slug("--Get  user--"), "get-user");
+    }
+
+    #[test]
+    fn json_is_reindented_without_reordering() {
+        let body = r#"{"b":1.50,"a":[],"s":"x, {y}: \"z\"","n":[1,{ }]}"#;
+        let pretty = prettify_json(body).unwrap();
+        assert_eq!(
+            pretty,
+            "{\n  \"b\": 1.50,\n  \"a\": [],\n  \"s\": \"x, {y}: \\\"z\\\"\",\n  \"n\": [\n    1,\n    {}\n  ]\n}"
+        );
+        assert_eq!(prettify_json(&pretty).as_deref(), Some(pretty.as_str()));
+        assert_eq!(prettify_json("{\"id\": {{id}}}"), None);
+        assert_eq!(prettify_json("name=x"), None);
+    }
+
+    #[test]
+    fn ndjson_bodies_split_into_documents() {
+        let body = "{\"id\": 1}\n\n{\"id\": 2}\r\n[3]\n";
+        let docs = ndjson_documents(body, false).unwrap();
+        assert_eq!(docs, vec![serde_json::json!({"id": 1}), serde_json::json!({"id": 2}), serde_json::json!([3])]);
+        // One document is only NDJSON when the server says so
+        assert!(ndjson_documents("{\"id\": 1}\n", false).is_none());
+        assert_eq!(ndjson_documents("{\"id\": 1}\n", true).unwrap().len(), 1);
+        // A pretty-printed document spans lines but isn't NDJSON
+        assert!(ndjson_documents("{\n  \"id\": 1\n}", false).is_none());
+        assert!(ndjson_documents("{\"id\": 1}\nnot json", true).is_none());
+
+        assert!(is_ndjson_content_type("application/x-ndjson; charset=utf-8"));
+        assert!(!is_ndjson_content_type("application/json"));
+    }
+
+    #[test]
+    fn code_block_fence_outgrows_backticks_inside() {
+        assert_eq!(markdown_code_block("json", "{}\n"), "```json\n{}\n```\n");
+        assert_eq!(markdown_code_block("", "see ```rust``` and ````"), "`````\nsee ```rust``` and ````\n`````\n");
+    }
+
+    #[test]
+    fn formats_boundaries() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1024), "1.0 KB");
+        assert_eq!(format_bytes(1536), "1.5 KB");
+        assert_eq!(format_bytes(1024 * 1024 - 1), "1024.0 KB");
+        assert_eq!(format_bytes(1024 * 1024), "1.0 MB");
+        assert_eq!(format_bytes(1024 * 1024 * 1024), "1.0 GB");
+    }
+
+    fn is_uuid_v4(value: &str) -> bool {
+        let parts: Vec<&str> = value.split('-').collect();
+        parts.iter().map(|p| p.len()).collect::<Vec<_>>() == [8, 4, 4, 4, 12]
+            && value.chars().all(|c| c == '-' || c.is_ascii_hexdigit())
+            && parts[2].starts_with('4')
+    }
+
+    #[test]
+    fn dynamic_variable_formats() {
+        assert!(is_uuid_v4(&dynamic_variable("$guid").unwrap()));
+        assert!(is_uuid_v4(&dynamic_variable("$randomUUID").unwrap()));
+
+        let timestamp: i64 = dynamic_variable("$timestamp").unwrap().parse().unwrap();
+        assert!((timestamp - chrono::Utc::now().timestamp()).abs() < 5);
+
+        let iso = dynamic_variable("$isoTimestamp").unwrap();
+        assert!(chrono::DateTime::parse_from_rfc3339(&iso).is_ok());
+        assert!(iso.ends_with('Z'));
+        assert_eq!(iso.len(), "2024-01-02T03:04:05.678Z".len());
+
+        for _ in 0..50 {
+            let n: u32 = dynamic_variable("$randomInt").unwrap().parse().unwrap();
+            assert!(n <= 1000);
+        }
+
+        assert_eq!(dynamic_variable("$randomColor"), None);
+        assert_eq!(dynamic_variable("guid"), None);
+    }
+
+    #[test]
+    fn each_dynamic_occurrence_gets_a_fresh_value() {
+        let result = substitute_dynamic_variables("{{$guid}}/{{$guid}}");
+        let (first, second) = result.split_once('/').unwrap();
+        assert!(is_uuid_v4(first) && is_uuid_v4(second));
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn unknown_dynamic_variables_are_left_alone() {
+        assert_eq!(substitute_dynamic_variables("{{$nope}} {{name}} {{$"), "{{$nope}} {{name}} {{$");
+        let result = substitute_dynamic_variables("id={{$nope}}&n={{$randomInt}}");
+        assert!(result.starts_with("id={{$nope}}&n="));
+        assert!(result["id={{$nope}}&n=".len()..].parse::<u32>().is_ok());
+    }
+
+    #[test]
+    fn env_var_names_are_shell_safe() {
+        assert_eq!(env_var_name("API_KEY"), "API_KEY");
+        assert_eq!(env_var_name("base-url"), "base_url");
+        assert_eq!(env_var_name(" user.id "), "user_id");
+        assert_eq!(env_var_name("2fa"), "_2fa");
+    }
+
+    #[test]
+    fn export_lines_quote_and_escape_values() {
+        assert_eq!(dotenv_line("token", "abc"), "token=\"abc\"");
+        assert_eq!(dotenv_line("v", "say \"hi\"\\n\n$HOME"), "v=\"say \\\"hi\\\"\\\\n\\n\\$HOME\"");
+        assert_eq!(dotenv_line("cmd", "`id`"), "cmd=\"\\`id\\`\"");
+        assert_eq!(shell_export_line("token", "abc"), "export token='abc'");
+        assert_eq!(shell_export_line("v", "it's $HOME"), "export v='it'\\''s $HOME'");
+    }
+
+    #[test]
+    fn scroll_offset_keeps_selection_visible() {
+        // Moving within the viewport doesn't scroll
+        assert_eq!(scroll_offset(0, Some(9), 10, 1000), 0);
+        assert_eq!(scroll_offset(5, Some(5), 10, 1000), 5);
+        // Past the bottom edge: the selection becomes the last visible row
+        assert_eq!(scroll_offset(0, Some(10), 10, 1000), 1);
+        assert_eq!(scroll_offset(0, Some(999), 10, 1000), 990);
+        // Past the top edge: the selection becomes the first visible row
+        assert_eq!(scroll_offset(50, Some(42), 10, 1000), 42);
+        // A list that shrank doesn't leave empty rows below it
+        assert_eq!(scroll_offset(500, None, 10, 20), 10);
+        assert_eq!(scroll_offset(3, Some(1), 10, 5), 0);
+    }
+
+    #[test]
+    fn ages_round_down_to_the_largest_unit() {
+        assert_eq!(format_age(-5), "just now");
+        assert_eq!(format_age(59), "just now");
+        assert_eq!(format_age(60), "1m ago");
+        assert_eq!(format_age(12 * 60 + 59), "12m ago");
+        assert_eq!(format_age(3600 * 3 + 10), "3h ago");
+        assert_eq!(format_age(86400 * 2), "2d ago");
+    }
+
+    #[test]
+    fn query_pairs_round_trip_through_with_query() {
+        let url = "{{host}}/search?q={{term}}&flag&page=2#results";
+        let pairs = query_pairs(url);
+        assert_eq!(
+            pairs,
+            vec![
+                (String::from("q"), Some(String::from("{{term}}"))),
+                (String::from("flag"), None),
+                (String::from("page"), Some(String::from("2"))),
+            ]
+        );
+        assert_eq!(with_query(url, &pairs), url);
+        assert_eq!(with_query(url, &pairs[2..]), "{{host}}/search?page=2#results");
+        assert_eq!(with_query(url, &[]), "{{host}}/search#results");
+        assert!(query_pairs("https://api.test/users").is_empty());
+        assert_eq!(with_query("https://api.test/users", &pairs[..1]), "https://api.test/users?q={{term}}");
+    }
+
+    #[test]
+    fn query_values_are_encoded_around_placeholders() {
+        let pairs = vec![(String::from("q"), Some(String::from("a&b=c #{{tag}}")))];
+        let url = with_query("https://api.test/search", &pairs);
+        assert_eq!(url, "https://api.test/search?q=a%26b%3Dc+%23{{tag}}");
+        assert_eq!(query_pairs(&url), pairs);
+        assert_eq!(query_pairs("https://api.test/?sum=1%2B1&name=J+Doe&expr=a=b")[2].1.as_deref(), Some("a=b"));
+        assert_eq!(query_pairs("https://api.test/?sum=1%2B1&name=J+Doe")[..2], [
+            (String::from("sum"), Some(String::from("1+1"))),
+            (String::from("name"), Some(String::from("J Doe"))),
+        ]);
+    }
+
+    #[test]
+    fn removes_only_the_listed_query_params() {
+        let url = "https://api.test/users?page=2&debug=true&tag=a&tag=b#top";
+        assert_eq!(remove_query_params(url, &[("debug", Some("true"))]), "https://api.test/users?page=2&tag=a&tag=b#top");
+        assert_eq!(remove_query_params(url, &[("tag", Some("b"))]), "https://api.test/users?page=2&debug=true&tag=a#top");
+        assert_eq!(remove_query_params("https://api.test/?flag", &[("flag", None)]), "https://api.test/");
+        // A param whose value differs from the raw URL's is left alone
+        assert_eq!(remove_query_params(url, &[("page", Some("3"))]), url);
+        assert_eq!(remove_query_params("https://api.test/users", &[("page", None)]), "https://api.test/users");
+    }
+}
@@ -0,0 +1,606 @@
+//! Just enough YAML to read OpenAPI specs: block mappings and sequences,
+//! `[..]` and `{..}` flow collections, plain and quoted scalars, `|` and `>`
+//! block scalars and comments. Anchors, aliases, tags and multi-document
+//! streams aren't supported. Anything it can't read faithfully (including
+//! tab indentation and duplicate keys) is an error rather than a guess.
+
+use anyhow::{bail, Context, Result};
+use serde_json::{Map, Number, Value};
+
+/// Parse a YAML document into the equivalent JSON value.
+pub fn parse(text: &str) -> Result<Value> {
+    let lines = text
+        .trim_start_matches('\u{feff}')
+        .lines()
+        .enumerate()
+        .filter(|(_, raw)| !raw.starts_with('%') && !matches!(raw.trim_end(), "---" | "..."))
+        .map(|(index, raw)| {
+            let text = raw.trim_start_matches(' ');
+            Line { number: index + 1, indent: raw.len() - text.len(), text: text.trim_end().to_string() }
+        })
+        .collect();
+    let mut parser = Parser { lines, pos: 0 };
+    let value = parser.node()?;
+    if parser.peek()?.is_some() {
+        bail!("line {}: unexpected content", parser.lines[parser.pos].number);
+    }
+    Ok(value)
+}
+
+struct Line {
+    number: usize,
+    indent: usize,
+    /// The line without its indentation or trailing whitespace.
+    text: String,
+}
+
+struct Parser {
+    lines: Vec<Line>,
+    pos: usize,
+}
+
+impl Parser {
+    /// The indentation and comment-free text of the next line with content,
+    /// skipping blank and comment lines. YAML doesn't allow tabs in
+    /// indentation, so a line indented with one is an error.
+    fn peek(&mut self) -> Result<Option<(usize, String)>> {
+        while let Some(line) = self.lines.get(self.pos) {
+            if line.text.is_empty() || line.text.starts_with('#') {
+                self.pos += 1;
+                continue;
+            }
+            if line.text.starts_with('\t') {
+                bail!("line {}: tabs can't be used for indentation", line.number);
+            }
+            return Ok(Some((line.indent, strip_comment(&line.text).to_string())));
+        }
+        Ok(None)
+    }
+
+    /// The node starting at the next line with content.
+    fn node(&mut self) -> Result<Value> {
+        let Some((indent, text)) = self.peek()? else {
+            return Ok(Value::Null);
+        };
+        if is_sequence_item(&text) {
+            self.sequence(indent)
+        } else if split_key(&text).is_some() {
+            self.mapping(indent)
+        } else {
+            self.pos += 1;
+            self.inline(&text, indent.saturating_sub(1))
+        }
+    }
+
+    fn mapping(&mut self, indent: usize) -> Result<Value> {
+        let mut map = Map::new();
+        while let Some((line_indent, text)) = self.peek()? {
+            if line_indent < indent || is_sequence_item(&text) {
+                break;
+            }
+            let number = self.lines[self.pos].number;
+            if line_indent > indent {
+                bail!("line {}: unexpected indentation", number);
+            }
+            let Some((key, rest)) = split_key(&text) else {
+                bail!("line {}: expected `key: value`", number);
+            };
+            self.pos += 1;
+            if map.contains_key(&key) {
+                bail!("line {}: duplicate key `{}`", number, key);
+            }
+            if is_sequence_item(&rest) || split_key(&rest).is_some() {
+                bail!("line {}: a block can't start on its key's line", number);
+            }
+            let value = if rest.is_empty() {
+                match self.peek()? {
+                    Some((next, _)) if next > indent => self.node()?,
+                    // A sequence may sit at its key's indentation
+                    Some((next, text)) if next == indent && is_sequence_item(&text) => self.sequence(indent)?,
+                    _ => Value::Null,
+                }
+            } else {
+                self.inline(&rest, indent)?
+            };
+            map.insert(key, value);
+        }
+        Ok(Value::Object(map))
+    }
+
+    fn sequence(&mut self, indent: usize) -> Result<Value> {
+        let mut items = Vec::new();
+        while let Some((line_indent, text)) = self.peek()? {
+            if line_indent != indent || !is_sequence_item(&text) {
+                if line_indent > indent {
+                    bail!("line {}: unexpected indentation", self.lines[self.pos].number);
+                }
+                break;
+            }
+            let rest = text[1..].trim_start();
+            if rest.is_empty() {
+                self.pos += 1;
+                items.push(match self.peek()? {
+                    Some((next, _)) if next > indent => self.node()?,
+                    _ => Value::Null,
+                });
+            } else if is_sequence_item(rest) || split_key(rest).is_some() {
+                // A block starting on the item's own line (`- name: id`):
+                // read the rest of the line as if it were indented on its own
+                let line = &mut self.lines[self.pos];
+                line.indent += text.len() - rest.len();
+                line.text = rest.to_string();
+                items.push(self.node()?);
+            } else {
+                self.pos += 1;
+                items.push(self.inline(rest, indent)?);
+            }
+        }
+        Ok(Value::Array(items))
+    }
+
+    /// A value written on the line just read: a scalar or flow collection,
+    /// possibly continued on following lines, or a block scalar header.
+    /// `parent` is the indentation of the key or item it belongs to.
+    fn inline(&mut self, text: &str, parent: usize) -> Result<Value> {
+        let number = self.lines[self.pos - 1].number;
+        let text = text.trim();
+        match text.chars().next() {
+            Some('|' | '>') => self.block_scalar(text, parent).with_context(|| format!("line {}", number)),
+            Some('&' | '*' | '!') => bail!("line {}: anchors, aliases and tags aren't supported", number),
+            Some('[' | '{') => {
+                let mut flow = text.to_string();
+                while !flow_closed(&flow) {
+                    let Some((_, next)) = self.peek()? else {
+                        bail!("line {}: unterminated flow collection", number);
+                    };
+                    flow.push(' ');
+                    flow.push_str(&next);
+                    self.pos += 1;
+                }
+                Flow { text: &flow, pos: 0 }.document().with_context(|| format!("line {}", number))
+            }
+            Some('"' | '\'') => {
+                let mut quoted_text = text.to_string();
+                loop {
+                    if let Some((value, len)) = quoted(&quoted_text) {
+                        if !quoted_text[len..].trim().is_empty() {
+                            bail!("line {}: unexpected text after a quoted string", number);
+                        }
+                        return Ok(Value::String(value));
+                    }
+                    // Quoted strings may span lines, folded into spaces
+                    let Some(line) = self.lines.get(self.pos) else {
+                        bail!("line {}: unterminated quoted string", number);
+                    };
+                    quoted_text.push(' ');
+                    quoted_text.push_str(line.text.trim_start());
+                    self.pos += 1;
+                }
+            }
+            _ => {
+                let mut plain = text.to_string();
+                while let Some((indent, next)) = self.peek()?
+                    && indent > parent
+                {
+                    if split_key(&next).is_some() {
+                        bail!("line {}: unexpected indentation", self.lines[self.pos].number);
+                    }
+                    plain.push(' ');
+                    plain.push_str(&next);
+                    self.pos += 1;
+                }
+                Ok(plain_scalar(&plain))
+            }
+        }
+    }
+
+    /// The `|` (literal) or `>` (folded) block scalar introduced by `header`.
+    fn block_scalar(&mut self, header: &str, parent: usize) -> Result<Value> {
+        let folded = header.starts_with('>');
+        let mut chomp = None;
+        let mut explicit_indent = None;
+        for c in header[1..].chars() {
+            match c {
+                '-' | '+' if chomp.is_none() => chomp = Some(c),
+                '1'..='9' if explicit_indent.is_none() => explicit_indent = c.to_digit(10).map(|n| parent + n as usize),
+                _ => bail!("invalid block scalar header `{}`", header),
+            }
+        }
+
+        // `None` for blank lines
+        let mut lines: Vec<Option<String>> = Vec::new();
+        let mut content_indent = explicit_indent;
+        while let Some(line) = self.lines.get(self.pos) {
+            if line.text.is_empty() {
+                lines.push(None);
+                self.pos += 1;
+                continue;
+            }
+            if line.indent <= parent {
+                break;
+            }
+            let indent = *content_indent.get_or_insert(line.indent);
+            if line.indent < indent {
+                break;
+            }
+            lines.push(Some(format!("{}{}", " ".repeat(line.indent - indent), line.text)));
+            self.pos += 1;
+        }
+
+        let trailing = lines.iter().rev().take_while(|line| line.is_none()).count();
+        let body = &lines[..lines.len() - trailing];
+        let mut text = String::new();
+        for (i, line) in body.iter().enumerate() {
+            match line {
+                None => text.push('\n'),
+                Some(line) => {
+                    if let Some(Some(previous)) = i.checked_sub(1).map(|p| &body[p]) {
+                        // Folding joins lines with a space, except around more-indented ones
+                        let newline = !folded || line.starts_with(' ') || previous.starts_with(' ');
+                        text.push(if newline { '\n' } else { ' ' });
+                    }
+                    text.push_str(line);
+                }
+            }
+        }
+        if !body.is_empty() {
+            match chomp {
+                Some('-') => {}
+                Some(_) => text.push_str(&"\n".repeat(trailing + 1)),
+                None => text.push('\n'),
+            }
+        }
+        Ok(Value::String(text))
+    }
+}
+
+/// A one-line `[..]` or `{..}` collection.
+struct Flow<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl Flow<'_> {
+    fn document(mut self) -> Result<Value> {
+        let value = self.value()?;
+        self.skip_whitespace();
+        if self.pos < self.text.len() {
+            bail!("unexpected `{}` after a flow collection", &self.text[self.pos..]);
+        }
+        Ok(value)
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.text[self.pos..].chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek()
+            && c.is_whitespace()
+        {
+            self.pos += c.len_utf8();
+        }
+    }
+
+    fn value(&mut self) -> Result<Value> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('[') => {
+                self.pos += 1;
+                let mut items = Vec::new();
+                loop {
+                    self.skip_whitespace();
+                    if self.peek() == Some(']') {
+                        self.pos += 1;
+                        break;
+                    }
+                    items.push(self.value()?);
+                    if self.separator(']')? {
+                        break;
+                    }
+                }
+                Ok(Value::Array(items))
+            }
+            Some('{') => {
+                self.pos += 1;
+                let mut map = Map::new();
+                loop {
+                    self.skip_whitespace();
+                    if self.peek() == Some('}') {
+                        self.pos += 1;
+                        break;
+                    }
+                    let key = match self.quoted()? {
+                        Some(key) => key,
+                        None => self.plain(true).to_string(),
+                    };
+                    if map.contains_key(&key) {
+                        bail!("duplicate key `{}`", key);
+                    }
+                    self.skip_whitespace();
+                    let value = if self.peek() == Some(':') {
+                        self.pos += 1;
+                        self.skip_whitespace();
+                        match self.peek() {
+                            Some(',' | '}') => Value::Null,
+                            _ => self.value()?,
+                        }
+                    } else {
+                        Value::Null
+                    };
+                    map.insert(key, value);
+                    if self.separator('}')? {
+                        break;
+                    }
+                }
+                Ok(Value::Object(map))
+            }
+            _ => match self.quoted()? {
+                Some(text) => Ok(Value::String(text)),
+                None => Ok(plain_scalar(self.plain(false))),
+            },
+        }
+    }
+
+    /// Consume a `,` or the collection's `close`; true for the latter.
+    fn separator(&mut self, close: char) -> Result<bool> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(',') => {
+                self.pos += 1;
+                Ok(false)
+            }
+            Some(c) if c == close => {
+                self.pos += 1;
+                Ok(true)
+            }
+            _ => bail!("expected `,` or `{}` in a flow collection", close),
+        }
+    }
+
+    fn quoted(&mut self) -> Result<Option<String>> {
+        if !matches!(self.peek(), Some('"' | '\'')) {
+            return Ok(None);
+        }
+        let Some((text, len)) = quoted(&self.text[self.pos..]) else {
+            bail!("unterminated quoted string");
+        };
+        self.pos += len;
+        Ok(Some(text))
+    }
+
+    fn plain(&mut self, key: bool) -> &str {
+        let start = self.pos;
+        while let Some(c) = self.peek() {
+            if matches!(c, ',' | ']' | '}') || (key && c == ':') {
+                break;
+            }
+            self.pos += c.len_utf8();
+        }
+        self.text[start..self.pos].trim()
+    }
+}
+
+fn is_sequence_item(text: &str) -> bool {
+    text == "-" || text.starts_with("- ")
+}
+
+/// The key and the rest of a `key: value` line, or `None` if it isn't one.
+fn split_key(text: &str) -> Option<(String, String)> {
+    if text.starts_with('"') || text.starts_with('\'') {
+        let (key, len) = quoted(text)?;
+        let rest = text[len..].trim_start().strip_prefix(':')?;
+        return (rest.is_empty() || rest.starts_with(' ')).then(|| (key, rest.trim().to_string()));
+    }
+    if text.starts_with('[') || text.starts_with('{') || is_sequence_item(text) {
+        return None;
+    }
+    let colon = text.find(": ").or_else(|| text.ends_with(':').then(|| text.len() - 1))?;
+    Some((text[..colon].trim_end().to_string(), text[colon + 1..].trim().to_string()))
+}
+
+/// `text` up to a ` #` comment that isn't inside quotes.
+fn strip_comment(text: &str) -> &str {
+    let mut quote = None;
+    let mut escaped = false;
+    let mut previous = ' ';
+    for (i, c) in text.char_indices() {
+        match quote {
+            Some('"') if escaped => escaped = false,
+            Some('"') if c == '\\' => escaped = true,
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '#' && previous.is_whitespace() => return text[..i].trim_end(),
+            None if matches!(c, '"' | '\'') && (previous.is_whitespace() || matches!(previous, '[' | '{' | ',')) => {
+                quote = Some(c);
+            }
+            None => {}
+        }
+        previous = c;
+    }
+    text
+}
+
+/// Whether every `[` and `{` in `text` outside quotes is closed.
+fn flow_closed(text: &str) -> bool {
+    let mut depth = 0i32;
+    let mut quote = None;
+    let mut escaped = false;
+    for c in text.chars() {
+        match quote {
+            Some('"') if escaped => escaped = false,
+            Some('"') if c == '\\' => escaped = true,
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None => match c {
+                '[' | '{' => depth += 1,
+                ']' | '}' => depth -= 1,
+                '"' | '\'' => quote = Some(c),
+                _ => {}
+            },
+        }
+    }
+    depth <= 0
+}
+
+/// The quoted string `text` starts with and the length it takes up, or
+/// `None` if it isn't closed.
+fn quoted(text: &str) -> Option<(String, usize)> {
+    let quote = text.chars().next()?;
+    let mut value = String::new();
+    let mut chars = text.char_indices().skip(1);
+    while let Some((i, c)) = chars.next() {
+        if c == quote {
+            // '' is an escaped quote in single-quoted strings
+            if quote == '\'' && text[i + 1..].starts_with('\'') {
+                value.push('\'');
+                chars.next();
+                continue;
+            }
+            return Some((value, i + 1));
+        }
+        if quote == '"' && c == '\\' {
+            let (_, escape) = chars.next()?;
+            match escape {
+                'n' => value.push('\n'),
+                't' => value.push('\t'),
+                'r' => value.push('\r'),
+                '0' => value.push('\0'),
+                'x' | 'u' | 'U' => {
+                    let digits = match escape {
+                        'x' => 2,
+                        'u' => 4,
+                        _ => 8,
+                    };
+                    let hex: String = chars.by_ref().take(digits).map(|(_, c)| c).collect();
+                    let decoded = u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32);
+                    value.push(decoded.unwrap_or(char::REPLACEMENT_CHARACTER));
+                }
+                other => value.push(other),
+            }
+        } else {
+            value.push(c);
+        }
+    }
+    None
+}
+
+/// A plain (unquoted) scalar: null, a boolean, a number or a string.
+fn plain_scalar(text: &str) -> Value {
+    match text {
+        "" | "~" | "null" | "Null" | "NULL" => Value::Null,
+        "true" | "True" | "TRUE" => Value::Bool(true),
+        "false" | "False" | "FALSE" => Value::Bool(false),
+        _ => {
+            if let Ok(n) = text.parse::<i64>() {
+                return Value::from(n);
+            }
+            let numeric = text.chars().all(|c| c.is_ascii_digit() || matches!(c, '+' | '-' | '.' | 'e' | 'E'))
+                && text.chars().any(|c| c.is_ascii_digit());
+            if numeric
+                && let Some(n) = text.parse::<f64>().ok().and_then(Number::from_f64)
+            {
+                return Value::Number(n);
+            }
+            Value::String(text.to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse;
+    use serde_json::json;
+
+    #[test]
+    fn block_and_flow_nodes_parse_to_json() {
+        let yaml = r#"
+# A spec
+openapi: 3.0.3
+info:
+  title: 'Users: the API'
+  version: "1.0"
+  description: |
+    First line
+    second line
+  summary: >-
+    Folded
+    text
+servers:
+- url: https://api.example.com  # production
+paths:
+  /users/{id}:
+    get:
+      tags: [Users, "Admin"]
+      parameters:
+        - name: id
+          in: path
+          required: true
+        - { name: expand, in: query, example: 3 }
+      deprecated: false
+      x-empty:
+      x-list:
+        -
+        - - nested
+"#;
+        let value = parse(yaml).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "openapi": "3.0.3",
+                "info": {
+                    "title": "Users: the API",
+                    "version": "1.0",
+                    "description": "First line\nsecond line\n",
+                    "summary": "Folded text",
+                },
+                "servers": [{ "url": "https://api.example.com" }],
+                "paths": {
+                    "/users/{id}": {
+                        "get": {
+                            "tags": ["Users", "Admin"],
+                            "parameters": [
+                                { "name": "id", "in": "path", "required": true },
+                                { "name": "expand", "in": "query", "example": 3 },
+                            ],
+                            "deprecated": false,
+                            "x-empty": null,
+                            "x-list": [null, ["nested"]],
+                        }
+                    }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn unsupported_or_malformed_yaml_is_an_error() {
+        assert!(parse("a: &anchor 1\nb: *anchor").is_err());
+        assert!(parse("a: 1\n   b: 2").is_err());
+        assert!(parse("a: [1, 2").is_err());
+    }
+
+    #[test]
+    fn tab_indentation_is_an_error() {
+        let error = parse("a:\n\tb: 1").unwrap_err();
+        assert_eq!(error.to_string(), "line 2: tabs can't be used for indentation");
+        assert!(parse("a:\n  - 1\n\t- 2").is_err());
+        // Tabs inside values are kept
+        assert_eq!(parse("a: \"x\ty\"").unwrap(), json!({ "a": "x\ty" }));
+    }
+
+    #[test]
+    fn duplicate_keys_are_an_error() {
+        let error = parse("a: 1\nb: 2\na: 3").unwrap_err();
+        assert_eq!(error.to_string(), "line 3: duplicate key `a`");
+        assert!(parse("a: {b: 1, b: 2}").is_err());
+        assert!(parse("a:\n  b: 1\nc:\n  b: 2").is_ok());
+    }
+
+    #[test]
+    fn blocks_starting_on_the_key_line_are_an_error() {
+        assert_eq!(parse("k: - x").unwrap_err().to_string(), "line 1: a block can't start on its key's line");
+        assert!(parse("k: a: b").is_err());
+        assert_eq!(parse("k: -x").unwrap(), json!({ "k": "-x" }));
+    }
+}